@@ -0,0 +1,127 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity >=0.8.0;
+
+// The cheatcodes supported by forge, see https://onbjerg.github.io/foundry-book/reference/cheatcodes.html
+interface Vm {
+    // Sets block.timestamp
+    function warp(uint256) external;
+    // Sets block.number
+    function roll(uint256) external;
+    // Sets block.basefee
+    function fee(uint256) external;
+    // Sets block.chainid
+    function chainId(uint256) external;
+    // Sets block.coinbase
+    function coinbase(address) external;
+    // Sets block.difficulty
+    function difficulty(uint256) external;
+    // Loads a storage slot from an address
+    function load(address account, bytes32 slot) external returns (bytes32);
+    // Stores a value to an address' storage slot
+    function store(address account, bytes32 slot, bytes32 value) external;
+    // Signs data
+    function sign(uint256 privateKey, bytes32 digest) external returns (uint8 v, bytes32 r, bytes32 s);
+    // Computes the address of a private key
+    function addr(uint256 privateKey) external returns (address);
+    // Derives a private key from a mnemonic at the path m/44'/60'/0'/0/{index}
+    function deriveKey(string calldata mnemonic, uint32 index) external returns (uint256);
+    // Derives a private key from a mnemonic at the path {path}{index}
+    function deriveKey(string calldata mnemonic, string calldata path, uint32 index) external returns (uint256);
+    // Performs a foreign function call via the terminal
+    function ffi(string[] calldata) external returns (bytes memory);
+    // Sets the msg.sender of the next call
+    function prank(address) external;
+    // Sets the msg.sender of all subsequent calls until `stopPrank` is called
+    function startPrank(address) external;
+    // Sets the msg.sender and tx.origin of the next call
+    function prank(address, address) external;
+    // Sets the msg.sender and tx.origin of all subsequent calls until `stopPrank` is called
+    function startPrank(address, address) external;
+    // Like `prank(address, address)`, sets tx.origin only for the pranked call and not for its nested calls if `subtree` is false
+    function prank(address, address, bool subtree) external;
+    // Like `startPrank(address, address)`, sets tx.origin only for the pranked calls and not for their nested calls if `subtree` is false
+    function startPrank(address, address, bool subtree) external;
+    // Resets the msg.sender of subsequent calls
+    function stopPrank() external;
+    // Makes the next call or create a transaction sent by tx.origin, to be broadcast by a script
+    function broadcast() external;
+    // Makes all subsequent calls and creates transactions sent by tx.origin until `stopBroadcast` is called
+    function startBroadcast() external;
+    // Stops making transactions to broadcast
+    function stopBroadcast() external;
+    // Sets the balance of an address
+    function deal(address who, uint256 newBalance) external;
+    // Sets the nonce of an address
+    function setNonce(address who, uint64 newNonce) external;
+    // Returns the nonce of an address
+    function getNonce(address who) external returns (uint64);
+    // Sets the code of an address
+    function etch(address who, bytes calldata code) external;
+    // Gets the code of an address on the forked chain, ignoring local changes
+    function getForkCode(address who) external returns (bytes memory);
+    // Gets the value of a storage slot on the forked chain, ignoring local changes
+    function getForkStorageAt(address who, bytes32 slot) external returns (bytes32);
+    // Creates a fork of a chain at a block or the latest block, returns its id
+    function createFork(string calldata url, uint256 blockNumber) external returns (uint256);
+    function createFork(string calldata url) external returns (uint256);
+    // Makes a fork the active fork, its state is kept when another fork is selected
+    function selectFork(uint256 forkId) external;
+    // Gets the id of the active fork
+    function activeFork() external returns (uint256);
+    // Moves the fork to another block
+    function rollFork(uint256 blockNumber) external;
+    // Keeps the state of an address the same on all forks
+    function makePersistent(address who) external;
+    // Executes a transaction of the forked chain on the current state
+    function transact(bytes32 txHash) external;
+    // Expects an error on the next call: the exact revert data, revert data starting with the selector, or any revert
+    function expectRevert(bytes calldata) external;
+    function expectRevert(bytes4) external;
+    function expectRevert() external;
+    // Records all storage reads and writes
+    function record() external;
+    // Returns all storage reads and writes of an address since `record` was called
+    function accesses(address) external returns (bytes32[] memory reads, bytes32[] memory writes);
+    // Expects an event to be emitted, checking the selected topics and data
+    function expectEmit(bool checkTopic1, bool checkTopic2, bool checkTopic3, bool checkData) external;
+    // Mocks the return data of calls to an address with the given calldata
+    function mockCall(address, bytes calldata, bytes calldata) external;
+    // Clears all mocked calls
+    function clearMockedCalls() external;
+    // Expects a call to an address with the given calldata
+    function expectCall(address, bytes calldata) external;
+    // Expects a call to an address with the given value and calldata
+    function expectCall(address, uint256, bytes calldata) external;
+    // Returns the creation bytecode of an artifact, eg. `getCode("Contract.sol:Contract")`
+    function getCode(string calldata) external returns (bytes memory);
+    // Reads a file, the path has to be allowed by `fs_permissions` in the config
+    function readFile(string calldata) external returns (string memory);
+    // Reads the next line of a file, returns an empty string once the whole file was read
+    function readLine(string calldata) external returns (string memory);
+    // Writes a file, the path has to be allowed by `fs_permissions` in the config
+    function writeFile(string calldata, string calldata) external;
+    // Removes a file, the path has to be allowed by `fs_permissions` in the config
+    function removeFile(string calldata) external;
+    // Labels an address in the traces
+    function label(address addr, string calldata label) external;
+    // Converts a value to a string: checksummed for addresses, 0x-prefixed hex for bytes and bytes32
+    function toString(address value) external returns (string memory);
+    function toString(bytes calldata value) external returns (string memory);
+    function toString(bytes32 value) external returns (string memory);
+    function toString(bool value) external returns (string memory);
+    function toString(uint256 value) external returns (string memory);
+    function toString(int256 value) external returns (string memory);
+    // Discards the current fuzz input if the condition is false
+    function assume(bool) external;
+    // Snapshots the state of the EVM, returns the id of the snapshot
+    function snapshot() external returns (uint256);
+    // Reverts the state of the EVM to a snapshot
+    function revertTo(uint256) external returns (bool);
+    // Reads an environment variable and parses it as the returned type
+    function envBool(string calldata) external returns (bool);
+    function envUint(string calldata) external returns (uint256);
+    function envInt(string calldata) external returns (int256);
+    function envAddress(string calldata) external returns (address);
+    function envBytes32(string calldata) external returns (bytes32);
+    function envString(string calldata) external returns (string memory);
+}
@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.10;
+
+import "ds-test/test.sol";
+import "../Token.sol";
+import "./utils/Vm.sol";
+
+contract TokenTest is DSTest {
+    event Transfer(address indexed from, address indexed to, uint256 amount);
+
+    Vm constant vm = Vm(HEVM_ADDRESS);
+    Token token;
+    address alice = address(0xA11CE);
+
+    function setUp() public {
+        token = new Token("Token", "TKN", 1_000_000e18);
+        vm.label(alice, "alice");
+    }
+
+    function testTransfer(uint96 amount) public {
+        vm.expectEmit(true, true, false, true);
+        emit Transfer(address(this), alice, amount);
+        token.transfer(alice, amount);
+        assertEq(token.balanceOf(alice), amount);
+    }
+
+    function testTransferFrom() public {
+        token.approve(alice, 100);
+        vm.prank(alice);
+        token.transferFrom(address(this), alice, 100);
+        assertEq(token.balanceOf(alice), 100);
+        assertEq(token.allowance(address(this), alice), 0);
+    }
+
+    function testCannotTransferMoreThanBalance() public {
+        vm.prank(alice);
+        vm.expectRevert(stdError.arithmeticError);
+        token.transfer(address(this), 1);
+    }
+}
+
+library stdError {
+    bytes public constant arithmeticError = abi.encodeWithSignature("Panic(uint256)", 0x11);
+}
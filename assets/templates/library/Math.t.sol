@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.10;
+
+import "ds-test/test.sol";
+import "../Math.sol";
+import "./utils/Vm.sol";
+
+contract MathTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    function testMax(uint256 a, uint256 b) public {
+        uint256 max = Math.max(a, b);
+        assertTrue(max >= a && max >= b);
+    }
+
+    function testMin(uint256 a, uint256 b) public {
+        uint256 min = Math.min(a, b);
+        assertTrue(min <= a && min <= b);
+    }
+
+    function testAverage(uint256 a, uint256 b) public {
+        // only check inputs that can't overflow the naive average
+        vm.assume(a < type(uint128).max && b < type(uint128).max);
+        assertEq(Math.average(a, b), (a + b) / 2);
+    }
+}
@@ -0,0 +1,17 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.10;
+
+import "../Counter.sol";
+import "../test/utils/Vm.sol";
+
+// Run with `forge run src/script/Counter.s.sol`
+contract CounterScript {
+    Vm constant vm = Vm(address(uint160(uint256(keccak256("hevm cheat code")))));
+
+    function run() public returns (Counter counter) {
+        vm.startPrank(address(this));
+        counter = new Counter(address(this));
+        counter.increment();
+        vm.stopPrank();
+    }
+}
@@ -0,0 +1,16 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.10;
+
+contract Counter {
+    address public owner;
+    uint256 public number;
+
+    constructor(address _owner) {
+        owner = _owner;
+    }
+
+    function increment() public {
+        require(msg.sender == owner, "not the owner");
+        number++;
+    }
+}
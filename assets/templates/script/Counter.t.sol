@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.10;
+
+import "ds-test/test.sol";
+import "../Counter.sol";
+import "../script/Counter.s.sol";
+import "./utils/Vm.sol";
+
+contract CounterTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+    Counter counter;
+
+    function setUp() public {
+        counter = new Counter(address(this));
+    }
+
+    function testIncrement() public {
+        counter.increment();
+        assertEq(counter.number(), 1);
+    }
+
+    function testOnlyOwnerCanIncrement(address caller) public {
+        vm.assume(caller != address(this));
+        vm.prank(caller);
+        vm.expectRevert("not the owner");
+        counter.increment();
+    }
+
+    function testScript() public {
+        Counter deployed = new CounterScript().run();
+        assertEq(deployed.number(), 1);
+    }
+}
@@ -167,11 +167,11 @@ async fn main() -> eyre::Result<()> {
             );
         }
         Subcommands::Block { rpc_url, block, full, field, to_json } => {
-            let provider = Provider::try_from(rpc_url)?;
+            let provider = Provider::try_from(utils::resolve_rpc_url(&rpc_url)?)?;
             println!("{}", Cast::new(provider).block(block, full, field, to_json).await?);
         }
         Subcommands::BlockNumber { rpc_url } => {
-            let provider = Provider::try_from(rpc_url)?;
+            let provider = Provider::try_from(utils::resolve_rpc_url(&rpc_url)?)?;
             println!("{}", Cast::new(provider).block_number().await?);
         }
         Subcommands::Call { eth, address, sig, args, block } => {
@@ -194,32 +194,32 @@ async fn main() -> eyre::Result<()> {
             println!("{}", SimpleCast::calldata(sig, &args)?);
         }
         Subcommands::Chain { rpc_url } => {
-            let provider = Provider::try_from(rpc_url)?;
+            let provider = Provider::try_from(utils::resolve_rpc_url(&rpc_url)?)?;
             println!("{}", Cast::new(provider).chain().await?);
         }
         Subcommands::ChainId { rpc_url } => {
-            let provider = Provider::try_from(rpc_url)?;
+            let provider = Provider::try_from(utils::resolve_rpc_url(&rpc_url)?)?;
             println!("{}", Cast::new(provider).chain_id().await?);
         }
         Subcommands::Client { rpc_url } => {
-            let provider = Provider::try_from(rpc_url)?;
+            let provider = Provider::try_from(utils::resolve_rpc_url(&rpc_url)?)?;
             println!("{}", provider.client_version().await?);
         }
         Subcommands::ComputeAddress { rpc_url, address, nonce } => {
             let pubkey = Address::from_str(&address).expect("invalid pubkey provided");
-            let provider = Provider::try_from(rpc_url)?;
+            let provider = Provider::try_from(utils::resolve_rpc_url(&rpc_url)?)?;
             let addr = Cast::new(&provider).compute_address(pubkey, nonce).await?;
             println!("Computed Address: {:?}", addr);
         }
         Subcommands::Code { block, who, rpc_url } => {
-            let provider = Provider::try_from(rpc_url)?;
+            let provider = Provider::try_from(utils::resolve_rpc_url(&rpc_url)?)?;
             println!("{}", Cast::new(provider).code(who, block).await?);
         }
         Subcommands::Namehash { name } => {
             println!("{}", SimpleCast::namehash(&name)?);
         }
         Subcommands::Tx { rpc_url, hash, field, to_json } => {
-            let provider = Provider::try_from(rpc_url)?;
+            let provider = Provider::try_from(utils::resolve_rpc_url(&rpc_url)?)?;
             println!("{}", Cast::new(&provider).transaction(hash, field, to_json).await?)
         }
         Subcommands::SendTx {
@@ -409,25 +409,25 @@ async fn main() -> eyre::Result<()> {
             println!("{}", pretty_data);
         }
         Subcommands::Age { block, rpc_url } => {
-            let provider = Provider::try_from(rpc_url)?;
+            let provider = Provider::try_from(utils::resolve_rpc_url(&rpc_url)?)?;
             println!(
                 "{}",
                 Cast::new(provider).age(block.unwrap_or(BlockId::Number(Latest))).await?
             );
         }
         Subcommands::Balance { block, who, rpc_url } => {
-            let provider = Provider::try_from(rpc_url)?;
+            let provider = Provider::try_from(utils::resolve_rpc_url(&rpc_url)?)?;
             println!("{}", Cast::new(provider).balance(who, block).await?);
         }
         Subcommands::BaseFee { block, rpc_url } => {
-            let provider = Provider::try_from(rpc_url)?;
+            let provider = Provider::try_from(utils::resolve_rpc_url(&rpc_url)?)?;
             println!(
                 "{}",
                 Cast::new(provider).base_fee(block.unwrap_or(BlockId::Number(Latest))).await?
             );
         }
         Subcommands::GasPrice { rpc_url } => {
-            let provider = Provider::try_from(rpc_url)?;
+            let provider = Provider::try_from(utils::resolve_rpc_url(&rpc_url)?)?;
             println!("{}", Cast::new(provider).gas_price().await?);
         }
         Subcommands::Keccak { data } => {
@@ -480,7 +480,7 @@ async fn main() -> eyre::Result<()> {
             }
         }
         Subcommands::ResolveName { who, rpc_url, verify } => {
-            let provider = Provider::try_from(rpc_url)?;
+            let provider = Provider::try_from(utils::resolve_rpc_url(&rpc_url)?)?;
             let who = unwrap_or_stdin(who)?;
             let address = provider.resolve_name(&who).await?;
             if verify {
@@ -494,7 +494,7 @@ async fn main() -> eyre::Result<()> {
             println!("{:?}", address);
         }
         Subcommands::LookupAddress { who, rpc_url, verify } => {
-            let provider = Provider::try_from(rpc_url)?;
+            let provider = Provider::try_from(utils::resolve_rpc_url(&rpc_url)?)?;
             let who = unwrap_or_stdin(who)?;
             let name = provider.lookup_address(who).await?;
             if verify {
@@ -508,17 +508,17 @@ async fn main() -> eyre::Result<()> {
             println!("{}", name);
         }
         Subcommands::Storage { address, slot, rpc_url, block } => {
-            let provider = Provider::try_from(rpc_url)?;
+            let provider = Provider::try_from(utils::resolve_rpc_url(&rpc_url)?)?;
             let value = provider.get_storage_at(address, slot, block).await?;
             println!("{:?}", value);
         }
         Subcommands::Proof { address, slots, rpc_url, block } => {
-            let provider = Provider::try_from(rpc_url)?;
+            let provider = Provider::try_from(utils::resolve_rpc_url(&rpc_url)?)?;
             let value = provider.get_proof(address, slots, block).await?;
             println!("{}", serde_json::to_string(&value)?);
         }
         Subcommands::Receipt { hash, field, to_json, rpc_url, cast_async, confirmations } => {
-            let provider = Provider::try_from(rpc_url)?;
+            let provider = Provider::try_from(utils::resolve_rpc_url(&rpc_url)?)?;
             println!(
                 "{}",
                 Cast::new(provider)
@@ -527,7 +527,7 @@ async fn main() -> eyre::Result<()> {
             );
         }
         Subcommands::Nonce { block, who, rpc_url } => {
-            let provider = Provider::try_from(rpc_url)?;
+            let provider = Provider::try_from(utils::resolve_rpc_url(&rpc_url)?)?;
             println!("{}", Cast::new(provider).nonce(who, block).await?);
         }
         Subcommands::EtherscanSource { chain, address, etherscan_api_key } => {
@@ -684,6 +684,7 @@ async fn main() -> eyre::Result<()> {
         Subcommands::Completions { shell } => {
             generate(shell, &mut Opts::command(), "cast", &mut std::io::stdout())
         }
+        Subcommands::External(args) => cmd::external::run_external("cast", args)?,
     };
     Ok(())
 }
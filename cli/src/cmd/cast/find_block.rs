@@ -1,6 +1,6 @@
 //! cast find-block subcommand
 
-use crate::cmd::Cmd;
+use crate::{cmd::Cmd, utils};
 use cast::Cast;
 use clap::Parser;
 use ethers::prelude::*;
@@ -29,7 +29,7 @@ impl Cmd for FindBlockArgs {
 impl FindBlockArgs {
     async fn query_block(timestamp: u64, rpc_url: String) -> Result<()> {
         let ts_target = U256::from(timestamp);
-        let provider = Provider::try_from(rpc_url)?;
+        let provider = Provider::try_from(utils::resolve_rpc_url(&rpc_url)?)?;
         let last_block_num = provider.get_block_number().await?;
         let cast_provider = Cast::new(provider);
 
@@ -0,0 +1,127 @@
+//! Support for external subcommands
+//!
+//! Like cargo, an unknown subcommand `forge foo` is dispatched to an executable named `forge-foo`
+//! on the `PATH`, so the CLI can be extended without forking it.
+//!
+//! The plugin receives the remaining arguments as is, a JSON object with the resolved config and
+//! the project metadata on stdin and the following environment variables, where `<BIN>` is the
+//! uppercased name of the invoking binary:
+//!
+//! - `<BIN>_BIN`: the path of the invoking `forge` or `cast` binary
+//! - `<BIN>_ROOT`: the root of the project
+//! - `<BIN>_PROFILE`: the selected config profile
+
+use foundry_config::{find_project_root_path, Config};
+use serde::Serialize;
+use std::{
+    env,
+    ffi::OsString,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+/// The data a plugin receives on stdin
+#[derive(Debug, Serialize)]
+pub struct PluginInput {
+    /// The version of the invoking binary
+    pub version: &'static str,
+    /// The root of the project
+    pub root: PathBuf,
+    /// The selected config profile
+    pub profile: String,
+    /// The resolved config of the project, all paths are absolute
+    pub config: Config,
+}
+
+/// Runs the external subcommand `args[0]` of `bin` with the remaining `args` and exits with its
+/// exit code.
+pub fn run_external(bin: &str, args: Vec<String>) -> eyre::Result<()> {
+    let (name, args) =
+        args.split_first().ok_or_else(|| eyre::eyre!("no subcommand was provided"))?;
+    let program = format!("{}-{}", bin, name);
+    let path = find_executable(&program, env::var_os("PATH")).ok_or_else(|| {
+        eyre::eyre!(
+            "no such subcommand: `{}`\n\n\tExternal subcommands are executables named `{}` on the PATH. Run `{} --help` for the list of builtin subcommands.",
+            name,
+            program,
+            bin
+        )
+    })?;
+
+    let root = find_project_root_path()?;
+    let config = Config::try_from(Config::figment_with_root(&root))?.sanitized();
+    let input = PluginInput {
+        version: crate::utils::VERSION_MESSAGE,
+        root: root.clone(),
+        profile: config.profile.to_string(),
+        config,
+    };
+    let json = serde_json::to_vec(&input)?;
+
+    let prefix = bin.to_uppercase();
+    let mut child = Command::new(&path)
+        .args(args)
+        .env(format!("{}_BIN", prefix), env::current_exe()?)
+        .env(format!("{}_ROOT", prefix), &root)
+        .env(format!("{}_PROFILE", prefix), &input.profile)
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|err| eyre::eyre!("failed to run `{}`: {}", path.display(), err))?;
+
+    // write from another thread so a plugin that doesn't read stdin can't block us, a plugin that
+    // exits without reading everything is not an error
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let writer = std::thread::spawn(move || {
+        let _ = stdin.write_all(&json);
+    });
+    let status = child.wait()?;
+    let _ = writer.join();
+
+    std::process::exit(status.code().unwrap_or(1))
+}
+
+/// Returns the first executable file named `program` in the directories of `paths`
+fn find_executable(program: &str, paths: Option<OsString>) -> Option<PathBuf> {
+    let program = format!("{}{}", program, env::consts::EXE_SUFFIX);
+    env::split_paths(&paths?).map(|dir| dir.join(&program)).find(|path| is_executable(path))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_find_executable_on_path() {
+        let dir = env::temp_dir().join(format!("foundry-plugins-{}", std::process::id()));
+        let (first, second) = (dir.join("first"), dir.join("second"));
+        std::fs::create_dir_all(&first).unwrap();
+        std::fs::create_dir_all(&second).unwrap();
+        let plugin = second.join(format!("forge-hello{}", env::consts::EXE_SUFFIX));
+        std::fs::write(&plugin, "#!/bin/sh\n").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&plugin, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+
+        let paths = env::join_paths([&first, &second]).unwrap();
+        assert_eq!(find_executable("forge-hello", Some(paths.clone())), Some(plugin));
+        assert_eq!(find_executable("forge-missing", Some(paths)), None);
+        assert_eq!(find_executable("forge-hello", None), None);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -67,6 +67,10 @@ pub struct BindArgs {
     #[clap(long = "single-file", help = "Generate bindings as a single file.")]
     #[serde(skip)]
     single_file: bool,
+
+    #[clap(long = "skip-build", help = "Skips running forge build before generating binding")]
+    #[serde(skip)]
+    skip_build: bool,
 }
 
 impl BindArgs {
@@ -93,7 +97,7 @@ impl BindArgs {
         eyre::ensure!(
             !multi.is_empty(),
             r#"
-No contract artifacts found. Hint: Have you built your contracts yet? `forge bind` does not currently invoke `forge build`, although this is planned for future versions.
+No contract artifacts found. Hint: Have you built your contracts yet? Run `forge bind` without `--skip-build` to compile them first.
             "#
         );
         Ok(multi)
@@ -139,12 +143,18 @@ impl Cmd for BindArgs {
     type Output = ();
 
     fn run(self) -> eyre::Result<Self::Output> {
+        if !self.skip_build {
+            let config: Config = (&self).into();
+            let project = config.project()?;
+            crate::cmd::utils::compile(&project, false, false)?;
+        }
+
         if !self.overwrite && self.bindings_exist() {
             println!("Bindings found. Checking for consistency.");
             return self.check_existing_bindings()
         }
 
-        if self.overwrite {
+        if self.overwrite && self.bindings_exist() {
             fs::remove_dir_all(self.bindings_root())?;
         }
 
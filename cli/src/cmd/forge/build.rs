@@ -1,11 +1,17 @@
 //! build command
 
-use ethers::solc::{Project, ProjectCompileOutput};
-use std::path::PathBuf;
+use ethers::solc::{
+    artifacts::{BytecodeObject, CompactBytecode, Offsets},
+    Project, ProjectCompileOutput,
+};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 
 use crate::{cmd::Cmd, opts::forge::CompilerArgs};
 
-use crate::cmd::forge::watch::WatchArgs;
+use crate::cmd::forge::{vyper, watch::WatchArgs};
 use clap::{Parser, ValueHint};
 use ethers::solc::remappings::Remapping;
 use foundry_config::{
@@ -129,6 +135,13 @@ pub struct BuildArgs {
     #[clap(help = "print compiled contract sizes", long = "sizes")]
     pub sizes: bool,
 
+    #[clap(
+        help = "exit with an error if the deployed bytecode of any contract exceeds the 24576 bytes size limit",
+        long = "check-sizes"
+    )]
+    #[serde(skip)]
+    pub check_sizes: bool,
+
     #[clap(help = "ignore warnings with specific error codes", long)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub ignored_error_codes: Vec<u64>,
@@ -185,13 +198,39 @@ pub struct BuildArgs {
     )]
     #[serde(skip)]
     pub via_ir: bool,
+
+    #[clap(
+        help = "also write the artifacts in hardhat's format to `<out>/hardhat/<sourceName>/<contractName>.json`",
+        long
+    )]
+    #[serde(skip)]
+    pub hardhat_artifacts: bool,
 }
 
 impl Cmd for BuildArgs {
     type Output = ProjectCompileOutput;
     fn run(self) -> eyre::Result<Self::Output> {
-        let project = self.project()?;
-        crate::cmd::utils::compile(&project, self.names, self.sizes)
+        let config: Config = (&self).into();
+        let project = config.project()?;
+        let output = crate::cmd::utils::compile(&project, self.names, self.sizes)?;
+        vyper::compile_vyper_sources(
+            &project,
+            config.vyper.as_deref().unwrap_or_else(|| Path::new(vyper::VYPER)),
+        )?;
+        if config.hardhat_artifacts {
+            write_hardhat_artifacts(&project, output.clone())?;
+        }
+        if self.check_sizes {
+            let oversized = crate::cmd::utils::oversized_contracts(output.clone());
+            if !oversized.is_empty() {
+                eyre::bail!(
+                    "{} contract(s) exceed the size limit:\n{}",
+                    oversized.len(),
+                    crate::cmd::utils::format_sizes(&oversized)
+                )
+            }
+        }
+        Ok(output)
     }
 }
 
@@ -284,6 +323,10 @@ impl Provider for BuildArgs {
             dict.insert("force".to_string(), self.force.into());
         }
 
+        if self.hardhat_artifacts {
+            dict.insert("hardhat_artifacts".to_string(), true.into());
+        }
+
         if self.compiler.optimize {
             dict.insert("optimizer".to_string(), self.compiler.optimize.into());
         }
@@ -301,3 +344,75 @@ impl Provider for BuildArgs {
         Ok(Map::from([(Config::selected_profile(), dict)]))
     }
 }
+
+/// The `_format` identifier of hardhat's contract artifacts
+pub const HARDHAT_ARTIFACT_FORMAT: &str = "hh-sol-artifact-1";
+
+/// A contract artifact as hardhat writes it to `artifacts/<sourceName>/<contractName>.json`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HardhatArtifact {
+    #[serde(rename = "_format")]
+    pub format: String,
+    pub contract_name: String,
+    /// the source file of the contract, relative to the project root
+    pub source_name: String,
+    pub abi: serde_json::Value,
+    pub bytecode: BytecodeObject,
+    pub deployed_bytecode: BytecodeObject,
+    pub link_references: BTreeMap<String, BTreeMap<String, Vec<Offsets>>>,
+    pub deployed_link_references: BTreeMap<String, BTreeMap<String, Vec<Offsets>>>,
+}
+
+/// Writes all artifacts of the `output` in hardhat's format to `<out>/hardhat`, using hardhat's
+/// `<sourceName>/<contractName>.json` layout
+pub fn write_hardhat_artifacts(
+    project: &Project,
+    output: ProjectCompileOutput,
+) -> eyre::Result<()> {
+    let out = project.paths.artifacts.join("hardhat");
+    for (id, artifact) in output.into_artifacts() {
+        let source_name = id
+            .source
+            .strip_prefix(project.root())
+            .unwrap_or(&id.source)
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        let (bytecode, link_references) = split_bytecode(artifact.bytecode);
+        let (deployed_bytecode, deployed_link_references) =
+            split_bytecode(artifact.deployed_bytecode.and_then(|bytecode| bytecode.bytecode));
+
+        let hh = HardhatArtifact {
+            format: HARDHAT_ARTIFACT_FORMAT.to_string(),
+            abi: artifact
+                .abi
+                .as_ref()
+                .map(serde_json::to_value)
+                .transpose()?
+                .unwrap_or_else(|| serde_json::Value::Array(vec![])),
+            contract_name: id.name,
+            source_name,
+            bytecode,
+            deployed_bytecode,
+            link_references,
+            deployed_link_references,
+        };
+
+        let file = out.join(Path::new(&hh.source_name)).join(format!("{}.json", hh.contract_name));
+        if let Some(parent) = file.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&file, serde_json::to_string_pretty(&hh)?)?;
+    }
+    Ok(())
+}
+
+/// Returns the bytecode object and its link references, an empty `0x` bytecode if there is none
+fn split_bytecode(
+    bytecode: Option<CompactBytecode>,
+) -> (BytecodeObject, BTreeMap<String, BTreeMap<String, Vec<Offsets>>>) {
+    bytecode
+        .map(|bytecode| (bytecode.object, bytecode.link_references))
+        .unwrap_or_else(|| (BytecodeObject::Bytecode(Default::default()), Default::default()))
+}
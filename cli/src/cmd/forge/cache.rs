@@ -0,0 +1,237 @@
+//! cache command
+
+use crate::cmd::Cmd;
+use clap::{Parser, Subcommand, ValueHint};
+use ethers::solc::{Project, ProjectPathsConfig, Solc};
+use foundry_config::{Chain, Config};
+use std::path::{Path, PathBuf};
+
+/// Command to list and remove cached data
+#[derive(Debug, Parser)]
+pub struct CacheArgs {
+    #[clap(subcommand)]
+    pub sub: CacheSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CacheSubcommands {
+    #[clap(about = "Lists the cached rpc storage, solc binaries and the project's build cache")]
+    Ls(CacheLsArgs),
+    #[clap(about = "Removes cached data, by default the rpc storage of all chains")]
+    Clean(CacheCleanArgs),
+}
+
+#[derive(Debug, Clone, Parser)]
+pub struct CacheLsArgs {
+    #[clap(
+        help = "only list the rpc storage of these chains, by name or id, eg. `mainnet` or `1`"
+    )]
+    chains: Vec<Chain>,
+    #[clap(
+        help = "the project's root path, default being the current working directory",
+        long,
+        value_hint = ValueHint::DirPath
+    )]
+    root: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Parser)]
+pub struct CacheCleanArgs {
+    #[clap(
+        help = "only remove the rpc storage of these chains, by name or id, eg. `mainnet` or `1`"
+    )]
+    chains: Vec<Chain>,
+    #[clap(help = "only remove the rpc storage of these blocks", long, short)]
+    blocks: Vec<u64>,
+    #[clap(help = "remove all installed solc binaries", long)]
+    solc: bool,
+    #[clap(help = "remove the project's build cache and artifacts", long)]
+    build: bool,
+    #[clap(
+        help = "the project's root path, default being the current working directory",
+        long,
+        value_hint = ValueHint::DirPath
+    )]
+    root: Option<PathBuf>,
+}
+
+impl Cmd for CacheArgs {
+    type Output = ();
+
+    fn run(self) -> eyre::Result<Self::Output> {
+        match self.sub {
+            CacheSubcommands::Ls(cmd) => cmd.run(),
+            CacheSubcommands::Clean(cmd) => cmd.run(),
+        }
+    }
+}
+
+impl Cmd for CacheLsArgs {
+    type Output = ();
+
+    fn run(self) -> eyre::Result<Self::Output> {
+        println!("rpc storage:");
+        for (chain, blocks) in cached_chains(&self.chains)? {
+            let size: u64 = blocks.iter().map(|(_, path)| dir_size(path)).sum();
+            println!("  {} ({})", chain, format_size(size));
+            for (block, path) in blocks {
+                println!("    {} ({})", block, format_size(dir_size(&path)));
+            }
+        }
+
+        if let Some(svm_home) = Solc::svm_home().filter(|p| p.is_dir()) {
+            println!("solc:");
+            let mut versions = subdirs(&svm_home)?;
+            versions.sort();
+            for version in versions {
+                println!(
+                    "  {} ({})",
+                    version.file_name().unwrap_or_default().to_string_lossy(),
+                    format_size(dir_size(&version))
+                );
+            }
+        }
+
+        let paths = project_paths(self.root)?;
+        if paths.cache.exists() {
+            println!("build:");
+            println!(
+                "  {} ({})",
+                paths.cache.display(),
+                format_size(std::fs::metadata(&paths.cache)?.len())
+            );
+            if paths.artifacts.exists() {
+                println!(
+                    "  {} ({})",
+                    paths.artifacts.display(),
+                    format_size(dir_size(&paths.artifacts))
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Cmd for CacheCleanArgs {
+    type Output = ();
+
+    fn run(self) -> eyre::Result<Self::Output> {
+        let clean_rpc =
+            !self.chains.is_empty() || !self.blocks.is_empty() || !(self.solc || self.build);
+
+        if clean_rpc {
+            for (chain, blocks) in cached_chains(&self.chains)? {
+                for (block, path) in blocks {
+                    if self.blocks.is_empty() || self.blocks.contains(&block) {
+                        println!("Removing rpc storage of {} at block {}", chain, block);
+                        std::fs::remove_dir_all(path)?;
+                    }
+                }
+            }
+        }
+
+        if self.solc {
+            if let Some(svm_home) = Solc::svm_home().filter(|p| p.is_dir()) {
+                for version in subdirs(&svm_home)? {
+                    println!("Removing {}", version.display());
+                    std::fs::remove_dir_all(version)?;
+                }
+            }
+        }
+
+        if self.build {
+            let paths = project_paths(self.root)?;
+            println!("Removing {} and {}", paths.cache.display(), paths.artifacts.display());
+            Project::builder().paths(paths).build()?.cleanup()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the paths of the project at `root`, or the current working directory
+fn project_paths(root: Option<PathBuf>) -> eyre::Result<ProjectPathsConfig> {
+    let root = root.unwrap_or_else(|| std::env::current_dir().unwrap());
+    Ok(Config::load_with_root(root).project_paths())
+}
+
+/// Returns all cached blocks of all chains in [`Config::foundry_cache_dir()`], only the given
+/// `chains` if not empty
+fn cached_chains(chains: &[Chain]) -> eyre::Result<Vec<(String, Vec<(u64, PathBuf)>)>> {
+    let cache_dir = match Config::foundry_cache_dir().filter(|p| p.is_dir()) {
+        Some(dir) => dir,
+        None => return Ok(vec![]),
+    };
+    let names = chains.iter().map(|chain| chain.to_string()).collect::<Vec<_>>();
+
+    let mut cached = Vec::new();
+    for chain_dir in subdirs(&cache_dir)? {
+        let chain = chain_dir.file_name().unwrap_or_default().to_string_lossy().to_string();
+        if !names.is_empty() && !names.contains(&chain) {
+            continue
+        }
+        let mut blocks = subdirs(&chain_dir)?
+            .into_iter()
+            .filter_map(|dir| {
+                let block = dir.file_name()?.to_str()?.parse::<u64>().ok()?;
+                Some((block, dir))
+            })
+            .collect::<Vec<_>>();
+        blocks.sort();
+        cached.push((chain, blocks));
+    }
+    cached.sort();
+    Ok(cached)
+}
+
+/// Returns all directories in `dir`
+fn subdirs(dir: &Path) -> eyre::Result<Vec<PathBuf>> {
+    let mut dirs = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            dirs.push(path);
+        }
+    }
+    Ok(dirs)
+}
+
+/// Returns the total size of all files in `dir`
+fn dir_size(dir: &Path) -> u64 {
+    walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Formats the number of bytes as a human readable size
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "kB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1000.0 && unit < UNITS.len() - 1 {
+        size /= 1000.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[unit])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1_500), "1.5 kB");
+        assert_eq!(format_size(2_340_000_000), "2.3 GB");
+    }
+}
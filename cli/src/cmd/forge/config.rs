@@ -9,7 +9,10 @@ use foundry_config::{figment::Figment, Config};
 
 foundry_config::impl_figment_convert!(ConfigArgs, opts, evm_opts);
 
-/// Command to list currently set config values
+/// Command to list the resolved config values.
+///
+/// The values are those of the selected `FOUNDRY_PROFILE` with all env vars and the given CLI flags
+/// applied, exactly as `build`, `test` etc. would use them.
 #[derive(Debug, Clone, Parser)]
 pub struct ConfigArgs {
     #[clap(help = "prints currently set config values as json", long)]
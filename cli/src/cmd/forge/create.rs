@@ -1,15 +1,26 @@
 //! Create command
 
 use crate::{
-    cmd::{forge::build::BuildArgs, Cmd},
+    cmd::{
+        forge::{
+            build::BuildArgs,
+            flatten::CoreFlattenArgs,
+            verify::{self, RetryArgs, VerifyArgs},
+        },
+        Cmd,
+    },
     opts::{EthereumOpts, WalletType},
     utils::parse_u256,
 };
 use ethers::{
     abi::{Abi, Constructor, Token},
-    prelude::{artifacts::BytecodeObject, ContractFactory, Http, Middleware, Provider},
-    types::{transaction::eip2718::TypedTransaction, Chain, U256},
+    prelude::{
+        artifacts::{output_selection::ContractOutputSelection, BytecodeObject},
+        ContractFactory, Http, Middleware, Provider,
+    },
+    types::{transaction::eip2718::TypedTransaction, Address, Chain, U256},
 };
+use foundry_config::Config;
 
 use eyre::Result;
 use foundry_utils::parse_tokens;
@@ -62,23 +73,58 @@ pub struct CreateArgs {
 
     #[clap(long = "value", help = "value to send with the contract creation tx", env = "ETH_VALUE", parse(try_from_str = parse_u256))]
     value: Option<U256>,
+
+    #[clap(
+        long,
+        help = "verify the contract on etherscan after the deployment, requires `ETHERSCAN_API_KEY` to be set"
+    )]
+    verify: bool,
 }
 
 impl Cmd for CreateArgs {
     type Output = ();
 
-    fn run(self) -> Result<Self::Output> {
+    fn run(mut self) -> Result<Self::Output> {
+        if self.verify {
+            if self.contract.path.is_none() {
+                eyre::bail!("Contract info must be provided in the format <path>:<name> to verify")
+            }
+            // the metadata contains the full compiler version required by etherscan
+            self.opts
+                .compiler
+                .extra_output
+                .get_or_insert_with(Vec::new)
+                .push(ContractOutputSelection::Metadata);
+        }
+
         // Find Project & Compile
         let project = self.opts.project()?;
         let compiled = crate::cmd::utils::compile(&project, self.opts.names, self.opts.sizes)?;
 
+        let compiler_version = compiled
+            .find(&self.contract.name)
+            .and_then(|artifact| artifact.metadata.as_ref())
+            .map(|metadata| format!("v{}", metadata.compiler.version));
+
         // Get ABI and BIN
         let (abi, bin, _) =
             crate::cmd::utils::read_artifact(&project, compiled, self.contract.clone())?;
 
         let bin = match bin.object {
             BytecodeObject::Bytecode(_) => bin.object,
-            _ => eyre::bail!("Dynamic linking not supported in `create` command - deploy the library contract first, then provide the address to link at compile time")
+            _ => {
+                let unlinked = bin
+                    .link_references
+                    .iter()
+                    .flat_map(|(file, libs)| {
+                        libs.keys().map(move |lib| format!("\n    {}:{}:<address>", file, lib))
+                    })
+                    .collect::<String>();
+                eyre::bail!(
+                    "Dynamic linking not supported in `create` command - deploy the library contracts first, then provide their addresses to link at compile time with `--libraries`:{}",
+                    unlinked
+                )
+            }
         };
 
         // Add arguments to constructor
@@ -100,23 +146,48 @@ impl Cmd for CreateArgs {
             None => vec![],
         };
 
+        let constructor_args = ethers::abi::encode(&params);
+
         // Deploy with signer
         let rt = tokio::runtime::Runtime::new().expect("could not start tokio rt");
         let chain_id = rt.block_on(provider.get_chainid())?;
-        if let Some(signer) = rt.block_on(self.eth.signer_with(chain_id, provider))? {
+        let address = if let Some(signer) = rt.block_on(self.eth.signer_with(chain_id, provider))? {
             match signer {
-                WalletType::Ledger(signer) => {
-                    rt.block_on(self.deploy(abi, bin, params, signer))?;
-                }
-                WalletType::Local(signer) => {
-                    rt.block_on(self.deploy(abi, bin, params, signer))?;
-                }
-                WalletType::Trezor(signer) => {
-                    rt.block_on(self.deploy(abi, bin, params, signer))?;
-                }
+                WalletType::Ledger(signer) => rt.block_on(self.deploy(abi, bin, params, signer))?,
+                WalletType::Local(signer) => rt.block_on(self.deploy(abi, bin, params, signer))?,
+                WalletType::Trezor(signer) => rt.block_on(self.deploy(abi, bin, params, signer))?,
             }
         } else {
             eyre::bail!("could not find artifact")
+        };
+
+        if self.verify {
+            let config = Config::from(&self.opts);
+            let verify = VerifyArgs {
+                address,
+                contract: self.contract.clone(),
+                constructor_args: Some(hex::encode(constructor_args)),
+                compiler_version,
+                num_of_optimizations: config.optimizer.then(|| config.optimizer_runs as u32),
+                chain_id: chain_id.as_u64(),
+                etherscan_key: self.eth.etherscan_api_key.clone(),
+                flatten: false,
+                watch: true,
+                verifier: None,
+                verifier_url: None,
+                retry: RetryArgs { retries: 15, delay: 5 },
+                opts: CoreFlattenArgs {
+                    root: self.opts.root.clone(),
+                    contracts: self.opts.contracts.clone(),
+                    remappings: self.opts.remappings.clone(),
+                    remappings_env: self.opts.remappings_env.clone(),
+                    cache_path: self.opts.cache_path.clone(),
+                    lib_paths: self.opts.lib_paths.clone(),
+                    hardhat: self.opts.hardhat,
+                },
+            };
+            println!("Starting contract verification...");
+            rt.block_on(verify::run_verify(&verify))?;
         }
 
         Ok(())
@@ -124,13 +195,14 @@ impl Cmd for CreateArgs {
 }
 
 impl CreateArgs {
+    /// Deploys the contract and returns its address once the transaction was mined
     async fn deploy<M: Middleware + 'static>(
-        self,
+        &self,
         abi: Abi,
         bin: BytecodeObject,
         args: Vec<Token>,
         provider: M,
-    ) -> Result<()> {
+    ) -> Result<Address> {
         let chain = provider.get_chainid().await?.as_u64();
         let deployer_address =
             provider.default_sender().expect("no sender address set for provider");
@@ -180,7 +252,7 @@ impl CreateArgs {
         println!("Deployed to: {:?}", deployed_contract.address());
         println!("Transaction hash: {:?}", receipt.transaction_hash);
 
-        Ok(())
+        Ok(deployed_contract.address())
     }
 
     fn parse_constructor_args(
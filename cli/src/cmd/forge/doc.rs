@@ -0,0 +1,410 @@
+//! doc command
+
+use crate::{
+    cmd::{
+        abi_signature,
+        forge::build::{self, BuildArgs},
+        Cmd,
+    },
+    opts::forge::CompilerArgs,
+};
+use clap::{Parser, ValueHint};
+use ethers::{
+    abi::{Abi, Event, Function, Param, StateMutability},
+    solc::artifacts::output_selection::ContractOutputSelection,
+};
+use serde_json::{to_value, Value};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Command to generate the documentation of a project from its natspec comments
+#[derive(Debug, Clone, Parser)]
+pub struct DocArgs {
+    #[clap(
+        help = "the output directory of the mdbook, default being `docs` in the project's root",
+        long,
+        value_hint = ValueHint::DirPath
+    )]
+    book_dir: Option<PathBuf>,
+    #[clap(help = "build the html site with `mdbook build` after generating the book", long)]
+    build: bool,
+
+    /// All build arguments are supported
+    #[clap(flatten)]
+    opts: build::BuildArgs,
+}
+
+impl Cmd for DocArgs {
+    type Output = ();
+
+    fn run(self) -> eyre::Result<Self::Output> {
+        let DocArgs { book_dir, build, opts } = self;
+
+        // natspec comments are only included in the compiler output if requested
+        let mut extra_output = opts.compiler.extra_output.clone().unwrap_or_default();
+        for selection in [
+            ContractOutputSelection::Abi,
+            ContractOutputSelection::DevDoc,
+            ContractOutputSelection::UserDoc,
+        ] {
+            if !extra_output.iter().any(|s| s.to_string() == selection.to_string()) {
+                extra_output.push(selection);
+            }
+        }
+        let opts = BuildArgs {
+            compiler: CompilerArgs { extra_output: Some(extra_output), ..opts.compiler },
+            ..opts
+        };
+
+        let project = opts.project()?;
+        let output = super::super::suppress_compile(&project)?;
+
+        let mut definitions = BTreeMap::new();
+        let mut contracts = Vec::new();
+        for (id, artifact) in output.into_artifacts() {
+            // only document the project's own contracts
+            if !id.source.starts_with(&project.paths.sources) ||
+                id.source.starts_with(&project.paths.tests)
+            {
+                continue
+            }
+            if !definitions.contains_key(&id.source) {
+                let content = std::fs::read_to_string(&id.source)?;
+                definitions.insert(id.source.clone(), contract_definitions(&content));
+            }
+            let (kind, bases) = definitions[&id.source]
+                .get(&id.name)
+                .cloned()
+                .unwrap_or_else(|| ("contract".to_string(), vec![]));
+
+            let abi = artifact.abi.as_ref().map(to_value).transpose()?.unwrap_or_default();
+            contracts.push(ContractDoc {
+                name: id.name,
+                kind,
+                source: id.source.strip_prefix(project.root()).unwrap_or(&id.source).to_path_buf(),
+                bases,
+                abi: serde_json::from_value(abi).unwrap_or_default(),
+                devdoc: to_value(&artifact.devdoc)?,
+                userdoc: to_value(&artifact.userdoc)?,
+            });
+        }
+        contracts.sort_by(|a, b| a.name.cmp(&b.name));
+        contracts.dedup_by(|a, b| a.name == b.name);
+
+        let out = book_dir.unwrap_or_else(|| project.root().join("docs"));
+        write_book(project.root(), &out, &contracts)?;
+        println!("Generated documentation for {} contracts in {}", contracts.len(), out.display());
+
+        if build {
+            let status =
+                Command::new("mdbook").arg("build").current_dir(&out).status().map_err(|err| {
+                    eyre::eyre!("Failed to run `mdbook build`, is mdbook installed? {}", err)
+                })?;
+            if !status.success() {
+                eyre::bail!("`mdbook build` failed")
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Everything that's documented about a single contract
+#[derive(Debug, Clone)]
+pub struct ContractDoc {
+    pub name: String,
+    /// `contract`, `abstract contract`, `interface` or `library`
+    pub kind: String,
+    /// the source file of the contract, relative to the project root
+    pub source: PathBuf,
+    /// the names of the contracts this contract inherits from
+    pub bases: Vec<String>,
+    pub abi: Abi,
+    /// solc's `devdoc` output
+    pub devdoc: Value,
+    /// solc's `userdoc` output
+    pub userdoc: Value,
+}
+
+/// Returns the kind and the base contracts of all contracts defined in the solidity `content`
+fn contract_definitions(content: &str) -> BTreeMap<String, (String, Vec<String>)> {
+    let mut definitions = BTreeMap::new();
+    if let Ok((source_unit, _)) = solang_parser::parse(content, 0) {
+        for part in source_unit.0 {
+            if let solang_parser::pt::SourceUnitPart::ContractDefinition(contract) = part {
+                let bases = contract.base.iter().map(|base| base.name.name.clone()).collect();
+                definitions.insert(contract.name.name.clone(), (contract.ty.to_string(), bases));
+            }
+        }
+    }
+    definitions
+}
+
+/// Writes the mdbook of all `contracts` to `out`, using the project's README as introduction if
+/// it exists
+fn write_book(root: &Path, out: &Path, contracts: &[ContractDoc]) -> eyre::Result<()> {
+    let src = out.join("src");
+    std::fs::create_dir_all(&src)?;
+
+    let title = root.file_name().unwrap_or_default().to_string_lossy();
+    std::fs::write(
+        out.join("book.toml"),
+        format!("[book]\ntitle = \"{}\"\nsrc = \"src\"\n\n[output.html]\n", title),
+    )?;
+
+    let readme = root.join("README.md");
+    let intro =
+        if readme.is_file() { std::fs::read_to_string(readme)? } else { format!("# {}\n", title) };
+    std::fs::write(src.join("README.md"), intro)?;
+
+    let documented = contracts.iter().map(|c| c.name.as_str()).collect::<BTreeSet<_>>();
+    let mut summary = String::from("# Summary\n\n- [Introduction](README.md)\n");
+    for contract in contracts {
+        writeln!(summary, "- [{}]({}.md)", contract.name, contract.name)?;
+        std::fs::write(
+            src.join(format!("{}.md", contract.name)),
+            render_contract(contract, &documented),
+        )?;
+    }
+    std::fs::write(src.join("SUMMARY.md"), summary)?;
+    Ok(())
+}
+
+/// Renders the markdown page of a single contract, linking all bases that are in `documented`
+pub fn render_contract(contract: &ContractDoc, documented: &BTreeSet<&str>) -> String {
+    let mut page = String::new();
+    let _ = writeln!(page, "# {}\n", contract.name);
+    let _ = writeln!(page, "*{}* defined in `{}`\n", contract.kind, contract.source.display());
+
+    if !contract.bases.is_empty() {
+        let bases = contract
+            .bases
+            .iter()
+            .map(|base| {
+                if documented.contains(base.as_str()) {
+                    format!("[{}]({}.md)", base, base)
+                } else {
+                    base.clone()
+                }
+            })
+            .collect::<Vec<_>>();
+        let _ = writeln!(page, "**Inherits:** {}\n", bases.join(", "));
+    }
+
+    if let Some(title) = doc_str(&contract.devdoc, "title") {
+        let _ = writeln!(page, "**{}**\n", title);
+    }
+    if let Some(author) = doc_str(&contract.devdoc, "author") {
+        let _ = writeln!(page, "**Author:** {}\n", author);
+    }
+    if let Some(notice) = doc_str(&contract.userdoc, "notice") {
+        let _ = writeln!(page, "{}\n", notice);
+    }
+    if let Some(details) = doc_str(&contract.devdoc, "details") {
+        let _ = writeln!(page, "{}\n", details);
+    }
+
+    let mut functions = contract.abi.functions().collect::<Vec<_>>();
+    functions.sort_by(|a, b| a.name.cmp(&b.name));
+    if !functions.is_empty() {
+        page.push_str("## Functions\n\n");
+        for function in functions {
+            let signature = abi_signature(&function.name, &function.inputs);
+            let devdoc = doc_entry(&contract.devdoc, "methods", &signature);
+            let userdoc = doc_entry(&contract.userdoc, "methods", &signature);
+            let _ = writeln!(page, "### {}\n", function.name);
+            let _ = writeln!(page, "```solidity\n{}\n```\n", format_function(function));
+            render_entry(&mut page, devdoc, userdoc);
+            render_params(&mut page, "Parameters", &function.inputs, devdoc.get("params"));
+            render_params(&mut page, "Returns", &function.outputs, devdoc.get("returns"));
+        }
+    }
+
+    let mut events = contract.abi.events().collect::<Vec<_>>();
+    events.sort_by(|a, b| a.name.cmp(&b.name));
+    if !events.is_empty() {
+        page.push_str("## Events\n\n");
+        for event in events {
+            let inputs = event
+                .inputs
+                .iter()
+                .map(|input| Param {
+                    name: input.name.clone(),
+                    kind: input.kind.clone(),
+                    internal_type: None,
+                })
+                .collect::<Vec<_>>();
+            let signature = abi_signature(&event.name, &inputs);
+            let devdoc = doc_entry(&contract.devdoc, "events", &signature);
+            let userdoc = doc_entry(&contract.userdoc, "events", &signature);
+            let _ = writeln!(page, "### {}\n", event.name);
+            let _ = writeln!(page, "```solidity\n{}\n```\n", format_event(event));
+            render_entry(&mut page, devdoc, userdoc);
+            render_params(&mut page, "Parameters", &inputs, devdoc.get("params"));
+        }
+    }
+
+    page
+}
+
+static NULL: Value = Value::Null;
+
+/// Returns the string value of the `key` of a natspec object
+fn doc_str<'a>(doc: &'a Value, key: &str) -> Option<&'a str> {
+    doc.get(key).and_then(Value::as_str)
+}
+
+/// Returns the natspec entry of the `signature` in the `section` (`methods`, `events`)
+fn doc_entry<'a>(doc: &'a Value, section: &str, signature: &str) -> &'a Value {
+    doc.get(section).and_then(|entries| entries.get(signature)).unwrap_or(&NULL)
+}
+
+/// Writes the notice and details of a function or event
+fn render_entry(page: &mut String, devdoc: &Value, userdoc: &Value) {
+    if let Some(notice) = doc_str(userdoc, "notice") {
+        let _ = writeln!(page, "{}\n", notice);
+    }
+    if let Some(details) = doc_str(devdoc, "details") {
+        let _ = writeln!(page, "{}\n", details);
+    }
+}
+
+/// Writes a table of the `params` and their natspec descriptions, unnamed return values are
+/// documented as `_0`, `_1`, ... by solc
+fn render_params(page: &mut String, heading: &str, params: &[Param], docs: Option<&Value>) {
+    if params.is_empty() {
+        return
+    }
+    let _ = writeln!(page, "**{}**\n", heading);
+    page.push_str("|Name|Type|Description|\n|----|----|-----------|\n");
+    for (i, param) in params.iter().enumerate() {
+        let key = if param.name.is_empty() { format!("_{}", i) } else { param.name.clone() };
+        let description = docs.and_then(|docs| doc_str(docs, &key)).unwrap_or_default();
+        let name = if param.name.is_empty() { String::new() } else { format!("`{}`", param.name) };
+        let _ = writeln!(page, "|{}|`{}`|{}|", name, param.kind, description);
+    }
+    page.push('\n');
+}
+
+/// Formats the function as it would be declared in an interface
+fn format_function(function: &Function) -> String {
+    let params = |params: &[Param]| {
+        params
+            .iter()
+            .map(|param| {
+                if param.name.is_empty() {
+                    param.kind.to_string()
+                } else {
+                    format!("{} {}", param.kind, param.name)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut formatted =
+        format!("function {}({}) external", function.name, params(&function.inputs));
+    match function.state_mutability {
+        StateMutability::Pure => formatted.push_str(" pure"),
+        StateMutability::View => formatted.push_str(" view"),
+        StateMutability::Payable => formatted.push_str(" payable"),
+        StateMutability::NonPayable => {}
+    }
+    if !function.outputs.is_empty() {
+        let _ = write!(formatted, " returns ({})", params(&function.outputs));
+    }
+    formatted.push(';');
+    formatted
+}
+
+/// Formats the event as it would be declared in an interface
+fn format_event(event: &Event) -> String {
+    let params = event
+        .inputs
+        .iter()
+        .map(|param| {
+            let indexed = if param.indexed { " indexed" } else { "" };
+            format!("{}{} {}", param.kind, indexed, param.name)
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let anonymous = if event.anonymous { " anonymous" } else { "" };
+    format!("event {}({}){};", event.name, params, anonymous)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_book_dir_and_artifacts_dir() {
+        let args = DocArgs::parse_from(["doc", "--book-dir", "book", "-o", "artifacts"]);
+        assert_eq!(args.book_dir, Some(PathBuf::from("book")));
+        assert_eq!(args.opts.out_path, Some(PathBuf::from("artifacts")));
+    }
+
+    #[test]
+    fn can_find_contract_definitions() {
+        let content = r#"
+pragma solidity ^0.8.0;
+
+interface IToken {}
+abstract contract Owned {}
+contract Token is IToken, Owned {}
+library Math {}
+"#;
+        let definitions = contract_definitions(content);
+        assert_eq!(
+            definitions["Token"],
+            ("contract".to_string(), vec!["IToken".to_string(), "Owned".to_string()])
+        );
+        assert_eq!(definitions["IToken"].0, "interface");
+        assert_eq!(definitions["Math"].0, "library");
+    }
+
+    #[test]
+    fn can_render_contract() {
+        let abi: Abi = serde_json::from_str(
+            r#"[
+            {"type":"function","name":"transfer","stateMutability":"nonpayable","inputs":[{"name":"to","type":"address"},{"name":"amount","type":"uint256"}],"outputs":[{"name":"","type":"bool"}]},
+            {"type":"event","name":"Transfer","anonymous":false,"inputs":[{"name":"from","type":"address","indexed":true},{"name":"to","type":"address","indexed":true},{"name":"amount","type":"uint256","indexed":false}]}
+        ]"#,
+        )
+        .unwrap();
+        let contract = ContractDoc {
+            name: "Token".to_string(),
+            kind: "contract".to_string(),
+            source: PathBuf::from("src/Token.sol"),
+            bases: vec!["Owned".to_string(), "IERC20".to_string()],
+            abi,
+            devdoc: serde_json::json!({
+                "title": "A simple token",
+                "methods": {
+                    "transfer(address,uint256)": {
+                        "params": { "to": "the recipient" },
+                        "returns": { "_0": "whether the transfer succeeded" }
+                    }
+                }
+            }),
+            userdoc: serde_json::json!({
+                "events": { "Transfer(address,address,uint256)": { "notice": "Emitted on transfers" } }
+            }),
+        };
+
+        let page = render_contract(&contract, &BTreeSet::from(["Owned"]));
+        assert!(page.starts_with("# Token\n"));
+        assert!(page.contains("**Inherits:** [Owned](Owned.md), IERC20"));
+        assert!(page.contains("**A simple token**"));
+        assert!(
+            page.contains("function transfer(address to, uint256 amount) external returns (bool);")
+        );
+        assert!(page.contains("|`to`|`address`|the recipient|"));
+        assert!(page.contains("||`bool`|whether the transfer succeeded|"));
+        assert!(page
+            .contains("event Transfer(address indexed from, address indexed to, uint256 amount);"));
+        assert!(page.contains("Emitted on transfers"));
+    }
+}
@@ -81,6 +81,7 @@ impl Cmd for FlattenArgs {
             compiler: Default::default(),
             names: false,
             sizes: false,
+            check_sizes: false,
             ignored_error_codes: vec![],
             no_auto_detect: false,
             use_solc: None,
@@ -90,6 +91,7 @@ impl Cmd for FlattenArgs {
             libraries: vec![],
             watch: Default::default(),
             via_ir: false,
+            hardhat_artifacts: false,
         };
 
         let config = Config::from(&build_args);
@@ -99,6 +101,7 @@ impl Cmd for FlattenArgs {
         let flattened = paths
             .flatten(&target_path)
             .map_err(|err| eyre::Error::msg(format!("failed to flatten the file: {}", err)))?;
+        let flattened = dedup_licenses_and_pragmas(&flattened);
 
         match output {
             Some(output) => {
@@ -112,3 +115,89 @@ impl Cmd for FlattenArgs {
         Ok(())
     }
 }
+
+/// Moves all license identifiers and pragmas of the flattened file to the top and removes
+/// duplicates.
+///
+/// `solc` rejects files with multiple SPDX license identifiers, so distinct licenses are combined
+/// into a single `AND` expression.
+pub fn dedup_licenses_and_pragmas(flattened: &str) -> String {
+    const SPDX: &str = "SPDX-License-Identifier:";
+
+    let mut licenses: Vec<&str> = Vec::new();
+    let mut pragmas: Vec<&str> = Vec::new();
+    let mut body = Vec::new();
+
+    for line in flattened.lines() {
+        let trimmed = line.trim();
+        if let Some(license) = trimmed.strip_prefix("//").and_then(|l| l.trim().strip_prefix(SPDX))
+        {
+            let license = license.trim();
+            if !licenses.contains(&license) {
+                licenses.push(license);
+            }
+        } else if trimmed.starts_with("pragma ") {
+            if !pragmas.contains(&trimmed) {
+                pragmas.push(trimmed);
+            }
+        } else {
+            body.push(line);
+        }
+    }
+
+    let mut out = String::new();
+    if !licenses.is_empty() {
+        out.push_str(&format!("// {} {}\n", SPDX, licenses.join(" AND ")));
+    }
+    for pragma in &pragmas {
+        out.push_str(pragma);
+        out.push('\n');
+    }
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out.push_str(body.join("\n").trim_start_matches('\n'));
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_dedup_licenses_and_pragmas() {
+        let flattened = r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract A {}
+
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+pragma abicoder v2;
+
+contract B {}
+
+// SPDX-License-Identifier: AGPL-3.0-only
+pragma solidity >=0.8.10;
+
+contract C is A, B {}
+"#;
+        assert_eq!(
+            dedup_licenses_and_pragmas(flattened),
+            r#"// SPDX-License-Identifier: MIT AND AGPL-3.0-only
+pragma solidity ^0.8.0;
+pragma abicoder v2;
+pragma solidity >=0.8.10;
+
+contract A {}
+
+
+contract B {}
+
+
+contract C is A, B {}
+"#
+        );
+    }
+}
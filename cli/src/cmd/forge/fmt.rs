@@ -7,7 +7,8 @@ use ethers::solc::ProjectPathsConfig;
 use rayon::prelude::*;
 use similar::{ChangeTag, TextDiff};
 
-use forge_fmt::{Formatter, FormatterConfig, Visitable};
+use forge_fmt::{Formatter, Visitable};
+use foundry_config::Config;
 
 use crate::cmd::Cmd;
 
@@ -39,17 +40,19 @@ impl Cmd for FmtArgs {
     type Output = ();
 
     fn run(self) -> eyre::Result<Self::Output> {
+        let project_root = self
+            .root
+            .unwrap_or_else(|| std::env::current_dir().expect("failed to get current directory"));
+        let config = Config::load_with_root(&project_root);
+
         let root = if let Some(path) = self.path {
             path
         } else {
-            let root = self.root.unwrap_or_else(|| {
-                std::env::current_dir().expect("failed to get current directory")
-            });
-            if !root.is_dir() {
+            if !project_root.is_dir() {
                 return Err(eyre::eyre!("Root path should be a directory"))
             }
 
-            ProjectPathsConfig::find_source_dir(&root)
+            ProjectPathsConfig::find_source_dir(&project_root)
         };
 
         let paths = if root.is_dir() {
@@ -74,7 +77,7 @@ impl Cmd for FmtArgs {
 
                 let mut output = String::new();
                 let mut formatter =
-                    Formatter::new(&mut output, &source, FormatterConfig::default());
+                    Formatter::new(&mut output, &source, config.fmt.clone());
 
                 source_unit.visit(&mut formatter).unwrap();
 
@@ -135,7 +138,7 @@ impl Cmd for FmtArgs {
             })
             .collect::<eyre::Result<Vec<Option<String>>>>()?;
 
-        if !diffs.is_empty() {
+        if diffs.iter().any(Option::is_some) {
             for (i, diff) in diffs.iter().flatten().enumerate() {
                 if i > 0 {
                     println!();
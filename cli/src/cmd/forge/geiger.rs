@@ -0,0 +1,211 @@
+//! geiger command
+
+use crate::cmd::Cmd;
+use clap::{Parser, ValueHint};
+use foundry_config::Config;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+/// Cheatcodes that reach outside of the EVM sandbox, like executing commands or accessing the
+/// file system and environment of the host
+pub const UNSAFE_CHEATCODES: &[&str] = &[
+    "ffi",
+    "setEnv",
+    "envBool",
+    "envUint",
+    "envInt",
+    "envAddress",
+    "envBytes32",
+    "envString",
+    "envBytes",
+    "readFile",
+    "readLine",
+    "writeFile",
+    "writeLine",
+    "closeFile",
+    "removeFile",
+];
+
+static RE_UNSAFE_CHEATCODE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(r"\.[ \t]*({})[ \t]*\(", UNSAFE_CHEATCODES.join("|"))).unwrap()
+});
+
+/// Command to scan sources for the usage of unsafe cheatcodes
+#[derive(Debug, Clone, Parser)]
+pub struct GeigerArgs {
+    #[clap(
+        help = "the files or directories to scan, defaults to the project's sources, tests and libraries",
+        value_hint = ValueHint::AnyPath
+    )]
+    paths: Vec<PathBuf>,
+    #[clap(
+        help = "the project's root path, default being the current working directory",
+        long,
+        value_hint = ValueHint::DirPath
+    )]
+    root: Option<PathBuf>,
+    #[clap(help = "exit with 1 if any unsafe cheatcode is found", long)]
+    check: bool,
+}
+
+/// A single usage of an unsafe cheatcode
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsafeCheatcode {
+    pub file: PathBuf,
+    /// 1-based line number
+    pub line: usize,
+    pub cheatcode: String,
+    pub source: String,
+}
+
+impl std::fmt::Display for UnsafeCheatcode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: `{}` {}", self.file.display(), self.line, self.cheatcode, self.source)
+    }
+}
+
+impl Cmd for GeigerArgs {
+    type Output = Vec<UnsafeCheatcode>;
+
+    fn run(self) -> eyre::Result<Self::Output> {
+        let root = self.root.unwrap_or_else(|| std::env::current_dir().unwrap());
+        let root = dunce::canonicalize(root)?;
+
+        let paths = if self.paths.is_empty() {
+            let paths = Config::load_with_root(&root).project_paths();
+            std::iter::once(paths.sources)
+                .chain(std::iter::once(paths.tests))
+                .chain(paths.libraries)
+                .filter(|p| p.exists())
+                .collect()
+        } else {
+            self.paths
+        };
+
+        let mut files = paths
+            .into_iter()
+            .flat_map(|path| {
+                if path.is_dir() {
+                    ethers::solc::utils::source_files(path)
+                } else {
+                    vec![path]
+                }
+            })
+            .collect::<Vec<_>>();
+        files.sort();
+        files.dedup();
+
+        let mut found = Vec::new();
+        for file in files {
+            let content = std::fs::read_to_string(&file)
+                .map_err(|err| eyre::eyre!("Failed to read `{}`: {}", file.display(), err))?;
+            let display_path = file.strip_prefix(&root).unwrap_or(&file);
+            found.extend(find_unsafe_cheatcodes(display_path, &content));
+        }
+
+        found.iter().for_each(|usage| println!("{}", usage));
+        if found.is_empty() {
+            println!("No unsafe cheatcodes found");
+        } else {
+            println!("Found {} usage(s) of unsafe cheatcodes", found.len());
+            if self.check {
+                std::process::exit(1);
+            }
+        }
+
+        Ok(found)
+    }
+}
+
+/// Returns all usages of [`UNSAFE_CHEATCODES`] in the given solidity source, skipping comments
+pub fn find_unsafe_cheatcodes(file: &Path, content: &str) -> Vec<UnsafeCheatcode> {
+    let mut found = Vec::new();
+    let mut in_block_comment = false;
+    for (idx, line) in content.lines().enumerate() {
+        let code = strip_comments(line, &mut in_block_comment);
+        for cap in RE_UNSAFE_CHEATCODE.captures_iter(&code) {
+            found.push(UnsafeCheatcode {
+                file: file.to_path_buf(),
+                line: idx + 1,
+                cheatcode: cap[1].to_string(),
+                source: line.trim().to_string(),
+            });
+        }
+    }
+    found
+}
+
+/// Removes `//` and `/* */` comments and the contents of string literals from the line,
+/// `in_block_comment` tracks whether the line starts inside a block comment
+fn strip_comments(line: &str, in_block_comment: &mut bool) -> String {
+    let mut code = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if *in_block_comment {
+            if c == '*' && chars.peek() == Some(&'/') {
+                chars.next();
+                *in_block_comment = false;
+            }
+            continue
+        }
+        match c {
+            '/' if chars.peek() == Some(&'/') => return code,
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                *in_block_comment = true;
+            }
+            '"' | '\'' => {
+                // Keep the quotes, but skip the contents up to the unescaped closing quote
+                code.push(c);
+                while let Some(next) = chars.next() {
+                    if next == '\\' {
+                        chars.next();
+                    } else if next == c {
+                        code.push(c);
+                        break
+                    }
+                }
+            }
+            _ => code.push(c),
+        }
+    }
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_find_unsafe_cheatcodes() {
+        let content = r#"
+contract ContractTest is DSTest {
+    function testFfi() public {
+        string[] memory inputs = new string[](1);
+        // vm.ffi(inputs);
+        /* vm.setEnv("a", "b");
+           vm.ffi(inputs); */
+        bytes memory res = vm.ffi(inputs);
+        cheats.writeFile("out.txt", "data"); vm . setEnv("a", "b");
+        vm.warp(1);
+        string memory url = "http://vm.ffi(inputs)"; vm.setEnv("/*", "b");
+        emit log('// \' vm.ffi(x)'); vm.ffi(inputs);
+    }
+}
+"#;
+        let found = find_unsafe_cheatcodes(Path::new("test/Contract.t.sol"), content);
+        let found =
+            found.into_iter().map(|usage| (usage.line, usage.cheatcode)).collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                (8, "ffi".to_string()),
+                (9, "writeFile".to_string()),
+                (9, "setEnv".to_string()),
+                (11, "setEnv".to_string()),
+                (12, "ffi".to_string())
+            ]
+        );
+    }
+}
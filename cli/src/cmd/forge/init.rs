@@ -25,35 +25,90 @@ pub struct InitArgs {
     value_hint = ValueHint::DirPath
     )]
     root: Option<PathBuf>,
-    #[clap(help = "optional solidity template to start from", long, short)]
-    template: Option<String>,
     #[clap(
-        help = "initialize without creating a git repository",
-        conflicts_with = "template",
-        long
+        help = "optional template to start from, either a built-in template (`library`, `script`, `erc20`) or a git repository",
+        long,
+        short
     )]
+    template: Option<String>,
+    #[clap(help = "initialize without creating a git repository", long)]
     no_git: bool,
-    #[clap(help = "do not create initial commit", conflicts_with = "template", long)]
+    #[clap(help = "do not create initial commit", long)]
     no_commit: bool,
     #[clap(help = "do not print messages", short, long)]
     quiet: bool,
-    #[clap(
-        help = "run without installing libs from the network",
-        conflicts_with = "template",
-        long,
-        alias = "no-deps"
-    )]
+    #[clap(help = "run without installing libs from the network", long, alias = "no-deps")]
     offline: bool,
-    #[clap(help = "force init if project dir is not empty", conflicts_with = "template", long)]
+    #[clap(help = "force init if project dir is not empty", long)]
     force: bool,
     #[clap(
         help = "initialize .vscode/settings.json file with solidity settings and generate a remappings.txt file.",
-        conflicts_with = "template",
         long
     )]
     vscode: bool,
 }
 
+/// The templates that ship with forge and can be used via `forge init --template <name>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuiltinTemplate {
+    /// A solidity library with fuzz tests
+    Library,
+    /// A contract that is deployed by a script contract
+    Script,
+    /// An ERC-20 token
+    Erc20,
+}
+
+impl BuiltinTemplate {
+    /// Returns the sources of the template as `(path, content)` pairs, relative to the project
+    /// root
+    fn files(&self) -> Vec<(&'static str, &'static str)> {
+        match self {
+            BuiltinTemplate::Library => vec![
+                ("src/Math.sol", include_str!("../../../../assets/templates/library/Math.sol")),
+                (
+                    "src/test/Math.t.sol",
+                    include_str!("../../../../assets/templates/library/Math.t.sol"),
+                ),
+            ],
+            BuiltinTemplate::Script => vec![
+                (
+                    "src/Counter.sol",
+                    include_str!("../../../../assets/templates/script/Counter.sol"),
+                ),
+                (
+                    "src/script/Counter.s.sol",
+                    include_str!("../../../../assets/templates/script/Counter.s.sol"),
+                ),
+                (
+                    "src/test/Counter.t.sol",
+                    include_str!("../../../../assets/templates/script/Counter.t.sol"),
+                ),
+            ],
+            BuiltinTemplate::Erc20 => vec![
+                ("src/Token.sol", include_str!("../../../../assets/templates/erc20/Token.sol")),
+                (
+                    "src/test/Token.t.sol",
+                    include_str!("../../../../assets/templates/erc20/Token.t.sol"),
+                ),
+            ],
+        }
+    }
+}
+
+impl FromStr for BuiltinTemplate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "library" | "lib" => Ok(BuiltinTemplate::Library),
+            "script" => Ok(BuiltinTemplate::Script),
+            "erc20" | "erc-20" => Ok(BuiltinTemplate::Erc20),
+            s => Err(format!("Unknown template `{}`", s)),
+        }
+    }
+}
+
 impl Cmd for InitArgs {
     type Output = ();
 
@@ -67,9 +122,16 @@ impl Cmd for InitArgs {
         }
         let root = dunce::canonicalize(root)?;
 
-        // if a template is provided, then this command is just an alias to `git clone <url>
+        let builtin = template.as_deref().and_then(|t| t.parse::<BuiltinTemplate>().ok());
+
+        // if a git template is provided, then this command is just an alias to `git clone <url>
         // <path>`
-        if let Some(template) = template {
+        if let Some(template) = template.filter(|_| builtin.is_none()) {
+            if no_git || no_commit || offline || force || vscode {
+                eyre::bail!(
+                    "`--no-git`, `--no-commit`, `--offline`, `--force` and `--vscode` can only be used with the built-in templates"
+                )
+            }
             let template = if template.starts_with("https://") {
                 template
             } else {
@@ -100,15 +162,42 @@ impl Cmd for InitArgs {
             let test = src.join("test");
             std::fs::create_dir_all(&test)?;
 
-            // write the contract file
-            let contract_path = src.join("Contract.sol");
-            std::fs::write(contract_path, include_str!("../../../../assets/ContractTemplate.sol"))?;
-            // write the tests
-            let contract_path = test.join("Contract.t.sol");
-            std::fs::write(
-                contract_path,
-                include_str!("../../../../assets/ContractTemplate.t.sol"),
-            )?;
+            if let Some(builtin) = builtin {
+                // write the sources of the template
+                for (path, content) in builtin.files() {
+                    write_file(&root.join(path), content)?;
+                }
+                // write the cheatcode interface used by the tests
+                write_file(
+                    &test.join("utils/Vm.sol"),
+                    include_str!("../../../../assets/templates/Vm.sol"),
+                )?;
+                // write the CI workflow
+                write_file(
+                    &root.join(".github/workflows/test.yml"),
+                    include_str!("../../../../assets/templates/ci.yml"),
+                )?;
+                let gitignore_path = root.join(".gitignore");
+                if !gitignore_path.exists() {
+                    std::fs::write(
+                        gitignore_path,
+                        include_str!("../../../../assets/.gitignoreTemplate"),
+                    )?;
+                }
+            } else {
+                // write the contract file
+                let contract_path = src.join("Contract.sol");
+                std::fs::write(
+                    contract_path,
+                    include_str!("../../../../assets/ContractTemplate.sol"),
+                )?;
+                // write the tests
+                let contract_path = test.join("Contract.t.sol");
+                std::fs::write(
+                    contract_path,
+                    include_str!("../../../../assets/ContractTemplate.t.sol"),
+                )?;
+            }
 
             let dest = root.join(Config::FILE_NAME);
             if !dest.exists() {
@@ -120,7 +209,13 @@ impl Cmd for InitArgs {
                         .remappings
                         .push("ds-test/=lib/ds-test/src/".parse::<Remapping>().unwrap().into());
                 }
-                std::fs::write(dest, config.to_string_pretty()?)?;
+                let mut content = config.to_string_pretty()?;
+                // add the profiles selected by the CI workflow and for local development
+                if config.profile == Config::DEFAULT_PROFILE {
+                    content.push_str("\n\n");
+                    content.push_str(include_str!("../../../../assets/templates/profiles.toml"));
+                }
+                std::fs::write(dest, content)?;
             }
 
             // sets up git
@@ -150,6 +245,15 @@ impl Cmd for InitArgs {
     }
 }
 
+/// Writes the `content` to the `path`, creating all parent dirs
+fn write_file(path: &Path, content: &str) -> eyre::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
 /// initializes the root dir
 fn init_git_repo(root: &Path, no_commit: bool) -> eyre::Result<()> {
     let is_git = Command::new("git")
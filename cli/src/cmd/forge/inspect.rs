@@ -8,8 +8,11 @@ use crate::{
     opts::forge::CompilerArgs,
 };
 use clap::Parser;
-use ethers::prelude::artifacts::output_selection::{
-    ContractOutputSelection, EvmOutputSelection, EwasmOutputSelection,
+use ethers::{
+    abi::Abi,
+    prelude::artifacts::output_selection::{
+        ContractOutputSelection, EvmOutputSelection, EwasmOutputSelection,
+    },
 };
 use serde_json::{to_value, Value};
 
@@ -95,6 +98,12 @@ pub struct InspectArgs {
     #[clap(help = "the contract artifact field to inspect")]
     pub mode: ContractArtifactFields,
 
+    #[clap(
+        long,
+        help = "pretty print the abi as a solidity interface and the method identifiers, gas estimates and storage layout as tables instead of json"
+    )]
+    pub pretty: bool,
+
     /// All build arguments are supported
     #[clap(flatten)]
     build: build::BuildArgs,
@@ -103,7 +112,7 @@ pub struct InspectArgs {
 impl Cmd for InspectArgs {
     type Output = ();
     fn run(self) -> eyre::Result<Self::Output> {
-        let InspectArgs { contract, mode, build } = self;
+        let InspectArgs { contract, mode, pretty, build } = self;
 
         // Map mode to ContractOutputSelection
         let mut cos = build.compiler.extra_output.unwrap_or_default();
@@ -168,6 +177,10 @@ impl Cmd for InspectArgs {
 
         // Match on ContractArtifactFields and Pretty Print
         match mode {
+            ContractArtifactFields::Abi if pretty => {
+                let abi: Abi = serde_json::from_value(to_value(&artifact.abi)?)?;
+                println!("{}", foundry_utils::abi_to_solidity(&abi, &contract)?);
+            }
             ContractArtifactFields::Abi => {
                 println!("{}", serde_json::to_string_pretty(&to_value(&artifact.abi)?)?);
             }
@@ -197,15 +210,24 @@ impl Cmd for InspectArgs {
                     ))?
                 );
             }
+            ContractArtifactFields::MethodIdentifiers if pretty => {
+                print_method_identifiers(&to_value(&artifact.method_identifiers)?);
+            }
             ContractArtifactFields::MethodIdentifiers => {
                 println!(
                     "{}",
                     serde_json::to_string_pretty(&to_value(&artifact.method_identifiers)?)?
                 );
             }
+            ContractArtifactFields::GasEstimates if pretty => {
+                print_gas_estimates(&to_value(&artifact.gas_estimates)?);
+            }
             ContractArtifactFields::GasEstimates => {
                 println!("{}", serde_json::to_string_pretty(&to_value(&artifact.gas_estimates)?)?);
             }
+            ContractArtifactFields::StorageLayout if pretty => {
+                print_storage_layout(&to_value(&artifact.storage_layout)?);
+            }
             ContractArtifactFields::StorageLayout => {
                 println!("{}", serde_json::to_string_pretty(&to_value(&artifact.storage_layout)?)?);
             }
@@ -247,3 +269,65 @@ impl Cmd for InspectArgs {
         Ok(())
     }
 }
+
+/// Prints the `selector signature` pairs of the method identifiers, ordered by signature
+fn print_method_identifiers(identifiers: &Value) {
+    if let Some(identifiers) = identifiers.as_object() {
+        println!("{: <10} Signature", "Selector");
+        for (signature, selector) in identifiers {
+            println!("{: <10} {}", selector.as_str().unwrap_or_default(), signature);
+        }
+    }
+}
+
+/// Prints the creation costs and the costs of all external and internal functions
+fn print_gas_estimates(estimates: &Value) {
+    if let Some(creation) = estimates.get("creation").and_then(Value::as_object) {
+        println!("Creation");
+        for (name, cost) in creation {
+            println!("  {: <20} {}", name, to_table_cell(cost));
+        }
+    }
+    for section in ["external", "internal"] {
+        if let Some(functions) = estimates.get(section).and_then(Value::as_object) {
+            if functions.is_empty() {
+                continue
+            }
+            println!("{}{}", section[..1].to_uppercase(), &section[1..]);
+            for (signature, cost) in functions {
+                println!("  {: <50} {}", signature, to_table_cell(cost));
+            }
+        }
+    }
+}
+
+/// Prints the storage layout as a table of `name | type | slot | offset | contract`
+fn print_storage_layout(layout: &Value) {
+    let types = layout.get("types");
+    println!("{: <25} {: <35} {: <6} {: <6} Contract", "Name", "Type", "Slot", "Offset");
+    for slot in layout.get("storage").and_then(Value::as_array).into_iter().flatten() {
+        let ty = slot.get("type").and_then(Value::as_str).unwrap_or_default();
+        // prefer the human readable label of the type if it's available
+        let ty = types
+            .and_then(|types| types.get(ty))
+            .and_then(|t| t.get("label"))
+            .and_then(Value::as_str)
+            .unwrap_or(ty);
+        println!(
+            "{: <25} {: <35} {: <6} {: <6} {}",
+            slot.get("label").map(to_table_cell).unwrap_or_default(),
+            ty,
+            slot.get("slot").map(to_table_cell).unwrap_or_default(),
+            slot.get("offset").map(to_table_cell).unwrap_or_default(),
+            slot.get("contract").map(to_table_cell).unwrap_or_default(),
+        );
+    }
+}
+
+/// Returns the value as it should be displayed in a table, without quotes for strings
+fn to_table_cell(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
@@ -189,3 +189,53 @@ fn install_as_submodule(dep: &Dependency, libs: &Path, no_commit: bool) -> eyre:
 
     Ok(())
 }
+
+/// Removes all remappings from the project's `remappings.txt` that point into the dependency's
+/// directory in `lib`
+pub(crate) fn remove_remappings(root: impl AsRef<Path>, dep: &Dependency) -> eyre::Result<()> {
+    let remappings_txt = root.as_ref().join("remappings.txt");
+    if !remappings_txt.is_file() {
+        return Ok(())
+    }
+    let content = std::fs::read_to_string(&remappings_txt)?;
+    let filtered = filter_remappings(&content, &dep.name);
+    if filtered != content {
+        std::fs::write(&remappings_txt, filtered)?;
+    }
+    Ok(())
+}
+
+/// Returns the `remappings.txt` content without the remappings that target `lib/<name>`, each
+/// line ends with a newline
+fn filter_remappings(content: &str, name: &str) -> String {
+    let lib = format!("lib/{}", name);
+    content
+        .lines()
+        .filter(|line| {
+            line.split_once('=')
+                .map(|(_, path)| {
+                    let path = path.trim().trim_start_matches("./");
+                    path != lib && !path.starts_with(&format!("{}/", lib))
+                })
+                .unwrap_or(true)
+        })
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_filter_dependency_remappings() {
+        let content =
+            "ds-test/=lib/ds-test/src/\nsolmate/=lib/solmate/src/\nother/=./lib/ds-test/\n";
+        assert_eq!(filter_remappings(content, "ds-test"), "solmate/=lib/solmate/src/\n");
+        assert_eq!(filter_remappings(content, "ds"), content);
+        assert_eq!(
+            filter_remappings("solmate/=lib/solmate/src/", "ds-test"),
+            "solmate/=lib/solmate/src/\n"
+        );
+    }
+}
@@ -39,17 +39,24 @@
 
 pub mod bind;
 pub mod build;
+pub mod cache;
 pub mod config;
 pub mod create;
+pub mod doc;
 pub mod flatten;
 pub mod fmt;
+pub mod geiger;
 pub mod init;
 pub mod inspect;
 pub mod install;
+pub mod mutate;
+pub mod node;
 pub mod remappings;
 pub mod run;
+pub mod selectors;
 pub mod snapshot;
 pub mod test;
 pub mod tree;
 pub mod verify;
+pub mod vyper;
 pub mod watch;
@@ -0,0 +1,676 @@
+//! mutate command
+
+use crate::cmd::Cmd;
+use clap::{Parser, ValueHint};
+use ethers::utils::keccak256;
+use foundry_config::Config;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    ops::Range,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::{mpsc, Arc, Mutex},
+    thread,
+};
+
+/// The binary operators that are swapped and their replacements
+const OPERATOR_SWAPS: &[(&str, &str)] = &[
+    ("+", "-"),
+    ("-", "+"),
+    ("*", "/"),
+    ("/", "*"),
+    ("%", "*"),
+    ("==", "!="),
+    ("!=", "=="),
+    ("<", ">="),
+    (">", "<="),
+    ("<=", ">"),
+    (">=", "<"),
+    ("&&", "||"),
+    ("||", "&&"),
+];
+
+/// Command to check how many mutations of the sources are caught by the tests
+#[derive(Debug, Clone, Parser)]
+pub struct MutateArgs {
+    #[clap(
+        help = "the source files to mutate, defaults to all of the project's sources",
+        value_hint = ValueHint::FilePath
+    )]
+    paths: Vec<PathBuf>,
+    #[clap(
+        help = "the project's root path, default being the current working directory",
+        long,
+        value_hint = ValueHint::DirPath
+    )]
+    root: Option<PathBuf>,
+    #[clap(
+        help = "the number of mutants to test in parallel, defaults to the number of CPUs",
+        long,
+        short
+    )]
+    jobs: Option<usize>,
+    #[clap(help = "test all mutants again instead of reusing the cached outcomes", long)]
+    no_cache: bool,
+    #[clap(help = "print the outcome of each mutant as json", long)]
+    json: bool,
+    #[clap(
+        help = "arguments passed to `forge test`, e.g. `-- --match-contract Token`",
+        last = true
+    )]
+    test_args: Vec<String>,
+}
+
+/// The kinds of mutations applied to the sources
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MutationKind {
+    /// Swaps a binary operator, e.g. `+` with `-`
+    OperatorSwap,
+    /// Negates the condition of a `require`
+    RequireNegation,
+    /// Deletes a statement of a function body
+    StatementDeletion,
+}
+
+impl fmt::Display for MutationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MutationKind::OperatorSwap => f.write_str("operator swap"),
+            MutationKind::RequireNegation => f.write_str("require negation"),
+            MutationKind::StatementDeletion => f.write_str("statement deletion"),
+        }
+    }
+}
+
+/// A single mutation of a source file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Mutant {
+    pub file: PathBuf,
+    /// 1-based line number
+    pub line: usize,
+    pub kind: MutationKind,
+    /// The byte range of the source that is replaced
+    pub span: Range<usize>,
+    pub original: String,
+    pub replacement: String,
+}
+
+impl Mutant {
+    /// Returns the mutated source
+    pub fn apply(&self, source: &str) -> String {
+        format!("{}{}{}", &source[..self.span.start], self.replacement, &source[self.span.end..])
+    }
+}
+
+impl fmt::Display for Mutant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}: `{}` -> `{}`",
+            self.file.display(),
+            self.line,
+            self.kind,
+            self.original,
+            self.replacement
+        )
+    }
+}
+
+/// The outcome of running the tests against a mutant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MutantOutcome {
+    /// At least one test failed
+    Killed,
+    /// All tests passed
+    Survived,
+    /// The mutant does not compile
+    Invalid,
+}
+
+/// The outcomes of previous runs, by the hash of the mutated project
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct MutantsCache {
+    outcomes: BTreeMap<String, MutantOutcome>,
+}
+
+impl MutantsCache {
+    const FILE_NAME: &'static str = "mutants.json";
+
+    fn read(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn write(&self, path: &Path) -> eyre::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+impl Cmd for MutateArgs {
+    type Output = Vec<(Mutant, MutantOutcome)>;
+
+    fn run(self) -> eyre::Result<Self::Output> {
+        let root = self.root.clone().unwrap_or_else(|| std::env::current_dir().unwrap());
+        let root = dunce::canonicalize(root)?;
+        let config = Config::load_with_root(&root).sanitized();
+
+        let files = if self.paths.is_empty() {
+            ethers::solc::utils::source_files(&config.src)
+        } else {
+            // relative paths are relative to the root, like the paths of the config
+            self.paths
+                .iter()
+                .map(|path| {
+                    dunce::canonicalize(root.join(path)).map_err(|err| {
+                        eyre::eyre!("Failed to resolve `{}`: {}", path.display(), err)
+                    })
+                })
+                .collect::<eyre::Result<_>>()?
+        };
+
+        let mut sources = BTreeMap::new();
+        let mut mutants = Vec::new();
+        for file in files {
+            let content = fs::read_to_string(&file)
+                .map_err(|err| eyre::eyre!("Failed to read `{}`: {}", file.display(), err))?;
+            let file = file
+                .strip_prefix(&root)
+                .map_err(|_| eyre::eyre!("`{}` is not part of the project", file.display()))?
+                .to_path_buf();
+            mutants.extend(find_mutants(&file, &content));
+            sources.insert(file, content);
+        }
+        if mutants.is_empty() {
+            println!("No mutants found");
+            return Ok(Vec::new())
+        }
+
+        // mutants of a project whose sources and tests didn't change have the same outcomes
+        let sources = Arc::new(sources);
+        let fingerprint = project_fingerprint(&config, &self.test_args)?;
+        let mutant_key = |mutant: &Mutant| {
+            let mut data = fingerprint.clone();
+            data.extend(mutant.file.to_string_lossy().as_bytes());
+            data.extend(mutant.apply(&sources[&mutant.file]).as_bytes());
+            hex::encode(keccak256(data))
+        };
+
+        let cache_path = config.cache_path.join(MutantsCache::FILE_NAME);
+        let mut cache =
+            if self.no_cache { MutantsCache::default() } else { MutantsCache::read(&cache_path) };
+
+        let mut outcomes: BTreeMap<usize, MutantOutcome> = BTreeMap::new();
+        let mut pending = Vec::new();
+        for (idx, mutant) in mutants.iter().enumerate() {
+            match cache.outcomes.get(&mutant_key(mutant)) {
+                Some(outcome) => {
+                    outcomes.insert(idx, *outcome);
+                }
+                None => pending.push((idx, mutant.clone())),
+            }
+        }
+
+        if !self.json {
+            println!(
+                "Testing {} mutants ({} cached) of {} files",
+                pending.len(),
+                outcomes.len(),
+                sources.len()
+            );
+        }
+
+        let jobs = self.jobs.unwrap_or_else(rayon::current_num_threads).max(1).min(pending.len());
+        let queue = Arc::new(Mutex::new(pending.into_iter()));
+        let (tx, rx) = mpsc::channel();
+        for _ in 0..jobs {
+            let (root, config, sources, queue, tx) =
+                (root.clone(), config.clone(), sources.clone(), queue.clone(), tx.clone());
+            let test_args = self.test_args.clone();
+            thread::spawn(move || {
+                let workspace = match Workspace::new(&root, &config) {
+                    Ok(workspace) => workspace,
+                    Err(err) => {
+                        let _ = tx.send(Err(err));
+                        return
+                    }
+                };
+                loop {
+                    let next = queue.lock().unwrap().next();
+                    let (idx, mutant) = match next {
+                        Some(next) => next,
+                        None => return,
+                    };
+                    let outcome = workspace.test(&mutant, &sources[&mutant.file], &test_args);
+                    if tx.send(outcome.map(|outcome| (idx, outcome))).is_err() {
+                        return
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        for result in rx {
+            let (idx, outcome) = result?;
+            let mutant = &mutants[idx];
+            if !self.json {
+                println!("{:?}: {}", outcome, mutant);
+            }
+            cache.outcomes.insert(mutant_key(mutant), outcome);
+            outcomes.insert(idx, outcome);
+        }
+        cache.write(&cache_path)?;
+
+        let results: Vec<(Mutant, MutantOutcome)> =
+            mutants.into_iter().enumerate().map(|(idx, mutant)| (mutant, outcomes[&idx])).collect();
+
+        if self.json {
+            let json: Vec<_> = results
+                .iter()
+                .map(
+                    |(mutant, outcome)| serde_json::json!({ "mutant": mutant, "outcome": outcome }),
+                )
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&json)?);
+        } else {
+            print_report(&results);
+        }
+
+        Ok(results)
+    }
+}
+
+/// Prints the surviving mutants and the share of the valid mutants the tests killed
+fn print_report(results: &[(Mutant, MutantOutcome)]) {
+    let count = |expected| results.iter().filter(|(_, outcome)| *outcome == expected).count();
+    let (killed, survived, invalid) = (
+        count(MutantOutcome::Killed),
+        count(MutantOutcome::Survived),
+        count(MutantOutcome::Invalid),
+    );
+
+    if survived > 0 {
+        println!("\nSurviving mutants:");
+        results
+            .iter()
+            .filter(|(_, outcome)| *outcome == MutantOutcome::Survived)
+            .for_each(|(mutant, _)| println!("    {}", mutant));
+    }
+
+    println!(
+        "\n{} killed, {} survived, {} invalid of {} mutants",
+        killed,
+        survived,
+        invalid,
+        results.len()
+    );
+    if killed + survived > 0 {
+        println!("Mutation score: {:.2}%", killed as f64 / (killed + survived) as f64 * 100.0);
+    }
+}
+
+/// Returns the data the outcomes of mutants depend on: the sources and tests of the project and
+/// the arguments of `forge test`
+fn project_fingerprint(config: &Config, test_args: &[String]) -> eyre::Result<Vec<u8>> {
+    let mut files: Vec<_> = [&config.src, &config.test]
+        .into_iter()
+        .flat_map(|dir| ethers::solc::utils::source_files(dir.as_path()))
+        .collect();
+    files.sort();
+    files.dedup();
+
+    let mut data = test_args.join(" ").into_bytes();
+    for file in files {
+        data.extend(file.to_string_lossy().as_bytes());
+        data.extend(fs::read(&file)?);
+    }
+    Ok(keccak256(data).to_vec())
+}
+
+/// A copy of the project in which a worker tests its mutants, removed once it is dropped
+struct Workspace {
+    dir: tempfile::TempDir,
+}
+
+impl Workspace {
+    /// Copies the project to a temporary directory, skipping the build artifacts and caches
+    fn new(root: &Path, config: &Config) -> eyre::Result<Self> {
+        let dir = tempfile::Builder::new().prefix("forge-mutate-").tempdir()?;
+        let skipped = [root.join(".git"), config.out.clone(), config.cache_path.clone()];
+
+        let walker = walkdir::WalkDir::new(root)
+            .into_iter()
+            .filter_entry(|entry| !skipped.iter().any(|skip| entry.path() == skip));
+        for entry in walker {
+            let entry = entry?;
+            let target = dir.path().join(entry.path().strip_prefix(root)?);
+            if entry.file_type().is_dir() {
+                fs::create_dir_all(&target)?;
+            } else {
+                fs::copy(entry.path(), &target)?;
+            }
+        }
+        Ok(Self { dir })
+    }
+
+    fn root(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Builds and tests the project with the mutant applied, then restores the original source
+    fn test(
+        &self,
+        mutant: &Mutant,
+        source: &str,
+        test_args: &[String],
+    ) -> eyre::Result<MutantOutcome> {
+        let file = self.root().join(&mutant.file);
+        fs::write(&file, mutant.apply(source))?;
+
+        let outcome = if !self.forge(["build"]).status()?.success() {
+            MutantOutcome::Invalid
+        } else if self.forge(["test"]).args(test_args).status()?.success() {
+            MutantOutcome::Survived
+        } else {
+            MutantOutcome::Killed
+        };
+
+        fs::write(&file, source)?;
+        Ok(outcome)
+    }
+
+    /// Returns a command that runs forge in the workspace
+    fn forge<'a>(&self, args: impl IntoIterator<Item = &'a str>) -> Command {
+        let mut cmd = Command::new(std::env::current_exe().expect("failed to find forge"));
+        cmd.args(args)
+            .arg("--root")
+            .arg(self.root())
+            .current_dir(self.root())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        cmd
+    }
+}
+
+/// The kinds of blocks whose braces are tracked to find the statements of function bodies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Block {
+    /// A contract, struct, or a block inside a function body if `true`
+    Scope(bool),
+    /// The braces of call options or a struct literal, e.g. `call{value: 1}`
+    Expression,
+}
+
+/// Returns all mutants of the given solidity source
+pub fn find_mutants(file: &Path, source: &str) -> Vec<Mutant> {
+    let code = code_mask(source);
+    let bytes = source.as_bytes();
+    let is_code = |idx: usize| code.get(idx).copied().unwrap_or(false);
+    let is_ident = |byte: u8| byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'$';
+    // the previous code character before `idx` that isn't whitespace
+    let prev_code_char = |idx: usize| {
+        (0..idx).rev().find(|i| is_code(*i) && !bytes[*i].is_ascii_whitespace()).map(|i| bytes[i])
+    };
+    let word_at = |idx: usize, word: &str| {
+        source[idx..].starts_with(word) &&
+            (idx == 0 || !is_ident(bytes[idx - 1])) &&
+            !bytes.get(idx + word.len()).copied().map_or(false, is_ident)
+    };
+
+    let mut spans: Vec<(MutationKind, Range<usize>, String)> = Vec::new();
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut statement_start = 0;
+    let mut parens = 0usize;
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if !is_code(idx) {
+            idx += 1;
+            continue
+        }
+        let byte = bytes[idx];
+
+        // pragma and import directives are not mutated
+        if word_at(idx, "pragma") || word_at(idx, "import") {
+            idx = source[idx..].find(';').map_or(bytes.len(), |end| idx + end + 1);
+            statement_start = idx;
+            continue
+        }
+
+        if word_at(idx, "require") {
+            if let Some(condition) = require_condition(source, &code, idx + "require".len()) {
+                let replacement = format!("!({})", &source[condition.clone()]);
+                spans.push((MutationKind::RequireNegation, condition, replacement));
+            }
+        }
+
+        match byte {
+            b'(' | b'[' => parens += 1,
+            b')' | b']' => parens = parens.saturating_sub(1),
+            b'{' => {
+                let header = &source[statement_start..idx];
+                let block = if is_expression_brace(source, idx) {
+                    Block::Expression
+                } else {
+                    let in_body = matches!(blocks.last(), Some(Block::Scope(true)));
+                    Block::Scope(in_body || is_function_header(header))
+                };
+                if block != Block::Expression {
+                    statement_start = idx + 1;
+                }
+                blocks.push(block);
+            }
+            b'}' => {
+                if blocks.pop() != Some(Block::Expression) {
+                    statement_start = idx + 1;
+                }
+            }
+            b';' if parens == 0 => {
+                if blocks.last() == Some(&Block::Scope(true)) {
+                    let statement = &source[statement_start..=idx];
+                    let start = statement_start + (statement.len() - statement.trim_start().len());
+                    if start < idx {
+                        spans.push((
+                            MutationKind::StatementDeletion,
+                            start..idx + 1,
+                            String::new(),
+                        ));
+                    }
+                }
+                statement_start = idx + 1;
+            }
+            _ => {}
+        }
+
+        // binary operators are runs of operator characters, e.g. `>=`, but not `>>=`
+        const OPERATOR_CHARS: &[u8] = b"+-*/%<>=!&|^~";
+        if OPERATOR_CHARS.contains(&byte) {
+            let end = (idx..bytes.len())
+                .find(|i| !is_code(*i) || !OPERATOR_CHARS.contains(&bytes[*i]))
+                .unwrap_or(bytes.len());
+            let operator = &source[idx..end];
+            // an operator after an operand is binary, `-` in `x = -1` or `return -1` is unary
+            let is_binary = match prev_code_char(idx) {
+                Some(prev) if is_ident(prev) => {
+                    let before = source[..idx].trim_end();
+                    let word = before.trim_end_matches(|c: char| is_ident(c as u8));
+                    &before[word.len()..] != "return"
+                }
+                Some(prev) => prev == b')' || prev == b']',
+                None => false,
+            };
+            if let Some((_, replacement)) =
+                OPERATOR_SWAPS.iter().find(|(op, _)| *op == operator).filter(|_| is_binary)
+            {
+                spans.push((MutationKind::OperatorSwap, idx..end, replacement.to_string()));
+            }
+            idx = end;
+            continue
+        }
+
+        idx += 1;
+    }
+
+    spans
+        .into_iter()
+        .map(|(kind, span, replacement)| Mutant {
+            file: file.to_path_buf(),
+            line: source[..span.start].matches('\n').count() + 1,
+            kind,
+            original: source[span.clone()].to_string(),
+            span,
+            replacement,
+        })
+        .collect()
+}
+
+/// Returns the range of the condition of the `require` call whose name ends at `idx`
+fn require_condition(source: &str, code: &[bool], idx: usize) -> Option<Range<usize>> {
+    let bytes = source.as_bytes();
+    let open = idx + source[idx..].len() - source[idx..].trim_start().len();
+    if bytes.get(open) != Some(&b'(') {
+        return None
+    }
+    let mut depth = 0usize;
+    for end in open + 1..bytes.len() {
+        if !code[end] {
+            continue
+        }
+        match bytes[end] {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' if depth > 0 => depth -= 1,
+            b',' | b')' if depth == 0 => {
+                let condition = &source[open + 1..end];
+                let start = open + 1 + (condition.len() - condition.trim_start().len());
+                let end = open + 1 + condition.trim_end().len();
+                return (start < end).then(|| start..end)
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Returns `true` if the brace at `idx` opens call options or a struct literal, which start with
+/// a `name:`
+fn is_expression_brace(source: &str, idx: usize) -> bool {
+    let rest = source[idx + 1..].trim_start();
+    let name_len = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(0);
+    name_len > 0 && {
+        let after = rest[name_len..].trim_start();
+        after.starts_with(':') && !after.starts_with(":=")
+    }
+}
+
+/// Returns `true` if the header of a block declares a function like body
+fn is_function_header(header: &str) -> bool {
+    header.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).any(|word| {
+        matches!(word, "function" | "modifier" | "constructor" | "fallback" | "receive")
+    })
+}
+
+/// Returns whether each byte of the source is code, i.e. not part of a comment or string literal
+fn code_mask(source: &str) -> Vec<bool> {
+    let bytes = source.as_bytes();
+    let mut mask = vec![true; bytes.len()];
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let end = match bytes[idx] {
+            b'/' if bytes.get(idx + 1) == Some(&b'/') => {
+                source[idx..].find('\n').map_or(bytes.len(), |end| idx + end)
+            }
+            b'/' if bytes.get(idx + 1) == Some(&b'*') => {
+                source[idx + 2..].find("*/").map_or(bytes.len(), |end| idx + 2 + end + 2)
+            }
+            quote @ (b'"' | b'\'') => {
+                let mut end = idx + 1;
+                while end < bytes.len() && bytes[end] != quote {
+                    end += if bytes[end] == b'\\' { 2 } else { 1 };
+                }
+                (end + 1).min(bytes.len())
+            }
+            _ => {
+                idx += 1;
+                continue
+            }
+        };
+        mask[idx..end].iter_mut().for_each(|is_code| *is_code = false);
+        idx = end;
+    }
+    mask
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mutations(source: &str) -> Vec<(MutationKind, String, String)> {
+        find_mutants(Path::new("src/Contract.sol"), source)
+            .into_iter()
+            .map(|mutant| (mutant.kind, mutant.original, mutant.replacement))
+            .collect()
+    }
+
+    #[test]
+    fn can_find_mutants() {
+        let source = r#"
+pragma solidity >=0.8.0;
+import "./Other.sol";
+
+contract Counter {
+    struct Info { uint256 a; }
+    uint256 public count; // count * 2
+    function add(uint256 x) public {
+        require(x >= 1, "x must be > 0");
+        count = count + x;
+        payable(msg.sender).call{value: 0}("/* not a comment */");
+    }
+}
+"#;
+        let found = mutations(source);
+        assert_eq!(
+            found,
+            vec![
+                (MutationKind::RequireNegation, "x >= 1".to_string(), "!(x >= 1)".to_string()),
+                (MutationKind::OperatorSwap, ">=".to_string(), "<".to_string()),
+                (
+                    MutationKind::StatementDeletion,
+                    "require(x >= 1, \"x must be > 0\");".to_string(),
+                    "".to_string()
+                ),
+                (MutationKind::OperatorSwap, "+".to_string(), "-".to_string()),
+                (MutationKind::StatementDeletion, "count = count + x;".to_string(), "".to_string()),
+                (
+                    MutationKind::StatementDeletion,
+                    "payable(msg.sender).call{value: 0}(\"/* not a comment */\");".to_string(),
+                    "".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn can_apply_mutant() {
+        let source = "contract C { function f(int a) public returns (int) { return -a * 2; } }";
+        let mutants = find_mutants(Path::new("src/C.sol"), source);
+        // the unary minus is not swapped
+        assert_eq!(mutants.len(), 2);
+        assert_eq!(
+            mutants[0].apply(source),
+            "contract C { function f(int a) public returns (int) { return -a / 2; } }"
+        );
+        assert_eq!(mutants[0].line, 1);
+        assert_eq!(
+            mutants[1].apply(source),
+            "contract C { function f(int a) public returns (int) {  } }"
+        );
+    }
+}
@@ -0,0 +1,815 @@
+//! The chain of the node
+
+use super::{
+    state::{AccountState, NodeState},
+    transaction::{unsigned_transaction_hash, Transaction, TransactionRequest},
+};
+use ethers::{
+    types::{Address, Bytes, H256, U256},
+    utils::keccak256,
+};
+use forge::executor::{builder::Backend, Executor, Log, TransactOut, TransactResult};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// A mined block
+#[derive(Debug, Clone)]
+pub struct Block {
+    pub number: u64,
+    pub hash: H256,
+    pub parent_hash: H256,
+    pub timestamp: u64,
+    pub coinbase: Address,
+    pub gas_limit: U256,
+    pub gas_used: u64,
+    pub base_fee: U256,
+    /// The hashes of the transactions in the block
+    pub transactions: Vec<H256>,
+}
+
+/// The receipt of a mined transaction
+#[derive(Debug, Clone)]
+pub struct Receipt {
+    pub block_number: u64,
+    pub block_hash: H256,
+    /// The index of the transaction in its block
+    pub index: usize,
+    pub success: bool,
+    pub gas_used: u64,
+    pub cumulative_gas_used: u64,
+    pub effective_gas_price: U256,
+    /// The address of the created contract, if any
+    pub contract_address: Option<Address>,
+    pub logs: Vec<Log>,
+    /// The index of the first log of the transaction in its block
+    pub first_log_index: usize,
+}
+
+/// A log matched by a [LogFilter]
+#[derive(Debug, Clone)]
+pub struct MatchedLog<'a> {
+    pub log: &'a Log,
+    pub receipt: &'a Receipt,
+    pub transaction_hash: H256,
+    /// The index of the log in its block
+    pub log_index: usize,
+}
+
+/// The filter of `eth_getLogs`
+#[derive(Debug, Clone, Default)]
+pub struct LogFilter {
+    pub from_block: Option<u64>,
+    pub to_block: Option<u64>,
+    /// The addresses to match, any address matches if empty
+    pub addresses: Vec<Address>,
+    /// The topics to match at each position, `None` matches any topic
+    pub topics: Vec<Option<Vec<H256>>>,
+}
+
+impl LogFilter {
+    fn matches(&self, log: &Log) -> bool {
+        if !self.addresses.is_empty() && !self.addresses.contains(&log.address) {
+            return false
+        }
+        self.topics.iter().enumerate().all(|(i, topics)| match topics {
+            Some(topics) => log.topics.get(i).map(|topic| topics.contains(topic)).unwrap_or(false),
+            None => true,
+        })
+    }
+}
+
+/// The state of the chain saved by `evm_snapshot`
+#[derive(Clone)]
+struct Snapshot {
+    executor: Executor<Backend>,
+    blocks: Vec<Block>,
+    transactions: BTreeMap<H256, (Transaction, Receipt)>,
+    pending: Vec<Transaction>,
+    time_offset: i64,
+    next_timestamp: Option<u64>,
+}
+
+/// A local chain on top of an [Executor].
+///
+/// Only the latest state is kept, so state queries for older blocks are answered with the latest
+/// state as well. The base fee is fixed.
+pub struct Node {
+    executor: Executor<Backend>,
+    /// The unlocked development accounts
+    accounts: Vec<Address>,
+    /// The accounts whose transactions are accepted without a signature, e.g. to act as the owner
+    /// of a forked contract
+    impersonated: BTreeSet<Address>,
+    /// The gas price suggested to clients, at least the base fee
+    gas_price: U256,
+    /// Whether a block is mined for each transaction as soon as it is sent
+    automine: bool,
+    /// All blocks mined by the node, starting with the genesis block
+    blocks: Vec<Block>,
+    /// The mined transactions and their receipts, by hash
+    transactions: BTreeMap<H256, (Transaction, Receipt)>,
+    /// The transactions that were sent but not mined yet, in order
+    pending: Vec<Transaction>,
+    /// The seconds added to the system time to compute the timestamps of new blocks
+    time_offset: i64,
+    /// The timestamp of the next block, if it was set explicitly
+    next_timestamp: Option<u64>,
+    /// The saved states by snapshot id
+    snapshots: BTreeMap<u64, Snapshot>,
+    /// The id of the next snapshot
+    next_snapshot_id: u64,
+    /// The accounts and storage slots that were changed, i.e. the state to dump
+    touched: BTreeMap<Address, BTreeSet<U256>>,
+}
+
+impl Node {
+    /// Creates a node on top of the state of the `executor` and funds the development accounts
+    /// with `balance`
+    pub fn new(
+        mut executor: Executor<Backend>,
+        accounts: Vec<Address>,
+        balance: U256,
+        gas_price: U256,
+        automine: bool,
+    ) -> Self {
+        for account in &accounts {
+            executor.set_balance(*account, balance);
+        }
+
+        let env = executor.env();
+        let genesis = Block {
+            number: env.block.number.as_u64(),
+            hash: H256::zero(),
+            parent_hash: H256::zero(),
+            timestamp: env.block.timestamp.as_u64(),
+            coinbase: env.block.coinbase,
+            gas_limit: env.block.gas_limit,
+            gas_used: 0,
+            base_fee: env.block.basefee,
+            transactions: Vec::new(),
+        };
+        let genesis = Block { hash: block_hash(&genesis), ..genesis };
+        let touched = accounts.iter().map(|account| (*account, BTreeSet::new())).collect();
+
+        Self {
+            executor,
+            accounts,
+            impersonated: BTreeSet::new(),
+            gas_price,
+            automine,
+            blocks: vec![genesis],
+            transactions: BTreeMap::new(),
+            pending: Vec::new(),
+            time_offset: 0,
+            next_timestamp: None,
+            snapshots: BTreeMap::new(),
+            next_snapshot_id: 1,
+            touched,
+        }
+    }
+
+    pub fn chain_id(&self) -> u64 {
+        self.executor.env().cfg.chain_id.as_u64()
+    }
+
+    pub fn accounts(&self) -> &[Address] {
+        &self.accounts
+    }
+
+    pub fn coinbase(&self) -> Address {
+        self.executor.env().block.coinbase
+    }
+
+    pub fn base_fee(&self) -> U256 {
+        self.latest_block().base_fee
+    }
+
+    pub fn gas_price(&self) -> U256 {
+        self.gas_price.max(self.base_fee())
+    }
+
+    pub fn is_automine(&self) -> bool {
+        self.automine
+    }
+
+    pub fn latest_block(&self) -> &Block {
+        self.blocks.last().expect("the genesis block always exists")
+    }
+
+    /// Returns the block gas limit, capped at `u64::MAX`
+    pub fn block_gas_limit(&self) -> u64 {
+        self.latest_block().gas_limit.min(u64::MAX.into()).as_u64()
+    }
+
+    pub fn block_number(&self) -> u64 {
+        self.latest_block().number
+    }
+
+    /// Returns the number of the genesis block of the node, e.g. the forked block
+    pub fn first_block_number(&self) -> u64 {
+        self.blocks[0].number
+    }
+
+    /// Returns the block with the given number, blocks before the genesis block of the node are
+    /// unknown
+    pub fn block_by_number(&self, number: u64) -> Option<&Block> {
+        let index = number.checked_sub(self.blocks[0].number)?;
+        self.blocks.get(index as usize)
+    }
+
+    pub fn block_by_hash(&self, hash: H256) -> Option<&Block> {
+        self.blocks.iter().find(|block| block.hash == hash)
+    }
+
+    pub fn balance(&self, address: Address) -> U256 {
+        self.executor.get_balance(address)
+    }
+
+    /// Returns the nonce of an account, including its pending transactions if `pending` is set
+    pub fn nonce(&self, address: Address, pending: bool) -> u64 {
+        let nonce = self.executor.get_nonce(address);
+        if pending {
+            nonce + self.pending.iter().filter(|tx| tx.from == address).count() as u64
+        } else {
+            nonce
+        }
+    }
+
+    pub fn code(&self, address: Address) -> Bytes {
+        Bytes(self.executor.get_code(address))
+    }
+
+    pub fn storage(&self, address: Address, slot: U256) -> U256 {
+        self.executor.get_storage(address, slot)
+    }
+
+    pub fn set_balance(&mut self, address: Address, balance: U256) {
+        self.touched.entry(address).or_default();
+        self.executor.set_balance(address, balance)
+    }
+
+    pub fn set_code(&mut self, address: Address, code: Bytes) {
+        self.touched.entry(address).or_default();
+        self.executor.set_code(address, code.0)
+    }
+
+    pub fn set_storage(&mut self, address: Address, slot: U256, value: U256) {
+        self.touched.entry(address).or_default().insert(slot);
+        self.executor.set_storage(address, slot, value)
+    }
+
+    /// Accepts transactions of `address` without a signature until impersonation is stopped
+    pub fn impersonate(&mut self, address: Address) {
+        self.impersonated.insert(address);
+    }
+
+    /// Stops impersonating `address`, returns `false` if it was not impersonated
+    pub fn stop_impersonating(&mut self, address: Address) -> bool {
+        self.impersonated.remove(&address)
+    }
+
+    /// Returns a mined or pending transaction and its receipt, if it was mined
+    pub fn transaction(&self, hash: H256) -> Option<(&Transaction, Option<&Receipt>)> {
+        if let Some((tx, receipt)) = self.transactions.get(&hash) {
+            return Some((tx, Some(receipt)))
+        }
+        self.pending.iter().find(|tx| tx.hash == hash).map(|tx| (tx, None))
+    }
+
+    /// Executes a call on the latest state without persisting it
+    pub fn call(&self, request: &TransactionRequest) -> TransactResult {
+        let tx = request.to_tx_env(Address::zero(), self.block_gas_limit());
+        self.executor.transact(tx)
+    }
+
+    /// Estimates the gas limit a transaction needs to succeed.
+    ///
+    /// The gas used by a transaction is not always sufficient as its gas limit, e.g. because of
+    /// refunds or the gas calls have to retain, so the lowest gas limit is searched for.
+    pub fn estimate_gas(&self, request: &TransactionRequest) -> Result<u64, TransactResult> {
+        let cap = request.gas.map(|gas| gas.as_u64()).unwrap_or_else(|| self.block_gas_limit());
+        let mut tx = request.to_tx_env(Address::zero(), cap);
+        let result = self.executor.transact(tx.clone());
+        if !result.is_success() {
+            return Err(result)
+        }
+
+        let (mut lowest, mut highest) = (result.gas.saturating_sub(1), cap);
+        while lowest + 1 < highest {
+            let mid = lowest + (highest - lowest) / 2;
+            tx.gas_limit = mid;
+            if self.executor.transact(tx.clone()).is_success() {
+                highest = mid;
+            } else {
+                lowest = mid;
+            }
+        }
+        Ok(highest)
+    }
+
+    /// Sends a transaction of one of the unlocked development accounts or an impersonated account
+    pub fn send_transaction(&mut self, request: &TransactionRequest) -> eyre::Result<H256> {
+        let from = request.from.ok_or_else(|| eyre::eyre!("missing `from` address"))?;
+        if !self.accounts.contains(&from) && !self.impersonated.contains(&from) {
+            eyre::bail!("{:?} is not an unlocked account", from)
+        }
+
+        let (gas_price, max_priority_fee, kind) = match request.max_fee_per_gas {
+            Some(max_fee) => {
+                (max_fee, Some(request.max_priority_fee_per_gas.unwrap_or_default()), 2)
+            }
+            None => (request.gas_price.unwrap_or_else(|| self.gas_price()), None, 0),
+        };
+        let mut tx = Transaction {
+            hash: H256::zero(),
+            kind,
+            from,
+            to: request.to,
+            nonce: request
+                .nonce
+                .map(|nonce| nonce.as_u64())
+                .unwrap_or_else(|| self.nonce(from, true)),
+            value: request.value.unwrap_or_default(),
+            input: request.data.clone().unwrap_or_default(),
+            gas_limit: match request.gas {
+                Some(gas) => gas.as_u64(),
+                None => {
+                    self.estimate_gas(request).map_err(|_| eyre::eyre!("gas estimation failed"))?
+                }
+            },
+            gas_price,
+            max_priority_fee,
+            chain_id: Some(self.chain_id()),
+            signature: None,
+        };
+        tx.hash = unsigned_transaction_hash(&tx);
+        self.add_transaction(tx)
+    }
+
+    /// Validates a transaction and adds it to the pending transactions, which are mined right away
+    /// if automining is enabled
+    pub fn add_transaction(&mut self, tx: Transaction) -> eyre::Result<H256> {
+        if let Some(chain_id) = tx.chain_id {
+            if chain_id != self.chain_id() {
+                eyre::bail!("invalid chain id {}, expected {}", chain_id, self.chain_id())
+            }
+        }
+        let nonce = self.nonce(tx.from, true);
+        if tx.nonce < nonce {
+            eyre::bail!("nonce too low: {}, expected {}", tx.nonce, nonce)
+        }
+        if tx.nonce > nonce {
+            eyre::bail!("nonce too high: {}, expected {}", tx.nonce, nonce)
+        }
+        if tx.gas_limit > self.block_gas_limit() {
+            eyre::bail!("gas limit {} exceeds the block gas limit", tx.gas_limit)
+        }
+        if tx.gas_price < self.base_fee() {
+            eyre::bail!("gas price {} is lower than the base fee {}", tx.gas_price, self.base_fee())
+        }
+        let cost = U256::from(tx.gas_limit).saturating_mul(tx.gas_price).saturating_add(tx.value);
+        if self.balance(tx.from) < cost {
+            eyre::bail!("insufficient funds for gas * price + value")
+        }
+        if self.transaction(tx.hash).is_some() {
+            eyre::bail!("transaction {:?} already exists", tx.hash)
+        }
+
+        let hash = tx.hash;
+        self.pending.push(tx);
+        if self.automine {
+            self.mine();
+        }
+        Ok(hash)
+    }
+
+    /// Mines a block with as many pending transactions as fit into its gas limit and returns it
+    pub fn mine(&mut self) -> &Block {
+        let parent = self.latest_block().clone();
+        let number = parent.number + 1;
+        let timestamp = match self.next_timestamp.take() {
+            Some(timestamp) => timestamp,
+            None => self.now().max(parent.timestamp + 1),
+        };
+        let block_env = &mut self.executor.env_mut().block;
+        block_env.number = number.into();
+        block_env.timestamp = timestamp.into();
+
+        // Include transactions as long as their gas limits fit into the block
+        let block_gas_limit = self.block_gas_limit();
+        let mut gas_limits = 0u64;
+        let count = self
+            .pending
+            .iter()
+            .take_while(|tx| {
+                gas_limits = gas_limits.saturating_add(tx.gas_limit);
+                gas_limits <= block_gas_limit
+            })
+            .count();
+
+        let mut block = Block {
+            number,
+            hash: H256::zero(),
+            parent_hash: parent.hash,
+            timestamp,
+            coinbase: parent.coinbase,
+            gas_limit: parent.gas_limit,
+            gas_used: 0,
+            base_fee: parent.base_fee,
+            transactions: Vec::new(),
+        };
+        let mut receipts = Vec::new();
+        let mut log_index = 0;
+        for (index, tx) in self.pending.drain(..count).collect::<Vec<_>>().into_iter().enumerate() {
+            let result = self.executor.transact_committing(tx.to_tx_env());
+            for (address, account) in &result.state_changeset {
+                self.touched.entry(*address).or_default().extend(account.storage.keys());
+            }
+            block.gas_used += result.gas;
+            let receipt = Receipt {
+                block_number: number,
+                block_hash: H256::zero(),
+                index,
+                success: result.is_success(),
+                gas_used: result.gas,
+                cumulative_gas_used: block.gas_used,
+                effective_gas_price: tx.effective_gas_price(block.base_fee),
+                contract_address: match result.out {
+                    TransactOut::Create(_, address) if result.is_success() => address,
+                    _ => None,
+                },
+                first_log_index: log_index,
+                logs: result.logs,
+            };
+            log_index += receipt.logs.len();
+            block.transactions.push(tx.hash);
+            receipts.push((tx, receipt));
+        }
+
+        block.hash = block_hash(&block);
+        for (tx, mut receipt) in receipts {
+            receipt.block_hash = block.hash;
+            print_transaction(&tx, &receipt);
+            self.transactions.insert(tx.hash, (tx, receipt));
+        }
+        self.blocks.push(block);
+        self.latest_block()
+    }
+
+    /// Returns the current time of the chain, i.e. the system time shifted by the time offset
+    fn now(&self) -> u64 {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_secs())
+            .unwrap_or_default();
+        if self.time_offset < 0 {
+            now.saturating_sub(self.time_offset.unsigned_abs())
+        } else {
+            now.saturating_add(self.time_offset as u64)
+        }
+    }
+
+    /// Moves the time of the chain forward by `seconds` and returns the total time offset
+    pub fn increase_time(&mut self, seconds: u64) -> i64 {
+        self.time_offset = self.time_offset.saturating_add(seconds.min(i64::MAX as u64) as i64);
+        self.time_offset
+    }
+
+    /// Sets the timestamp of the next block, later blocks continue from this timestamp
+    pub fn set_next_block_timestamp(&mut self, timestamp: u64) -> eyre::Result<()> {
+        let latest = self.latest_block().timestamp;
+        if timestamp <= latest {
+            eyre::bail!(
+                "timestamp {} is not greater than the latest timestamp {}",
+                timestamp,
+                latest
+            )
+        }
+        let now = self.now().min(i64::MAX as u64) as i64;
+        self.time_offset =
+            self.time_offset.saturating_add((timestamp.min(i64::MAX as u64) as i64) - now);
+        self.next_timestamp = Some(timestamp);
+        Ok(())
+    }
+
+    /// Saves the current state of the chain and returns the id to revert to it
+    pub fn snapshot(&mut self) -> u64 {
+        let id = self.next_snapshot_id;
+        self.next_snapshot_id += 1;
+        self.snapshots.insert(
+            id,
+            Snapshot {
+                executor: self.executor.clone(),
+                blocks: self.blocks.clone(),
+                transactions: self.transactions.clone(),
+                pending: self.pending.clone(),
+                time_offset: self.time_offset,
+                next_timestamp: self.next_timestamp,
+            },
+        );
+        id
+    }
+
+    /// Reverts the chain to the state of a snapshot.
+    ///
+    /// The snapshot and all snapshots taken after it are removed, so it can only be reverted to
+    /// once. Returns `false` if there is no snapshot with the id.
+    pub fn revert(&mut self, id: u64) -> bool {
+        let snapshot = match self.snapshots.remove(&id) {
+            Some(snapshot) => snapshot,
+            None => return false,
+        };
+        self.snapshots.retain(|snapshot, _| *snapshot < id);
+
+        self.executor = snapshot.executor;
+        self.blocks = snapshot.blocks;
+        self.transactions = snapshot.transactions;
+        self.pending = snapshot.pending;
+        self.time_offset = snapshot.time_offset;
+        self.next_timestamp = snapshot.next_timestamp;
+        true
+    }
+
+    /// Returns the state of all accounts that were changed on the node
+    pub fn dump_state(&self) -> NodeState {
+        let accounts = self
+            .touched
+            .iter()
+            .map(|(address, slots)| {
+                let account = AccountState {
+                    balance: self.balance(*address),
+                    nonce: self.nonce(*address, false),
+                    code: self.code(*address),
+                    storage: slots
+                        .iter()
+                        .map(|slot| (*slot, self.storage(*address, *slot)))
+                        .filter(|(_, value)| !value.is_zero())
+                        .collect(),
+                };
+                (*address, account)
+            })
+            .collect();
+        let latest = self.latest_block();
+        NodeState { block_number: latest.number, timestamp: latest.timestamp, accounts }
+    }
+
+    /// Applies a dumped state and continues the chain from its block.
+    ///
+    /// The history of the node is discarded, so the block of the state becomes the genesis block.
+    pub fn load_state(&mut self, state: NodeState) {
+        for (address, account) in state.accounts {
+            self.set_balance(address, account.balance);
+            self.executor.set_nonce(address, account.nonce);
+            if !account.code.0.is_empty() {
+                self.set_code(address, account.code);
+            }
+            for (slot, value) in account.storage {
+                self.set_storage(address, slot, value);
+            }
+        }
+
+        let block_env = &mut self.executor.env_mut().block;
+        block_env.number = state.block_number.into();
+        block_env.timestamp = state.timestamp.into();
+        let genesis = Block {
+            number: state.block_number,
+            timestamp: state.timestamp,
+            parent_hash: H256::zero(),
+            gas_used: 0,
+            transactions: Vec::new(),
+            ..self.latest_block().clone()
+        };
+        let genesis = Block { hash: block_hash(&genesis), ..genesis };
+        self.blocks = vec![genesis];
+        self.transactions.clear();
+        self.pending.clear();
+        self.snapshots.clear();
+    }
+
+    /// Returns all logs that match the filter
+    pub fn logs(&self, filter: &LogFilter) -> Vec<MatchedLog<'_>> {
+        let from = filter.from_block.unwrap_or_else(|| self.block_number());
+        let to = filter.to_block.unwrap_or_else(|| self.block_number());
+        let mut logs = Vec::new();
+        // Only the mined blocks are visited, the range of the filter can be arbitrarily large
+        for block in self.blocks.iter().filter(|block| (from..=to).contains(&block.number)) {
+            for hash in &block.transactions {
+                let (_, receipt) = &self.transactions[hash];
+                for (i, log) in receipt.logs.iter().enumerate() {
+                    if filter.matches(log) {
+                        logs.push(MatchedLog {
+                            log,
+                            receipt,
+                            transaction_hash: *hash,
+                            log_index: receipt.first_log_index + i,
+                        });
+                    }
+                }
+            }
+        }
+        logs
+    }
+}
+
+/// Computes a unique hash of the block from its header fields and transactions
+fn block_hash(block: &Block) -> H256 {
+    let mut data = block.parent_hash.as_bytes().to_vec();
+    data.extend_from_slice(&block.number.to_be_bytes());
+    data.extend_from_slice(&block.timestamp.to_be_bytes());
+    for tx in &block.transactions {
+        data.extend_from_slice(tx.as_bytes());
+    }
+    keccak256(data).into()
+}
+
+fn print_transaction(tx: &Transaction, receipt: &Receipt) {
+    println!();
+    println!("    Transaction: {:?}", tx.hash);
+    if let Some(address) = receipt.contract_address {
+        println!("    Contract created: {:?}", address);
+    }
+    println!("    Gas used: {}", receipt.gas_used);
+    println!("    Block: {}", receipt.block_number);
+    if !receipt.success {
+        println!("    Error: reverted");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use forge::executor::ExecutorBuilder;
+
+    fn node() -> Node {
+        let executor = ExecutorBuilder::new().build();
+        Node::new(executor, vec![Address::repeat_byte(1)], U256::exp10(18), U256::zero(), true)
+    }
+
+    #[test]
+    fn can_mine_transactions() {
+        let mut node = node();
+        let request = TransactionRequest {
+            from: Some(Address::repeat_byte(1)),
+            to: Some(Address::repeat_byte(2)),
+            value: Some(1000u64.into()),
+            ..Default::default()
+        };
+
+        let hash = node.send_transaction(&request).unwrap();
+        assert_eq!(node.block_number(), 1);
+        assert_eq!(node.balance(Address::repeat_byte(2)), 1000u64.into());
+        assert_eq!(node.nonce(Address::repeat_byte(1), false), 1);
+
+        let (tx, receipt) = node.transaction(hash).unwrap();
+        assert_eq!(tx.gas_limit, 21000);
+        let receipt = receipt.unwrap();
+        assert!(receipt.success);
+        assert_eq!(receipt.gas_used, 21000);
+        assert_eq!(node.latest_block().transactions, vec![hash]);
+        assert_eq!(node.block_by_hash(receipt.block_hash).unwrap().number, 1);
+    }
+
+    #[test]
+    fn rejects_invalid_transactions() {
+        let mut node = node();
+        let request = TransactionRequest {
+            from: Some(Address::repeat_byte(1)),
+            to: Some(Address::repeat_byte(2)),
+            nonce: Some(1u64.into()),
+            ..Default::default()
+        };
+        assert!(node.send_transaction(&request).is_err());
+
+        let request = TransactionRequest { from: Some(Address::repeat_byte(2)), ..request };
+        assert!(node.send_transaction(&request).is_err());
+        assert_eq!(node.block_number(), 0);
+    }
+
+    #[test]
+    fn can_impersonate_accounts() {
+        let mut node = node();
+        let owner = Address::repeat_byte(3);
+        let request = TransactionRequest {
+            from: Some(owner),
+            to: Some(Address::repeat_byte(2)),
+            value: Some(1000u64.into()),
+            ..Default::default()
+        };
+        node.set_balance(owner, U256::exp10(18));
+        assert!(node.send_transaction(&request).is_err());
+
+        node.impersonate(owner);
+        node.send_transaction(&request).unwrap();
+        assert_eq!(node.balance(Address::repeat_byte(2)), 1000u64.into());
+
+        assert!(node.stop_impersonating(owner));
+        assert!(!node.stop_impersonating(owner));
+        assert!(node.send_transaction(&request).is_err());
+    }
+
+    #[test]
+    fn can_set_code_and_storage() {
+        let mut node = node();
+        let address = Address::repeat_byte(4);
+        node.set_code(address, Bytes::from(vec![0x60, 0x00]));
+        node.set_storage(address, 1u64.into(), 42u64.into());
+        assert_eq!(node.code(address), Bytes::from(vec![0x60, 0x00]));
+        assert_eq!(node.storage(address, 1u64.into()), 42u64.into());
+        assert_eq!(node.storage(address, 2u64.into()), U256::zero());
+    }
+
+    #[test]
+    fn can_revert_to_snapshots() {
+        let mut node = node();
+        let request = TransactionRequest {
+            from: Some(Address::repeat_byte(1)),
+            to: Some(Address::repeat_byte(2)),
+            value: Some(1000u64.into()),
+            ..Default::default()
+        };
+
+        let first = node.snapshot();
+        let hash = node.send_transaction(&request).unwrap();
+        let second = node.snapshot();
+        node.send_transaction(&request).unwrap();
+        assert_eq!(node.balance(Address::repeat_byte(2)), 2000u64.into());
+
+        assert!(node.revert(first));
+        assert_eq!(node.block_number(), 0);
+        assert_eq!(node.balance(Address::repeat_byte(2)), U256::zero());
+        assert_eq!(node.nonce(Address::repeat_byte(1), false), 0);
+        assert!(node.transaction(hash).is_none());
+
+        // Reverting removes the snapshot and all later snapshots
+        assert!(!node.revert(first));
+        assert!(!node.revert(second));
+    }
+
+    #[test]
+    fn can_set_block_timestamps() {
+        let mut node = node();
+        let timestamp = node.latest_block().timestamp;
+        assert!(node.set_next_block_timestamp(timestamp).is_err());
+
+        let next = node.now() + 1000;
+        node.set_next_block_timestamp(next).unwrap();
+        assert_eq!(node.mine().timestamp, next);
+        assert!(node.mine().timestamp > next);
+
+        node.increase_time(3600);
+        assert!(node.mine().timestamp >= next + 3600);
+    }
+
+    #[test]
+    fn can_dump_and_load_state() {
+        let (mut node, mut loaded) = (node(), node());
+        let request = TransactionRequest {
+            from: Some(Address::repeat_byte(1)),
+            to: Some(Address::repeat_byte(2)),
+            value: Some(1000u64.into()),
+            ..Default::default()
+        };
+        node.send_transaction(&request).unwrap();
+        node.set_code(Address::repeat_byte(3), Bytes::from(vec![0x60, 0x00]));
+        node.set_storage(Address::repeat_byte(3), 1u64.into(), 42u64.into());
+
+        let state = node.dump_state();
+        assert_eq!(state.block_number, 1);
+        assert_eq!(state.accounts[&Address::repeat_byte(1)].nonce, 1);
+        assert_eq!(state.accounts[&Address::repeat_byte(2)].balance, 1000u64.into());
+        assert_eq!(state.accounts[&Address::repeat_byte(3)].storage[&1u64.into()], 42u64.into());
+
+        loaded.load_state(state.clone());
+        assert_eq!(loaded.block_number(), 1);
+        assert_eq!(loaded.nonce(Address::repeat_byte(1), false), 1);
+        assert_eq!(loaded.balance(Address::repeat_byte(1)), node.balance(Address::repeat_byte(1)));
+        assert_eq!(loaded.code(Address::repeat_byte(3)), Bytes::from(vec![0x60, 0x00]));
+        assert_eq!(loaded.dump_state(), state);
+
+        loaded.send_transaction(&request).unwrap();
+        assert_eq!(loaded.block_number(), 2);
+    }
+
+    #[test]
+    fn can_queue_transactions_without_automine() {
+        let mut node = node();
+        node.automine = false;
+        let request = TransactionRequest {
+            from: Some(Address::repeat_byte(1)),
+            to: Some(Address::repeat_byte(2)),
+            gas: Some(21000u64.into()),
+            ..Default::default()
+        };
+        let first = node.send_transaction(&request).unwrap();
+        let second = node.send_transaction(&request).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(node.nonce(Address::repeat_byte(1), true), 2);
+        assert!(node.transaction(first).unwrap().1.is_none());
+
+        let block = node.mine();
+        assert_eq!(block.transactions, vec![first, second]);
+        assert_eq!(node.nonce(Address::repeat_byte(1), false), 2);
+    }
+}
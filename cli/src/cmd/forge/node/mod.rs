@@ -0,0 +1,164 @@
+//! node command
+
+mod backend;
+mod rpc;
+mod server;
+mod state;
+mod transaction;
+
+pub use backend::Node;
+pub use state::NodeState;
+
+use crate::{cmd::Cmd, opts::evm::EvmArgs, utils};
+use clap::Parser;
+use ethers::{
+    signers::{coins_bip39::English, LocalWallet, MnemonicBuilder, Signer},
+    types::U256,
+    utils::parse_ether,
+};
+use forge::executor::{inspector::Precompiles, opts::EvmOpts, ExecutorBuilder};
+use foundry_config::Config;
+use std::{
+    net::{IpAddr, SocketAddr},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// The mnemonic the development accounts are derived from by default
+pub const DEFAULT_MNEMONIC: &str = "test test test test test test test test test test test junk";
+
+/// Command to run a local development node
+#[derive(Debug, Clone, Parser)]
+pub struct NodeArgs {
+    /// The port to listen on.
+    #[clap(long, short, default_value = "8545")]
+    port: u16,
+
+    /// The IP address to listen on.
+    #[clap(long, default_value = "127.0.0.1")]
+    host: IpAddr,
+
+    /// The number of development accounts to generate.
+    #[clap(long, default_value = "10")]
+    accounts: u32,
+
+    /// The balance of each development account in ether.
+    #[clap(long, default_value = "10000")]
+    balance: String,
+
+    /// The mnemonic the development accounts are derived from.
+    #[clap(long, default_value = DEFAULT_MNEMONIC)]
+    mnemonic: String,
+
+    /// Mine a block every given number of seconds instead of one block per transaction.
+    #[clap(long, value_name = "SECONDS")]
+    block_time: Option<u64>,
+
+    /// Load the state of the node from a file written by `--dump-state`.
+    #[clap(long, value_name = "PATH")]
+    load_state: Option<PathBuf>,
+
+    /// Dump the state of the node to the given file when it is stopped.
+    #[clap(long, value_name = "PATH")]
+    dump_state: Option<PathBuf>,
+
+    /// Also dump the state every given number of seconds.
+    #[clap(long, value_name = "SECONDS", requires = "dump-state")]
+    state_interval: Option<u64>,
+
+    #[clap(flatten, next_help_heading = "EVM OPTIONS")]
+    evm_opts: EvmArgs,
+}
+
+impl Cmd for NodeArgs {
+    type Output = ();
+
+    fn run(self) -> eyre::Result<Self::Output> {
+        let figment = Config::figment().merge(&self.evm_opts);
+        let mut evm_opts = figment.extract::<EvmOpts>()?;
+        let config = Config::from_provider(figment).sanitized();
+        utils::resolve_fork_url(&mut evm_opts, &config)?;
+
+        let wallets = (0..self.accounts)
+            .map(|index| {
+                Ok(MnemonicBuilder::<English>::default()
+                    .phrase(self.mnemonic.as_str())
+                    .index(index)?
+                    .build()?)
+            })
+            .collect::<eyre::Result<Vec<LocalWallet>>>()?;
+        let balance = parse_ether(&self.balance)?;
+
+        let executor = ExecutorBuilder::new()
+            .with_config(evm_opts.evm_env())
+            .with_spec(utils::evm_spec(&config.evm_version))
+            .with_memory_limit(evm_opts.memory_limit)
+            .with_fork(utils::get_fork(&evm_opts, &config.rpc_storage_caching))
+            .with_precompiles(Precompiles::from_commands(&config.precompiles, evm_opts.ffi))
+            .build();
+        let gas_price = U256::from(evm_opts.env.gas_price);
+        let mut node = Node::new(
+            executor,
+            wallets.iter().map(Signer::address).collect(),
+            balance,
+            gas_price,
+            self.block_time.is_none(),
+        );
+        if let Some(ref path) = self.load_state {
+            node.load_state(NodeState::read(path)?);
+        }
+
+        println!("Available accounts");
+        println!("==================");
+        for (i, wallet) in wallets.iter().enumerate() {
+            println!("({}) {:?} ({} ETH)", i, wallet.address(), self.balance);
+        }
+        println!();
+        println!("Private keys");
+        println!("==================");
+        for (i, wallet) in wallets.iter().enumerate() {
+            println!("({}) 0x{}", i, hex::encode(wallet.signer().to_bytes()));
+        }
+        println!();
+        println!("Mnemonic: {}", self.mnemonic);
+        println!("Chain id: {}", node.chain_id());
+        println!("Gas price: {}", node.gas_price());
+        println!("Gas limit: {}", node.block_gas_limit());
+        if let Some(ref fork_url) = evm_opts.fork_url {
+            println!("Fork: {} at block {}", fork_url, node.block_number());
+        }
+        match self.block_time {
+            Some(block_time) => println!("Block time: {}s", block_time),
+            None => println!("Block time: automine"),
+        }
+
+        let addr = SocketAddr::new(self.host, self.port);
+        println!();
+        println!("Listening on {}", addr);
+
+        let node = Arc::new(Mutex::new(node));
+        utils::block_on(async move {
+            if let Some(block_time) = self.block_time {
+                tokio::spawn(server::mine_interval(node.clone(), Duration::from_secs(block_time)));
+            }
+            if let (Some(path), Some(interval)) = (self.dump_state.clone(), self.state_interval) {
+                tokio::spawn(server::dump_interval(
+                    node.clone(),
+                    path,
+                    Duration::from_secs(interval),
+                ));
+            }
+
+            tokio::select! {
+                result = server::serve(addr, node.clone()) => result?,
+                _ = tokio::signal::ctrl_c() => {}
+            }
+            if let Some(ref path) = self.dump_state {
+                node.lock().expect("node lock poisoned").dump_state().write(path)?;
+                println!("Dumped state to {}", path.display());
+            }
+            Ok::<_, eyre::Report>(())
+        })
+    }
+}
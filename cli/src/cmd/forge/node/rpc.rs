@@ -0,0 +1,525 @@
+//! JSON-RPC method handlers
+
+use super::{
+    backend::{Block, LogFilter, MatchedLog, Node, Receipt},
+    transaction::{decode_raw_transaction, Transaction, TransactionRequest},
+};
+use ethers::{
+    types::{Address, Bytes, H256, U256},
+    utils::keccak256,
+};
+use forge::executor::{TransactOut, TransactResult};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// A JSON-RPC request
+#[derive(Debug, Clone, Deserialize)]
+pub struct Request {
+    #[serde(default)]
+    pub id: Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+/// A JSON-RPC error
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self { code, message: message.into(), data: None }
+    }
+
+    pub fn parse_error() -> Self {
+        Self::new(-32700, "parse error")
+    }
+
+    pub fn invalid_request() -> Self {
+        Self::new(-32600, "invalid request")
+    }
+
+    pub fn method_not_found(method: &str) -> Self {
+        Self::new(-32601, format!("method not found: {}", method))
+    }
+
+    pub fn invalid_params(message: impl std::fmt::Display) -> Self {
+        Self::new(-32602, format!("invalid params: {}", message))
+    }
+
+    /// An error of the node, e.g. a rejected transaction
+    pub fn server_error(message: impl std::fmt::Display) -> Self {
+        Self::new(-32000, message.to_string())
+    }
+
+    /// The error of a reverted call, which includes the revert data like geth
+    pub fn reverted(result: &TransactResult) -> Self {
+        let data = match &result.out {
+            TransactOut::Call(data) => data.to_vec(),
+            _ => Vec::new(),
+        };
+        let message = match foundry_utils::decode_revert(&data, None) {
+            Ok(reason) => format!("execution reverted: {}", reason),
+            Err(_) => format!("execution reverted: {:?}", result.status),
+        };
+        Self { code: 3, message, data: Some(format!("0x{}", hex::encode(data)).into()) }
+    }
+}
+
+/// Handles the body of an HTTP request, which is either a single request or a batch of requests,
+/// and returns the response
+pub fn handle_body(node: &mut Node, body: &[u8]) -> Value {
+    match serde_json::from_slice::<Value>(body) {
+        Ok(Value::Array(requests)) if !requests.is_empty() => {
+            Value::Array(requests.into_iter().map(|request| handle_value(node, request)).collect())
+        }
+        Ok(request) => handle_value(node, request),
+        Err(_) => response(Value::Null, Err(RpcError::parse_error())),
+    }
+}
+
+fn handle_value(node: &mut Node, request: Value) -> Value {
+    match serde_json::from_value::<Request>(request) {
+        Ok(Request { id, method, params }) => response(id, handle(node, &method, params)),
+        Err(_) => response(Value::Null, Err(RpcError::invalid_request())),
+    }
+}
+
+fn response(id: Value, result: Result<Value, RpcError>) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({ "jsonrpc": "2.0", "id": id, "error": error }),
+    }
+}
+
+/// Executes a JSON-RPC method.
+///
+/// The block parameter of state queries is ignored, since only the latest state is kept.
+pub fn handle(node: &mut Node, method: &str, params: Value) -> Result<Value, RpcError> {
+    let result = match method {
+        "web3_clientVersion" => json!(format!("forge/v{}", env!("CARGO_PKG_VERSION"))),
+        "web3_sha3" => {
+            let data: Bytes = param(&params, 0)?;
+            json!(H256::from(keccak256(&data)))
+        }
+        "net_version" => json!(node.chain_id().to_string()),
+        "net_listening" => json!(true),
+        "net_peerCount" => json!(quantity(0)),
+        "eth_chainId" => json!(quantity(node.chain_id())),
+        "eth_blockNumber" => json!(quantity(node.block_number())),
+        "eth_accounts" => json!(node.accounts()),
+        "eth_coinbase" => json!(node.coinbase()),
+        "eth_gasPrice" => json!(node.gas_price()),
+        "eth_maxPriorityFeePerGas" => json!(U256::exp10(9)),
+        "eth_mining" => json!(node.is_automine()),
+        "eth_syncing" => json!(false),
+        "eth_hashrate" => json!(quantity(0)),
+        "eth_getBalance" => json!(node.balance(param(&params, 0)?)),
+        "eth_getTransactionCount" => {
+            let pending = params.get(1).and_then(Value::as_str) == Some("pending");
+            json!(quantity(node.nonce(param(&params, 0)?, pending)))
+        }
+        "eth_getCode" => json!(node.code(param(&params, 0)?)),
+        "eth_getStorageAt" => {
+            let slot: U256 = param(&params, 1)?;
+            json!(H256::from_uint(&node.storage(param(&params, 0)?, slot)))
+        }
+        "eth_call" => {
+            let result = node.call(&param(&params, 0)?);
+            if !result.is_success() {
+                return Err(RpcError::reverted(&result))
+            }
+            match result.out {
+                TransactOut::Call(data) => json!(Bytes(data)),
+                TransactOut::Create(data, _) => json!(Bytes(data)),
+                _ => json!(Bytes::default()),
+            }
+        }
+        "eth_estimateGas" => {
+            json!(quantity(
+                node.estimate_gas(&param(&params, 0)?)
+                    .map_err(|result| RpcError::reverted(&result))?
+            ))
+        }
+        "eth_sendTransaction" => {
+            json!(node.send_transaction(&param(&params, 0)?).map_err(RpcError::server_error)?)
+        }
+        "eth_sendRawTransaction" => {
+            let raw: Bytes = param(&params, 0)?;
+            let tx = decode_raw_transaction(&raw).map_err(RpcError::invalid_params)?;
+            json!(node.add_transaction(tx).map_err(RpcError::server_error)?)
+        }
+        "eth_getTransactionByHash" => match node.transaction(param(&params, 0)?) {
+            Some((tx, receipt)) => transaction_json(tx, receipt),
+            None => Value::Null,
+        },
+        "eth_getTransactionReceipt" => match node.transaction(param(&params, 0)?) {
+            Some((tx, Some(receipt))) => receipt_json(tx, receipt),
+            _ => Value::Null,
+        },
+        "eth_getBlockByNumber" => {
+            let number = block_number(node, params.get(0))?;
+            match node.block_by_number(number) {
+                Some(block) => {
+                    block_json(node, block, param::<Option<bool>>(&params, 1)?.unwrap_or_default())
+                }
+                None => Value::Null,
+            }
+        }
+        "eth_getBlockByHash" => match node.block_by_hash(param(&params, 0)?) {
+            Some(block) => {
+                block_json(node, block, param::<Option<bool>>(&params, 1)?.unwrap_or_default())
+            }
+            None => Value::Null,
+        },
+        "eth_getBlockTransactionCountByNumber" => {
+            let number = block_number(node, params.get(0))?;
+            match node.block_by_number(number) {
+                Some(block) => json!(quantity(block.transactions.len() as u64)),
+                None => Value::Null,
+            }
+        }
+        "eth_getBlockTransactionCountByHash" => match node.block_by_hash(param(&params, 0)?) {
+            Some(block) => json!(quantity(block.transactions.len() as u64)),
+            None => Value::Null,
+        },
+        "eth_getLogs" => {
+            let filter = log_filter(node, params.get(0).unwrap_or(&Value::Null))?;
+            Value::Array(node.logs(&filter).iter().map(log_json).collect())
+        }
+        "evm_mine" => {
+            if let Some(timestamp) = optional_number_param(&params, 0)? {
+                node.set_next_block_timestamp(timestamp).map_err(RpcError::invalid_params)?;
+            }
+            node.mine();
+            json!("0x0")
+        }
+        "evm_snapshot" => json!(quantity(node.snapshot())),
+        "evm_revert" => json!(node.revert(number_param(&params, 0)?)),
+        "evm_increaseTime" => json!(node.increase_time(number_param(&params, 0)?)),
+        "evm_setNextBlockTimestamp" => {
+            node.set_next_block_timestamp(number_param(&params, 0)?)
+                .map_err(RpcError::invalid_params)?;
+            Value::Null
+        }
+        "anvil_impersonateAccount" => {
+            node.impersonate(param(&params, 0)?);
+            Value::Null
+        }
+        "anvil_stopImpersonatingAccount" => {
+            node.stop_impersonating(param(&params, 0)?);
+            Value::Null
+        }
+        "anvil_setBalance" => {
+            node.set_balance(param(&params, 0)?, param(&params, 1)?);
+            Value::Null
+        }
+        "anvil_setCode" => {
+            node.set_code(param(&params, 0)?, param(&params, 1)?);
+            Value::Null
+        }
+        "anvil_setStorageAt" => {
+            let value: H256 = param(&params, 2)?;
+            node.set_storage(param(&params, 0)?, param(&params, 1)?, value.into_uint());
+            Value::Null
+        }
+        _ => return Err(RpcError::method_not_found(method)),
+    };
+    Ok(result)
+}
+
+/// Deserializes the parameter at `index`, missing parameters are deserialized from `null`
+fn param<T: DeserializeOwned>(params: &Value, index: usize) -> Result<T, RpcError> {
+    serde_json::from_value(params.get(index).cloned().unwrap_or(Value::Null))
+        .map_err(RpcError::invalid_params)
+}
+
+/// Parses the number parameter at `index`, which test frameworks send as JSON number, hex quantity
+/// or decimal string
+fn number_param(params: &Value, index: usize) -> Result<u64, RpcError> {
+    optional_number_param(params, index)?
+        .ok_or_else(|| RpcError::invalid_params(format!("missing parameter {}", index)))
+}
+
+fn optional_number_param(params: &Value, index: usize) -> Result<Option<u64>, RpcError> {
+    let number = match params.get(index) {
+        None | Some(Value::Null) => return Ok(None),
+        Some(Value::Number(number)) => number.as_u64(),
+        Some(Value::String(number)) => match number.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16).ok(),
+            None => number.parse().ok(),
+        },
+        Some(_) => None,
+    };
+    number
+        .map(Some)
+        .ok_or_else(|| RpcError::invalid_params(format!("invalid number at parameter {}", index)))
+}
+
+/// Resolves a block number or tag, missing values default to the latest block
+fn block_number(node: &Node, value: Option<&Value>) -> Result<u64, RpcError> {
+    match value.and_then(Value::as_str) {
+        None | Some("latest") | Some("pending") | Some("safe") | Some("finalized") => {
+            Ok(node.block_number())
+        }
+        Some("earliest") => Ok(node.first_block_number()),
+        Some(number) => u64::from_str_radix(number.trim_start_matches("0x"), 16)
+            .map_err(|_| RpcError::invalid_params(format!("invalid block number `{}`", number))),
+    }
+}
+
+/// Parses the filter object of `eth_getLogs`
+fn log_filter(node: &Node, filter: &Value) -> Result<LogFilter, RpcError> {
+    let mut log_filter = LogFilter::default();
+    if let Some(hash) = filter.get("blockHash") {
+        let hash: H256 = serde_json::from_value(hash.clone()).map_err(RpcError::invalid_params)?;
+        let number = node.block_by_hash(hash).map(|block| block.number);
+        let number = number.ok_or_else(|| RpcError::invalid_params("unknown block hash"))?;
+        log_filter.from_block = Some(number);
+        log_filter.to_block = Some(number);
+    } else {
+        log_filter.from_block = Some(block_number(node, filter.get("fromBlock"))?);
+        log_filter.to_block = Some(block_number(node, filter.get("toBlock"))?);
+    }
+
+    log_filter.addresses = match filter.get("address") {
+        Some(Value::Array(addresses)) => addresses
+            .iter()
+            .map(|address| serde_json::from_value(address.clone()))
+            .collect::<Result<_, _>>()
+            .map_err(RpcError::invalid_params)?,
+        Some(Value::Null) | None => Vec::new(),
+        Some(address) => {
+            vec![serde_json::from_value(address.clone()).map_err(RpcError::invalid_params)?]
+        }
+    };
+
+    if let Some(Value::Array(topics)) = filter.get("topics") {
+        for topic in topics {
+            log_filter.topics.push(match topic {
+                Value::Null => None,
+                Value::Array(_) => {
+                    Some(serde_json::from_value(topic.clone()).map_err(RpcError::invalid_params)?)
+                }
+                _ => Some(vec![
+                    serde_json::from_value(topic.clone()).map_err(RpcError::invalid_params)?
+                ]),
+            });
+        }
+    }
+    Ok(log_filter)
+}
+
+fn quantity(value: u64) -> String {
+    format!("{:#x}", value)
+}
+
+fn block_json(node: &Node, block: &Block, full_transactions: bool) -> Value {
+    let transactions: Vec<Value> = block
+        .transactions
+        .iter()
+        .map(|hash| match node.transaction(*hash) {
+            Some((tx, receipt)) if full_transactions => transaction_json(tx, receipt),
+            _ => json!(hash),
+        })
+        .collect();
+    json!({
+        "number": quantity(block.number),
+        "hash": block.hash,
+        "parentHash": block.parent_hash,
+        "nonce": "0x0000000000000000",
+        "mixHash": H256::zero(),
+        "sha3Uncles": H256::zero(),
+        "logsBloom": Bytes::from(vec![0u8; 256]),
+        "transactionsRoot": H256::zero(),
+        "stateRoot": H256::zero(),
+        "receiptsRoot": H256::zero(),
+        "miner": block.coinbase,
+        "difficulty": quantity(0),
+        "totalDifficulty": quantity(0),
+        "extraData": "0x",
+        "size": quantity(0),
+        "gasLimit": block.gas_limit,
+        "gasUsed": quantity(block.gas_used),
+        "timestamp": quantity(block.timestamp),
+        "baseFeePerGas": block.base_fee,
+        "transactions": transactions,
+        "uncles": [],
+    })
+}
+
+fn transaction_json(tx: &Transaction, receipt: Option<&Receipt>) -> Value {
+    let mut value = json!({
+        "hash": tx.hash,
+        "type": quantity(tx.kind as u64),
+        "nonce": quantity(tx.nonce),
+        "from": tx.from,
+        "to": tx.to,
+        "value": tx.value,
+        "gas": quantity(tx.gas_limit),
+        "input": tx.input,
+        "blockHash": receipt.map(|receipt| receipt.block_hash),
+        "blockNumber": receipt.map(|receipt| quantity(receipt.block_number)),
+        "transactionIndex": receipt.map(|receipt| quantity(receipt.index as u64)),
+        "v": quantity(tx.signature.map(|signature| signature.v).unwrap_or_default()),
+        "r": tx.signature.map(|signature| signature.r).unwrap_or_default(),
+        "s": tx.signature.map(|signature| signature.s).unwrap_or_default(),
+    });
+    if let Some(chain_id) = tx.chain_id {
+        value["chainId"] = quantity(chain_id).into();
+    }
+    match tx.max_priority_fee {
+        Some(max_priority_fee) => {
+            value["maxFeePerGas"] = json!(tx.gas_price);
+            value["maxPriorityFeePerGas"] = json!(max_priority_fee);
+            value["gasPrice"] =
+                json!(receipt.map(|receipt| receipt.effective_gas_price).unwrap_or(tx.gas_price));
+        }
+        None => value["gasPrice"] = json!(tx.gas_price),
+    }
+    if tx.kind != 0 {
+        value["accessList"] = json!([]);
+    }
+    value
+}
+
+fn receipt_json(tx: &Transaction, receipt: &Receipt) -> Value {
+    let logs: Vec<Value> = receipt
+        .logs
+        .iter()
+        .enumerate()
+        .map(|(i, log)| {
+            log_json(&MatchedLog {
+                log,
+                receipt,
+                transaction_hash: tx.hash,
+                log_index: receipt.first_log_index + i,
+            })
+        })
+        .collect();
+    json!({
+        "transactionHash": tx.hash,
+        "transactionIndex": quantity(receipt.index as u64),
+        "blockHash": receipt.block_hash,
+        "blockNumber": quantity(receipt.block_number),
+        "from": tx.from,
+        "to": tx.to,
+        "type": quantity(tx.kind as u64),
+        "status": quantity(receipt.success as u64),
+        "gasUsed": quantity(receipt.gas_used),
+        "cumulativeGasUsed": quantity(receipt.cumulative_gas_used),
+        "effectiveGasPrice": receipt.effective_gas_price,
+        "contractAddress": receipt.contract_address,
+        "logs": logs,
+        "logsBloom": Bytes::from(vec![0u8; 256]),
+    })
+}
+
+fn log_json(log: &MatchedLog<'_>) -> Value {
+    json!({
+        "address": log.log.address,
+        "topics": log.log.topics,
+        "data": Bytes(log.log.data.clone()),
+        "blockHash": log.receipt.block_hash,
+        "blockNumber": quantity(log.receipt.block_number),
+        "transactionHash": log.transaction_hash,
+        "transactionIndex": quantity(log.receipt.index as u64),
+        "logIndex": quantity(log.log_index as u64),
+        "removed": false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use forge::executor::ExecutorBuilder;
+
+    fn node() -> Node {
+        let executor = ExecutorBuilder::new().build();
+        Node::new(executor, vec![Address::repeat_byte(1)], U256::exp10(18), U256::zero(), true)
+    }
+
+    #[test]
+    fn can_handle_requests() {
+        let mut node = node();
+        let response = handle_body(
+            &mut node,
+            br#"[{"jsonrpc":"2.0","id":1,"method":"eth_accounts","params":[]},{"jsonrpc":"2.0","id":2,"method":"eth_foo","params":[]}]"#,
+        );
+        assert_eq!(response[0]["id"], 1);
+        assert_eq!(response[0]["result"], json!([Address::repeat_byte(1)]));
+        assert_eq!(response[1]["error"]["code"], -32601);
+
+        let response = handle_body(&mut node, b"{");
+        assert_eq!(response["error"]["code"], -32700);
+    }
+
+    #[test]
+    fn can_send_transactions() {
+        let mut node = node();
+        let params = json!([{
+            "from": Address::repeat_byte(1),
+            "to": Address::repeat_byte(2),
+            "value": "0x3e8",
+        }]);
+        let hash = handle(&mut node, "eth_sendTransaction", params).unwrap();
+        assert_eq!(handle(&mut node, "eth_blockNumber", json!([])).unwrap(), "0x1");
+
+        let receipt = handle(&mut node, "eth_getTransactionReceipt", json!([hash])).unwrap();
+        assert_eq!(receipt["status"], "0x1");
+        assert_eq!(receipt["gasUsed"], "0x5208");
+
+        let balance =
+            handle(&mut node, "eth_getBalance", json!([Address::repeat_byte(2), "latest"]))
+                .unwrap();
+        assert_eq!(balance, "0x3e8");
+
+        let block = handle(&mut node, "eth_getBlockByNumber", json!(["latest", false])).unwrap();
+        assert_eq!(block["transactions"], json!([hash]));
+    }
+
+    #[test]
+    fn rejects_quantities_larger_than_64_bits() {
+        let mut node = node();
+        let too_large = "0x10000000000000000";
+        let params = json!([{ "from": Address::repeat_byte(1), "gas": too_large }]);
+        assert_eq!(handle(&mut node, "eth_sendTransaction", params).unwrap_err().code, -32602);
+        let params = json!([{ "from": Address::repeat_byte(1), "nonce": too_large }]);
+        assert_eq!(handle(&mut node, "eth_sendTransaction", params).unwrap_err().code, -32602);
+        let params = json!([{ "to": Address::repeat_byte(2), "gas": too_large }]);
+        assert_eq!(handle(&mut node, "eth_estimateGas", params).unwrap_err().code, -32602);
+    }
+
+    #[test]
+    fn can_get_logs_of_large_ranges() {
+        let mut node = node();
+        let params = json!([{ "fromBlock": "0x0", "toBlock": "0xffffffffffffffff" }]);
+        assert_eq!(handle(&mut node, "eth_getLogs", params).unwrap(), json!([]));
+    }
+
+    #[test]
+    fn can_set_storage() {
+        let mut node = node();
+        let address = Address::repeat_byte(2);
+        let value = H256::from_low_u64_be(42);
+        handle(&mut node, "anvil_setStorageAt", json!([address, "0x1", value])).unwrap();
+        let storage = handle(&mut node, "eth_getStorageAt", json!([address, "0x1"])).unwrap();
+        assert_eq!(storage, json!(value));
+    }
+
+    #[test]
+    fn can_parse_number_params() {
+        let params = json!([3600, "0xe10", "3600", null, true]);
+        assert_eq!(number_param(&params, 0).unwrap(), 3600);
+        assert_eq!(number_param(&params, 1).unwrap(), 3600);
+        assert_eq!(number_param(&params, 2).unwrap(), 3600);
+        assert_eq!(optional_number_param(&params, 3).unwrap(), None);
+        assert!(number_param(&params, 4).is_err());
+        assert!(number_param(&params, 5).is_err());
+    }
+}
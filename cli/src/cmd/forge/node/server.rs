@@ -0,0 +1,92 @@
+//! The HTTP server of the node
+
+use super::{backend::Node, rpc};
+use hyper::{
+    header,
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// Serves the JSON-RPC API of the node over HTTP at `addr`
+pub async fn serve(addr: SocketAddr, node: Arc<Mutex<Node>>) -> eyre::Result<()> {
+    let make_service = make_service_fn(move |_| {
+        let node = node.clone();
+        async move { Ok::<_, Infallible>(service_fn(move |req| handle(node.clone(), req))) }
+    });
+    Server::try_bind(&addr)?.serve(make_service).await?;
+    Ok(())
+}
+
+/// Mines a block every `block_time`
+pub async fn mine_interval(node: Arc<Mutex<Node>>, block_time: Duration) {
+    let mut interval = tokio::time::interval(block_time);
+    // The first tick completes immediately
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        let node = node.clone();
+        let _ = tokio::task::spawn_blocking(move || {
+            node.lock().expect("node lock poisoned").mine();
+        })
+        .await;
+    }
+}
+
+/// Dumps the state of the node to `path` every `interval`
+pub async fn dump_interval(node: Arc<Mutex<Node>>, path: PathBuf, interval: Duration) {
+    let mut interval = tokio::time::interval(interval);
+    // The first tick completes immediately
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        let node = node.clone();
+        let path = path.clone();
+        let _ = tokio::task::spawn_blocking(move || {
+            let state = node.lock().expect("node lock poisoned").dump_state();
+            if let Err(err) = state.write(&path) {
+                eprintln!("Failed to dump state to {}: {}", path.display(), err);
+            }
+        })
+        .await;
+    }
+}
+
+async fn handle(node: Arc<Mutex<Node>>, req: Request<Body>) -> Result<Response<Body>, Infallible> {
+    // Allow requests of frontends served from other origins
+    let response = Response::builder()
+        .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+        .header(header::ACCESS_CONTROL_ALLOW_HEADERS, "*")
+        .header(header::ACCESS_CONTROL_ALLOW_METHODS, "POST, OPTIONS");
+
+    let response = match *req.method() {
+        Method::OPTIONS => response.body(Body::empty()),
+        Method::POST => match hyper::body::to_bytes(req.into_body()).await {
+            Ok(body) => {
+                // Executing transactions may block, e.g. when fetching the state of a fork
+                let result = tokio::task::spawn_blocking(move || {
+                    let mut node = node.lock().expect("node lock poisoned");
+                    rpc::handle_body(&mut node, &body)
+                })
+                .await;
+                match result {
+                    Ok(result) => response
+                        .header(header::CONTENT_TYPE, "application/json")
+                        .body(Body::from(result.to_string())),
+                    Err(_) => {
+                        response.status(StatusCode::INTERNAL_SERVER_ERROR).body(Body::empty())
+                    }
+                }
+            }
+            Err(_) => response.status(StatusCode::BAD_REQUEST).body(Body::empty()),
+        },
+        _ => response.status(StatusCode::METHOD_NOT_ALLOWED).body(Body::empty()),
+    };
+    Ok(response.expect("valid response"))
+}
@@ -0,0 +1,35 @@
+//! Persisting the state of the node
+
+use ethers::types::Address;
+pub use forge::executor::AccountState;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path};
+
+/// The state of the node written by `--dump-state` and read by `--load-state`.
+///
+/// Only the state of the accounts and the latest block are kept, the history of the chain is not.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeState {
+    pub block_number: u64,
+    pub timestamp: u64,
+    pub accounts: BTreeMap<Address, AccountState>,
+}
+
+impl NodeState {
+    pub fn read(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|err| eyre::eyre!("failed to read state from {}: {}", path.display(), err))?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Writes the state to a temporary file first, so an interrupted write does not corrupt a
+    /// previously written state
+    pub fn write(&self, path: impl AsRef<Path>) -> eyre::Result<()> {
+        let path = path.as_ref();
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
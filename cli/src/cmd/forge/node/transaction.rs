@@ -0,0 +1,222 @@
+//! Transactions sent to the node
+
+use ethers::{
+    types::{Address, Bytes, Signature, H256, U256, U64},
+    utils::{
+        keccak256,
+        rlp::{Rlp, RlpStream},
+    },
+};
+use forge::executor::{CreateScheme, TransactTo, TxEnv};
+use serde::Deserialize;
+
+/// A transaction that was sent to the node, either signed or from one of the unlocked development
+/// accounts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaction {
+    pub hash: H256,
+    /// The EIP-2718 type: `0` for legacy, `1` for access list and `2` for EIP-1559 transactions
+    pub kind: u8,
+    pub from: Address,
+    /// The callee, `None` for contract creations
+    pub to: Option<Address>,
+    pub nonce: u64,
+    pub value: U256,
+    pub input: Bytes,
+    pub gas_limit: u64,
+    /// The gas price of legacy transactions or the max fee per gas of EIP-1559 transactions
+    pub gas_price: U256,
+    /// The max priority fee per gas of EIP-1559 transactions
+    pub max_priority_fee: Option<U256>,
+    pub chain_id: Option<u64>,
+    /// The signature, `None` for transactions of the development accounts
+    pub signature: Option<Signature>,
+}
+
+impl Transaction {
+    /// Returns the gas price the sender pays with the given base fee
+    pub fn effective_gas_price(&self, base_fee: U256) -> U256 {
+        match self.max_priority_fee {
+            Some(priority_fee) => self.gas_price.min(base_fee.saturating_add(priority_fee)),
+            None => self.gas_price,
+        }
+    }
+
+    /// Converts the transaction into the transaction environment of the executor
+    pub fn to_tx_env(&self) -> TxEnv {
+        TxEnv {
+            caller: self.from,
+            transact_to: match self.to {
+                Some(to) => TransactTo::Call(to),
+                None => TransactTo::Create(CreateScheme::Create),
+            },
+            data: self.input.0.clone(),
+            value: self.value,
+            gas_limit: self.gas_limit,
+            gas_price: self.gas_price,
+            gas_priority_fee: self.max_priority_fee,
+            ..Default::default()
+        }
+    }
+}
+
+/// The transaction object of `eth_sendTransaction`, `eth_call` and `eth_estimateGas`
+///
+/// The gas limit and the nonce are 64 bit quantities, requests with larger values are rejected
+/// when they are deserialized.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionRequest {
+    pub from: Option<Address>,
+    pub to: Option<Address>,
+    pub gas: Option<U64>,
+    pub gas_price: Option<U256>,
+    pub max_fee_per_gas: Option<U256>,
+    pub max_priority_fee_per_gas: Option<U256>,
+    pub value: Option<U256>,
+    #[serde(alias = "input")]
+    pub data: Option<Bytes>,
+    pub nonce: Option<U64>,
+}
+
+impl TransactionRequest {
+    /// Converts the request into the transaction environment of the executor, missing values are
+    /// filled with the given defaults
+    pub fn to_tx_env(&self, default_from: Address, gas_limit: u64) -> TxEnv {
+        TxEnv {
+            caller: self.from.unwrap_or(default_from),
+            transact_to: match self.to {
+                Some(to) => TransactTo::Call(to),
+                None => TransactTo::Create(CreateScheme::Create),
+            },
+            data: self.data.clone().unwrap_or_default().0,
+            value: self.value.unwrap_or_default(),
+            gas_limit: self.gas.map(|gas| gas.as_u64()).unwrap_or(gas_limit),
+            ..Default::default()
+        }
+    }
+}
+
+/// Decodes a signed legacy, EIP-2930 or EIP-1559 transaction and recovers its sender
+pub fn decode_raw_transaction(raw: &[u8]) -> eyre::Result<Transaction> {
+    let first = *raw.first().ok_or_else(|| eyre::eyre!("empty transaction"))?;
+    // Legacy transactions are RLP lists, typed transactions are prefixed with their type
+    let (kind, payload) = if first >= 0xc0 { (0, raw) } else { (first, &raw[1..]) };
+    let rlp = Rlp::new(payload);
+
+    // The number of fields and the number of fee fields of each transaction type
+    let (field_count, fee_fields) = match kind {
+        0 => (9, 1),
+        1 => (11, 1),
+        2 => (12, 2),
+        _ => eyre::bail!("unsupported transaction type {}", kind),
+    };
+    if rlp.item_count()? != field_count {
+        eyre::bail!("invalid transaction: expected {} fields", field_count)
+    }
+
+    // Typed transactions start with the chain id
+    let offset = if kind == 0 { 0 } else { 1 };
+    let nonce: u64 = rlp.val_at(offset)?;
+    let (max_priority_fee, gas_price) = if fee_fields == 2 {
+        (Some(rlp.val_at(offset + 1)?), rlp.val_at(offset + 2)?)
+    } else {
+        (None, rlp.val_at(offset + 1)?)
+    };
+    let gas_limit: u64 = rlp.val_at(offset + 1 + fee_fields)?;
+    let to = rlp.at(offset + 2 + fee_fields)?;
+    let to = if to.is_empty() { None } else { Some(to.as_val::<Address>()?) };
+    let value: U256 = rlp.val_at(offset + 3 + fee_fields)?;
+    let input: Vec<u8> = rlp.val_at(offset + 4 + fee_fields)?;
+
+    let v: u64 = rlp.val_at(field_count - 3)?;
+    let r: U256 = rlp.val_at(field_count - 2)?;
+    let s: U256 = rlp.val_at(field_count - 1)?;
+
+    // The signed message consists of all fields but the signature, legacy transactions replace
+    // the signature with the chain id since EIP-155
+    let (chain_id, sighash) = if kind == 0 {
+        let chain_id = (v >= 35).then(|| (v - 35) / 2);
+        let mut stream = RlpStream::new_list(if chain_id.is_some() { 9 } else { 6 });
+        for i in 0..6 {
+            stream.append_raw(rlp.at(i)?.as_raw(), 1);
+        }
+        if let Some(chain_id) = chain_id {
+            stream.append(&chain_id);
+            stream.append(&0u8);
+            stream.append(&0u8);
+        }
+        (chain_id, keccak256(stream.out()))
+    } else {
+        let mut stream = RlpStream::new_list(field_count - 3);
+        for i in 0..field_count - 3 {
+            stream.append_raw(rlp.at(i)?.as_raw(), 1);
+        }
+        let mut message = vec![kind];
+        message.extend_from_slice(&stream.out());
+        (Some(rlp.val_at(0)?), keccak256(message))
+    };
+
+    let signature = Signature { r, s, v };
+    let from = signature.recover(H256::from(sighash))?;
+
+    Ok(Transaction {
+        hash: keccak256(raw).into(),
+        kind,
+        from,
+        to,
+        nonce,
+        value,
+        input: input.into(),
+        gas_limit,
+        gas_price,
+        max_priority_fee,
+        chain_id,
+        signature: Some(signature),
+    })
+}
+
+/// Computes the hash of an unsigned transaction of a development account
+pub fn unsigned_transaction_hash(tx: &Transaction) -> H256 {
+    let mut stream = RlpStream::new_list(7);
+    stream.append(&tx.nonce);
+    stream.append(&tx.gas_price);
+    stream.append(&tx.gas_limit);
+    match tx.to {
+        Some(to) => stream.append(&to),
+        None => stream.append_empty_data(),
+    };
+    stream.append(&tx.value);
+    stream.append(&tx.input.0.to_vec());
+    stream.append(&tx.from);
+    keccak256(stream.out()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_decode_eip155_transaction() {
+        // The example transaction of EIP-155
+        let raw = hex::decode("f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83").unwrap();
+        let tx = decode_raw_transaction(&raw).unwrap();
+        assert_eq!(tx.kind, 0);
+        assert_eq!(tx.chain_id, Some(1));
+        assert_eq!(tx.nonce, 9);
+        assert_eq!(tx.gas_price, U256::from(20_000_000_000u64));
+        assert_eq!(tx.gas_limit, 21000);
+        assert_eq!(tx.to, Some(Address::repeat_byte(0x35)));
+        assert_eq!(tx.value, U256::exp10(18));
+        assert_eq!(
+            tx.from,
+            "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F".parse::<Address>().unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_transaction_types() {
+        assert!(decode_raw_transaction(&[]).is_err());
+        assert!(decode_raw_transaction(&[0x03, 0xc0]).is_err());
+    }
+}
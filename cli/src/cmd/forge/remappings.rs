@@ -2,7 +2,8 @@
 
 use crate::cmd::Cmd;
 use clap::{Parser, ValueHint};
-use ethers::solc::{remappings::Remapping, ProjectPathsConfig};
+use ethers::solc::remappings::Remapping;
+use foundry_config::Config;
 use std::path::{Path, PathBuf};
 
 /// Command to list remappings
@@ -15,7 +16,7 @@ pub struct RemappingArgs {
     )]
     root: Option<PathBuf>,
     #[clap(
-        help = "the paths where your libraries are installed",
+        help = "the paths where your libraries are installed, if set only the remappings inferred from these paths are printed",
         long,
         value_hint = ValueHint::DirPath
     )]
@@ -29,14 +30,17 @@ impl Cmd for RemappingArgs {
         let root = self.root.unwrap_or_else(|| std::env::current_dir().unwrap());
         let root = dunce::canonicalize(root)?;
 
-        let lib_paths = if self.lib_paths.is_empty() {
-            ProjectPathsConfig::find_libs(&root)
-        } else {
-            self.lib_paths
-        };
-        let remappings: Vec<_> =
-            lib_paths.iter().flat_map(|lib| relative_remappings(lib, &root)).collect();
-        remappings.iter().for_each(|x| println!("{}", x));
+        if !self.lib_paths.is_empty() {
+            let remappings: Vec<_> =
+                self.lib_paths.iter().flat_map(|lib| relative_remappings(lib, &root)).collect();
+            remappings.iter().for_each(|x| println!("{}", x));
+            return Ok(())
+        }
+
+        // the config already merges the inferred remappings with `remappings.txt`, the
+        // `foundry.toml` and env vars
+        let config = Config::load_with_root(&root);
+        config.remappings.iter().for_each(|x| println!("{}", x));
         Ok(())
     }
 }
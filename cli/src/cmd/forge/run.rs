@@ -1,5 +1,12 @@
 use crate::{
-    cmd::{compile_files, forge::build::BuildArgs, Cmd},
+    cmd::{
+        compile_files,
+        forge::{
+            build::BuildArgs,
+            test::{storage_layouts, StorageLayouts},
+        },
+        Cmd,
+    },
     opts::evm::EvmArgs,
     utils,
 };
@@ -8,25 +15,33 @@ use clap::{Parser, ValueHint};
 use ethers::{
     abi::{Abi, RawLog},
     solc::{
-        artifacts::{CompactContractBytecode, ContractBytecode, ContractBytecodeSome},
+        artifacts::{
+            output_selection::ContractOutputSelection, CompactContractBytecode, ContractBytecode,
+            ContractBytecodeSome,
+        },
         Project,
     },
-    types::{Address, Bytes, U256},
+    types::{Address, Bytes, TransactionRequest, U256},
 };
 use forge::{
     debug::DebugArena,
     decode::decode_console_logs,
     executor::{
-        opts::EvmOpts, CallResult, DatabaseRef, DeployResult, EvmError, Executor, ExecutorBuilder,
-        RawCallResult,
+        inspector::Precompiles, opts::EvmOpts, CallResult, DatabaseRef, DeployResult, EvmError,
+        Executor, ExecutorBuilder, RawCallResult, StateDump,
+    },
+    trace::{
+        identifier::LocalTraceIdentifier, CallTraceArena, CallTraceDecoder, TraceFormat, TraceKind,
     },
-    trace::{identifier::LocalTraceIdentifier, CallTraceArena, CallTraceDecoder, TraceKind},
     CALLER,
 };
 use foundry_config::{figment::Figment, Config};
 use foundry_utils::{encode_args, IntoFunction, PostLinkInput};
-use std::{collections::BTreeMap, path::PathBuf};
-use ui::{TUIExitReason, Tui, Ui};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+use ui::{Breakpoint, TUIExitReason, Tui, Ui, Watchpoint};
 
 // Loads project's figment and merges the build cli arguments into it
 foundry_config::impl_figment_convert!(RunArgs, opts, evm_opts);
@@ -55,6 +70,34 @@ pub struct RunArgs {
     #[clap(long)]
     pub debug: bool,
 
+    /// Stop the debugger at a source line.
+    #[clap(long = "breakpoint", value_name = "FILE:LINE", requires = "debug")]
+    pub breakpoints: Vec<String>,
+
+    /// Stop the debugger when a storage slot is written.
+    ///
+    /// Either the slot or the name of a storage variable, optionally qualified by its contract.
+    #[clap(long = "watchpoint", value_name = "SLOT|[CONTRACT.]VARIABLE", requires = "debug")]
+    pub watchpoints: Vec<String>,
+
+    /// The format of the printed traces: text, json, geth or parity.
+    #[clap(long, value_name = "FORMAT", default_value = "text")]
+    pub trace_format: TraceFormat,
+
+    /// Write the transactions marked by the broadcast cheatcodes to a JSON file.
+    #[clap(long, value_name = "FILE", value_hint = ValueHint::FilePath)]
+    pub transactions: Option<PathBuf>,
+
+    /// Load the state of the accounts from a file written by `--dump-state` before deploying the
+    /// script.
+    #[clap(long, value_name = "PATH", value_hint = ValueHint::FilePath)]
+    pub load_state: Option<PathBuf>,
+
+    /// Dump the state of the accounts to the given file after the script ran, to share a
+    /// prepared state across runs.
+    #[clap(long, value_name = "PATH", value_hint = ValueHint::FilePath)]
+    pub dump_state: Option<PathBuf>,
+
     #[clap(flatten, next_help_heading = "BUILD OPTIONS")]
     pub opts: BuildArgs,
 
@@ -66,9 +109,14 @@ impl Cmd for RunArgs {
     type Output = ();
     fn run(self) -> eyre::Result<Self::Output> {
         let figment: Figment = From::from(&self);
-        let evm_opts = figment.extract::<EvmOpts>()?;
+        let mut evm_opts = figment.extract::<EvmOpts>()?;
         let verbosity = evm_opts.verbosity;
-        let config = Config::from_provider(figment).sanitized();
+        let mut config = Config::from_provider(figment).sanitized();
+        utils::resolve_fork_url(&mut evm_opts, &config)?;
+        if !self.watchpoints.is_empty() {
+            // needed to resolve the slots of watched variables
+            config.extra_output.push(ContractOutputSelection::StorageLayout);
+        }
 
         let BuildOutput {
             project,
@@ -76,6 +124,7 @@ impl Cmd for RunArgs {
             highlevel_known_contracts,
             sources,
             predeploy_libraries,
+            storage_layouts,
         } = self.build(&config, &evm_opts)?;
 
         let known_contracts = highlevel_known_contracts
@@ -100,22 +149,40 @@ impl Cmd for RunArgs {
             .with_cheatcodes(evm_opts.ffi)
             .with_config(evm_opts.evm_env())
             .with_spec(crate::utils::evm_spec(&config.evm_version))
-            .with_fork(utils::get_fork(&evm_opts, &config.rpc_storage_caching));
-        if verbosity >= 3 {
+            .with_memory_limit(evm_opts.memory_limit)
+            .with_fork(utils::get_fork(&evm_opts, &config.rpc_storage_caching))
+            .with_precompiles(Precompiles::from_commands(&config.precompiles, evm_opts.ffi))
+            .with_fs_permissions(config.fs_permissions.clone())
+            .with_rpc_endpoints(config.rpc_endpoints.clone());
+        if verbosity >= 5 {
+            builder = builder.with_storage_tracing();
+        } else if verbosity >= 3 {
             builder = builder.with_tracing();
         }
         if self.debug {
+            if config.via_ir {
+                println!("Warning: the source maps of contracts compiled with `via_ir` are less accurate, the debugger may not highlight the matching source code");
+            }
             builder = builder.with_tracing().with_debugger();
         }
 
         let mut result = {
-            let mut runner =
-                Runner::new(builder.build(), evm_opts.initial_balance, evm_opts.sender);
+            let mut executor = builder.build();
+            if let Some(path) = &self.load_state {
+                executor.load_state(&StateDump::read(path)?);
+            }
+            let mut runner = Runner::new(executor, evm_opts.initial_balance, evm_opts.sender);
             let (address, mut result) =
                 runner.setup(&predeploy_libraries, bytecode, needs_setup)?;
 
             let RunResult {
-                success, gas, logs, traces, debug: run_debug, labeled_addresses, ..
+                success,
+                gas,
+                logs,
+                traces,
+                debug: run_debug,
+                labeled_addresses,
+                transactions,
             } = runner.run(
                 address,
                 if let Some(calldata) = self.sig.strip_prefix("0x") {
@@ -123,6 +190,7 @@ impl Cmd for RunArgs {
                 } else {
                     encode_args(&IntoFunction::into(self.sig), &self.args)?.into()
                 },
+                self.dump_state.is_some(),
             )?;
 
             result.success &= success;
@@ -132,6 +200,11 @@ impl Cmd for RunArgs {
             result.traces.extend(traces);
             result.debug = run_debug;
             result.labeled_addresses.extend(labeled_addresses);
+            result.transactions = transactions;
+
+            if let Some(path) = &self.dump_state {
+                runner.executor.dump_state().write(path)?;
+            }
 
             result
         };
@@ -161,10 +234,14 @@ impl Cmd for RunArgs {
                 })
                 .collect();
 
+            let breakpoints = self.breakpoints(&sources)?;
+            let watchpoints = self.watchpoints(&storage_layouts)?;
             let calls: Vec<DebugArena> = result.debug.expect("we should have collected debug info");
             let flattened = calls.last().expect("we should have collected debug info").flatten(0);
             let tui =
-                Tui::new(flattened, 0, decoder.contracts, highlevel_known_contracts, source_code)?;
+                Tui::new(flattened, 0, decoder.contracts, highlevel_known_contracts, source_code)?
+                    .with_breakpoints(breakpoints)
+                    .with_watchpoints(watchpoints);
             match tui.start().expect("Failed to start tui") {
                 TUIExitReason::CharExit => return Ok(()),
             }
@@ -186,8 +263,12 @@ impl Cmd for RunArgs {
                         };
 
                         if should_include {
-                            decoder.decode(trace);
-                            println!("{}", trace);
+                            // Raw formats contain the calldata, so they are formatted before
+                            // decoding
+                            if !self.trace_format.is_raw() {
+                                decoder.decode(trace);
+                            }
+                            println!("{}", self.trace_format.format(trace));
                         }
                     }
                     println!();
@@ -208,6 +289,27 @@ impl Cmd for RunArgs {
                     println!("  {}", log);
                 }
             }
+
+            if !result.transactions.is_empty() {
+                println!("== Transactions to broadcast ==");
+                for tx in &result.transactions {
+                    let to = tx
+                        .to
+                        .as_ref()
+                        .map(|to| format!("{:?}", to))
+                        .unwrap_or_else(|| "contract creation".to_string());
+                    println!(
+                        "  {:?} -> {} value: {}",
+                        tx.from.unwrap_or_default(),
+                        to,
+                        tx.value.unwrap_or_default()
+                    );
+                }
+            }
+        }
+
+        if let Some(path) = &self.transactions {
+            std::fs::write(path, serde_json::to_string_pretty(&result.transactions)?)?;
         }
         Ok(())
     }
@@ -227,15 +329,84 @@ pub struct BuildOutput {
     pub highlevel_known_contracts: BTreeMap<String, ContractBytecodeSome>,
     pub sources: BTreeMap<u32, String>,
     pub predeploy_libraries: Vec<ethers::types::Bytes>,
+    pub storage_layouts: StorageLayouts,
 }
 
 impl RunArgs {
+    /// Resolves the `--breakpoint` arguments to the lines of the sources whose paths end with
+    /// the given file
+    fn breakpoints(&self, sources: &BTreeMap<u32, String>) -> eyre::Result<Vec<Breakpoint>> {
+        let mut breakpoints = Vec::new();
+        for breakpoint in &self.breakpoints {
+            let (file, line) = breakpoint.rsplit_once(':').ok_or_else(|| {
+                eyre::eyre!("Invalid breakpoint `{}`, expected `<FILE>:<LINE>`", breakpoint)
+            })?;
+            let line = line
+                .parse()
+                .map_err(|_| eyre::eyre!("Invalid line in breakpoint `{}`", breakpoint))?;
+            let file = Path::new(file.trim_start_matches("./"));
+            let len = breakpoints.len();
+            breakpoints.extend(
+                sources
+                    .iter()
+                    .filter(|(_, path)| Path::new(path).ends_with(file))
+                    .map(|(source, _)| Breakpoint { source: *source, line }),
+            );
+            if breakpoints.len() == len {
+                eyre::bail!("No source file matches the breakpoint `{}`", breakpoint)
+            }
+        }
+        Ok(breakpoints)
+    }
+
+    /// Resolves the `--watchpoint` arguments to storage slots, looking up the slots of variables in
+    /// the storage layouts
+    fn watchpoints(&self, storage_layouts: &StorageLayouts) -> eyre::Result<Vec<Watchpoint>> {
+        let mut watchpoints = Vec::new();
+        for watch in &self.watchpoints {
+            let slot = match watch.strip_prefix("0x") {
+                Some(hex) => U256::from_str_radix(hex, 16).ok(),
+                None => U256::from_dec_str(watch).ok(),
+            };
+            if let Some(slot) = slot {
+                watchpoints.push(Watchpoint { contract: None, slot });
+                continue
+            }
+
+            let (contract, variable) = match watch.split_once('.') {
+                Some((contract, variable)) => (Some(contract), variable),
+                None => (None, watch.as_str()),
+            };
+            let len = watchpoints.len();
+            for (name, slots) in storage_layouts {
+                if contract.map_or(false, |contract| contract != name) {
+                    continue
+                }
+                watchpoints.extend(
+                    slots
+                        .iter()
+                        .filter(|(_, labels)| labels.iter().any(|label| label == variable))
+                        .map(|(slot, _)| Watchpoint { contract: Some(name.clone()), slot: *slot }),
+                );
+            }
+            if watchpoints.len() == len {
+                eyre::bail!("No storage variable matches the watchpoint `{}`", watch)
+            }
+        }
+        Ok(watchpoints)
+    }
+
     /// Compiles the file with auto-detection and compiler params.
     pub fn build(&self, config: &Config, evm_opts: &EvmOpts) -> eyre::Result<BuildOutput> {
         let target_contract = dunce::canonicalize(&self.path)?;
         let project = config.ephemeral_no_artifacts_project()?;
         let output = compile_files(&project, vec![target_contract])?;
 
+        let storage_layouts = if self.watchpoints.is_empty() {
+            StorageLayouts::default()
+        } else {
+            storage_layouts(output.clone())
+        };
         let (sources, all_contracts) = output.output().split();
 
         let contracts: BTreeMap<String, CompactContractBytecode> = all_contracts
@@ -309,6 +480,7 @@ impl RunArgs {
             contract,
             highlevel_known_contracts,
             sources: sources.into_ids().collect(),
+            storage_layouts,
             predeploy_libraries: run_dependencies,
         })
     }
@@ -321,6 +493,7 @@ struct RunResult {
     pub debug: Option<Vec<DebugArena>>,
     pub gas: u64,
     pub labeled_addresses: BTreeMap<Address, String>,
+    pub transactions: Vec<TransactionRequest>,
 }
 
 struct Runner<DB: DatabaseRef> {
@@ -406,6 +579,7 @@ impl<DB: DatabaseRef> Runner<DB> {
                             success: !reverted,
                             debug: vec![constructor_debug, debug].into_iter().collect(),
                             gas,
+                            transactions: vec![],
                         },
                     )
                 }
@@ -421,14 +595,35 @@ impl<DB: DatabaseRef> Runner<DB> {
                     debug: vec![constructor_debug].into_iter().collect(),
                     gas: 0,
                     labeled_addresses: Default::default(),
+                    transactions: vec![],
                 },
             )
         })
     }
 
-    pub fn run(&mut self, address: Address, calldata: Bytes) -> eyre::Result<RunResult> {
-        let RawCallResult { reverted, gas, stipend, logs, traces, labels, debug, .. } =
-            self.executor.call_raw(self.sender, address, calldata.0, 0.into())?;
+    /// Calls `address` with `calldata`, persisting the state changes of the call only if `commit`
+    /// is set, e.g. to dump them with `--dump-state`
+    pub fn run(
+        &mut self,
+        address: Address,
+        calldata: Bytes,
+        commit: bool,
+    ) -> eyre::Result<RunResult> {
+        let RawCallResult {
+            reverted,
+            gas,
+            stipend,
+            logs,
+            traces,
+            labels,
+            debug,
+            broadcastable_transactions,
+            ..
+        } = if commit {
+            self.executor.call_raw_committing(self.sender, address, calldata.0, 0.into())?
+        } else {
+            self.executor.call_raw(self.sender, address, calldata.0, 0.into())?
+        };
         Ok(RunResult {
             success: !reverted,
             gas: gas.overflowing_sub(stipend).0,
@@ -436,6 +631,7 @@ impl<DB: DatabaseRef> Runner<DB> {
             traces: traces.map(|traces| vec![(TraceKind::Execution, traces)]).unwrap_or_default(),
             debug: vec![debug].into_iter().collect(),
             labeled_addresses: labels,
+            transactions: broadcastable_transactions,
         })
     }
 }
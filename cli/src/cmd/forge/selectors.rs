@@ -0,0 +1,213 @@
+//! upload-selectors command
+
+use crate::{
+    cmd::{abi_signature, forge::build::BuildArgs, suppress_compile, Cmd},
+    utils,
+};
+use clap::Parser;
+use ethers::abi::Abi;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+const FOURBYTE_IMPORT_URL: &str = "https://www.4byte.directory/api/v1/import-abi/";
+const OPENCHAIN_IMPORT_URL: &str = "https://api.openchain.xyz/signature-database/v1/import";
+
+/// Command to upload the selectors of a project's contracts to the public signature databases
+#[derive(Debug, Clone, Parser)]
+pub struct UploadSelectorsArgs {
+    #[clap(
+        help = "only upload the selectors of these contracts, defaults to all contracts",
+        value_name = "CONTRACT"
+    )]
+    selected_contracts: Vec<String>,
+    #[clap(help = "print the signatures without uploading them", long)]
+    dry_run: bool,
+
+    /// All build arguments are supported
+    #[clap(flatten)]
+    opts: BuildArgs,
+}
+
+/// The signatures of a set of contracts, in the request format of openchain's `import` endpoint
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SelectorImportData {
+    /// the signatures of all functions and errors, which share the 4 byte selector format
+    pub function: BTreeSet<String>,
+    pub event: BTreeSet<String>,
+}
+
+impl SelectorImportData {
+    /// Adds all functions, errors and events of the abi
+    pub fn extend(&mut self, abi: &Abi) {
+        self.function.extend(abi.functions().map(|f| abi_signature(&f.name, &f.inputs)));
+        self.function.extend(abi.errors().map(|e| abi_signature(&e.name, &e.inputs)));
+        self.event.extend(abi.events().map(|e| {
+            let types = e.inputs.iter().map(|p| p.kind.to_string()).collect::<Vec<_>>();
+            format!("{}({})", e.name, types.join(","))
+        }));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.function.is_empty() && self.event.is_empty()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct FourbyteImportResponse {
+    num_processed: u64,
+    num_imported: u64,
+    num_duplicates: u64,
+    num_ignored: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenchainImportResponse {
+    ok: bool,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    result: Option<OpenchainImportResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenchainImportResult {
+    function: OpenchainImportCount,
+    event: OpenchainImportCount,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenchainImportCount {
+    imported: BTreeMap<String, String>,
+    duplicated: BTreeMap<String, String>,
+}
+
+impl Cmd for UploadSelectorsArgs {
+    type Output = ();
+
+    fn run(self) -> eyre::Result<Self::Output> {
+        let UploadSelectorsArgs { selected_contracts, dry_run, opts } = self;
+
+        let project = opts.project()?;
+        let output = suppress_compile(&project)?;
+
+        let mut data = SelectorImportData::default();
+        // the abi entries of all functions and events, as expected by 4byte
+        let mut entries = BTreeSet::new();
+        for (id, artifact) in output.into_artifacts() {
+            if id.source.starts_with(&project.paths.tests) {
+                continue
+            }
+            if !selected_contracts.is_empty() && !selected_contracts.contains(&id.name) {
+                continue
+            }
+            let abi = match artifact.abi {
+                Some(abi) => serde_json::to_value(&abi)?,
+                None => continue,
+            };
+            if let Value::Array(items) = &abi {
+                entries.extend(
+                    items
+                        .iter()
+                        .filter(|item| matches!(item["type"].as_str(), Some("function" | "event")))
+                        .map(Value::to_string),
+                );
+            }
+            data.extend(&serde_json::from_value(abi)?);
+        }
+
+        if data.is_empty() {
+            println!("No selectors found");
+            return Ok(())
+        }
+
+        if dry_run {
+            data.function.iter().chain(data.event.iter()).for_each(|sig| println!("{}", sig));
+            return Ok(())
+        }
+
+        println!(
+            "Uploading {} function and error and {} event signatures",
+            data.function.len(),
+            data.event.len()
+        );
+        let contract_abi = format!("[{}]", entries.into_iter().collect::<Vec<_>>().join(","));
+        utils::block_on(async {
+            upload_to_fourbyte(contract_abi).await?;
+            upload_to_openchain(&data).await
+        })
+    }
+}
+
+/// Uploads the abi entries to 4byte.directory
+async fn upload_to_fourbyte(contract_abi: String) -> eyre::Result<()> {
+    let resp = reqwest::Client::new()
+        .post(FOURBYTE_IMPORT_URL)
+        .json(&serde_json::json!({ "contract_abi": contract_abi }))
+        .send()
+        .await?;
+    let status = resp.status();
+    if !status.is_success() {
+        eyre::bail!("Failed to upload selectors to 4byte: {}\n{}", status, resp.text().await?)
+    }
+    let resp: FourbyteImportResponse = resp.json().await?;
+    println!(
+        "4byte: processed {}, imported {}, duplicates {}, ignored {}",
+        resp.num_processed, resp.num_imported, resp.num_duplicates, resp.num_ignored
+    );
+    Ok(())
+}
+
+/// Uploads the signatures to openchain's signature database
+async fn upload_to_openchain(data: &SelectorImportData) -> eyre::Result<()> {
+    let resp: OpenchainImportResponse =
+        reqwest::Client::new().post(OPENCHAIN_IMPORT_URL).json(data).send().await?.json().await?;
+    if !resp.ok {
+        eyre::bail!("Failed to upload selectors to openchain: {}", resp.error.unwrap_or_default())
+    }
+    if let Some(result) = resp.result {
+        println!(
+            "openchain: imported {} functions and {} events, duplicates {} functions and {} events",
+            result.function.imported.len(),
+            result.event.imported.len(),
+            result.function.duplicated.len(),
+            result.event.duplicated.len()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_contracts_and_source_dir() {
+        let args =
+            UploadSelectorsArgs::parse_from(["upload-selectors", "Token", "--contracts", "src"]);
+        assert_eq!(args.selected_contracts, vec!["Token".to_string()]);
+        assert_eq!(args.opts.contracts, Some(std::path::PathBuf::from("src")));
+    }
+
+    #[test]
+    fn can_collect_signatures() {
+        let abi: Abi = serde_json::from_str(
+            r#"[
+            {"type":"function","name":"transfer","stateMutability":"nonpayable","inputs":[{"name":"to","type":"address"},{"name":"amount","type":"uint256"}],"outputs":[{"name":"","type":"bool"}]},
+            {"type":"event","name":"Transfer","anonymous":false,"inputs":[{"name":"from","type":"address","indexed":true},{"name":"to","type":"address","indexed":true},{"name":"amount","type":"uint256","indexed":false}]},
+            {"type":"error","name":"InsufficientBalance","inputs":[{"name":"available","type":"uint256"}]}
+        ]"#,
+        )
+        .unwrap();
+        let mut data = SelectorImportData::default();
+        data.extend(&abi);
+        assert_eq!(
+            data.function,
+            BTreeSet::from([
+                "InsufficientBalance(uint256)".to_string(),
+                "transfer(address,uint256)".to_string()
+            ])
+        );
+        assert_eq!(data.event, BTreeSet::from(["Transfer(address,address,uint256)".to_string()]));
+    }
+}
@@ -1,7 +1,7 @@
 //! Test command
 use crate::{
     cmd::{
-        forge::{build::BuildArgs, run::RunArgs},
+        forge::{build::BuildArgs, run::RunArgs, vyper},
         Cmd,
     },
     opts::evm::EvmArgs,
@@ -9,16 +9,35 @@ use crate::{
 };
 use ansi_term::Colour;
 use clap::{AppSettings, Parser};
+use ethers::{
+    solc::{artifacts::output_selection::ContractOutputSelection, ProjectCompileOutput},
+    types::{Address, Bytes, U256},
+    utils::to_checksum,
+};
 use forge::{
     decode::decode_console_logs,
-    executor::opts::EvmOpts,
+    executor::{inspector::Precompiles, opts::EvmOpts, StateDiff},
+    fuzz::FuzzParamOpts,
     gas_report::GasReport,
-    trace::{identifier::LocalTraceIdentifier, CallTraceDecoder, TraceKind},
+    trace::{
+        identifier::{LocalTraceIdentifier, TraceIdentifier},
+        CallTraceArena, CallTraceDecoder, TraceFormat, TraceKind,
+    },
     MultiContractRunner, MultiContractRunnerBuilder, TestFilter, TestKind, TestResult,
 };
 use foundry_config::{figment::Figment, Config};
+use proptest::test_runner::{RngAlgorithm, TestRng, TestRunner};
 use regex::Regex;
-use std::{collections::BTreeMap, path::PathBuf, str::FromStr, sync::mpsc::channel, thread};
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::mpsc::channel,
+    thread,
+    time::Duration,
+};
 
 #[derive(Debug, Clone, Parser)]
 pub struct Filter {
@@ -53,6 +72,14 @@ pub struct Filter {
     pub path_pattern_inverse: Option<regex::Regex>,
 }
 
+impl Filter {
+    /// Returns whether the tests are filtered by their source file, see `--match-path` and
+    /// `--no-match-path`
+    pub fn has_path_filter(&self) -> bool {
+        self.path_pattern.is_some() || self.path_pattern_inverse.is_some()
+    }
+}
+
 impl TestFilter for Filter {
     fn matches_test(&self, test_name: impl AsRef<str>) -> bool {
         let mut ok = true;
@@ -124,6 +151,16 @@ pub struct TestArgs {
     #[clap(long, value_name = "TEST FUNCTION")]
     debug: Option<Regex>,
 
+    /// Stop the debugger at a source line.
+    #[clap(long = "breakpoint", value_name = "FILE:LINE", requires = "debug")]
+    breakpoints: Vec<String>,
+
+    /// Stop the debugger when a storage slot is written.
+    ///
+    /// Either the slot or the name of a storage variable, optionally qualified by its contract.
+    #[clap(long = "watchpoint", value_name = "SLOT|[CONTRACT.]VARIABLE", requires = "debug")]
+    watchpoints: Vec<String>,
+
     /// Print a gas report.
     #[clap(long = "gas-report")]
     gas_report: bool,
@@ -136,6 +173,60 @@ pub struct TestArgs {
     #[clap(long, short)]
     json: bool,
 
+    /// Write the counterexamples of the failing fuzz tests to the file as JSON.
+    ///
+    /// Each counterexample has the test, the decoded args, the raw calldata and a command that
+    /// replays it.
+    #[clap(long, value_name = "FILE")]
+    counterexamples: Option<PathBuf>,
+
+    /// Print the net state changes of each test.
+    ///
+    /// This includes created accounts, balance and nonce changes and all written storage slots,
+    /// which are annotated with the names of the variables from the storage layout of local
+    /// contracts where possible.
+    #[clap(long)]
+    state_diff: bool,
+
+    /// The format of the printed traces: text, json, geth or parity.
+    ///
+    /// The geth and parity formats match the output of geth's `callTracer` and parity's
+    /// `trace_transaction`, so they can be loaded into existing trace analysis tools.
+    #[clap(long, value_name = "FORMAT", default_value = "text")]
+    trace_format: TraceFormat,
+
+    /// Write a gas flamegraph of each test to the directory.
+    ///
+    /// The files are in the folded stacks format, which can be rendered with tools like
+    /// `inferno-flamegraph` or speedscope.
+    #[clap(long, value_name = "DIR")]
+    flamegraph: Option<PathBuf>,
+
+    /// Seed the random number generator of the fuzzer to reproduce a fuzz run.
+    ///
+    /// The seed of a run is printed when a fuzz test fails. Overrides `fuzz_seed` of the config.
+    #[clap(long, value_name = "SEED", parse(try_from_str = utils::parse_u256))]
+    fuzz_seed: Option<U256>,
+
+    /// Fuzz each fuzz test for this many seconds instead of a number of runs.
+    ///
+    /// A test stops early when it fails. Overrides `fuzz_max_time` of the config.
+    #[clap(long, value_name = "SECS")]
+    fuzz_max_time: Option<u64>,
+
+    /// Split the runs of each fuzz test across this many threads.
+    ///
+    /// Overrides `fuzz_workers` of the config.
+    #[clap(long, value_name = "THREADS")]
+    fuzz_workers: Option<usize>,
+
+    /// Call the fuzz test with the calldata of a counterexample instead of fuzzing it.
+    ///
+    /// The calldata is sent from `--sender`, the `Replay:` line of a failing fuzz test prints the
+    /// whole command.
+    #[clap(long, value_name = "CALLDATA")]
+    replay: Option<Bytes>,
+
     #[clap(flatten, next_help_heading = "EVM OPTIONS")]
     evm_opts: EvmArgs,
 
@@ -162,7 +253,14 @@ impl Cmd for TestArgs {
         // Merge all configs
         let figment: Figment = From::from(&self);
         let mut evm_opts = figment.extract::<EvmOpts>()?;
-        let config = Config::from_provider(figment).sanitized();
+        let mut config = Config::from_provider(figment).sanitized();
+        utils::resolve_fork_url(&mut evm_opts, &config)?;
+        // Storage layouts are used to name the slots in state diffs and in the storage accesses
+        // of traces at the highest verbosity
+        let needs_storage_layouts = self.state_diff || evm_opts.verbosity >= 5;
+        if needs_storage_layouts {
+            config.extra_output.push(ContractOutputSelection::StorageLayout);
+        }
 
         // Setup the fuzzer
         // TODO: Add CLI Options to modify the persistence
@@ -173,19 +271,51 @@ impl Cmd for TestArgs {
             max_global_rejects: config.fuzz_max_global_rejects,
             ..Default::default()
         };
-        let fuzzer = proptest::test_runner::TestRunner::new(cfg);
-
-        // Set up the project
+        // Always pick the seed ourselves, so it can be printed to reproduce failing runs
+        let fuzz_seed = self
+            .fuzz_seed
+            .or(config.fuzz_seed)
+            .unwrap_or_else(|| U256::from(ethers::core::rand::random::<[u8; 32]>()));
+        let mut seed = [0u8; 32];
+        fuzz_seed.to_big_endian(&mut seed);
+        let fuzzer = TestRunner::new_with_rng(cfg, TestRng::from_seed(RngAlgorithm::ChaCha, &seed));
+
+        // Set up the project, if the tests are filtered by path only the matching files and their
+        // imports are compiled
         let project = config.project()?;
-        let output = crate::cmd::compile(&project, false, false)?;
+        let output = if self.filter.has_path_filter() {
+            let files = [&project.paths.sources, &project.paths.tests]
+                .into_iter()
+                .filter(|dir| dir.exists())
+                .flat_map(ethers::solc::utils::source_files)
+                .filter(|file| self.filter.matches_path(file.to_string_lossy()))
+                .collect::<BTreeSet<_>>();
+            if files.is_empty() {
+                eyre::bail!("No source files match the path filter")
+            }
+            crate::cmd::utils::compile_files(&project, files.into_iter().collect())?
+        } else {
+            crate::cmd::compile(&project, false, false)?
+        };
+        vyper::compile_vyper_sources(
+            &project,
+            config.vyper.as_deref().unwrap_or_else(|| Path::new(vyper::VYPER)),
+        )?;
+
+        let storage_layouts = if needs_storage_layouts {
+            storage_layouts(output.clone())
+        } else {
+            Default::default()
+        };
 
         // Determine print verbosity and executor verbosity
         let verbosity = evm_opts.verbosity;
-        if self.gas_report && evm_opts.verbosity < 3 {
+        if (self.gas_report || self.flamegraph.is_some()) && evm_opts.verbosity < 3 {
             evm_opts.verbosity = 3;
         }
 
         // Prepare the test builder
+        let sender = evm_opts.sender;
         let evm_spec = crate::utils::evm_spec(&config.evm_version);
         let mut runner = MultiContractRunnerBuilder::default()
             .fuzzer(fuzzer)
@@ -193,6 +323,26 @@ impl Cmd for TestArgs {
             .evm_spec(evm_spec)
             .sender(evm_opts.sender)
             .with_fork(utils::get_fork(&evm_opts, &config.rpc_storage_caching))
+            .with_precompiles(Precompiles::from_commands(&config.precompiles, evm_opts.ffi))
+            .with_fs_permissions(config.fs_permissions.clone())
+            .with_rpc_endpoints(config.rpc_endpoints.clone())
+            .with_state_diff(self.state_diff)
+            .with_fuzz_param_opts(FuzzParamOpts {
+                uniform_ints: config.fuzz_uniform_ints,
+                edge_percent: config.fuzz_edge_percent,
+                max_string_len: config.fuzz_max_string_len,
+                max_array_len: config.fuzz_max_array_len,
+                max_bytes_len: config.fuzz_max_bytes_len,
+                dictionary_weight: config.fuzz_dictionary_weight,
+                coverage_guided: config.fuzz_coverage_guided,
+                actors: config.fuzz_actors,
+            })
+            .with_fuzz_corpus(config.cache.then(|| config.cache_path.join("fuzz")))
+            .with_fuzz_max_time(
+                self.fuzz_max_time.or(config.fuzz_max_time).map(Duration::from_secs),
+            )
+            .with_fuzz_workers(self.fuzz_workers.unwrap_or(config.fuzz_workers))
+            .with_fuzz_replay(self.replay.clone())
             .build(output, evm_opts)?;
 
         if self.debug.is_some() {
@@ -228,6 +378,9 @@ impl Cmd for TestArgs {
                         sig,
                         args: Vec::new(),
                         debug: true,
+                        breakpoints: self.breakpoints,
+                        watchpoints: self.watchpoints,
+                        trace_format: self.trace_format,
                         opts: self.opts,
                         evm_opts: self.evm_opts,
                     };
@@ -242,15 +395,27 @@ impl Cmd for TestArgs {
                         Use --match-contract and --match-path to further limit the search.", n))
             }
         } else {
+            let source_paths = runner.source_paths.clone();
             let TestArgs { filter, .. } = self;
-            test(
+            let outcome = test(
                 runner,
                 verbosity,
                 filter,
                 self.json,
                 self.allow_failure,
                 (self.gas_report, config.gas_reports),
-            )
+                storage_layouts,
+                self.trace_format,
+                self.flamegraph.as_deref(),
+                sender,
+            )?
+            .with_fuzz_seed(fuzz_seed);
+
+            if let Some(path) = &self.counterexamples {
+                let counterexamples = outcome.counterexamples(&source_paths, sender);
+                std::fs::write(path, serde_json::to_string_pretty(&counterexamples)?)?;
+            }
+            Ok(outcome)
         }
     }
 }
@@ -283,12 +448,46 @@ impl Test {
     }
 }
 
+/// The counterexample of a failing fuzz test, as written by `--counterexamples`
+#[derive(Debug, Clone, Serialize)]
+pub struct FuzzCounterExample {
+    /// The name of the test contract
+    pub contract: String,
+    /// The signature of the test
+    pub test: String,
+    /// The source file of the test contract
+    pub path: String,
+    /// The revert reason, if any
+    pub reason: Option<String>,
+    /// The decoded args, formatted like in the test output
+    pub args: Vec<String>,
+    /// The raw calldata of the failing call
+    pub calldata: Bytes,
+    /// The command that replays the failing call
+    pub replay: String,
+}
+
+/// Returns the `forge test` command that calls the test `sig` of the test contract `id` with
+/// `calldata` from `sender`
+fn replay_command(path: &str, id: &str, sig: &str, sender: Address, calldata: &Bytes) -> String {
+    format!(
+        "forge test --match-path {} --match-contract '^{}$' --match-test '^{}$' --sender {} --replay {}",
+        path,
+        utils::get_contract_name(id),
+        sig.split('(').next().unwrap_or(sig),
+        to_checksum(&sender, None),
+        calldata
+    )
+}
+
 /// Represents the bundled results of all tests
 pub struct TestOutcome {
     /// Whether failures are allowed
     allow_failure: bool,
     /// All test results `contract -> (test name -> TestResult)`
     pub results: BTreeMap<String, BTreeMap<String, forge::TestResult>>,
+    /// The seed of the fuzzer
+    pub fuzz_seed: Option<U256>,
 }
 
 impl TestOutcome {
@@ -296,12 +495,23 @@ impl TestOutcome {
         results: BTreeMap<String, BTreeMap<String, forge::TestResult>>,
         allow_failure: bool,
     ) -> Self {
-        Self { results, allow_failure }
+        Self { results, allow_failure, fuzz_seed: None }
+    }
+
+    /// Sets the seed of the fuzzer, which is printed if a fuzz test fails
+    pub fn with_fuzz_seed(mut self, fuzz_seed: U256) -> Self {
+        self.fuzz_seed = Some(fuzz_seed);
+        self
     }
 
     /// Iterator over all succeeding tests and their names
     pub fn successes(&self) -> impl Iterator<Item = (&String, &forge::TestResult)> {
-        self.tests().filter(|(_, t)| t.success)
+        self.tests().filter(|(_, t)| t.success && !t.skipped)
+    }
+
+    /// Iterator over all skipped tests and their names
+    pub fn skips(&self) -> impl Iterator<Item = (&String, &forge::TestResult)> {
+        self.tests().filter(|(_, t)| t.skipped)
     }
 
     /// Iterator over all failing tests and their names
@@ -309,6 +519,39 @@ impl TestOutcome {
         self.tests().filter(|(_, t)| !t.success)
     }
 
+    /// Returns the counterexamples of all failing fuzz tests
+    ///
+    /// Counterexamples without an actor are replayed from `sender`.
+    pub fn counterexamples(
+        &self,
+        source_paths: &BTreeMap<String, String>,
+        sender: Address,
+    ) -> Vec<FuzzCounterExample> {
+        self.results
+            .iter()
+            .flat_map(|(id, tests)| tests.iter().map(move |(sig, result)| (id, sig, result)))
+            .filter_map(|(id, sig, result)| {
+                let counterexample = result.counterexample.as_ref()?;
+                let path = source_paths.get(id).cloned().unwrap_or_default();
+                Some(FuzzCounterExample {
+                    contract: utils::get_contract_name(id).to_string(),
+                    test: sig.clone(),
+                    replay: replay_command(
+                        &path,
+                        id,
+                        sig,
+                        counterexample.sender.unwrap_or(sender),
+                        &counterexample.calldata,
+                    ),
+                    path,
+                    reason: result.reason.clone(),
+                    args: foundry_utils::format_tokens(&counterexample.args).collect(),
+                    calldata: counterexample.calldata.clone(),
+                })
+            })
+            .collect()
+    }
+
     /// Iterator over all tests and their names
     pub fn tests(&self) -> impl Iterator<Item = (&String, &forge::TestResult)> {
         self.results.values().flat_map(|tests| tests.iter())
@@ -333,11 +576,20 @@ impl TestOutcome {
                 }
                 println!();
 
+                if let Some(seed) =
+                    self.fuzz_seed.filter(|_| self.failures().any(|(_, t)| t.is_fuzz()))
+                {
+                    println!("Fuzz seed: {}, run with `--fuzz-seed {}` to reproduce", seed, seed);
+                    println!();
+                }
+
                 let successes = self.successes().count();
+                let skips = self.skips().count();
                 println!(
-                    "Encountered a total of {} failing tests, {} tests succeeded",
+                    "Encountered a total of {} failing tests, {} tests succeeded, {} skipped",
                     Colour::Red.paint(failures.to_string()),
-                    Colour::Green.paint(successes.to_string())
+                    Colour::Green.paint(successes.to_string()),
+                    Colour::Yellow.paint(skips.to_string())
                 );
                 std::process::exit(1);
             }
@@ -347,7 +599,13 @@ impl TestOutcome {
 }
 
 fn short_test_result(name: &str, result: &forge::TestResult) {
-    let status = if result.success {
+    let status = if result.skipped {
+        let txt = match &result.reason {
+            Some(reason) => format!("[SKIP. Reason: {}]", reason),
+            None => "[SKIP]".to_string(),
+        };
+        Colour::Yellow.paint(txt)
+    } else if result.success {
         Colour::Green.paint("[PASS]")
     } else {
         let txt = match (&result.reason, &result.counterexample) {
@@ -363,13 +621,124 @@ fn short_test_result(name: &str, result: &forge::TestResult) {
             (None, None) => "[FAIL]".to_string(),
         };
 
-        Colour::Red.paint(txt)
+        Colour::Red.paint(label_addresses(&txt, &result.labeled_addresses))
     };
 
-    println!("{} {} {}", status, name, result.kind.gas_used());
+    match &result.kind {
+        // The max gas and the rejected inputs are not part of the gas snapshot format
+        TestKind::Fuzz(cases) if !result.skipped => println!(
+            "{} {} {} (max: {}, rejects: {})",
+            status,
+            name,
+            result.kind.gas_used(),
+            cases.highest_gas(false),
+            result.rejects
+        ),
+        _ => println!("{} {} {}", status, name, result.kind.gas_used()),
+    }
+}
+
+/// Replaces the addresses in `text` that were labeled with the `label` cheatcode with their labels
+fn label_addresses(text: &str, labels: &BTreeMap<Address, String>) -> String {
+    labels.iter().fold(text.to_string(), |text, (address, label)| {
+        text.replace(&format!("{:?}", address), label).replace(&to_checksum(address, None), label)
+    })
+}
+
+/// The names of the variables in each storage slot, by contract name
+pub(crate) type StorageLayouts = BTreeMap<String, BTreeMap<U256, Vec<String>>>;
+
+/// Collects the storage layouts of all compiled contracts
+pub(crate) fn storage_layouts(output: ProjectCompileOutput) -> StorageLayouts {
+    output
+        .into_artifacts()
+        .filter_map(|(id, artifact)| {
+            let layout = serde_json::to_value(artifact.storage_layout?).ok()?;
+            let mut slots: BTreeMap<U256, Vec<String>> = BTreeMap::new();
+            for var in layout.get("storage").and_then(Value::as_array).into_iter().flatten() {
+                let slot = var.get("slot").and_then(Value::as_str);
+                let label = var.get("label").and_then(Value::as_str);
+                if let (Some(slot), Some(label)) = (slot, label) {
+                    if let Ok(slot) = U256::from_dec_str(slot) {
+                        slots.entry(slot).or_default().push(label.to_string());
+                    }
+                }
+            }
+            Some((id.name, slots))
+        })
+        .collect()
+}
+
+/// Names the slots of the storage accesses in the decoded `trace` after the storage variables of
+/// the contract that executed them
+fn label_storage_accesses(trace: &mut CallTraceArena, storage_layouts: &StorageLayouts) {
+    for node in &mut trace.arena {
+        let layout =
+            node.trace.contract.as_ref().and_then(|contract| storage_layouts.get(contract));
+        if let Some(layout) = layout {
+            for access in &mut node.storage {
+                if let Some(vars) = layout.get(&access.slot) {
+                    access.names = vars.clone();
+                }
+            }
+        }
+    }
+}
+
+/// Prints the state changes of a test, one account after another
+fn print_state_diff(
+    diff: &StateDiff,
+    labels: &BTreeMap<Address, String>,
+    identifier: &LocalTraceIdentifier,
+    storage_layouts: &StorageLayouts,
+) {
+    for (address, account) in &diff.accounts {
+        let (contract, _, _) = identifier.identify_address(address, account.code.as_ref());
+        let name = labels.get(address).or(contract.as_ref());
+        let mut header = format!("  {:?}", address);
+        if let Some(name) = name {
+            header.push_str(&format!(" ({})", name));
+        }
+        if account.created {
+            header.push_str(" [created]");
+        }
+        println!("{}", header);
+
+        if let Some(change) = &account.balance {
+            println!("    balance: {} -> {}", change.before, change.after);
+        }
+        if let Some(change) = &account.nonce {
+            println!("    nonce: {} -> {}", change.before, change.after);
+        }
+        let layout = contract.and_then(|contract| storage_layouts.get(&contract));
+        for (slot, change) in &account.storage {
+            let mut slot_name = format!("slot {}", slot);
+            if let Some(vars) = layout.and_then(|layout| layout.get(slot)) {
+                slot_name.push_str(&format!(" ({})", vars.join(", ")));
+            }
+            println!("    {}: {:#x} -> {:#x}", slot_name, change.before, change.after);
+        }
+    }
+}
+
+/// Writes the folded stacks of the test's `trace` to `<dir>/<contract>/<test>.folded`
+fn write_flamegraph(
+    dir: &Path,
+    contract_name: &str,
+    test_name: &str,
+    trace: &CallTraceArena,
+) -> eyre::Result<()> {
+    let dir = dir.join(utils::get_contract_name(contract_name));
+    std::fs::create_dir_all(&dir)?;
+    let test_name = test_name.split('(').next().unwrap_or(test_name);
+    let mut folded = trace.to_folded_stacks().join("\n");
+    folded.push('\n');
+    std::fs::write(dir.join(format!("{}.folded", test_name)), folded)?;
+    Ok(())
 }
 
 /// Runs all the tests
+#[allow(clippy::too_many_arguments)]
 fn test(
     mut runner: MultiContractRunner,
     verbosity: u8,
@@ -377,6 +746,10 @@ fn test(
     json: bool,
     allow_failure: bool,
     (gas_reporting, gas_reports): (bool, Vec<String>),
+    storage_layouts: StorageLayouts,
+    trace_format: TraceFormat,
+    flamegraph: Option<&Path>,
+    sender: Address,
 ) -> eyre::Result<TestOutcome> {
     if json {
         let results = runner.test(&filter, None)?;
@@ -384,6 +757,7 @@ fn test(
         Ok(TestOutcome::new(results, allow_failure))
     } else {
         let local_identifier = LocalTraceIdentifier::new(&runner.known_contracts);
+        let source_paths = runner.source_paths.clone();
         let (tx, rx) = channel::<(String, BTreeMap<String, TestResult>)>();
 
         thread::spawn(move || runner.test(&filter, Some(tx)).unwrap());
@@ -398,6 +772,19 @@ fn test(
             }
             for (name, result) in &mut tests {
                 short_test_result(name, result);
+                if let Some(counterexample) = &result.counterexample {
+                    let path = source_paths.get(&contract_name).map(String::as_str).unwrap_or("");
+                    println!(
+                        "Replay: {}",
+                        replay_command(
+                            path,
+                            &contract_name,
+                            name,
+                            counterexample.sender.unwrap_or(sender),
+                            &counterexample.calldata
+                        )
+                    );
+                }
 
                 // We only display logs at level 2 and above
                 if verbosity >= 2 {
@@ -436,14 +823,26 @@ fn test(
                             _ => false,
                         };
 
-                        // We decode the trace if we either need to build a gas report or we need
-                        // to print it
-                        if should_include || gas_reporting {
+                        // Raw formats contain the calldata, so they are formatted before decoding
+                        let raw_trace = (should_include && trace_format.is_raw())
+                            .then(|| trace_format.format(trace));
+
+                        // We decode the trace if we either need to build a gas report or a
+                        // flamegraph or we need to print it
+                        let is_flamegraph =
+                            flamegraph.is_some() && matches!(kind, TraceKind::Execution);
+                        if should_include || gas_reporting || is_flamegraph {
                             decoder.decode(trace);
                         }
 
+                        if let (Some(dir), true) = (flamegraph, is_flamegraph) {
+                            write_flamegraph(dir, &contract_name, name, trace)?;
+                        }
+
                         if should_include {
-                            decoded_traces.push(trace.to_string());
+                            label_storage_accesses(trace, &storage_layouts);
+                            decoded_traces
+                                .push(raw_trace.unwrap_or_else(|| trace_format.format(trace)));
                         }
                     }
 
@@ -456,6 +855,19 @@ fn test(
                         gas_report.analyze(&result.traces);
                     }
                 }
+
+                if let Some(diff) = &result.state_diff {
+                    if !diff.is_empty() {
+                        println!("State diff:");
+                        print_state_diff(
+                            diff,
+                            &result.labeled_addresses,
+                            &local_identifier,
+                            &storage_layouts,
+                        );
+                        println!();
+                    }
+                }
             }
             results.insert(contract_name, tests);
         }
@@ -4,10 +4,19 @@ use crate::cmd::{forge::build::BuildArgs, Cmd};
 use clap::Parser;
 use ethers::solc::Graph;
 use foundry_config::Config;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 
 foundry_config::impl_figment_convert!(TreeArgs, opts);
 use ethers::solc::resolver::{Charset, TreeOptions};
 
+static RE_SOL_PRAGMA_VERSION: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"pragma[ \t]+solidity[ \t]+([^;]+);").unwrap());
+
 /// Command to display the project's dependency tree
 #[derive(Debug, Clone, Parser)]
 pub struct TreeArgs {
@@ -25,10 +34,91 @@ impl Cmd for TreeArgs {
 
     fn run(self) -> eyre::Result<Self::Output> {
         let config: Config = From::from(&self);
-        let graph = Graph::resolve(&config.project_paths())?;
+        let paths = config.project_paths();
+        let graph = Graph::resolve(&paths)?;
         let opts = TreeOptions { charset: self.charset, no_dedupe: self.no_dedupe };
         graph.print_with_options(opts);
 
+        let duplicates = find_duplicates(graph.files().keys(), &config.libs);
+        if !duplicates.is_empty() {
+            println!();
+            println!(
+                "Found {} source file(s) that are imported from multiple locations:",
+                duplicates.len()
+            );
+            for (name, files) in duplicates {
+                println!("{}", name);
+                for path in files {
+                    let path_display =
+                        path.strip_prefix(&paths.root).unwrap_or(&path).display().to_string();
+                    match pragma_version(&path) {
+                        Some(version) => println!("    {} ({})", path_display, version),
+                        None => println!("    {}", path_display),
+                    }
+                }
+            }
+        }
+
         Ok(())
     }
 }
+
+/// Groups all files that live in one of the `libs` by their path relative to the innermost lib
+/// dir and returns the groups with more than one file, e.g. `lib/ds-test/src/test.sol` and
+/// `lib/solmate/lib/ds-test/src/test.sol` are both `ds-test/src/test.sol`
+fn find_duplicates<'a>(
+    files: impl IntoIterator<Item = &'a PathBuf>,
+    libs: &[PathBuf],
+) -> BTreeMap<String, Vec<PathBuf>> {
+    let lib_names = libs.iter().filter_map(|lib| lib.file_name()).collect::<Vec<_>>();
+
+    let mut by_name: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+    for file in files {
+        let components = file.components().collect::<Vec<_>>();
+        if let Some(pos) =
+            components.iter().rposition(|c| lib_names.iter().any(|lib| c.as_os_str() == *lib))
+        {
+            let name = components[pos + 1..].iter().collect::<PathBuf>();
+            by_name.entry(name.display().to_string()).or_default().push(file.clone());
+        }
+    }
+
+    by_name
+        .into_iter()
+        .filter(|(_, paths)| paths.len() > 1)
+        .map(|(name, mut paths)| {
+            paths.sort();
+            (name, paths)
+        })
+        .collect()
+}
+
+/// Returns the version requirement of the file's `pragma solidity` statement, if any
+fn pragma_version(file: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(file).ok()?;
+    RE_SOL_PRAGMA_VERSION.captures(&content).map(|caps| caps[1].trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_find_duplicates() {
+        let files = vec![
+            PathBuf::from("/root/src/Contract.sol"),
+            PathBuf::from("/root/lib/ds-test/src/test.sol"),
+            PathBuf::from("/root/lib/solmate/src/tokens/ERC20.sol"),
+            PathBuf::from("/root/lib/solmate/lib/ds-test/src/test.sol"),
+        ];
+        let duplicates = find_duplicates(&files, &[PathBuf::from("/root/lib")]);
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(
+            duplicates["ds-test/src/test.sol"],
+            vec![
+                PathBuf::from("/root/lib/ds-test/src/test.sol"),
+                PathBuf::from("/root/lib/solmate/lib/ds-test/src/test.sol"),
+            ]
+        );
+    }
+}
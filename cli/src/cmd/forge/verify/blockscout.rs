@@ -0,0 +1,76 @@
+//! Blockscout verification backend
+//!
+//! Blockscout exposes an etherscan compatible `contract` api module, so this reuses the etherscan
+//! request but submits it to the explorer's own api url.
+
+use super::{
+    etherscan::{create_verify_request, submit_verification, verification_status},
+    poll_verification, RetryArgs, VerifyArgs, VerifyCheckArgs,
+};
+use ethers::types::Chain;
+
+/// Returns the api url of the blockscout instance of well known chains
+pub fn blockscout_api_url(chain_id: u64) -> Option<&'static str> {
+    match Chain::try_from(chain_id).ok()? {
+        Chain::XDai => Some("https://blockscout.com/xdai/mainnet/api"),
+        _ => None,
+    }
+}
+
+/// Submits the contract's source code for verification on the blockscout instance of the chain
+pub async fn verify(args: &VerifyArgs) -> eyre::Result<()> {
+    let url = api_url(args.verifier_url.as_deref(), args.chain_id)?;
+    let verify_args = create_verify_request(args)?;
+
+    let resp = submit_verification(&url, None, &verify_args).await?;
+
+    let guid = match (resp.status.as_str(), resp.result) {
+        ("1", Some(guid)) => guid,
+        (_, result) => eyre::bail!(
+            "Encountered an error verifying this contract:\nResponse: `{}`\nDetails: `{}`",
+            resp.message,
+            result.unwrap_or_default()
+        ),
+    };
+
+    println!(
+        r#"Submitted contract for verification:
+                Response: `{}`
+                GUID: `{}`"#,
+        resp.message, guid
+    );
+
+    if args.watch {
+        check_verification(&url, &guid, args.retry).await?;
+    }
+    Ok(())
+}
+
+/// Checks the status of the verification with the given guid
+pub async fn check(args: &VerifyCheckArgs) -> eyre::Result<()> {
+    let url = api_url(args.verifier_url.as_deref(), args.chain_id)?;
+    check_verification(&url, &args.id, args.retry).await
+}
+
+fn api_url(verifier_url: Option<&str>, chain_id: u64) -> eyre::Result<String> {
+    verifier_url.or_else(|| blockscout_api_url(chain_id)).map(str::to_string).ok_or_else(|| {
+        eyre::eyre!("No known blockscout instance for chain {}, set `--verifier-url`", chain_id)
+    })
+}
+
+/// Polls the verification status of the given `guid`, see [poll_verification]
+async fn check_verification(url: &str, guid: &str, retry: RetryArgs) -> eyre::Result<()> {
+    poll_verification(retry, move || verification_status(url, None, guid)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_resolve_blockscout_url() {
+        assert_eq!(api_url(Some("https://example.com/api"), 1).unwrap(), "https://example.com/api");
+        assert_eq!(api_url(None, 100).unwrap(), "https://blockscout.com/xdai/mainnet/api");
+        assert!(api_url(None, 1).is_err());
+    }
+}
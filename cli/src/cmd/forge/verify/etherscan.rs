@@ -0,0 +1,217 @@
+//! Etherscan verification backend
+
+use super::{poll_verification, RetryArgs, VerificationStatus, VerifyArgs, VerifyCheckArgs};
+use crate::cmd::forge::flatten::dedup_licenses_and_pragmas;
+use ethers::etherscan::{
+    contract::{CodeFormat, VerifyContract},
+    Client,
+};
+use serde::{Deserialize, Serialize};
+
+/// The query parameters that select the endpoint of the `contract` module of an etherscan
+/// compatible api
+#[derive(Debug, Serialize)]
+struct Query<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    apikey: Option<&'a str>,
+    module: &'static str,
+    action: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    guid: Option<&'a str>,
+}
+
+/// The response of an etherscan compatible api
+#[derive(Debug, Deserialize)]
+pub(super) struct ApiResponse {
+    pub status: String,
+    pub message: String,
+    pub result: Option<String>,
+}
+
+/// Submits the verification `request` to the etherscan compatible api at `url`
+pub(super) async fn submit_verification(
+    url: &str,
+    key: Option<&str>,
+    request: &VerifyContract,
+) -> eyre::Result<ApiResponse> {
+    let query = Query { apikey: key, module: "contract", action: "verifysourcecode", guid: None };
+    Ok(reqwest::Client::new().post(url).query(&query).form(request).send().await?.json().await?)
+}
+
+/// Requests the status of the verification with the given `guid` from the etherscan compatible
+/// api at `url`
+pub(super) async fn verification_status(
+    url: &str,
+    key: Option<&str>,
+    guid: &str,
+) -> eyre::Result<VerificationStatus> {
+    let query =
+        Query { apikey: key, module: "contract", action: "checkverifystatus", guid: Some(guid) };
+    let resp: ApiResponse =
+        reqwest::Client::new().get(url).query(&query).send().await?.json().await?;
+    Ok(VerificationStatus {
+        status: resp.status,
+        message: resp.message,
+        result: resp.result.unwrap_or_default(),
+    })
+}
+
+/// The api the verification is submitted to, the etherscan instance of the chain or the
+/// `--verifier-url` of an etherscan compatible explorer
+enum EtherscanApi {
+    Client(Client),
+    Url { url: String, key: Option<String> },
+}
+
+impl EtherscanApi {
+    fn new(chain_id: u64, key: Option<&str>, verifier_url: Option<&str>) -> eyre::Result<Self> {
+        Ok(match verifier_url {
+            Some(url) => EtherscanApi::Url { url: url.to_string(), key: key.map(str::to_string) },
+            None => EtherscanApi::Client(client(chain_id, key)?),
+        })
+    }
+
+    async fn submit(&self, request: &VerifyContract) -> eyre::Result<ApiResponse> {
+        match self {
+            EtherscanApi::Client(etherscan) => {
+                let resp =
+                    etherscan.submit_contract_verification(request).await.map_err(|err| {
+                        eyre::eyre!("Failed to submit contract verification: {}", err)
+                    })?;
+                Ok(ApiResponse {
+                    status: resp.status,
+                    message: resp.message,
+                    result: Some(resp.result),
+                })
+            }
+            EtherscanApi::Url { url, key } => {
+                submit_verification(url, key.as_deref(), request).await
+            }
+        }
+    }
+
+    async fn status(&self, guid: &str) -> eyre::Result<VerificationStatus> {
+        match self {
+            EtherscanApi::Client(etherscan) => {
+                let resp = etherscan
+                    .check_contract_verification_status(guid.to_string())
+                    .await
+                    .map_err(|err| eyre::eyre!("Failed to request verification status: {}", err))?;
+                Ok(VerificationStatus {
+                    status: resp.status,
+                    message: resp.message,
+                    result: resp.result,
+                })
+            }
+            EtherscanApi::Url { url, key } => verification_status(url, key.as_deref(), guid).await,
+        }
+    }
+}
+
+/// Submits the contract's source code for verification on etherscan
+pub async fn verify(args: &VerifyArgs) -> eyre::Result<()> {
+    let verify_args = create_verify_request(args)?;
+    let etherscan = EtherscanApi::new(
+        args.chain_id,
+        args.etherscan_key.as_deref(),
+        args.verifier_url.as_deref(),
+    )?;
+
+    let resp = etherscan.submit(&verify_args).await?;
+    let result = resp.result.unwrap_or_default();
+
+    if resp.status == "0" {
+        if resp.message == "Contract source code already verified" {
+            println!("Contract source code already verified.");
+            return Ok(())
+        }
+
+        eyre::bail!(
+            "Encountered an error verifying this contract:\nResponse: `{}`\nDetails: `{}`",
+            resp.message,
+            result
+        );
+    }
+
+    println!(
+        r#"Submitted contract for verification:
+                Response: `{}`
+                GUID: `{}`"#,
+        resp.message, result
+    );
+    if let EtherscanApi::Client(client) = &etherscan {
+        println!("                url: {}#code", client.address_url(args.address));
+    }
+
+    if args.watch {
+        check_verification(&etherscan, &result, args.retry).await?;
+    }
+    Ok(())
+}
+
+/// Creates the etherscan compatible verification request for the contract
+pub(super) fn create_verify_request(args: &VerifyArgs) -> eyre::Result<VerifyContract> {
+    let compiler_version = args
+        .compiler_version
+        .clone()
+        .ok_or_else(|| eyre::eyre!("`--compiler-version` is required to verify the contract"))?;
+
+    let project = args.project(Default::default())?;
+    let path = args.contract.path.as_ref().unwrap();
+    let target = project.root().join(path);
+
+    let (source, contract_name, code_format) = if args.flatten {
+        let source = project
+            .flatten(&target)
+            .map_err(|err| eyre::eyre!("Failed to flatten contract: {}", err))?;
+        let source = dedup_licenses_and_pragmas(&source);
+        (source, args.contract.name.clone(), CodeFormat::SingleFile)
+    } else {
+        let input = project
+            .standard_json_input(&target)
+            .map_err(|err| eyre::eyre!("Failed to get standard json input: {}", err))?;
+        let source = serde_json::to_string(&input)?;
+        // the standard json input requires the fully qualified name of the contract
+        let name = format!("{}:{}", path, args.contract.name);
+        (source, name, CodeFormat::StandardJsonInput)
+    };
+
+    let mut verify_args =
+        VerifyContract::new(args.address, contract_name, source, compiler_version)
+            .constructor_arguments(args.constructor_args.clone())
+            .code_format(code_format);
+
+    if let Some(optimizations) = args.num_of_optimizations {
+        verify_args = verify_args.optimization(true).runs(optimizations);
+    } else {
+        verify_args = verify_args.optimization(false);
+    }
+
+    Ok(verify_args)
+}
+
+/// Checks the status of the verification with the given guid
+pub async fn check(args: &VerifyCheckArgs) -> eyre::Result<()> {
+    let etherscan = EtherscanApi::new(
+        args.chain_id,
+        args.etherscan_key.as_deref(),
+        args.verifier_url.as_deref(),
+    )?;
+    check_verification(&etherscan, &args.id, args.retry).await
+}
+
+fn client(chain_id: u64, etherscan_key: Option<&str>) -> eyre::Result<Client> {
+    let etherscan_key = etherscan_key
+        .ok_or_else(|| eyre::eyre!("An etherscan api key is required to verify on etherscan"))?;
+    Client::new(chain_id.try_into()?, etherscan_key)
+        .map_err(|err| eyre::eyre!("Failed to create etherscan client: {}", err))
+}
+
+/// Polls the verification status of the given `guid`, see [poll_verification]
+async fn check_verification(
+    etherscan: &EtherscanApi,
+    guid: &str,
+    retry: RetryArgs,
+) -> eyre::Result<()> {
+    poll_verification(retry, move || etherscan.status(guid)).await
+}
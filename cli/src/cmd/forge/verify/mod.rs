@@ -0,0 +1,279 @@
+//! Verify contract source code on a block explorer
+
+use crate::{
+    cmd::forge::{build::BuildArgs, flatten::CoreFlattenArgs},
+    opts::forge::{CompilerArgs, ContractInfo},
+};
+use clap::{ArgEnum, Parser};
+use ethers::{abi::Address, solc::Project};
+use foundry_config::{Config, EtherscanVerifier};
+use std::{future::Future, time::Duration};
+
+mod blockscout;
+mod etherscan;
+mod sourcify;
+
+/// The service used to verify the contract
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ArgEnum)]
+pub enum VerificationProviderType {
+    Etherscan,
+    Sourcify,
+    Blockscout,
+}
+
+/// Verification arguments
+#[derive(Debug, Clone, Parser)]
+pub struct VerifyArgs {
+    #[clap(help = "the target contract address")]
+    pub address: Address,
+
+    #[clap(help = "the contract source info `<path>:<contractname>`")]
+    pub contract: ContractInfo,
+
+    #[clap(long, help = "the encoded constructor arguments")]
+    pub constructor_args: Option<String>,
+
+    #[clap(long, help = "the compiler version used during build")]
+    pub compiler_version: Option<String>,
+
+    #[clap(long, help = "the number of optimization runs used")]
+    pub num_of_optimizations: Option<u32>,
+
+    // TODO: Allow choosing network using the provider or chainid as string
+    #[clap(long, help = "the chain id of the network you are verifying for", default_value = "1")]
+    pub chain_id: u64,
+
+    #[clap(help = "your etherscan api key", env = "ETHERSCAN_API_KEY")]
+    pub etherscan_key: Option<String>,
+
+    #[clap(
+        long,
+        help = "submit the flattened source code instead of the standard json input of the project"
+    )]
+    pub flatten: bool,
+
+    #[clap(long, help = "wait for the verification result after the submission")]
+    pub watch: bool,
+
+    #[clap(
+        long,
+        arg_enum,
+        help = "the verification provider, defaults to the `verifier` of the chain's `etherscan` config entry or etherscan"
+    )]
+    pub verifier: Option<VerificationProviderType>,
+
+    #[clap(
+        long,
+        help = "the api url of the verification provider, required for blockscout instances of chains that aren't known",
+        env = "VERIFIER_URL"
+    )]
+    pub verifier_url: Option<String>,
+
+    #[clap(flatten)]
+    pub retry: RetryArgs,
+
+    #[clap(flatten)]
+    pub opts: CoreFlattenArgs,
+}
+
+impl VerifyArgs {
+    /// Returns the [`Project`] of the contract to verify, configured to emit the given
+    /// `extra_output`
+    fn project(&self, compiler: CompilerArgs) -> eyre::Result<Project> {
+        let CoreFlattenArgs {
+            root,
+            contracts,
+            remappings,
+            remappings_env,
+            cache_path,
+            lib_paths,
+            hardhat,
+        } = self.opts.clone();
+
+        let build_args = BuildArgs {
+            root,
+            contracts,
+            remappings,
+            remappings_env,
+            cache_path,
+            lib_paths,
+            out_path: None,
+            compiler,
+            names: false,
+            sizes: false,
+            check_sizes: false,
+            ignored_error_codes: vec![],
+            no_auto_detect: false,
+            use_solc: None,
+            offline: false,
+            force: false,
+            hardhat,
+            libraries: vec![],
+            watch: Default::default(),
+            via_ir: false,
+            hardhat_artifacts: false,
+        };
+
+        build_args.project()
+    }
+}
+
+/// Check verification status arguments
+#[derive(Debug, Clone, Parser)]
+pub struct VerifyCheckArgs {
+    #[clap(
+        help = "the verification guid for etherscan and blockscout, or the address of the contract for sourcify"
+    )]
+    id: String,
+
+    // TODO: Allow choosing network using the provider or chainid as string
+    #[clap(long, help = "the chain id of the network you are verifying for", default_value = "1")]
+    chain_id: u64,
+
+    #[clap(help = "your etherscan api key", env = "ETHERSCAN_API_KEY")]
+    etherscan_key: Option<String>,
+
+    #[clap(
+        long,
+        arg_enum,
+        help = "the verification provider, defaults to the `verifier` of the chain's `etherscan` config entry or etherscan"
+    )]
+    verifier: Option<VerificationProviderType>,
+
+    #[clap(
+        long,
+        help = "the api url of the verification provider, required for blockscout instances of chains that aren't known",
+        env = "VERIFIER_URL"
+    )]
+    verifier_url: Option<String>,
+
+    #[clap(flatten)]
+    retry: RetryArgs,
+}
+
+/// Polling options used while waiting for a verification result
+#[derive(Debug, Clone, Copy, Parser)]
+pub struct RetryArgs {
+    #[clap(
+        long,
+        help = "number of times to check the verification status before giving up",
+        default_value = "15"
+    )]
+    pub retries: u32,
+
+    #[clap(long, help = "seconds to wait between status checks", default_value = "5")]
+    pub delay: u64,
+}
+
+/// Run the verify command to submit the contract's source code for verification
+pub async fn run_verify(args: &VerifyArgs) -> eyre::Result<()> {
+    if args.contract.path.is_none() {
+        eyre::bail!("Contract info must be provided in the format <path>:<name>")
+    }
+
+    let mut args = args.clone();
+    let verifier = resolve_etherscan_config(
+        args.chain_id,
+        args.verifier,
+        &mut args.etherscan_key,
+        &mut args.verifier_url,
+    )?;
+    match verifier {
+        VerificationProviderType::Etherscan => etherscan::verify(&args).await,
+        VerificationProviderType::Sourcify => sourcify::verify(&args).await,
+        VerificationProviderType::Blockscout => blockscout::verify(&args).await,
+    }
+}
+
+/// Run the verify-check command to check the status of a previously submitted verification
+pub async fn run_verify_check(args: &VerifyCheckArgs) -> eyre::Result<()> {
+    let mut args = args.clone();
+    let verifier = resolve_etherscan_config(
+        args.chain_id,
+        args.verifier,
+        &mut args.etherscan_key,
+        &mut args.verifier_url,
+    )?;
+    match verifier {
+        VerificationProviderType::Etherscan => etherscan::check(&args).await,
+        VerificationProviderType::Sourcify => sourcify::check(&args).await,
+        VerificationProviderType::Blockscout => blockscout::check(&args).await,
+    }
+}
+
+/// Resolves the verification provider from the config's `etherscan` entry for the chain, if it
+/// wasn't passed explicitly, and fills the api key and the verifier url that weren't passed
+/// explicitly from the entry if it is for the same provider.
+fn resolve_etherscan_config(
+    chain_id: u64,
+    verifier: Option<VerificationProviderType>,
+    etherscan_key: &mut Option<String>,
+    verifier_url: &mut Option<String>,
+) -> eyre::Result<VerificationProviderType> {
+    let config = Config::load();
+    let etherscan = match config.etherscan.find_chain(chain_id.into()) {
+        Some(etherscan) => etherscan,
+        None => return Ok(verifier.unwrap_or(VerificationProviderType::Etherscan)),
+    };
+    let configured = match etherscan.verifier.unwrap_or(EtherscanVerifier::Etherscan) {
+        EtherscanVerifier::Etherscan => VerificationProviderType::Etherscan,
+        EtherscanVerifier::Blockscout => VerificationProviderType::Blockscout,
+        EtherscanVerifier::Sourcify => VerificationProviderType::Sourcify,
+    };
+    let verifier = verifier.unwrap_or(configured);
+    if verifier != configured {
+        return Ok(verifier)
+    }
+
+    if etherscan_key.is_none() && verifier == VerificationProviderType::Etherscan {
+        *etherscan_key = Some(etherscan.resolve_key()?);
+    }
+    if verifier_url.is_none() {
+        *verifier_url = etherscan.resolve_url().transpose()?;
+    }
+    Ok(verifier)
+}
+
+/// The status of a submitted verification, as reported by an etherscan compatible api
+struct VerificationStatus {
+    status: String,
+    message: String,
+    result: String,
+}
+
+/// Checks the verification status with `check` until it's no longer pending, fails if it is
+/// still pending once the retries are exhausted
+async fn poll_verification<F, Fut>(retry: RetryArgs, mut check: F) -> eyre::Result<()>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = eyre::Result<VerificationStatus>>,
+{
+    for attempt in 0..retry.retries.max(1) {
+        if attempt > 0 {
+            tokio::time::sleep(Duration::from_secs(retry.delay)).await;
+        }
+
+        let VerificationStatus { status, message, result } = check().await?;
+
+        if status == "0" {
+            if result == "Pending in queue" {
+                println!("Verification is pending...");
+                continue
+            }
+
+            eyre::bail!(
+                "Contract verification failed:\nResponse: `{}`\nDetails: `{}`",
+                message,
+                result
+            );
+        }
+
+        println!("Contract successfully verified.");
+        return Ok(())
+    }
+
+    eyre::bail!(
+        "Verification is still pending after {} status checks, check it again later with `forge verify-check`",
+        retry.retries.max(1)
+    )
+}
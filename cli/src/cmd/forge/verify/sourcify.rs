@@ -0,0 +1,115 @@
+//! Sourcify verification backend
+
+use super::{VerifyArgs, VerifyCheckArgs};
+use crate::{cmd::suppress_compile, opts::forge::CompilerArgs};
+use ethers::solc::artifacts::output_selection::ContractOutputSelection;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path};
+
+const SOURCIFY_URL: &str = "https://sourcify.dev/server/";
+
+/// The request body of sourcify's `verify` endpoint
+#[derive(Debug, Serialize)]
+pub struct SourcifyVerifyRequest {
+    address: String,
+    chain: String,
+    /// All files required for the verification, keyed by their file name, this includes the
+    /// `metadata.json` and all sources listed in the metadata
+    files: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SourcifyVerificationResponse {
+    result: Vec<SourcifyResponseElement>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SourcifyResponseElement {
+    status: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SourcifyErrorResponse {
+    error: String,
+}
+
+/// Uploads the contract's metadata and all its sources to sourcify
+pub async fn verify(args: &VerifyArgs) -> eyre::Result<()> {
+    // the metadata lists all the sources and the compiler settings that are required
+    let compiler = CompilerArgs {
+        extra_output: Some(vec![ContractOutputSelection::Metadata]),
+        ..Default::default()
+    };
+    let project = args.project(compiler)?;
+    let output = suppress_compile(&project)?;
+
+    let path = args.contract.path.as_ref().unwrap();
+    let (_, artifact) = output
+        .into_artifacts()
+        .find(|(id, _)| id.name == args.contract.name && id.source.ends_with(path))
+        .ok_or_else(|| {
+            eyre::eyre!("Could not find artifact for `{}:{}`", path, args.contract.name)
+        })?;
+    let metadata = artifact
+        .metadata
+        .ok_or_else(|| eyre::eyre!("Missing metadata for `{}`", args.contract.name))?;
+
+    let mut files = BTreeMap::new();
+    for source in metadata.sources.inner.keys() {
+        let file = project.root().join(source);
+        let content = std::fs::read_to_string(&file)
+            .map_err(|err| eyre::eyre!("Failed to read source `{}`: {}", file.display(), err))?;
+        let name = Path::new(source)
+            .strip_prefix(project.root())
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|_| source.clone());
+        files.insert(name, content);
+    }
+    files.insert("metadata.json".to_string(), serde_json::to_string(&metadata)?);
+
+    let body = SourcifyVerifyRequest {
+        address: format!("{:?}", args.address),
+        chain: args.chain_id.to_string(),
+        files,
+    };
+
+    println!("Submitting `{}` with {} files to sourcify", args.contract.name, body.files.len());
+
+    let resp =
+        reqwest::Client::new().post(format!("{}verify", SOURCIFY_URL)).json(&body).send().await?;
+
+    let status = resp.status();
+    if !status.is_success() {
+        let err: SourcifyErrorResponse = resp.json().await?;
+        eyre::bail!(
+            "Failed to verify contract on sourcify:\nStatus: `{}`\nDetails: `{}`",
+            status,
+            err.error
+        );
+    }
+
+    let resp: SourcifyVerificationResponse = resp.json().await?;
+    print_status(resp.result.first().map(|r| r.status.as_str()));
+    Ok(())
+}
+
+/// Checks whether the contract at the given address is verified on sourcify
+pub async fn check(args: &VerifyCheckArgs) -> eyre::Result<()> {
+    let url = format!(
+        "{}check-by-addresses?addresses={}&chainIds={}",
+        SOURCIFY_URL, args.id, args.chain_id
+    );
+    let resp: Vec<SourcifyResponseElement> = reqwest::get(url).await?.json().await?;
+    print_status(resp.first().map(|r| r.status.as_str()));
+    Ok(())
+}
+
+fn print_status(status: Option<&str>) {
+    match status {
+        Some("perfect") => println!("Contract source code fully verified (full match)."),
+        Some("partial") => {
+            println!("Contract source code partially verified (metadata hash mismatch).")
+        }
+        _ => println!("Contract source code is not verified."),
+    }
+}
@@ -0,0 +1,209 @@
+//! Support for compiling the vyper sources of a project next to its solidity sources
+//!
+//! The artifacts of a `Contract.vy` file are written to `<out>/Contract.vy/Contract.json` in the
+//! same format as the solidity artifacts, so they can be used with `vm.getCode("Contract.vy")` and
+//! by any tool that consumes forge artifacts.
+
+use ethers::{
+    abi::Abi,
+    solc::{artifacts::CompactContractBytecode, Project},
+};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use semver::{Version, VersionReq};
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// The default vyper binary, resolved via `PATH`
+pub const VYPER: &str = "vyper";
+
+static RE_VYPER_VERSION_PRAGMA: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^#[ \t]*@version[ \t]+([^\r\n]+)").unwrap());
+
+/// A compiled vyper contract
+#[derive(Debug, Clone)]
+pub struct VyperArtifact {
+    /// The name of the contract, which is the file name without the `.vy` extension
+    pub name: String,
+    pub contract: CompactContractBytecode,
+}
+
+/// Returns all vyper files in the `dir`
+pub fn vyper_sources(dir: impl AsRef<Path>) -> Vec<PathBuf> {
+    walkdir::WalkDir::new(dir)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| e.path().extension().map(|ext| ext == "vy").unwrap_or_default())
+        .map(|e| e.path().into())
+        .collect()
+}
+
+/// Returns the version of the `vyper` binary
+pub fn vyper_version(vyper: &Path) -> eyre::Result<Version> {
+    let output = Command::new(vyper).arg("--version").output().map_err(|err| {
+        eyre::eyre!("Failed to run `{} --version`, is vyper installed? {}", vyper.display(), err)
+    })?;
+    let version = String::from_utf8_lossy(&output.stdout);
+    // versions look like `0.3.1+commit.0463ea4`
+    let version = version.trim().split('+').next().unwrap_or_default();
+    Ok(Version::parse(version)?)
+}
+
+/// Returns the version requirement of the file's `# @version` pragma, if any
+pub fn version_pragma(content: &str) -> Option<VersionReq> {
+    let caps = RE_VYPER_VERSION_PRAGMA.captures(content)?;
+    VersionReq::parse(caps[1].trim()).ok()
+}
+
+/// Compiles all vyper sources of the project with the `vyper` binary and writes their artifacts
+/// to the project's artifacts dir.
+///
+/// If the project is cached, sources whose artifact is newer than the source are not recompiled
+/// and their artifact is read instead.
+///
+/// Does nothing if the project doesn't contain any vyper sources.
+pub fn compile_vyper_sources(project: &Project, vyper: &Path) -> eyre::Result<Vec<VyperArtifact>> {
+    let mut sources = vyper_sources(&project.paths.sources);
+    if project.paths.tests.exists() && !project.paths.tests.starts_with(&project.paths.sources) {
+        sources.extend(vyper_sources(&project.paths.tests));
+    }
+    if sources.is_empty() {
+        return Ok(vec![])
+    }
+    sources.sort();
+
+    let (cached, dirty): (Vec<_>, Vec<_>) = sources.into_iter().partition(|source| {
+        project.cached && is_up_to_date(source, &artifact_path(project, source))
+    });
+
+    let mut artifacts = Vec::with_capacity(cached.len() + dirty.len());
+    for source in cached {
+        let content = std::fs::read_to_string(artifact_path(project, &source))?;
+        let name = source.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        artifacts.push(VyperArtifact { name, contract: serde_json::from_str(&content)? });
+    }
+    if dirty.is_empty() {
+        return Ok(artifacts)
+    }
+
+    let version = vyper_version(vyper)?;
+    tracing::trace!("compiling {} vyper files with {}", dirty.len(), version);
+    for source in dirty {
+        let artifact = compile_vyper_file(vyper, &version, &source)?;
+        let out = artifact_path(project, &source);
+        if let Some(parent) = out.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&out, serde_json::to_string_pretty(&artifact.contract)?)?;
+        artifacts.push(artifact);
+    }
+    Ok(artifacts)
+}
+
+/// Returns the path of the artifact of the vyper `source`, `<out>/Contract.vy/Contract.json`
+fn artifact_path(project: &Project, source: &Path) -> PathBuf {
+    let file_name = source.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let name = source.file_stem().unwrap_or_default().to_string_lossy().to_string();
+    project.paths.artifacts.join(file_name).join(format!("{}.json", name))
+}
+
+/// Whether the `artifact` exists and was written after the last change of the `source`
+fn is_up_to_date(source: &Path, artifact: &Path) -> bool {
+    let modified = |path: &Path| std::fs::metadata(path).and_then(|meta| meta.modified());
+    match (modified(source), modified(artifact)) {
+        (Ok(source), Ok(artifact)) => artifact >= source,
+        _ => false,
+    }
+}
+
+/// Compiles a single vyper file, ensuring that the `vyper` version satisfies the file's version
+/// pragma
+fn compile_vyper_file(vyper: &Path, version: &Version, file: &Path) -> eyre::Result<VyperArtifact> {
+    let content = std::fs::read_to_string(file)?;
+    if let Some(req) = version_pragma(&content) {
+        if !req.matches(version) {
+            eyre::bail!(
+                "{} requires vyper {} but the installed version is {}",
+                file.display(),
+                req,
+                version
+            )
+        }
+    }
+
+    let output =
+        Command::new(vyper).arg("-f").arg("abi,bytecode,bytecode_runtime").arg(file).output()?;
+    if !output.status.success() {
+        eyre::bail!(
+            "Failed to compile {}:\n{}",
+            file.display(),
+            String::from_utf8_lossy(&output.stderr)
+        )
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let contract = parse_vyper_output(&stdout)
+        .map_err(|err| eyre::eyre!("Failed to parse the output for {}: {}", file.display(), err))?;
+
+    let name = file.file_stem().unwrap_or_default().to_string_lossy().to_string();
+    Ok(VyperArtifact { name, contract })
+}
+
+/// Parses the output of `vyper -f abi,bytecode,bytecode_runtime` which prints every format on a
+/// separate line
+fn parse_vyper_output(output: &str) -> eyre::Result<CompactContractBytecode> {
+    let mut lines = output.lines().map(str::trim).filter(|l| !l.is_empty());
+    let mut next = || lines.next().ok_or_else(|| eyre::eyre!("Unexpected vyper output"));
+    let abi: Abi = serde_json::from_str(next()?)?;
+    let bytecode = next()?;
+    let deployed_bytecode = next()?;
+
+    let contract = serde_json::json!({
+        "abi": abi,
+        "bytecode": { "object": bytecode, "linkReferences": {} },
+        "deployedBytecode": {
+            "bytecode": { "object": deployed_bytecode, "linkReferences": {} }
+        }
+    });
+    Ok(serde_json::from_value(contract)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_version_pragma() {
+        let content = "# @version ^0.3.1\n\n@external\ndef foo() -> uint256:\n    return 1\n";
+        assert_eq!(version_pragma(content), Some(VersionReq::parse("^0.3.1").unwrap()));
+        assert_eq!(version_pragma("@external\ndef foo():\n    pass\n"), None);
+    }
+
+    #[test]
+    fn can_detect_up_to_date_artifacts() {
+        let dir = std::env::temp_dir().join("forge-vyper-up-to-date");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("Counter.vy");
+        let artifact = dir.join("Counter.json");
+        std::fs::write(&source, "@external\ndef foo():\n    pass\n").unwrap();
+        assert!(!is_up_to_date(&source, &artifact));
+        std::fs::write(&artifact, "{}").unwrap();
+        assert!(is_up_to_date(&source, &artifact));
+    }
+
+    #[test]
+    fn can_parse_vyper_output() {
+        let output = r#"[{"stateMutability": "view", "type": "function", "name": "foo", "inputs": [], "outputs": [{"name": "", "type": "uint256"}], "gas": 351}]
+0x61004656600436101561000d5761003b565b
+0x600436101561000d5761003b565b
+"#;
+        let contract = parse_vyper_output(output).unwrap();
+        assert!(contract.abi.unwrap().function("foo").is_ok());
+        assert!(contract.bytecode.unwrap().object.into_bytes().is_some());
+        assert!(contract.deployed_bytecode.unwrap().bytecode.is_some());
+    }
+}
@@ -6,6 +6,7 @@
 //! [`foundry_config::Config`].
 
 pub mod cast;
+pub mod external;
 pub mod forge;
 
 // Re-export our shared utilities
@@ -1,6 +1,6 @@
 use crate::{opts::forge::ContractInfo, term};
 use ethers::{
-    abi::Abi,
+    abi::{Abi, Param},
     prelude::{
         artifacts::{CompactBytecode, CompactDeployedBytecode},
         report::NoReporter,
@@ -17,7 +17,13 @@ pub trait Cmd: clap::Parser + Sized {
 
 use ethers::solc::{artifacts::CompactContractBytecode, Artifact, Project, ProjectCompileOutput};
 
-use foundry_utils::to_table;
+use ansi_term::Colour;
+
+/// Returns the canonical signature of a function, event or error, like `transfer(address,uint256)`
+pub fn abi_signature(name: &str, params: &[Param]) -> String {
+    let types = params.iter().map(|param| param.kind.to_string()).collect::<Vec<_>>();
+    format!("{}({})", name, types.join(","))
+}
 
 /// Compiles the provided [`Project`], throws if there's any compiler error and logs whether
 /// compilation was successful or if there was a cache hit.
@@ -69,23 +75,57 @@ If you are in a subdirectory in a Git repository, try adding `--root .`"#,
             let mut sizes = BTreeMap::new();
             for (_, contracts) in compiled_contracts.into_iter() {
                 for (name, contract) in contracts {
-                    let size = contract
-                        .get_bytecode_bytes()
-                        .map(|bytes| bytes.0.len())
-                        .unwrap_or_default();
-                    sizes.insert(name, size);
+                    sizes.insert(name, deployed_size(&contract));
                 }
             }
-            let json = serde_json::to_value(&sizes)?;
-            println!("name             size (bytes)");
-            println!("-----------------------------");
-            println!("{}", to_table(json));
+            println!("{}", format_sizes(&sizes));
         }
     }
 
     Ok(output)
 }
 
+/// The maximum size of a contract's deployed bytecode in bytes, see [EIP-170](https://eips.ethereum.org/EIPS/eip-170)
+pub const CONTRACT_SIZE_LIMIT: usize = 24576;
+
+/// Returns the size of the artifact's deployed bytecode in bytes
+pub fn deployed_size(artifact: &impl Artifact) -> usize {
+    artifact.get_deployed_bytecode_bytes().map(|bytes| bytes.0.len()).unwrap_or_default()
+}
+
+/// Returns the names and deployed sizes of all contracts in the output that exceed the
+/// [`CONTRACT_SIZE_LIMIT`], including the ones that weren't recompiled
+pub fn oversized_contracts(output: ProjectCompileOutput) -> BTreeMap<String, usize> {
+    output
+        .into_artifacts()
+        .map(|(id, artifact)| (id.name, deployed_size(&artifact)))
+        .filter(|(_, size)| *size > CONTRACT_SIZE_LIMIT)
+        .collect()
+}
+
+/// Formats the contract sizes as a table with their margin against the [`CONTRACT_SIZE_LIMIT`],
+/// contracts that exceed the limit are flagged
+pub fn format_sizes(sizes: &BTreeMap<String, usize>) -> String {
+    let width = sizes.keys().map(|name| name.len()).max().unwrap_or_default().max(4);
+    let mut table =
+        format!("{: <width$}  {: >10}  {: >12}\n", "name", "size (B)", "margin (B)", width = width);
+    table.push_str(&format!("{}\n", "-".repeat(width + 26)));
+    for (name, size) in sizes {
+        let margin = CONTRACT_SIZE_LIMIT as i64 - *size as i64;
+        let line = format!("{: <width$}  {: >10}  {: >12}", name, size, margin, width = width);
+        if margin < 0 {
+            table.push_str(&format!(
+                "{}  {}\n",
+                Colour::Red.paint(line),
+                Colour::Red.paint("exceeds the limit")
+            ));
+        } else {
+            table.push_str(&format!("{}\n", line));
+        }
+    }
+    table
+}
+
 /// Compiles the provided [`Project`], throws if there's any compiler error and logs whether
 /// compilation was successful or if there was a cache hit.
 /// Doesn't print anything to stdout, thus is "suppressed".
@@ -202,3 +242,23 @@ fn get_artifact_from_path(
             .ok_or_else(|| eyre::Error::msg(format!("bytecode not found for {}", contract_name)))?,
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_format_sizes() {
+        let sizes = BTreeMap::from([
+            ("Small".to_string(), 100),
+            ("Exact".to_string(), CONTRACT_SIZE_LIMIT),
+            ("Oversized".to_string(), CONTRACT_SIZE_LIMIT + 1),
+        ]);
+        let table = format_sizes(&sizes);
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[2].contains("24576") && lines[2].ends_with(" 0"));
+        assert!(lines[3].contains("-1") && lines[3].contains("exceeds the limit"));
+        assert!(lines[4].ends_with("24476"));
+    }
+}
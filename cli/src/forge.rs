@@ -85,9 +85,9 @@ fn main() -> eyre::Result<()> {
         Subcommands::Snapshot(cmd) => {
             cmd.run()?;
         }
-        // Subcommands::Fmt(cmd) => {
-        //     cmd.run()?;
-        // }
+        Subcommands::Fmt(cmd) => {
+            cmd.run()?;
+        }
         Subcommands::Config(cmd) => {
             cmd.run()?;
         }
@@ -100,6 +100,27 @@ fn main() -> eyre::Result<()> {
         Subcommands::Tree(cmd) => {
             cmd.run()?;
         }
+        Subcommands::Geiger(cmd) => {
+            cmd.run()?;
+        }
+        Subcommands::Cache(cmd) => {
+            cmd.run()?;
+        }
+        Subcommands::Doc(cmd) => {
+            cmd.run()?;
+        }
+        Subcommands::UploadSelectors(cmd) => {
+            cmd.run()?;
+        }
+        Subcommands::Node(cmd) => {
+            cmd.run()?;
+        }
+        Subcommands::Mutate(cmd) => {
+            cmd.run()?;
+        }
+        Subcommands::External(args) => {
+            cmd::external::run_external("forge", args)?;
+        }
     }
 
     Ok(())
@@ -135,6 +156,9 @@ fn remove(root: impl AsRef<std::path::Path>, dependencies: Vec<Dependency>) -> e
             .spawn()?
             .wait()?;
 
+        // drop any remappings that point into the removed dependency
+        cmd::forge::install::remove_remappings(&root, dep)?;
+
         Ok(())
     })
 }
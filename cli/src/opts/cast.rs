@@ -512,6 +512,9 @@ pub enum Subcommands {
         #[clap(arg_enum)]
         shell: clap_complete::Shell,
     },
+    /// Runs the `cast-<name>` executable on the `PATH` for an unknown subcommand `<name>`
+    #[clap(external_subcommand)]
+    External(Vec<String>),
 }
 
 #[derive(Debug, Parser)]
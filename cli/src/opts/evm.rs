@@ -38,6 +38,9 @@ pub struct EvmArgs {
     /// Fetch state over a remote endpoint instead of starting from an empty state.
     ///
     /// If you want to fetch state from a specific block number, see --fork-block-number.
+    ///
+    /// `ws://` and `wss://` endpoints are kept connected for the whole run, which avoids the
+    /// overhead of one http request per fetched value.
     #[clap(long, short, alias = "rpc-url")]
     #[serde(rename = "eth_rpc_url", skip_serializing_if = "Option::is_none")]
     pub fork_url: Option<String>,
@@ -71,6 +74,13 @@ pub struct EvmArgs {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sender: Option<Address>,
 
+    /// The maximum size of the memory of a call frame in bytes.
+    ///
+    /// Executions that expand the memory beyond the limit halt with an out of gas error.
+    #[clap(long, value_name = "BYTES")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_limit: Option<u64>,
+
     /// Enable the FFI cheatcode.
     #[clap(help = "enables the FFI cheatcode", long)]
     #[serde(skip)]
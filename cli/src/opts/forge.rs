@@ -6,14 +6,19 @@ use std::{path::PathBuf, str::FromStr};
 use crate::cmd::forge::{
     bind::BindArgs,
     build::BuildArgs,
+    cache::CacheArgs,
     config,
     create::CreateArgs,
-    flatten,
+    doc::DocArgs,
+    flatten, fmt, geiger,
     init::InitArgs,
     inspect,
     install::InstallArgs,
+    mutate::MutateArgs,
+    node::NodeArgs,
     remappings::RemappingArgs,
     run::RunArgs,
+    selectors::UploadSelectorsArgs,
     snapshot, test, tree,
     verify::{VerifyArgs, VerifyCheckArgs},
 };
@@ -76,12 +81,12 @@ pub enum Subcommands {
     Remappings(RemappingArgs),
 
     #[clap(
-        about = "Verify your smart contracts source code on Etherscan. Requires `ETHERSCAN_API_KEY` to be set."
+        about = "Verify your smart contracts source code on Etherscan, Sourcify or Blockscout. Etherscan requires `ETHERSCAN_API_KEY` to be set."
     )]
     VerifyContract(VerifyArgs),
 
     #[clap(
-        about = "Check verification status on Etherscan. Requires `ETHERSCAN_API_KEY` to be set."
+        about = "Check verification status on Etherscan, Sourcify or Blockscout. Etherscan requires `ETHERSCAN_API_KEY` to be set."
     )]
     VerifyCheck(VerifyCheckArgs),
 
@@ -110,17 +115,38 @@ pub enum Subcommands {
     #[clap(about = "Creates a snapshot of each test's gas usage")]
     Snapshot(snapshot::SnapshotArgs),
 
-    #[clap(about = "Shows the currently set config values")]
+    #[clap(
+        about = "Shows the resolved config values of the selected profile, including env var and CLI overrides"
+    )]
     Config(config::ConfigArgs),
 
     #[clap(about = "Concats a file with all of its imports")]
     Flatten(flatten::FlattenArgs),
-    // #[clap(about = "formats Solidity source files")]
-    // Fmt(FmtArgs),
+    #[clap(about = "Formats Solidity source files")]
+    Fmt(fmt::FmtArgs),
     #[clap(about = "Outputs a contract in a specified format (ir, assembly, ...)")]
     Inspect(inspect::InspectArgs),
     #[clap(about = "Display a tree visualization of the project's dependency graph")]
     Tree(tree::TreeArgs),
+    #[clap(about = "Detects the usage of unsafe cheatcodes in a project and its dependencies")]
+    Geiger(geiger::GeigerArgs),
+    #[clap(about = "Manages the rpc storage, solc and build caches")]
+    Cache(CacheArgs),
+    #[clap(about = "Generates an mdbook of the project's contracts from their natspec comments")]
+    Doc(DocArgs),
+    #[clap(
+        about = "Uploads the function, event and error signatures of the project's contracts to 4byte and openchain"
+    )]
+    UploadSelectors(UploadSelectorsArgs),
+    #[clap(about = "Runs a local development node backed by the executor of the tests")]
+    Node(NodeArgs),
+    #[clap(
+        about = "Mutates the project's sources and reports the mutants that the tests don't catch"
+    )]
+    Mutate(MutateArgs),
+    /// Runs the `forge-<name>` executable on the `PATH` for an unknown subcommand `<name>`
+    #[clap(external_subcommand)]
+    External(Vec<String>),
 }
 
 // A set of solc compiler settings that can be set via command line arguments, which are intended
@@ -129,7 +155,7 @@ pub enum Subcommands {
 // See also [`BuildArgs`]
 #[derive(Default, Debug, Clone, Parser, Serialize)]
 pub struct CompilerArgs {
-    #[clap(help = "Choose the evm version", long)]
+    #[clap(help = "Choose the evm version the contracts are compiled for and executed with", long)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub evm_version: Option<EvmVersion>,
 
@@ -139,11 +139,14 @@ impl EthereumOpts {
         }
     }
 
-    pub fn rpc_url(&self) -> Result<&str> {
+    /// Returns the RPC url to use, resolving the alias of one of the `rpc_endpoints` of the config
+    pub fn rpc_url(&self) -> Result<String> {
         if self.flashbots {
-            Ok(FLASHBOTS_URL)
+            Ok(FLASHBOTS_URL.to_string())
         } else {
-            Ok(self.rpc_url.as_deref().unwrap_or("http://localhost:8545"))
+            crate::utils::resolve_rpc_url(
+                self.rpc_url.as_deref().unwrap_or("http://localhost:8545"),
+            )
         }
     }
 }
@@ -156,7 +159,11 @@ impl figment::Provider for EthereumOpts {
 
     fn data(&self) -> Result<Map<Profile, Dict>, figment::Error> {
         let mut dict = Dict::new();
-        let rpc = self.rpc_url().map_err(|err| err.to_string())?;
+        let rpc = if self.flashbots {
+            FLASHBOTS_URL
+        } else {
+            self.rpc_url.as_deref().unwrap_or("http://localhost:8545")
+        };
         dict.insert("eth_rpc_url".to_string(), Value::from(rpc.to_string()));
         dict.insert("chain".to_string(), Value::from(self.chain.to_string()));
         Ok(Map::from([(Config::selected_profile(), dict)]))
@@ -66,12 +66,19 @@ pub fn subscriber() {
         .init()
 }
 
+/// Returns the hardfork the executor uses for the `evm` version, which determines the available
+/// opcodes, precompiles and the gas schedule
 pub fn evm_spec(evm: &EvmVersion) -> SpecId {
     match evm {
+        EvmVersion::Homestead => SpecId::HOMESTEAD,
+        EvmVersion::TangerineWhistle => SpecId::TANGERINE,
+        EvmVersion::SpuriousDragon => SpecId::SPURIOUS_DRAGON,
+        EvmVersion::Byzantium => SpecId::BYZANTIUM,
+        EvmVersion::Constantinople => SpecId::CONSTANTINOPLE,
+        EvmVersion::Petersburg => SpecId::PETERSBURG,
         EvmVersion::Istanbul => SpecId::ISTANBUL,
         EvmVersion::Berlin => SpecId::BERLIN,
         EvmVersion::London => SpecId::LONDON,
-        _ => panic!("Unsupported EVM version"),
     }
 }
 
@@ -150,6 +157,29 @@ pub fn block_on<F: Future>(future: F) -> F::Output {
     rt.block_on(future)
 }
 
+/// Resolves an RPC url or the alias of one of the `rpc_endpoints` of the config and interpolates
+/// its environment variables
+///
+/// Urls are returned as is, and so are aliases if the config can't be loaded
+pub fn resolve_rpc_url(url_or_alias: &str) -> eyre::Result<String> {
+    if url_or_alias.contains("://") {
+        return Ok(url_or_alias.to_string())
+    }
+    match Config::try_from(Config::figment()) {
+        Ok(config) => Ok(config.resolve_rpc_url(url_or_alias)?),
+        Err(_) => Ok(url_or_alias.to_string()),
+    }
+}
+
+/// Resolves the fork url of the [EvmOpts] if it is the alias of one of the `rpc_endpoints` of the
+/// config
+pub fn resolve_fork_url(evm_opts: &mut EvmOpts, config: &Config) -> eyre::Result<()> {
+    if let Some(ref url) = evm_opts.fork_url {
+        evm_opts.fork_url = Some(config.resolve_rpc_url(url)?);
+    }
+    Ok(())
+}
+
 /// Helper function that returns the [Fork] to use, if any.
 ///
 /// storage caching for the [Fork] will be enabled if
@@ -233,4 +263,12 @@ mod tests {
         let p = Path::new("contracts/Greeter.sol");
         assert!(!p.is_sol_test());
     }
+
+    #[test]
+    fn can_map_evm_versions() {
+        assert_eq!(evm_spec(&EvmVersion::Byzantium), SpecId::BYZANTIUM);
+        assert_eq!(evm_spec(&EvmVersion::Istanbul), SpecId::ISTANBUL);
+        assert_eq!(evm_spec(&EvmVersion::London), SpecId::LONDON);
+        assert_eq!(evm_spec(&"berlin".parse().unwrap()), SpecId::BERLIN);
+    }
 }
@@ -11,7 +11,7 @@ use foundry_config::{
     parse_with_profile, BasicConfig, Config, OptimizerDetails, SolidityErrorCode,
 };
 use pretty_assertions::assert_eq;
-use std::{env, fs, str::FromStr};
+use std::{env, fs, path::PathBuf, str::FromStr};
 
 // import forge utils as mod
 #[allow(unused)]
@@ -32,6 +32,19 @@ forgetest!(can_show_config, |prj: TestProject, mut cmd: TestCommand| {
     assert_eq!(expected, cmd.stdout().trim().to_string());
 });
 
+// tests the basic config gets printed as json
+forgetest!(can_show_basic_config_as_json, |prj: TestProject, mut cmd: TestCommand| {
+    cmd.set_current_dir(prj.root());
+    cmd.args(["config", "--basic", "--json"]);
+    cmd.set_env("FOUNDRY_OUT", "custom-out");
+
+    let expected = Config::load().into_basic();
+    let config: BasicConfig = serde_json::from_str(&cmd.stdout()).unwrap();
+    assert_eq!(config.src, expected.src);
+    assert_eq!(config.out, PathBuf::from("custom-out"));
+    assert_eq!(config.libs, expected.libs);
+});
+
 // checks that `clean` can be invoked even if out and cache don't exist
 forgetest!(can_clean_non_existing, |prj: TestProject, mut cmd: TestCommand| {
     cmd.arg("clean");
@@ -146,6 +159,25 @@ forgetest!(can_init_vscode, |prj: TestProject, mut cmd: TestCommand| {
     assert_eq!(content, "ds-test/=lib/ds-test/src/");
 });
 
+// Checks that a project can be initialized from a built-in template
+forgetest!(can_init_builtin_template, |prj: TestProject, mut cmd: TestCommand| {
+    prj.wipe();
+
+    cmd.arg("init").arg(prj.root()).args(["--template", "erc20", "--no-git"]);
+    cmd.assert_non_empty_stdout();
+    prj.assert_config_exists();
+
+    assert!(prj.root().join("src/Token.sol").is_file());
+    assert!(prj.root().join("src/test/Token.t.sol").is_file());
+    assert!(prj.root().join("src/test/utils/Vm.sol").is_file());
+    assert!(prj.root().join(".github/workflows/test.yml").is_file());
+    assert!(prj.root().join(".gitignore").is_file());
+    assert!(!prj.root().join("src/Contract.sol").exists());
+
+    cmd.fuse().arg("test").root_arg();
+    assert!(cmd.stdout_lossy().contains("[PASS]"));
+});
+
 // checks that config works
 // - foundry.toml is properly generated
 // - paths are resolved properly
@@ -246,6 +278,34 @@ forgetest_init!(can_get_evm_opts, |prj: TestProject, mut cmd: TestCommand| {
     assert_eq!(evm_opts.fork_url, Some(url.to_string()));
 });
 
+// checks that the profiles of the generated foundry.toml are selected with `FOUNDRY_PROFILE` and
+// that env vars and cli flags take precedence over them
+forgetest_init!(can_select_config_profiles, |prj: TestProject, mut cmd: TestCommand| {
+    cmd.set_current_dir(prj.root());
+    cmd.args(["config", "--json"]);
+
+    let config: Config = serde_json::from_str(&cmd.stdout()).unwrap();
+    assert_eq!(config.fuzz_runs, Config::default().fuzz_runs);
+
+    cmd.set_env("FOUNDRY_PROFILE", "ci");
+    let config: Config = serde_json::from_str(&cmd.stdout()).unwrap();
+    assert_eq!(config.fuzz_runs, 10000);
+    assert_eq!(config.verbosity, 3);
+    assert!(config.optimizer);
+
+    cmd.set_env("FOUNDRY_FUZZ_RUNS", "42");
+    let config: Config = serde_json::from_str(&cmd.stdout()).unwrap();
+    assert_eq!(config.fuzz_runs, 42);
+
+    cmd.set_env("FOUNDRY_PROFILE", "lite");
+    let config: Config = serde_json::from_str(&cmd.stdout()).unwrap();
+    assert!(!config.optimizer);
+
+    cmd.arg("--optimize");
+    let config: Config = serde_json::from_str(&cmd.stdout()).unwrap();
+    assert!(config.optimizer);
+});
+
 // checks that we can set various config values
 forgetest_init!(can_set_config_values, |prj: TestProject, _cmd: TestCommand| {
     let config = prj.config_from_output(["--via-ir"]);
@@ -287,6 +347,74 @@ forgetest_init!(can_emit_extra_output, |prj: TestProject, mut cmd: TestCommand|
     let _artifact: Metadata = ethers::solc::utils::read_json_file(metadata_path).unwrap();
 });
 
+// checks that hardhat style artifacts are emitted
+forgetest_init!(can_emit_hardhat_artifacts, |prj: TestProject, mut cmd: TestCommand| {
+    cmd.args(["build", "--hardhat-artifacts"]);
+    cmd.assert_non_empty_stdout();
+
+    let artifact_path = prj.paths().artifacts.join("hardhat/src/Contract.sol/Contract.json");
+    let artifact: serde_json::Value = ethers::solc::utils::read_json_file(artifact_path).unwrap();
+    assert_eq!(artifact["_format"], "hh-sol-artifact-1");
+    assert_eq!(artifact["contractName"], "Contract");
+    assert_eq!(artifact["sourceName"], "src/Contract.sol");
+    assert!(artifact["bytecode"].as_str().unwrap().starts_with("0x"));
+});
+
+// checks that the project can be compiled via the yul IR pipeline
+forgetest_init!(can_compile_via_ir, |prj: TestProject, mut cmd: TestCommand| {
+    cmd.args(["build", "--via-ir"]);
+    let out = cmd.stdout();
+    assert!(out.trim().contains("Compiler run successful"));
+
+    let artifact_path = prj.paths().artifacts.join("Contract.sol/Contract.json");
+    let artifact: ConfigurableContractArtifact =
+        ethers::solc::utils::read_json_file(artifact_path).unwrap();
+    assert!(artifact.bytecode.is_some());
+});
+
+// checks that an mdbook is generated from the natspec comments of the project's contracts
+forgetest_init!(can_generate_docs, |prj: TestProject, mut cmd: TestCommand| {
+    prj.inner()
+        .add_source(
+            "Greeter",
+            r#"
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.10;
+
+import "./Contract.sol";
+
+/// @title A greeter
+contract Greeter is Contract {
+    /// @notice Emitted when the greeting changes
+    event Greeting(string greeting);
+
+    /// @notice Changes the greeting
+    /// @param greeting the new greeting
+    function greet(string memory greeting) public {
+        emit Greeting(greeting);
+    }
+}
+   "#,
+        )
+        .unwrap();
+
+    cmd.arg("doc");
+    cmd.assert_non_empty_stdout();
+
+    let docs = prj.root().join("docs");
+    assert!(docs.join("book.toml").is_file());
+    let summary = std::fs::read_to_string(docs.join("src/SUMMARY.md")).unwrap();
+    assert!(summary.contains("- [Greeter](Greeter.md)"));
+    assert!(!summary.contains("ContractTest"));
+
+    let page = std::fs::read_to_string(docs.join("src/Greeter.md")).unwrap();
+    assert!(page.contains("**A greeter**"));
+    assert!(page.contains("**Inherits:** [Contract](Contract.md)"));
+    assert!(page.contains("Changes the greeting"));
+    assert!(page.contains("|`greeting`|`string`|the new greeting|"));
+    assert!(page.contains("Emitted when the greeting changes"));
+});
+
 forgetest!(can_set_solc_explicitly, |prj: TestProject, mut cmd: TestCommand| {
     prj.inner()
         .add_source(
@@ -600,6 +728,31 @@ contract Foo {
     assert_eq!(dynamic_bytecode, output);
 });
 
+// checks that `forge inspect --pretty` prints tables instead of json
+forgetest!(can_execute_inspect_command_pretty, |prj: TestProject, mut cmd: TestCommand| {
+    prj.inner()
+        .add_source(
+            "Foo",
+            r#"
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.10;
+contract Foo {
+    uint256 public number;
+    function run() external {}
+}
+    "#,
+        )
+        .unwrap();
+
+    cmd.args(["inspect", "Foo", "methods", "--pretty"]);
+    assert_eq!(cmd.stdout_lossy(), "Selector   Signature\n8381f58a   number()\nc0406226   run()\n");
+
+    cmd.fuse().args(["inspect", "Foo", "storage", "--pretty"]).root_arg();
+    let output = cmd.stdout_lossy();
+    assert!(output.contains("number"));
+    assert!(output.contains("uint256"));
+});
+
 forgetest_init!(can_parse_dapp_libraries, |prj: TestProject, mut cmd: TestCommand| {
     cmd.set_current_dir(prj.root());
     cmd.set_env(
@@ -613,6 +766,55 @@ forgetest_init!(can_parse_dapp_libraries, |prj: TestProject, mut cmd: TestComman
     );
 });
 
+// checks that `forge remappings` prints the inferred remappings merged with `remappings.txt`
+forgetest_init!(can_print_resolved_remappings, |prj: TestProject, mut cmd: TestCommand| {
+    cmd.set_current_dir(prj.root());
+    cmd.arg("remappings");
+    assert_eq!(cmd.stdout().trim(), "ds-test/=lib/ds-test/src/");
+
+    prj.create_file("remappings.txt", "other/=lib/other/");
+    assert_eq!(cmd.stdout().trim(), "ds-test/=lib/ds-test/src/\nother/=lib/other/");
+});
+
+// checks that only the files matching `--match-path` are compiled
+forgetest!(can_compile_filtered_by_path, |prj: TestProject, mut cmd: TestCommand| {
+    prj.insert_ds_test();
+
+    prj.inner()
+        .add_source(
+            "ATest.t.sol",
+            r#"
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.10;
+import "./test.sol";
+contract ATest is DSTest {
+    function testExample() public {
+        assertTrue(true);
+    }
+}
+   "#,
+        )
+        .unwrap();
+    prj.inner()
+        .add_source(
+            "Broken.sol",
+            r#"
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.10;
+contract Broken {
+    function broken() public {
+        does not compile
+    }
+}
+   "#,
+        )
+        .unwrap();
+
+    cmd.args(["test", "--match-path", ".*ATest.t.sol"]);
+    let out = cmd.stdout();
+    assert!(out.contains("Running 1 test for ATest.json:ATest"));
+});
+
 // test that `forge snapshot` commands work
 forgetest!(can_check_snapshot, |prj: TestProject, mut cmd: TestCommand| {
     prj.insert_ds_test();
@@ -660,6 +862,249 @@ contract BTest is DSTest {
     let _ = cmd.output();
 });
 
+// test that `forge test --state-diff` prints the written storage slots with their names
+forgetest!(can_print_state_diff, |prj: TestProject, mut cmd: TestCommand| {
+    prj.insert_ds_test();
+
+    prj.inner()
+        .add_source(
+            "CounterTest.t.sol",
+            r#"
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.10;
+import "./test.sol";
+contract CounterTest is DSTest {
+    uint256 public number;
+
+    function testIncrement() public {
+        number += 1;
+        assertEq(number, 1);
+    }
+}
+   "#,
+        )
+        .unwrap();
+
+    cmd.args(["test", "--state-diff"]);
+    let out = cmd.stdout();
+    assert!(out.contains("State diff:"));
+    assert!(out.contains("(CounterTest)"));
+    assert!(out.contains("(number): 0x0 -> 0x1"));
+});
+
+// test that storage accesses are shown in the traces at the highest verbosity
+forgetest!(can_trace_storage_accesses, |prj: TestProject, mut cmd: TestCommand| {
+    prj.insert_ds_test();
+
+    prj.inner()
+        .add_source(
+            "CounterTest.t.sol",
+            r#"
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.10;
+import "./test.sol";
+contract CounterTest is DSTest {
+    uint256 public number;
+
+    function testIncrement() public {
+        number += 1;
+    }
+}
+   "#,
+        )
+        .unwrap();
+
+    cmd.args(["test", "-vvvv"]);
+    assert!(!cmd.stdout().contains("(number)"));
+
+    cmd.fuse().args(["test", "-vvvvv"]);
+    let out = cmd.stdout();
+    assert!(out.contains("(number): 0x0\n"));
+    assert!(out.contains("(number): 0x0 -> 0x1"));
+});
+
+// test that traces can be printed in the format of geth's `callTracer`
+forgetest!(can_print_geth_traces, |prj: TestProject, mut cmd: TestCommand| {
+    prj.insert_ds_test();
+
+    prj.inner()
+        .add_source(
+            "ATest.t.sol",
+            r#"
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.10;
+import "./test.sol";
+contract ATest is DSTest {
+    function testExample() public {
+        assertTrue(true);
+    }
+}
+   "#,
+        )
+        .unwrap();
+
+    cmd.args(["test", "-vvvv", "--trace-format", "geth"]);
+    let out = cmd.stdout();
+    assert!(out.contains(r#""type":"CALL""#));
+    assert!(out.contains(r#""input":"0x"#));
+});
+
+forgetest!(can_write_flamegraph, |prj: TestProject, mut cmd: TestCommand| {
+    prj.insert_ds_test();
+
+    prj.inner()
+        .add_source(
+            "ATest.t.sol",
+            r#"
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.10;
+import "./test.sol";
+contract ATest is DSTest {
+    function testExample() public {
+        assertTrue(true);
+    }
+}
+   "#,
+        )
+        .unwrap();
+
+    let dir = prj.root().join("flamegraph");
+    cmd.arg("test").arg("--flamegraph").arg(&dir);
+    cmd.assert_non_empty_stdout();
+    let folded = std::fs::read_to_string(dir.join("ATest").join("testExample.folded")).unwrap();
+    assert!(folded.starts_with("ATest::testExample"));
+});
+
+forgetest!(can_write_counterexamples, |prj: TestProject, mut cmd: TestCommand| {
+    prj.insert_ds_test();
+
+    prj.inner()
+        .add_source(
+            "ATest.t.sol",
+            r#"
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.10;
+import "./test.sol";
+contract ATest is DSTest {
+    function testSmall(uint256 x) public {
+        assertTrue(x < 1000);
+    }
+}
+   "#,
+        )
+        .unwrap();
+
+    let file = prj.root().join("counterexamples.json");
+    cmd.args(["test", "--allow-failure", "--counterexamples"]).arg(&file);
+    assert!(cmd.stdout_lossy().contains("Replay: forge test"));
+
+    let counterexamples: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(file).unwrap()).unwrap();
+    let counterexample = &counterexamples[0];
+    assert_eq!(counterexample["contract"], "ATest");
+    assert_eq!(counterexample["test"], "testSmall(uint256)");
+    assert_eq!(counterexample["args"], serde_json::json!(["1000"]));
+    let calldata = counterexample["calldata"].as_str().unwrap();
+    let replay = counterexample["replay"].as_str().unwrap();
+    assert!(replay.contains("--match-test '^testSmall$' --sender 0x"));
+    assert!(replay.ends_with(&format!("--replay {}", calldata)));
+
+    // the replayed counterexample fails again
+    cmd.fuse().args(["test", "--allow-failure", "--replay", calldata]);
+    let stdout = cmd.stdout_lossy();
+    assert!(stdout.contains(&format!("[FAIL. Counterexample: calldata={}, args=[1000]]", calldata)));
+});
+
+forgetest!(prints_labels_in_failures, |prj: TestProject, mut cmd: TestCommand| {
+    prj.insert_ds_test();
+
+    prj.inner()
+        .add_source(
+            "ATest.t.sol",
+            r#"
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.10;
+import "./test.sol";
+interface Vm {
+    function label(address, string calldata) external;
+}
+contract ATest is DSTest {
+    error Unauthorized(address caller);
+    Vm constant vm = Vm(HEVM_ADDRESS);
+    function testLabel() public {
+        address alice = address(0xbeef);
+        vm.label(alice, "alice");
+        revert Unauthorized(alice);
+    }
+}
+   "#,
+        )
+        .unwrap();
+
+    cmd.args(["test", "--allow-failure"]);
+    let stdout = cmd.stdout_lossy();
+    assert!(stdout.contains("Unauthorized(alice)"));
+    assert!(!stdout.contains("beef"));
+});
+
+// tests that mutants of tested code are killed and mutants of untested code survive
+forgetest!(can_kill_and_keep_mutants, |prj: TestProject, mut cmd: TestCommand| {
+    prj.insert_ds_test();
+
+    prj.inner()
+        .add_source(
+            "Counter.sol",
+            r#"
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.10;
+contract Counter {
+    uint256 public number;
+    function increment() public {
+        number = number + 1;
+    }
+    function double(uint256 x) public pure returns (uint256) {
+        return x * 2;
+    }
+}
+   "#,
+        )
+        .unwrap();
+    prj.inner()
+        .add_source(
+            "Counter.t.sol",
+            r#"
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.10;
+import "./test.sol";
+import "./Counter.sol";
+contract CounterTest is DSTest {
+    function testIncrement() public {
+        Counter counter = new Counter();
+        counter.increment();
+        assertEq(counter.number(), 1);
+    }
+}
+   "#,
+        )
+        .unwrap();
+
+    // the path is relative to the root, not the current directory
+    cmd.set_current_dir(env::temp_dir());
+    cmd.args(["mutate", "src/Counter.sol", "--json", "--root"]).arg(prj.root());
+    let results: serde_json::Value = serde_json::from_str(&cmd.stdout()).unwrap();
+    let outcome = |original: &str| {
+        results
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|result| result["mutant"]["original"] == original)
+            .unwrap()["outcome"]
+            .clone()
+    };
+    assert_eq!(outcome("+"), "killed");
+    assert_eq!(outcome("*"), "survived");
+});
+
 // test that `forge build` does not print `(with warnings)` if there arent any
 forgetest!(can_compile_without_warnings, |prj: TestProject, mut cmd: TestCommand| {
     let config = Config {
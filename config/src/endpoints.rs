@@ -0,0 +1,79 @@
+//! Support types for configuring named RPC endpoints
+
+use crate::resolve::{interpolate, UnresolvedEnvVarError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// RPC endpoints by alias, configured in the `rpc_endpoints` table
+///
+/// ```toml
+/// [default.rpc_endpoints]
+/// mainnet = "https://eth-mainnet.alchemyapi.io/v2/${ALCHEMY_KEY}"
+/// local = "http://localhost:8545"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RpcEndpoints {
+    endpoints: BTreeMap<String, String>,
+}
+
+impl RpcEndpoints {
+    pub fn new(
+        endpoints: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
+    ) -> Self {
+        Self {
+            endpoints: endpoints
+                .into_iter()
+                .map(|(alias, url)| (alias.into(), url.into()))
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.endpoints.is_empty()
+    }
+
+    /// Returns the unresolved endpoint of the alias
+    pub fn get(&self, alias: &str) -> Option<&str> {
+        self.endpoints.get(alias).map(String::as_str)
+    }
+
+    /// Resolves an RPC url or the alias of an endpoint to the url to connect to.
+    ///
+    /// Environment variables of the form `${VAR}` are interpolated.
+    pub fn resolve(&self, url_or_alias: &str) -> Result<String, UnresolvedEnvVarError> {
+        interpolate(self.get(url_or_alias).unwrap_or(url_or_alias))
+    }
+
+    /// Returns all aliases and their resolved endpoints
+    pub fn resolved(&self) -> Result<BTreeMap<String, String>, UnresolvedEnvVarError> {
+        self.endpoints.iter().map(|(alias, url)| Ok((alias.clone(), interpolate(url)?))).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_resolve_aliases() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("_FOUNDRY_RPC_KEY", "123");
+            let endpoints = RpcEndpoints::new([
+                ("mainnet", "https://eth-mainnet.alchemyapi.io/v2/${_FOUNDRY_RPC_KEY}"),
+                ("optimism", "https://opt-mainnet.g.alchemy.com/v2/${_FOUNDRY_MISSING_KEY}"),
+            ]);
+            assert_eq!(
+                endpoints.resolve("mainnet").unwrap(),
+                "https://eth-mainnet.alchemyapi.io/v2/123"
+            );
+            assert_eq!(
+                endpoints.resolve("http://localhost:8545").unwrap(),
+                "http://localhost:8545"
+            );
+            assert!(endpoints.resolve("optimism").is_err());
+            assert!(endpoints.resolved().is_err());
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,130 @@
+//! Support types for configuring etherscan API keys
+
+use crate::{
+    resolve::{interpolate, UnresolvedEnvVarError},
+    Chain,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The etherscan API of a chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EtherscanConfig {
+    /// The chain of the API, defaults to the chain named like the alias of the config
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain: Option<Chain>,
+    /// The API url, required for explorers of chains that aren't known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// The API key, may contain environment variables of the form `${VAR}`
+    pub key: String,
+    /// The verification provider of the explorer, defaults to Etherscan
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verifier: Option<EtherscanVerifier>,
+}
+
+/// The verification providers an explorer configured in the `etherscan` table can use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EtherscanVerifier {
+    Etherscan,
+    Blockscout,
+    Sourcify,
+}
+
+impl EtherscanConfig {
+    /// Returns the API key with its environment variables interpolated
+    pub fn resolve_key(&self) -> Result<String, UnresolvedEnvVarError> {
+        interpolate(&self.key)
+    }
+
+    /// Returns the API url with its environment variables interpolated
+    pub fn resolve_url(&self) -> Option<Result<String, UnresolvedEnvVarError>> {
+        self.url.as_deref().map(interpolate)
+    }
+}
+
+/// Etherscan APIs by alias, configured in the `etherscan` table
+///
+/// ```toml
+/// [default.etherscan]
+/// mainnet = { key = "${ETHERSCAN_MAINNET_KEY}" }
+/// optimism = { key = "${OPTIMISM_KEY}", chain = 10 }
+/// gnosis = { key = "", url = "https://blockscout.com/xdai/mainnet/api", verifier = "blockscout" }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EtherscanConfigs {
+    configs: BTreeMap<String, EtherscanConfig>,
+}
+
+impl EtherscanConfigs {
+    pub fn new(configs: impl IntoIterator<Item = (impl Into<String>, EtherscanConfig)>) -> Self {
+        Self {
+            configs: configs.into_iter().map(|(alias, config)| (alias.into(), config)).collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.configs.is_empty()
+    }
+
+    /// Returns the config of the alias
+    pub fn get(&self, alias: &str) -> Option<&EtherscanConfig> {
+        self.configs.get(alias)
+    }
+
+    /// Returns the config of the chain, matched by its `chain` or its alias
+    pub fn find_chain(&self, chain: Chain) -> Option<&EtherscanConfig> {
+        self.configs.iter().find_map(|(alias, config)| {
+            let config_chain = config.chain.or_else(|| alias.parse().ok())?;
+            (config_chain.id() == chain.id()).then(|| config)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_find_chain() {
+        let configs = EtherscanConfigs::new([
+            (
+                "mainnet",
+                EtherscanConfig {
+                    chain: None,
+                    url: None,
+                    key: "mainnet".to_string(),
+                    verifier: None,
+                },
+            ),
+            (
+                "optimistic",
+                EtherscanConfig {
+                    chain: Some(10u64.into()),
+                    url: None,
+                    key: "op".to_string(),
+                    verifier: None,
+                },
+            ),
+        ]);
+        assert_eq!(configs.find_chain(1u64.into()).unwrap().key, "mainnet");
+        assert_eq!(configs.find_chain(10u64.into()).unwrap().key, "op");
+        assert!(configs.find_chain(5u64.into()).is_none());
+        assert_eq!(configs.get("optimistic").unwrap().key, "op");
+    }
+
+    #[test]
+    fn can_parse_verifier() {
+        let config: EtherscanConfig = toml::from_str(
+            r#"
+            key = ""
+            url = "https://blockscout.com/xdai/mainnet/api"
+            verifier = "blockscout"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(config.verifier, Some(EtherscanVerifier::Blockscout));
+    }
+}
@@ -0,0 +1,21 @@
+//! Configuration specific to the `forge fmt` command and the `forge_fmt` package
+
+use serde::{Deserialize, Serialize};
+
+/// Contains the config and rule set
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FormatterConfig {
+    /// Maximum line length where formatter will try to wrap the line
+    pub line_length: usize,
+    /// Number of spaces per indentation level
+    pub tab_width: usize,
+    /// Print spaces between brackets
+    pub bracket_spacing: bool,
+}
+
+impl Default for FormatterConfig {
+    fn default() -> Self {
+        FormatterConfig { line_length: 80, tab_width: 4, bracket_spacing: false }
+    }
+}
@@ -0,0 +1,185 @@
+//! Support for the filesystem permissions of the file cheatcodes
+
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt, io,
+    path::{Component, Path, PathBuf},
+};
+
+/// The paths the file cheatcodes are allowed to access.
+///
+/// Access to any path that is not covered by a permission is denied.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FsPermissions {
+    pub permissions: Vec<PathPermission>,
+    /// The root of the project the paths passed to the cheatcodes are relative to, the current
+    /// directory if not set
+    #[serde(skip)]
+    pub root: PathBuf,
+}
+
+impl FsPermissions {
+    pub fn new(permissions: impl IntoIterator<Item = PathPermission>) -> Self {
+        Self { permissions: permissions.into_iter().collect(), root: PathBuf::new() }
+    }
+
+    /// Resolves the `path` passed to a cheatcode relative to the root, see
+    /// [`canonicalize_path()`]
+    pub fn resolve(&self, path: impl AsRef<Path>) -> io::Result<PathBuf> {
+        let path = if self.root.as_os_str().is_empty() {
+            std::env::current_dir()?.join(path)
+        } else {
+            self.root.join(path)
+        };
+        Ok(canonicalize_path(&path))
+    }
+
+    /// Returns `true` if no permissions are granted
+    pub fn is_empty(&self) -> bool {
+        self.permissions.is_empty()
+    }
+
+    /// Returns `true` if `path` may be accessed with the given `kind` of access.
+    ///
+    /// If several permissions cover the path, the one of the most specific path applies, so a
+    /// read-only subdirectory of a read-write directory can't be written to.
+    pub fn is_path_allowed(&self, path: impl AsRef<Path>, kind: FsAccessKind) -> bool {
+        self.find_permission(path).map(|access| access.is_granted(kind)).unwrap_or_default()
+    }
+
+    /// Returns the access granted by the permission of the most specific path that contains
+    /// `path`, if any
+    pub fn find_permission(&self, path: impl AsRef<Path>) -> Option<FsAccessPermission> {
+        let path = path.as_ref();
+        self.permissions
+            .iter()
+            .filter(|perm| path.starts_with(&perm.path))
+            .max_by_key(|perm| perm.path.components().count())
+            .map(|perm| perm.access)
+    }
+
+    /// Updates all paths of the permissions with `f`, used to join relative paths with the root
+    pub fn map_paths(&mut self, f: impl Fn(&Path) -> PathBuf) {
+        for perm in self.permissions.iter_mut() {
+            perm.path = f(&perm.path);
+        }
+    }
+}
+
+/// Resolves the symlinks of `path` if it exists, so it can't point outside of a permitted
+/// directory, otherwise only lexically resolves its `.` and `..` components
+pub fn canonicalize_path(path: &Path) -> PathBuf {
+    ethers_solc::utils::canonicalize(path).unwrap_or_else(|_| normalize_path(path))
+}
+
+/// Lexically resolves the `.` and `..` components of `path`, so a path can't escape a permitted
+/// directory even if it doesn't exist yet
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// The access granted to a path and all of its subpaths
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PathPermission {
+    pub access: FsAccessPermission,
+    pub path: PathBuf,
+}
+
+impl PathPermission {
+    pub fn new(access: FsAccessPermission, path: impl Into<PathBuf>) -> Self {
+        Self { access, path: path.into() }
+    }
+
+    pub fn read(path: impl Into<PathBuf>) -> Self {
+        Self::new(FsAccessPermission::Read, path)
+    }
+
+    pub fn write(path: impl Into<PathBuf>) -> Self {
+        Self::new(FsAccessPermission::Write, path)
+    }
+
+    pub fn read_write(path: impl Into<PathBuf>) -> Self {
+        Self::new(FsAccessPermission::ReadWrite, path)
+    }
+}
+
+/// The kinds of access that can be granted to a path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FsAccessPermission {
+    ReadWrite,
+    Read,
+    Write,
+}
+
+impl FsAccessPermission {
+    /// Returns `true` if this permission allows the given `kind` of access
+    pub fn is_granted(&self, kind: FsAccessKind) -> bool {
+        match (self, kind) {
+            (FsAccessPermission::ReadWrite, _) => true,
+            (FsAccessPermission::Read, FsAccessKind::Read) => true,
+            (FsAccessPermission::Write, FsAccessKind::Write) => true,
+            _ => false,
+        }
+    }
+}
+
+/// The kind of access a file cheatcode needs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FsAccessKind {
+    Read,
+    Write,
+}
+
+impl fmt::Display for FsAccessKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FsAccessKind::Read => f.write_str("read"),
+            FsAccessKind::Write => f.write_str("write"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_find_most_specific_permission() {
+        let permissions = FsPermissions::new([
+            PathPermission::read_write("/project/out"),
+            PathPermission::read("/project/out/fixtures"),
+        ]);
+
+        assert!(permissions.is_path_allowed("/project/out/file.txt", FsAccessKind::Write));
+        assert!(permissions.is_path_allowed("/project/out/fixtures/a.json", FsAccessKind::Read));
+        assert!(!permissions.is_path_allowed("/project/out/fixtures/a.json", FsAccessKind::Write));
+        assert!(!permissions.is_path_allowed("/project/outside.txt", FsAccessKind::Read));
+        assert!(!FsPermissions::default().is_path_allowed("/project/out", FsAccessKind::Read));
+    }
+
+    #[test]
+    fn can_resolve_paths_relative_to_root() {
+        let mut permissions = FsPermissions::new([PathPermission::read("/project/fixtures")]);
+        permissions.root = PathBuf::from("/project");
+
+        let path = permissions.resolve("./fixtures/../fixtures/data.json").unwrap();
+        assert_eq!(path, PathBuf::from("/project/fixtures/data.json"));
+        assert!(permissions.is_path_allowed(&path, FsAccessKind::Read));
+        assert!(!permissions.is_path_allowed(
+            permissions.resolve("fixtures/../foundry.toml").unwrap(),
+            FsAccessKind::Read
+        ));
+    }
+}
@@ -3,6 +3,7 @@ extern crate core;
 
 use std::{
     borrow::Cow,
+    collections::BTreeMap,
     fmt,
     path::{Path, PathBuf},
     str::FromStr,
@@ -22,7 +23,10 @@ use crate::caching::StorageCachingConfig;
 use ethers_core::types::{Address, U256};
 pub use ethers_solc::artifacts::OptimizerDetails;
 use ethers_solc::{
-    artifacts::{output_selection::ContractOutputSelection, Optimizer, Settings},
+    artifacts::{
+        output_selection::ContractOutputSelection, BytecodeHash, Optimizer, Settings,
+        SettingsMetadata,
+    },
     cache::SOLIDITY_FILES_CACHE_FILENAME,
     error::SolcError,
     remappings::{RelativeRemapping, Remapping},
@@ -41,6 +45,21 @@ pub use crate::utils::*;
 
 pub mod caching;
 
+pub mod resolve;
+pub use resolve::UnresolvedEnvVarError;
+
+pub mod endpoints;
+pub use endpoints::RpcEndpoints;
+
+pub mod etherscan;
+pub use etherscan::{EtherscanConfig, EtherscanConfigs, EtherscanVerifier};
+
+pub mod fs_permissions;
+pub use fs_permissions::{FsAccessKind, FsAccessPermission, FsPermissions, PathPermission};
+
+pub mod fmt;
+pub use fmt::FormatterConfig;
+
 /// Foundry configuration
 ///
 /// # Defaults
@@ -100,7 +119,8 @@ pub struct Config {
     pub cache_path: PathBuf,
     /// whether to force a `project.clean()`
     pub force: bool,
-    /// evm version to use
+    /// evm version to use, for compiling the contracts as well as for executing them in tests
+    /// and scripts
     #[serde(with = "from_str_lowercase")]
     pub evm_version: EvmVersion,
     /// list of contracts to report gas of
@@ -115,6 +135,9 @@ pub struct Config {
     pub solc: Option<SolcReq>,
     /// whether to autodetect the solc compiler version to use
     pub auto_detect_solc: bool,
+    /// The vyper binary used to compile the `.vy` sources of the project, if not set `vyper` is
+    /// resolved via `PATH`
+    pub vyper: Option<PathBuf>,
     /// Offline mode, if set, network access (downloading solc) is disallowed.
     ///
     /// Relationship with `auto_detect_solc`:
@@ -130,16 +153,44 @@ pub struct Config {
     /// The "enabled" switch above provides two defaults which can be
     /// tweaked here. If "details" is given, "enabled" can be omitted.
     pub optimizer_details: Option<OptimizerDetails>,
+    /// The hash method of the metadata that's appended to the bytecode, `ipfs`, `bzzr1` or `none`
+    ///
+    /// This must match the setting the contracts are verified with, otherwise the bytecode won't
+    /// match
+    #[serde(with = "from_str_lowercase")]
+    pub bytecode_hash: BytecodeHash,
     /// verbosity to use
     pub verbosity: u8,
-    /// url of the rpc server that should be used for any rpc calls
+    /// url of the rpc server that should be used for any rpc calls, or the alias of one of the
+    /// `rpc_endpoints`
     pub eth_rpc_url: Option<String>,
+    /// RPC endpoints by alias, which can be used in place of urls, e.g. `--fork-url mainnet`
+    #[serde(default, skip_serializing_if = "RpcEndpoints::is_empty")]
+    pub rpc_endpoints: RpcEndpoints,
+    /// Etherscan API keys and urls by alias, used for the chain they are configured for
+    #[serde(default, skip_serializing_if = "EtherscanConfigs::is_empty")]
+    pub etherscan: EtherscanConfigs,
     /// list of solidity error codes to always silence in the compiler output
     pub ignored_error_codes: Vec<SolidityErrorCode>,
     /// The number of test cases that must execute for each property test
     pub fuzz_runs: u32,
     /// Whether to allow ffi cheatcodes in test
     pub ffi: bool,
+    /// Custom precompiles, mapping an address to the command that implements the precompile.
+    ///
+    /// The command is called with the hex encoded calldata as its last argument and has to print
+    /// the hex encoded output. The commands are only run if `ffi` is enabled.
+    pub precompiles: BTreeMap<Address, PrecompileCommand>,
+    /// The paths the file cheatcodes may read from or write to, relative to the root.
+    ///
+    /// Access to all other paths is denied.
+    #[serde(default, skip_serializing_if = "FsPermissions::is_empty")]
+    pub fs_permissions: FsPermissions,
+    /// The maximum size of the memory of a call frame in bytes, `0` disables the limit.
+    ///
+    /// Executions that expand the memory beyond the limit halt with an out of gas error instead
+    /// of allocating the memory.
+    pub memory_limit: u64,
     /// The address which will be executing all tests
     pub sender: Address,
     /// The tx.origin value during EVM execution
@@ -192,6 +243,11 @@ pub struct Config {
     /// output selection as separate files.
     #[serde(default)]
     pub extra_output_files: Vec<ContractOutputSelection>,
+    /// If set, `forge build` additionally writes every contract artifact in hardhat's
+    /// `hh-sol-artifact-1` format to `<out>/hardhat/<sourceName>/<contractName>.json`, so tools
+    /// that consume hardhat artifacts can be pointed at a forge build.
+    #[serde(default)]
+    pub hardhat_artifacts: bool,
     /// The maximum number of local test case rejections allowed
     /// by proptest, to be encountered during usage of `vm.assume`
     /// cheatcode.
@@ -200,6 +256,43 @@ pub struct Config {
     /// by proptest, to be encountered during usage of `vm.assume`
     /// cheatcode.
     pub fuzz_max_global_rejects: u32,
+    /// If set, random fuzzed uints are sampled uniformly from the range of their type. By default
+    /// small values are favoured, which finds boundary bugs quicker but skews the inputs.
+    #[serde(default)]
+    pub fuzz_uniform_ints: bool,
+    /// If set, the percentage of fuzzed ints and uints that are drawn from the edge cases of their
+    /// type, like 0, max, min and powers of two +/- 1, where overflow bugs cluster. By default 10%
+    /// of uints are edge cases and ints are sampled uniformly.
+    #[serde(default)]
+    pub fuzz_edge_percent: Option<u32>,
+    /// The max length of fuzzed strings in chars, the strings are valid UTF-8 and mix ASCII,
+    /// unicode, null bytes and long and empty strings
+    pub fuzz_max_string_len: usize,
+    /// The max length of fuzzed dynamic arrays, long arrays make tests with array params slow
+    pub fuzz_max_array_len: usize,
+    /// The max length of fuzzed `bytes`
+    pub fuzz_max_bytes_len: usize,
+    /// The percentage of fuzzed params that are drawn from the dictionary of values seen in the
+    /// state of the EVM and the calls of previous runs instead of being generated randomly
+    pub fuzz_dictionary_weight: u32,
+    /// If set, inputs of fuzz runs that take branches no previous run took are kept and mutated
+    /// preferentially, which finds bugs behind nested conditions quicker than random inputs
+    #[serde(default)]
+    pub fuzz_coverage_guided: bool,
+    /// The number of actor accounts that call fuzz tests. Each run is called by one of them, and
+    /// fuzzed addresses are often actors, which makes access control and multi user scenarios
+    /// fuzzable without pranks. Fuzz tests are called by the sender if there are none.
+    #[serde(default)]
+    pub fuzz_actors: usize,
+    /// The number of seconds each fuzz test is fuzzed for. If set, tests are fuzzed until the
+    /// time is up or they fail instead of for `fuzz_runs` runs.
+    #[serde(default)]
+    pub fuzz_max_time: Option<u64>,
+    /// The number of threads the runs of each fuzz test are split across. Each thread runs its
+    /// share of `fuzz_runs` with its own fuzz state, the results are merged.
+    pub fuzz_workers: usize,
+    /// The seed of the random number generator of the fuzzer, a random seed is used if unset
+    pub fuzz_seed: Option<U256>,
     /// Print the names of the compiled contracts
     pub names: bool,
     /// Print the sizes of the compiled contracts
@@ -212,6 +305,8 @@ pub struct Config {
     /// Disables storage caching entirely. This overrides any settings made in
     /// `rpc_storage_caching`
     pub no_storage_caching: bool,
+    /// Configures the formatter used by `forge fmt`
+    pub fmt: FormatterConfig,
     /// The root path where the config detection started from, `Config::with_root`
     #[doc(hidden)]
     //  We're skipping serialization here, so it won't be included in the [`Config::to_string()`]
@@ -354,6 +449,9 @@ impl Config {
 
         self.cache_path = p(&root, &self.cache_path);
 
+        self.fs_permissions.map_paths(|path| fs_permissions::canonicalize_path(&root.join(path)));
+        self.fs_permissions.root = root.clone();
+
         self
     }
 
@@ -426,7 +524,7 @@ impl Config {
     /// Ensures that the configured version is installed if explicitly set
     ///
     /// If `solc` is [`SolcReq::Version`] then this will download and install the solc version if
-    /// it's missing.
+    /// it's missing, unless `offline` is set, in which case this will error instead.
     ///
     /// If `solc` is [`SolcReq::Local`] then this will ensure that the path exists.
     fn ensure_solc(&self) -> Result<Option<Solc>, SolcError> {
@@ -436,6 +534,12 @@ impl Config {
                     let v = version.to_string();
                     let mut solc = Solc::find_svm_installed_version(&v)?;
                     if solc.is_none() {
+                        if self.offline {
+                            return Err(SolcError::msg(format!(
+                                "can't install missing solc {} in offline mode",
+                                version
+                            )))
+                        }
                         Solc::blocking_install(version)?;
                         solc = Solc::find_svm_installed_version(&v)?;
                     }
@@ -542,6 +646,7 @@ impl Config {
     ///   - all libraries
     ///   - the optimizer (including details, if configured)
     ///   - evm version
+    ///   - the bytecode hash of the metadata
     pub fn solc_settings(&self) -> Result<Settings, SolcError> {
         let libraries = parse_libraries(&self.libraries)?;
         let optimizer = self.optimizer();
@@ -549,6 +654,10 @@ impl Config {
         let mut settings = Settings {
             optimizer,
             evm_version: Some(self.evm_version),
+            metadata: Some(SettingsMetadata {
+                bytecode_hash: Some(self.bytecode_hash),
+                ..Default::default()
+            }),
             libraries,
             ..Default::default()
         }
@@ -572,7 +681,10 @@ impl Config {
     ///   3. `FOUNDRY_` prefixed environment variables
     ///
     /// The profile selected is the value set in the `FOUNDRY_PROFILE`
-    /// environment variable. If it is not set, it defaults to `default`.
+    /// environment variable. If it is not set, it defaults to `default`. Keys that are not set in
+    /// the selected profile fall back to the `default` profile.
+    ///
+    /// Command line flags are merged on top of the returned figment by the tools.
     ///
     /// # Example
     ///
@@ -685,6 +797,13 @@ impl Config {
                     &format!("[{}.optimizer_details.yulDetails]", self.profile),
                 );
         }
+        s = s.replace("[[fs_permissions]]", &format!("[[{}.fs_permissions]]", self.profile));
+        // same for the endpoint and formatter tables
+        for table in ["rpc_endpoints", "etherscan", "fmt"] {
+            s = s
+                .replace(&format!("[{}]", table), &format!("[{}.{}]", self.profile, table))
+                .replace(&format!("[{}.", table), &format!("[{}.{}.", self.profile, table));
+        }
 
         Ok(format!(
             r#"[{}]
@@ -693,6 +812,25 @@ impl Config {
         ))
     }
 
+    /// Returns the `eth_rpc_url` with the alias of an endpoint of `rpc_endpoints` resolved and its
+    /// environment variables interpolated
+    pub fn get_rpc_url(&self) -> Option<Result<String, UnresolvedEnvVarError>> {
+        self.eth_rpc_url.as_deref().map(|url| self.rpc_endpoints.resolve(url))
+    }
+
+    /// Resolves an RPC url or the alias of one of the `rpc_endpoints`
+    pub fn resolve_rpc_url(&self, url_or_alias: &str) -> Result<String, UnresolvedEnvVarError> {
+        self.rpc_endpoints.resolve(url_or_alias)
+    }
+
+    /// Returns the resolved etherscan API key configured for the chain, if any
+    pub fn get_etherscan_api_key(
+        &self,
+        chain: impl Into<Chain>,
+    ) -> Option<Result<String, UnresolvedEnvVarError>> {
+        self.etherscan.find_chain(chain.into()).map(EtherscanConfig::resolve_key)
+    }
+
     /// Returns the selected profile
     ///
     /// If the `FOUNDRY_PROFILE` env variable is not set, this returns the `DEFAULT_PROFILE`
@@ -878,18 +1016,36 @@ impl Default for Config {
             gas_reports: vec!["*".to_string()],
             solc: None,
             auto_detect_solc: true,
+            vyper: None,
             offline: false,
             optimizer: true,
             optimizer_runs: 200,
             optimizer_details: None,
+            bytecode_hash: BytecodeHash::Ipfs,
             extra_output: Default::default(),
             extra_output_files: Default::default(),
+            hardhat_artifacts: false,
             names: false,
             sizes: false,
             fuzz_runs: 256,
             fuzz_max_local_rejects: 1024,
             fuzz_max_global_rejects: 65536,
+            fuzz_uniform_ints: false,
+            fuzz_edge_percent: None,
+            fuzz_max_string_len: 256,
+            fuzz_max_array_len: 256,
+            fuzz_max_bytes_len: 100,
+            fuzz_dictionary_weight: 40,
+            fuzz_coverage_guided: false,
+            fuzz_actors: 0,
+            fuzz_max_time: None,
+            fuzz_workers: 1,
+            fuzz_seed: None,
             ffi: false,
+            precompiles: Default::default(),
+            fs_permissions: Default::default(),
+            // 32 MiB
+            memory_limit: 1 << 25,
             sender: "00a329c0648769A73afAc7F9381E08FB43dBEA72".parse().unwrap(),
             tx_origin: "00a329c0648769A73afAc7F9381E08FB43dBEA72".parse().unwrap(),
             initial_balance: U256::from(0xffffffffffffffffffffffffu128),
@@ -909,6 +1065,8 @@ impl Default for Config {
             block_difficulty: 0,
             block_gas_limit: None,
             eth_rpc_url: None,
+            rpc_endpoints: Default::default(),
+            etherscan: Default::default(),
             verbosity: 0,
             remappings: vec![],
             libraries: vec![],
@@ -917,6 +1075,7 @@ impl Default for Config {
             via_ir: false,
             rpc_storage_caching: Default::default(),
             no_storage_caching: false,
+            fmt: Default::default(),
         }
     }
 }
@@ -965,6 +1124,35 @@ impl<'de> Deserialize<'de> for SolidityErrorCode {
     }
 }
 
+/// The command of a custom precompile
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PrecompileCommand {
+    /// The command, a call to the precompile costs the estimated gas
+    Command(Vec<String>),
+    /// The command and the gas a call to the precompile costs
+    WithGas { command: Vec<String>, gas: u64 },
+}
+
+impl PrecompileCommand {
+    /// The program and its arguments
+    pub fn command(&self) -> &[String] {
+        match self {
+            PrecompileCommand::Command(command) | PrecompileCommand::WithGas { command, .. } => {
+                command
+            }
+        }
+    }
+
+    /// The configured gas of a call to the precompile, if any
+    pub fn gas(&self) -> Option<u64> {
+        match self {
+            PrecompileCommand::Command(_) => None,
+            PrecompileCommand::WithGas { gas, .. } => Some(*gas),
+        }
+    }
+}
+
 /// Variants for selecting the [`Solc`] instance
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -1310,6 +1498,20 @@ impl fmt::Display for Chain {
     }
 }
 
+impl FromStr for Chain {
+    type Err = String;
+
+    /// Parses either a chain id or a chain name, like `1` or `mainnet`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(id) = s.parse::<u64>() {
+            return Ok(ethers_core::types::Chain::try_from(id)
+                .map(Chain::Named)
+                .unwrap_or_else(|_| Chain::Id(id)))
+        }
+        s.to_lowercase().parse().map(Chain::Named).map_err(|err| format!("{}", err))
+    }
+}
+
 impl From<ethers_core::types::Chain> for Chain {
     fn from(id: ethers_core::types::Chain) -> Self {
         Chain::Named(id)
@@ -1417,6 +1619,50 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_endpoints() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file(
+                "foundry.toml",
+                r#"
+                [default]
+                eth_rpc_url = "mainnet"
+
+                [default.rpc_endpoints]
+                mainnet = "https://eth-mainnet.alchemyapi.io/v2/${_CONFIG_ALCHEMY_KEY}"
+                local = "http://localhost:8545"
+
+                [default.etherscan]
+                mainnet = { key = "${_CONFIG_ETHERSCAN_KEY}" }
+                optimistic = { key = "op-key", chain = 10, url = "https://api-optimistic.etherscan.io/api" }
+            "#,
+            )?;
+
+            let config = Config::load();
+            assert!(config.get_rpc_url().unwrap().is_err());
+            assert_eq!(config.resolve_rpc_url("local").unwrap(), "http://localhost:8545");
+            assert!(config.get_etherscan_api_key(1u64).unwrap().is_err());
+            assert_eq!(config.get_etherscan_api_key(10u64).unwrap().unwrap(), "op-key");
+            assert!(config.get_etherscan_api_key(5u64).is_none());
+
+            jail.set_env("_CONFIG_ALCHEMY_KEY", "123");
+            jail.set_env("_CONFIG_ETHERSCAN_KEY", "abc");
+            assert_eq!(
+                config.get_rpc_url().unwrap().unwrap(),
+                "https://eth-mainnet.alchemyapi.io/v2/123"
+            );
+            assert_eq!(config.get_etherscan_api_key(1u64).unwrap().unwrap(), "abc");
+
+            // the unresolved values are written back
+            jail.create_file("foundry.toml", &config.to_string_pretty().unwrap())?;
+            let loaded = Config::load();
+            assert_eq!(loaded.rpc_endpoints, config.rpc_endpoints);
+            assert_eq!(loaded.etherscan, config.etherscan);
+
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_profile_env() {
         figment::Jail::expect_with(|jail| {
@@ -1703,6 +1949,75 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_fmt_config() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file(
+                "foundry.toml",
+                r#"
+                [default]
+                fmt = { line_length = 100, bracket_spacing = true }
+            "#,
+            )?;
+
+            let config = Config::load();
+            assert_eq!(
+                config.fmt,
+                FormatterConfig { line_length: 100, tab_width: 4, bracket_spacing: true }
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn fmt_config_roundtrip() {
+        figment::Jail::expect_with(|jail| {
+            let config = Config {
+                fmt: FormatterConfig { line_length: 120, tab_width: 2, bracket_spacing: true },
+                ..Default::default()
+            };
+            jail.create_file("foundry.toml", &config.to_string_pretty().unwrap())?;
+
+            let loaded = Config::load();
+            assert_eq!(loaded.fmt, config.fmt);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_compiler_settings_per_profile() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file(
+                "foundry.toml",
+                r#"
+                [default]
+                evm_version = "berlin"
+                bytecode_hash = "none"
+
+                [production]
+                optimizer_runs = 1000000
+                bytecode_hash = "ipfs"
+            "#,
+            )?;
+
+            let config = Config::load();
+            assert_eq!(config.evm_version, EvmVersion::Berlin);
+            assert_eq!(config.bytecode_hash, BytecodeHash::None);
+            let settings = config.solc_settings().unwrap();
+            assert_eq!(settings.metadata.unwrap().bytecode_hash, Some(BytecodeHash::None));
+
+            jail.set_env("FOUNDRY_PROFILE", "production");
+            let config = Config::load();
+            assert_eq!(config.evm_version, EvmVersion::Berlin);
+            assert_eq!(config.optimizer_runs, 1000000);
+            assert_eq!(config.bytecode_hash, BytecodeHash::Ipfs);
+
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_precedence() {
         figment::Jail::expect_with(|jail| {
@@ -1861,6 +2176,131 @@ mod tests {
         });
     }
 
+    #[test]
+    fn can_reject_invalid_libraries() {
+        let libs = parse_libraries(&[
+            "src/DssSpell.sol:DssExecLib:0x8De6DDbCd5053d32292AAA0D2105A32d108484a6".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(
+            libs["src/DssSpell.sol"]["DssExecLib"],
+            "0x8De6DDbCd5053d32292AAA0D2105A32d108484a6"
+        );
+
+        assert!(parse_libraries(&["src/DssSpell.sol:DssExecLib".to_string()]).is_err());
+        assert!(parse_libraries(&[
+            ":DssExecLib:0x8De6DDbCd5053d32292AAA0D2105A32d108484a6".to_string()
+        ])
+        .is_err());
+        assert!(parse_libraries(&["src/DssSpell.sol:DssExecLib:0x1234".to_string()]).is_err());
+        assert!(parse_libraries(&[
+            "src/DssSpell.sol:DssExecLib:0x8De6DDbCd5053d32292AAA0D2105A32d108484a6:extra"
+                .to_string()
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn can_parse_precompiles() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file(
+                "foundry.toml",
+                r#"
+                [default]
+                precompiles = { "0x0000000000000000000000000000000000000100" = ["./verifier", "--fast"], "0x0000000000000000000000000000000000000101" = { command = ["./hasher"], gas = 5000 } }
+            "#,
+            )?;
+
+            let config = Config::load();
+            let verifier: Address = "0x0000000000000000000000000000000000000100".parse().unwrap();
+            let hasher: Address = "0x0000000000000000000000000000000000000101".parse().unwrap();
+            assert_eq!(
+                config.precompiles,
+                BTreeMap::from([
+                    (
+                        verifier,
+                        PrecompileCommand::Command(vec![
+                            "./verifier".to_string(),
+                            "--fast".to_string()
+                        ])
+                    ),
+                    (
+                        hasher,
+                        PrecompileCommand::WithGas {
+                            command: vec!["./hasher".to_string()],
+                            gas: 5000
+                        }
+                    ),
+                ])
+            );
+            assert_eq!(config.precompiles[&verifier].gas(), None);
+            assert_eq!(config.precompiles[&hasher].gas(), Some(5000));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn can_parse_fuzz_settings() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file(
+                "foundry.toml",
+                r#"
+                [default]
+                fuzz_max_array_len = 16
+                fuzz_max_bytes_len = 64
+                fuzz_seed = '0x3e8'
+                fuzz_max_time = 600
+                fuzz_workers = 4
+            "#,
+            )?;
+            jail.set_env("FOUNDRY_FUZZ_EDGE_PERCENT", 10);
+
+            let config = Config::load();
+            assert_eq!(config.fuzz_max_array_len, 16);
+            assert_eq!(config.fuzz_max_bytes_len, 64);
+            assert_eq!(config.fuzz_seed, Some(U256::from(1000)));
+            assert_eq!(config.fuzz_max_time, Some(600));
+            assert_eq!(config.fuzz_workers, 4);
+            assert_eq!(config.fuzz_edge_percent, Some(10));
+            assert_eq!(config.fuzz_max_string_len, Config::default().fuzz_max_string_len);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn can_parse_fs_permissions() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file(
+                "foundry.toml",
+                r#"
+                [default]
+                fs_permissions = [{ access = "read", path = "./out" }, { access = "read-write", path = "./fixtures" }]
+            "#,
+            )?;
+
+            let config = Config::load();
+            assert_eq!(
+                config.fs_permissions,
+                FsPermissions::new([
+                    PathPermission::read("./out"),
+                    PathPermission::read_write("./fixtures"),
+                ])
+            );
+
+            let config = config.canonic_at("/project");
+            assert!(config
+                .fs_permissions
+                .is_path_allowed("/project/fixtures/data.json", FsAccessKind::Write));
+            assert!(!config
+                .fs_permissions
+                .is_path_allowed("/project/out/a.json", FsAccessKind::Write));
+            assert!(!config
+                .fs_permissions
+                .is_path_allowed("/project/foundry.toml", FsAccessKind::Read));
+            Ok(())
+        });
+    }
+
     #[test]
     fn config_roundtrip() {
         figment::Jail::expect_with(|jail| {
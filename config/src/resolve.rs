@@ -0,0 +1,79 @@
+//! Helpers to resolve environment variables in config values
+
+use std::{env, fmt};
+
+/// An environment variable of a config value that is not set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedEnvVarError {
+    /// The config value that contains the variable
+    pub unresolved: String,
+    /// The name of the variable
+    pub var: String,
+}
+
+impl fmt::Display for UnresolvedEnvVarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Failed to resolve env var `{}` in `{}`: environment variable not found",
+            self.var, self.unresolved
+        )
+    }
+}
+
+impl std::error::Error for UnresolvedEnvVarError {}
+
+/// Replaces all `${VAR}` placeholders in the input with the values of the environment variables
+///
+/// # Example
+///
+/// ```
+/// use foundry_config::resolve::interpolate;
+/// std::env::set_var("RPC_KEY", "123");
+/// assert_eq!(interpolate("https://rpc.io/${RPC_KEY}").unwrap(), "https://rpc.io/123");
+/// ```
+pub fn interpolate(input: &str) -> Result<String, UnresolvedEnvVarError> {
+    let mut resolved = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find("${") {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        let var = rest[start + 2..end].trim();
+        let value = env::var(var).map_err(|_| UnresolvedEnvVarError {
+            unresolved: input.to_string(),
+            var: var.to_string(),
+        })?;
+        resolved.push_str(&rest[..start]);
+        resolved.push_str(&value);
+        rest = &rest[end + 1..];
+    }
+    resolved.push_str(rest);
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_interpolate_env_vars() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("_FOUNDRY_INTERPOLATE_KEY", "abc");
+            assert_eq!(interpolate("no vars").unwrap(), "no vars");
+            assert_eq!(
+                interpolate(
+                    "https://rpc.io/${_FOUNDRY_INTERPOLATE_KEY}/${ _FOUNDRY_INTERPOLATE_KEY }"
+                )
+                .unwrap(),
+                "https://rpc.io/abc/abc"
+            );
+            assert_eq!(interpolate("${unterminated").unwrap(), "${unterminated");
+
+            let err = interpolate("https://rpc.io/${_FOUNDRY_MISSING_KEY}").unwrap_err();
+            assert_eq!(err.var, "_FOUNDRY_MISSING_KEY");
+            Ok(())
+        });
+    }
+}
@@ -3,6 +3,7 @@
 use std::{collections::BTreeMap, path::PathBuf, str::FromStr};
 
 use crate::Config;
+use ethers_core::types::Address;
 use ethers_solc::{
     error::SolcError,
     remappings::{Remapping, RemappingError},
@@ -89,6 +90,8 @@ pub fn remappings_from_env_var(env_var: &str) -> Option<Result<Vec<Remapping>, R
 /// Parses all libraries in the form of
 /// `<file>:<lib>:<addr>`
 ///
+/// Returns an error if any of the parts is missing or if `<addr>` is not a valid address.
+///
 /// # Example
 ///
 /// ```
@@ -103,23 +106,29 @@ pub fn parse_libraries(
 ) -> Result<BTreeMap<String, BTreeMap<String, String>>, SolcError> {
     let mut libraries = BTreeMap::default();
     for lib in libs {
-        let mut items = lib.split(':');
-        let file = items
-            .next()
-            .ok_or_else(|| SolcError::msg(format!("failed to parse invalid library: {}", lib)))?;
-        let lib = items
-            .next()
-            .ok_or_else(|| SolcError::msg(format!("failed to parse invalid library: {}", lib)))?;
-        let addr = items
-            .next()
-            .ok_or_else(|| SolcError::msg(format!("failed to parse invalid library: {}", lib)))?;
+        let invalid = || {
+            SolcError::msg(format!(
+                "failed to parse invalid library `{}`, expected `<file>:<lib>:<address>`",
+                lib
+            ))
+        };
+        let mut items = lib.trim().split(':');
+        let file = items.next().filter(|file| !file.is_empty()).ok_or_else(invalid)?;
+        let name = items.next().filter(|name| !name.is_empty()).ok_or_else(invalid)?;
+        let addr = items.next().ok_or_else(invalid)?;
         if items.next().is_some() {
-            return Err(SolcError::msg(format!("failed to parse invalid library: {}", lib)))
+            return Err(invalid())
+        }
+        if Address::from_str(addr.trim_start_matches("0x")).is_err() {
+            return Err(SolcError::msg(format!(
+                "failed to parse invalid library `{}`, `{}` is not a valid address",
+                lib, addr
+            )))
         }
         libraries
             .entry(file.to_string())
             .or_insert_with(BTreeMap::default)
-            .insert(lib.to_string(), addr.to_string());
+            .insert(name.to_string(), addr.to_string());
     }
     Ok(libraries)
 }
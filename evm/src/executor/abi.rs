@@ -17,18 +17,39 @@ ethers::contract::abigen!(
             roll(uint256)
             warp(uint256)
             fee(uint256)
+            chainId(uint256)
+            coinbase(address)
+            difficulty(uint256)
             store(address,bytes32,bytes32)
             load(address,bytes32)(bytes32)
             ffi(string[])(bytes)
             addr(uint256)(address)
+            deriveKey(string,uint32)(uint256)
+            deriveKey(string,string,uint32)(uint256)
             sign(uint256,bytes32)(uint8,bytes32,bytes32)
             prank(address)
             startPrank(address)
             prank(address,address)
             startPrank(address,address)
+            prank(address,address,bool)
+            startPrank(address,address,bool)
             stopPrank()
+            broadcast()
+            startBroadcast()
+            stopBroadcast()
             deal(address,uint256)
+            setNonce(address,uint64)
+            getNonce(address)(uint64)
             etch(address,bytes)
+            getForkCode(address)(bytes)
+            getForkStorageAt(address,bytes32)(bytes32)
+            createFork(string,uint256)(uint256)
+            createFork(string)(uint256)
+            selectFork(uint256)
+            activeFork()(uint256)
+            rollFork(uint256)
+            makePersistent(address)
+            transact(bytes32)
             expectRevert()
             expectRevert(bytes)
             expectRevert(bytes4)
@@ -38,9 +59,28 @@ ethers::contract::abigen!(
             mockCall(address,bytes,bytes)
             clearMockedCalls()
             expectCall(address,bytes)
+            expectCall(address,uint256,bytes)
             getCode(string)
+            readFile(string)(string)
+            readLine(string)(string)
+            writeFile(string,string)
+            removeFile(string)
             label(address,string)
+            toString(address)(string)
+            toString(bytes)(string)
+            toString(bytes32)(string)
+            toString(bool)(string)
+            toString(uint256)(string)
+            toString(int256)(string)
             assume(bool)
+            snapshot()(uint256)
+            revertTo(uint256)(bool)
+            envBool(string)(bool)
+            envUint(string)(uint256)
+            envInt(string)(int256)
+            envAddress(string)(address)
+            envBytes32(string)(bytes32)
+            envString(string)(string)
     ]"#,
 );
 pub use hevm_mod::{HEVMCalls, HEVM_ABI};
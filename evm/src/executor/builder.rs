@@ -1,11 +1,16 @@
 use ethers::prelude::Provider;
+use foundry_config::{FsPermissions, RpcEndpoints};
 use revm::{
     db::{DatabaseRef, EmptyDB},
     Env, SpecId,
 };
 use std::path::PathBuf;
 
-use super::{fork::SharedBackend, inspector::InspectorStackConfig, Executor};
+use super::{
+    fork::{is_ws_url, SharedBackend},
+    inspector::{InspectorStackConfig, Precompiles},
+    Executor,
+};
 
 use ethers::types::{H160, H256, U256};
 
@@ -39,7 +44,9 @@ impl Fork {
     /// Initialises the Storage Backend
     ///
     /// If configured, then this will initialise the backend with the storage cache
-    pub fn into_backend(self, env: &Env) -> SharedBackend {
+    ///
+    /// Fails if the connection to a WebSocket endpoint can not be established
+    pub fn into_backend(self, env: &Env) -> eyre::Result<SharedBackend> {
         let Fork { cache_path, url, pin_block, chain_id } = self;
 
         let host = Url::parse(&url)
@@ -47,8 +54,6 @@ impl Fork {
             .and_then(|url| url.host().map(|host| host.to_string()))
             .unwrap_or_else(|| url.clone());
 
-        let provider = Provider::try_from(url).expect("Failed to establish provider");
-
         let mut meta =
             BlockchainDbMeta { cfg_env: env.cfg.clone(), block_env: env.block.clone(), host };
 
@@ -60,20 +65,43 @@ impl Fork {
 
         let db = BlockchainDb::new(meta, cache_path);
 
-        SharedBackend::new(provider, db, pin_block.map(Into::into))
+        if is_ws_url(&url) {
+            return SharedBackend::with_ws(url, db, pin_block.map(Into::into))
+        }
+        let provider = Provider::try_from(url.as_str()).expect("Failed to establish provider");
+        Ok(if url.starts_with("http") {
+            SharedBackend::with_batching(provider, db, pin_block.map(Into::into), url)
+        } else {
+            SharedBackend::new(provider, db, pin_block.map(Into::into))
+        })
     }
 }
 
+/// The database backend of an [Executor]
+///
+/// Cloning a forked backend is cheap and all clones share the same [SharedBackend], so it can be
+/// used by multiple executors to avoid fetching the same state more than once.
 pub enum Backend {
     Simple(EmptyDB),
     Forked(SharedBackend),
 }
 
+impl Clone for Backend {
+    fn clone(&self) -> Self {
+        match self {
+            Backend::Simple(_) => Backend::Simple(EmptyDB()),
+            Backend::Forked(inner) => Backend::Forked(inner.clone()),
+        }
+    }
+}
+
 impl Backend {
     /// Instantiates a new backend union based on whether there was or not a fork url specified
-    fn new(fork: Option<Fork>, env: &Env) -> Self {
+    pub fn new(fork: Option<Fork>, env: &Env) -> Self {
         if let Some(fork) = fork {
-            Backend::Forked(fork.into_backend(env))
+            Backend::Forked(
+                fork.into_backend(env).unwrap_or_else(|err| panic!("could not fork: {}", err)),
+            )
         } else {
             Backend::Simple(EmptyDB())
         }
@@ -124,6 +152,34 @@ impl ExecutorBuilder {
         self
     }
 
+    /// Grants the file cheatcodes access to the paths of the [FsPermissions]
+    #[must_use]
+    pub fn with_fs_permissions(mut self, fs_permissions: FsPermissions) -> Self {
+        self.inspector_config.fs_permissions = fs_permissions;
+        self
+    }
+
+    /// Lets `createFork` resolve the aliases of the [RpcEndpoints]
+    #[must_use]
+    pub fn with_rpc_endpoints(mut self, rpc_endpoints: RpcEndpoints) -> Self {
+        self.inspector_config.rpc_endpoints = rpc_endpoints;
+        self
+    }
+
+    /// Registers custom precompiles, see [Precompiles]
+    #[must_use]
+    pub fn with_precompiles(mut self, precompiles: Precompiles) -> Self {
+        self.inspector_config.precompiles = precompiles;
+        self
+    }
+
+    /// Limits the memory of each call frame to `limit` bytes, `0` disables the limit
+    #[must_use]
+    pub fn with_memory_limit(mut self, limit: u64) -> Self {
+        self.inspector_config.memory_limit = (limit > 0).then(|| limit);
+        self
+    }
+
     /// Enables tracing
     #[must_use]
     pub fn with_tracing(mut self) -> Self {
@@ -131,6 +187,14 @@ impl ExecutorBuilder {
         self
     }
 
+    /// Enables tracing, including the storage reads and writes of each call
+    #[must_use]
+    pub fn with_storage_tracing(mut self) -> Self {
+        self.inspector_config.tracing = true;
+        self.inspector_config.storage_tracing = true;
+        self
+    }
+
     /// Enables the debugger
     #[must_use]
     pub fn with_debugger(mut self) -> Self {
@@ -162,7 +226,15 @@ impl ExecutorBuilder {
 
     /// Builds the executor as configured.
     pub fn build(self) -> Executor<Backend> {
-        let db = Backend::new(self.fork, &self.env);
+        let db = Backend::new(self.fork.clone(), &self.env);
+        self.build_with_backend(db)
+    }
+
+    /// Builds the executor on top of an existing [Backend], ignoring the configured fork.
+    pub fn build_with_backend(mut self, db: Backend) -> Executor<Backend> {
+        if let Backend::Forked(backend) = &db {
+            self.inspector_config.fork = Some(backend.clone());
+        }
         Executor::new(db, self.env, self.inspector_config)
     }
 }
@@ -1,11 +1,12 @@
 //! Smart caching and deduplication of requests when using a forking provider
 use revm::{db::DatabaseRef, AccountInfo, KECCAK_EMPTY};
 
+use super::init::{is_connection_closed_error, is_pruned_state_error, pruned_state_message};
 use crate::executor::fork::BlockchainDb;
 use ethers::{
     core::abi::ethereum_types::BigEndianHash,
-    providers::Middleware,
-    types::{Address, BlockId, Bytes, H160, H256, U256},
+    providers::{Middleware, Provider, Ws},
+    types::{Address, BlockId, BlockNumber, Bytes, Transaction, H160, H256, U256},
     utils::keccak256,
 };
 use foundry_utils::RuntimeOrHandle;
@@ -21,18 +22,24 @@ use std::{
     pin::Pin,
     sync::mpsc::{channel as oneshot_channel, Sender as OneshotSender},
 };
-use tracing::trace;
+use tracing::{error, trace};
 
 type AccountFuture<Err> =
     Pin<Box<dyn Future<Output = (Result<(U256, U256, Bytes), Err>, Address)> + Send>>;
 type StorageFuture<Err> = Pin<Box<dyn Future<Output = (Result<U256, Err>, Address, U256)> + Send>>;
+type StorageBatchFuture<Err> =
+    Pin<Box<dyn Future<Output = Vec<(Result<U256, Err>, Address, U256)>> + Send>>;
 type BlockHashFuture<Err> = Pin<Box<dyn Future<Output = (Result<H256, Err>, u64)> + Send>>;
+/// Fetches a transaction and sends it to its listener
+type TransactionFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
 
-/// Request variants that are executed by the provider
+/// Request variants that are executed by the provider, with the block the state is fetched from
 enum ProviderRequest<Err> {
-    Account(AccountFuture<Err>),
-    Storage(StorageFuture<Err>),
+    Account(AccountFuture<Err>, Option<BlockId>),
+    Storage(StorageFuture<Err>, Option<BlockId>),
+    StorageBatch(StorageBatchFuture<Err>, Option<BlockId>),
     BlockHash(BlockHashFuture<Err>),
+    Transaction(TransactionFuture),
 }
 
 /// The Request type the Backend listens for
@@ -41,6 +48,8 @@ enum BackendRequest {
     Basic(Address, OneshotSender<AccountInfo>),
     Storage(Address, U256, OneshotSender<U256>),
     BlockHash(u64, OneshotSender<H256>),
+    Transaction(H256, OneshotSender<eyre::Result<Transaction>>),
+    Roll(u64, OneshotSender<SharedBackend>),
 }
 
 /// Handles an internal provider and listens for requests.
@@ -67,11 +76,20 @@ struct BackendHandler<M: Middleware> {
     /// The block to fetch data from.
     // This is an `Option` so that we can have less code churn in the functions below
     block_id: Option<BlockId>,
+    /// Sends storage requests that arrive at the same time as a single batch, if enabled
+    batcher: Option<StorageBatcher>,
+    /// Storage slots that are waiting to be requested in the next batch
+    queued_storage: Vec<(Address, U256)>,
+    /// Whether the node was reported to not have the state of the block, so it is only reported
+    /// once. From then on the state is fetched from the latest block instead
+    pruned_state_reported: bool,
+    /// Whether the connection to the node was reported to be closed, so it is only reported once
+    connection_closed_reported: bool,
 }
 
 impl<M> BackendHandler<M>
 where
-    M: Middleware + Clone + 'static,
+    M: Middleware + Clone + Unpin + 'static,
 {
     fn new(
         provider: M,
@@ -79,6 +97,7 @@ where
         rx: Receiver<BackendRequest>,
         shutdown: Receiver<OneshotSender<()>>,
         block_id: Option<BlockId>,
+        batcher: Option<StorageBatcher>,
     ) -> Self {
         Self {
             provider,
@@ -90,7 +109,61 @@ where
             incoming: rx.fuse(),
             shutdown: shutdown.fuse(),
             block_id,
+            batcher,
+            queued_storage: Default::default(),
+            pruned_state_reported: false,
+            connection_closed_reported: false,
+        }
+    }
+
+    /// Reports an error of the provider. Failed requests resolve to default values, so errors
+    /// because the node does not have the state of the block or because the connection to the
+    /// node was closed are reported loudly, they would otherwise surface as confusing test
+    /// failures.
+    fn on_error(&mut self, err: &eyre::Error, request: impl FnOnce() -> String) {
+        if !self.connection_closed_reported && is_connection_closed_error(err) {
+            self.connection_closed_reported = true;
+            error!(
+                target: "backendhandler",
+                "the connection to the fork endpoint was closed, the values that are not cached \
+                 yet resolve to default values: {}",
+                err
+            );
+        }
+        if !self.pruned_state_reported && is_pruned_state_error(err) {
+            self.pruned_state_reported = true;
+            let block = match self.block_id {
+                Some(BlockId::Number(BlockNumber::Number(number))) => number.as_u64(),
+                _ => self.db.meta().read().block_env.number.as_u64(),
+            };
+            error!(
+                target: "backendhandler",
+                "{}: {}. The values that are not cached yet are fetched from the latest block",
+                pruned_state_message(block),
+                err
+            );
+        }
+        trace!(target: "backendhandler", "Failed to get {}: {}", request(), err);
+    }
+
+    /// The block the state is fetched from, the latest block if the node does not have the state
+    /// of the pinned block
+    fn state_block_id(&self) -> Option<BlockId> {
+        if self.pruned_state_reported {
+            None
+        } else {
+            self.block_id
+        }
+    }
+
+    /// Whether a request for the state of `block_id` that failed with `err` is sent again for the
+    /// latest block, because the node does not have the state of the pinned block
+    fn retry_at_latest(&mut self, err: &eyre::Error, block_id: Option<BlockId>) -> bool {
+        if block_id.is_none() || !is_pruned_state_error(err) {
+            return false
         }
+        self.on_error(err, || "the state of the pinned block".to_string());
+        true
     }
 
     /// handle the request in queue in the future.
@@ -139,6 +212,36 @@ where
                     self.request_account_storage(addr, idx, sender);
                 }
             }
+            BackendRequest::Transaction(tx_hash, sender) => {
+                trace!(target: "backendhandler", "preparing transaction request, hash={:?}", tx_hash);
+                let provider = self.provider.clone();
+                let fut = Box::pin(async move {
+                    let tx = provider
+                        .get_transaction(tx_hash)
+                        .await
+                        .map_err(|err| eyre::eyre!(err))
+                        .and_then(|tx| {
+                            tx.ok_or_else(|| eyre::eyre!("transaction {:?} not found", tx_hash))
+                        });
+                    let _ = sender.send(tx);
+                });
+                self.pending_requests.push(ProviderRequest::Transaction(fut));
+            }
+            BackendRequest::Roll(number, sender) => {
+                trace!(target: "backendhandler", "rolling fork, number={}", number);
+                let mut meta = self.db.meta().read().clone();
+                meta.block_env.number = number.into();
+                // The state of the rolled block is only kept in memory, so it is never flushed to
+                // the cache of the pinned block
+                let db = BlockchainDb::new(meta, None);
+                let backend = SharedBackend::spawn(
+                    self.provider.clone(),
+                    db,
+                    Some(number.into()),
+                    self.batcher.clone(),
+                );
+                let _ = sender.send(backend);
+            }
         }
     }
 
@@ -154,28 +257,82 @@ where
                 entry.get_mut().push(listener);
             }
             Entry::Vacant(entry) => {
-                trace!(target: "backendhandler", "preparing storage request, address={:?}, idx={}", address, idx);
                 entry.insert(vec![listener]);
-                let provider = self.provider.clone();
-                let block_id = self.block_id;
-                let fut = Box::pin(async move {
-                    // serialize & deserialize back to U256
-                    let idx_req = H256::from_uint(&idx);
-                    let storage = provider.get_storage_at(address, idx_req, block_id).await;
-                    let storage =
-                        storage.map(|storage| storage.into_uint()).map_err(|err| eyre::eyre!(err));
-                    (storage, address, idx)
-                });
-                self.pending_requests.push(ProviderRequest::Storage(fut));
+                if self.batcher.is_some() {
+                    // sent with all other storage requests of this poll
+                    self.queued_storage.push((address, idx));
+                    return
+                }
+                self.pending_requests.push(self.get_storage_req(address, idx));
             }
         }
     }
 
+    /// returns the future that fetches the storage value
+    fn get_storage_req(&self, address: Address, idx: U256) -> ProviderRequest<eyre::Error> {
+        trace!(target: "backendhandler", "preparing storage request, address={:?}, idx={}", address, idx);
+        let provider = self.provider.clone();
+        let block_id = self.state_block_id();
+        let fut = Box::pin(async move {
+            // serialize & deserialize back to U256
+            let idx_req = H256::from_uint(&idx);
+            let storage = provider.get_storage_at(address, idx_req, block_id).await;
+            let storage =
+                storage.map(|storage| storage.into_uint()).map_err(|err| eyre::eyre!(err));
+            (storage, address, idx)
+        });
+        ProviderRequest::Storage(fut, block_id)
+    }
+
+    /// Sends all queued storage requests as a single batch request
+    fn send_storage_batch(&mut self) {
+        let batcher = match self.batcher.clone() {
+            Some(batcher) if !self.queued_storage.is_empty() => batcher,
+            _ => return,
+        };
+        let slots = std::mem::take(&mut self.queued_storage);
+        trace!(target: "backendhandler", "preparing storage batch request, slots={}", slots.len());
+        let block_id = self.state_block_id();
+        let fut = Box::pin(async move { batcher.get_storage_at(slots, block_id).await });
+        self.pending_requests.push(ProviderRequest::StorageBatch(fut, block_id));
+    }
+
+    /// Inserts the fetched storage value of `block_id` into the cache and notifies all listeners
+    fn on_storage(
+        &mut self,
+        resp: eyre::Result<U256>,
+        addr: Address,
+        idx: U256,
+        block_id: Option<BlockId>,
+    ) {
+        let value = match resp {
+            Ok(value) => value,
+            Err(err) if self.retry_at_latest(&err, block_id) => {
+                self.pending_requests.push(self.get_storage_req(addr, idx));
+                return
+            }
+            Err(err) => {
+                self.on_error(&err, || format!("storage for {} at {}", addr, idx));
+                Default::default()
+            }
+        };
+
+        // update the cache
+        self.db.storage().write().entry(addr).or_default().insert(idx, value);
+
+        // notify all listeners
+        if let Some(listeners) = self.storage_requests.remove(&(addr, idx)) {
+            listeners.into_iter().for_each(|l| {
+                let _ = l.send(value);
+            })
+        }
+    }
+
     /// returns the future that fetches the account data
     fn get_account_req(&self, address: Address) -> ProviderRequest<eyre::Error> {
         trace!(target: "backendhandler", "preparing account request, address={:?}", address);
         let provider = self.provider.clone();
-        let block_id = self.block_id;
+        let block_id = self.state_block_id();
         let fut = Box::pin(async move {
             let balance = provider.get_balance(address, block_id);
             let nonce = provider.get_transaction_count(address, block_id);
@@ -183,7 +340,7 @@ where
             let resp = tokio::try_join!(balance, nonce, code).map_err(|err| eyre::eyre!(err));
             (resp, address)
         });
-        ProviderRequest::Account(fut)
+        ProviderRequest::Account(fut, block_id)
     }
 
     /// process a request for an account
@@ -239,18 +396,26 @@ where
         while let Poll::Ready(Some(req)) = Pin::new(&mut pin.incoming).poll_next(cx) {
             pin.on_request(req)
         }
+        pin.send_storage_batch();
 
         // poll all requests in progress
         for n in (0..pin.pending_requests.len()).rev() {
             let mut request = pin.pending_requests.swap_remove(n);
             match &mut request {
-                ProviderRequest::Account(fut) => {
+                ProviderRequest::Account(fut, block_id) => {
                     if let Poll::Ready((resp, addr)) = fut.poll_unpin(cx) {
                         // get the response
-                        let (balance, nonce, code) = resp.unwrap_or_else(|_| {
-                            trace!("Failed to get account for {}", addr);
-                            Default::default()
-                        });
+                        let (balance, nonce, code) = match resp {
+                            Ok(resp) => resp,
+                            Err(err) if pin.retry_at_latest(&err, *block_id) => {
+                                pin.pending_requests.push(pin.get_account_req(addr));
+                                continue
+                            }
+                            Err(err) => {
+                                pin.on_error(&err, || format!("account for {}", addr));
+                                Default::default()
+                            }
+                        };
 
                         // conver it to revm-style types
                         let (code, code_hash) = if !code.0.is_empty() {
@@ -272,25 +437,26 @@ where
                         continue
                     }
                 }
-                ProviderRequest::Storage(fut) => {
+                ProviderRequest::Storage(fut, block_id) => {
                     if let Poll::Ready((resp, addr, idx)) = fut.poll_unpin(cx) {
-                        let value = resp.unwrap_or_else(|_| {
-                            trace!("Failed to get storage for {} at {}", addr, idx);
-                            Default::default()
-                        });
-
-                        // update the cache
-                        pin.db.storage().write().entry(addr).or_default().insert(idx, value);
-
-                        // notify all listeners
-                        if let Some(listeners) = pin.storage_requests.remove(&(addr, idx)) {
-                            listeners.into_iter().for_each(|l| {
-                                let _ = l.send(value);
-                            })
+                        pin.on_storage(resp, addr, idx, *block_id);
+                        continue
+                    }
+                }
+                ProviderRequest::StorageBatch(fut, block_id) => {
+                    if let Poll::Ready(resps) = fut.poll_unpin(cx) {
+                        let block_id = *block_id;
+                        for (resp, addr, idx) in resps {
+                            pin.on_storage(resp, addr, idx, block_id);
                         }
                         continue
                     }
                 }
+                ProviderRequest::Transaction(fut) => {
+                    if fut.poll_unpin(cx).is_ready() {
+                        continue
+                    }
+                }
                 ProviderRequest::BlockHash(fut) => {
                     if let Poll::Ready((block_hash, number)) = fut.poll_unpin(cx) {
                         let value = block_hash.unwrap_or_else(|_| {
@@ -318,8 +484,11 @@ where
         // the handler is finished if the request channel was closed and all requests are processed
         if pin.incoming.is_done() && pin.pending_requests.is_empty() {
             if let Poll::Ready(Some(ack)) = Pin::new(&mut pin.shutdown).poll_next(cx) {
-                // effectively flushing the cached storage if any
-                pin.db.cache().flush();
+                // effectively flushing the cached storage if any, unless some of it is the state
+                // of the latest block instead of the pinned block
+                if !pin.pruned_state_reported {
+                    pin.db.cache().flush();
+                }
                 // signaling back
                 let _ = ack.send(());
             }
@@ -386,14 +555,73 @@ impl SharedBackend {
     ///
     /// NOTE: this should be called with `Arc<Provider>`
     pub fn new<M>(provider: M, db: BlockchainDb, pin_block: Option<BlockId>) -> Self
+    where
+        M: Middleware + Unpin + 'static + Clone,
+    {
+        Self::spawn(provider, db, pin_block, None)
+    }
+
+    /// Same as [SharedBackend::new()], but storage requests that arrive at the same time are sent
+    /// to the http `url` as a single JSON-RPC batch request, instead of one request per slot.
+    pub fn with_batching<M>(
+        provider: M,
+        db: BlockchainDb,
+        pin_block: Option<BlockId>,
+        url: impl Into<String>,
+    ) -> Self
+    where
+        M: Middleware + Unpin + 'static + Clone,
+    {
+        Self::spawn(provider, db, pin_block, Some(StorageBatcher::new(url)))
+    }
+
+    /// Same as [SharedBackend::new()], but the provider keeps a persistent WebSocket connection to
+    /// the `ws://` or `wss://` `url`.
+    ///
+    /// Fails if the connection can not be established. The connection is not re-established if
+    /// it is closed later on, which is reported once as an error.
+    pub fn with_ws(
+        url: impl Into<String>,
+        db: BlockchainDb,
+        pin_block: Option<BlockId>,
+    ) -> eyre::Result<Self> {
+        let url = url.into();
+        // the connection is served by a task of the runtime of the handler, so it lives as long
+        // as the handler
+        let rt = RuntimeOrHandle::new();
+        let provider = rt
+            .block_on(Provider::<Ws>::connect(url.as_str()))
+            .map_err(|err| eyre::eyre!("failed to connect to {}: {}", url, err))?;
+        Ok(Self::spawn_on(rt, provider, db, pin_block, None))
+    }
+
+    fn spawn<M>(
+        provider: M,
+        db: BlockchainDb,
+        pin_block: Option<BlockId>,
+        batcher: Option<StorageBatcher>,
+    ) -> Self
+    where
+        M: Middleware + Unpin + 'static + Clone,
+    {
+        Self::spawn_on(RuntimeOrHandle::new(), provider, db, pin_block, batcher)
+    }
+
+    fn spawn_on<M>(
+        rt: RuntimeOrHandle,
+        provider: M,
+        db: BlockchainDb,
+        pin_block: Option<BlockId>,
+        batcher: Option<StorageBatcher>,
+    ) -> Self
     where
         M: Middleware + Unpin + 'static + Clone,
     {
         let (backend, backend_rx) = channel(1);
         let (shutdown, shutdown_rx) = channel(1);
-        let handler = BackendHandler::new(provider, db, backend_rx, shutdown_rx, pin_block);
+        let handler =
+            BackendHandler::new(provider, db, backend_rx, shutdown_rx, pin_block, batcher);
         // spawn the provider handler to background
-        let rt = RuntimeOrHandle::new();
         trace!(target: "backendhandler", "spawning Backendhandler");
         std::thread::spawn(move || match rt {
             RuntimeOrHandle::Runtime(runtime) => runtime.block_on(handler),
@@ -423,6 +651,102 @@ impl SharedBackend {
         self.backend.clone().try_send(req).map_err(|e| eyre::eyre!("{:?}", e))?;
         Ok(rx.recv()?)
     }
+
+    /// Fetches the transaction with the hash `tx_hash` from the provider
+    pub fn get_transaction(&self, tx_hash: H256) -> eyre::Result<Transaction> {
+        let (sender, rx) = oneshot_channel();
+        let req = BackendRequest::Transaction(tx_hash, sender);
+        self.backend.clone().try_send(req).map_err(|e| eyre::eyre!("{:?}", e))?;
+        rx.recv()?
+    }
+
+    /// Returns a backend of the same chain that fetches the state at block `number`.
+    ///
+    /// The new backend has its own handler, which only keeps its state in memory.
+    pub fn roll(&self, number: u64) -> eyre::Result<SharedBackend> {
+        let (sender, rx) = oneshot_channel();
+        let req = BackendRequest::Roll(number, sender);
+        self.backend.clone().try_send(req).map_err(|e| eyre::eyre!("{:?}", e))?;
+        Ok(rx.recv()?)
+    }
+}
+
+/// Fetches multiple storage slots with a single JSON-RPC batch request
+#[derive(Debug, Clone)]
+struct StorageBatcher {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl StorageBatcher {
+    fn new(url: impl Into<String>) -> Self {
+        Self { client: reqwest::Client::new(), url: url.into() }
+    }
+
+    /// Requests all `slots` via `eth_getStorageAt` in a single batch, the results are in the same
+    /// order as the `slots`
+    async fn get_storage_at(
+        &self,
+        slots: Vec<(Address, U256)>,
+        block_id: Option<BlockId>,
+    ) -> Vec<(eyre::Result<U256>, Address, U256)> {
+        let block = serde_json::to_value(block_id.unwrap_or(BlockId::Number(BlockNumber::Latest)))
+            .unwrap_or_default();
+        let batch = slots
+            .iter()
+            .enumerate()
+            .map(|(id, (address, idx))| {
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "method": "eth_getStorageAt",
+                    "params": [address, H256::from_uint(idx), block],
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let resp = async {
+            let resp = self.client.post(&self.url).json(&batch).send().await?;
+            Ok::<_, eyre::Error>(resp.json::<Vec<serde_json::Value>>().await?)
+        }
+        .await;
+
+        match resp {
+            Ok(resp) => {
+                let mut values = resp
+                    .into_iter()
+                    .filter_map(|resp| {
+                        let id = resp.get("id")?.as_u64()? as usize;
+                        let value = resp
+                            .get("result")
+                            .and_then(|value| value.as_str())
+                            .and_then(|value| {
+                                U256::from_str_radix(value.trim_start_matches("0x"), 16).ok()
+                            })
+                            .ok_or_else(|| eyre::eyre!("invalid response {}", resp));
+                        Some((id, value))
+                    })
+                    .collect::<HashMap<_, _>>();
+                slots
+                    .into_iter()
+                    .enumerate()
+                    .map(|(id, (address, idx))| {
+                        let value = values
+                            .remove(&id)
+                            .unwrap_or_else(|| Err(eyre::eyre!("missing response")));
+                        (value, address, idx)
+                    })
+                    .collect()
+            }
+            Err(err) => {
+                trace!(target: "backendhandler", "storage batch request failed: {}", err);
+                slots
+                    .into_iter()
+                    .map(|(address, idx)| (Err(eyre::eyre!("{}", err)), address, idx))
+                    .collect()
+            }
+        }
+    }
 }
 
 impl DatabaseRef for SharedBackend {
@@ -471,6 +795,7 @@ mod tests {
 
     use super::*;
     const ENDPOINT: &str = "https://mainnet.infura.io/v3/c60b0bb42f8a4c6481ecd229eddaca27";
+    const WS_ENDPOINT: &str = "wss://mainnet.infura.io/ws/v3/c60b0bb42f8a4c6481ecd229eddaca27";
 
     #[test]
     fn shared_backend() {
@@ -515,6 +840,78 @@ mod tests {
         assert_eq!(slots.len() as u64, max_slots);
     }
 
+    #[test]
+    fn shared_ws_backend() {
+        let meta = BlockchainDbMeta {
+            cfg_env: Default::default(),
+            block_env: Default::default(),
+            host: WS_ENDPOINT.to_string(),
+        };
+
+        let db = BlockchainDb::new(meta, None);
+        let backend = SharedBackend::with_ws(WS_ENDPOINT, db.clone(), None).unwrap();
+
+        // some rng contract from etherscan
+        let address: Address = "63091244180ae240c87d1f528f5f269134cb07b3".parse().unwrap();
+
+        let idx = U256::from(0u64);
+        let value = backend.storage(address, idx);
+        let account = backend.basic(address);
+
+        let mem_acc = db.accounts().read().get(&address).unwrap().clone();
+        assert_eq!(account.balance, mem_acc.balance);
+        assert_eq!(account.nonce, mem_acc.nonce);
+        let slots = db.storage().read().get(&address).unwrap().clone();
+        assert_eq!(slots.len(), 1);
+        assert_eq!(slots.get(&idx).copied().unwrap(), value);
+
+        let num = U256::from(10u64);
+        let hash = backend.block_hash(num);
+        let mem_hash = *db.block_hashes().read().get(&num.as_u64()).unwrap();
+        assert_eq!(hash, mem_hash);
+    }
+
+    #[test]
+    fn ws_backend_fails_without_connection() {
+        let meta = BlockchainDbMeta {
+            cfg_env: Default::default(),
+            block_env: Default::default(),
+            host: "localhost".to_string(),
+        };
+
+        let db = BlockchainDb::new(meta, None);
+        assert!(SharedBackend::with_ws("ws://localhost:1", db, None).is_err());
+    }
+
+    #[test]
+    fn can_batch_storage_requests() {
+        let provider = Provider::<Http>::try_from(ENDPOINT).unwrap();
+        let meta = BlockchainDbMeta {
+            cfg_env: Default::default(),
+            block_env: Default::default(),
+            host: ENDPOINT.to_string(),
+        };
+
+        let db = BlockchainDb::new(meta, None);
+        let backend = SharedBackend::with_batching(Arc::new(provider), db.clone(), None, ENDPOINT);
+
+        // some rng contract from etherscan
+        let address: Address = "63091244180ae240c87d1f528f5f269134cb07b3".parse().unwrap();
+
+        let max_slots = 5;
+        let handles = (0..max_slots)
+            .map(|i| {
+                let backend = backend.clone();
+                std::thread::spawn(move || backend.storage(address, U256::from(i)))
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        let slots = db.storage().read().get(&address).unwrap().clone();
+        assert_eq!(slots.len() as u64, max_slots);
+    }
+
     #[test]
     fn can_read_cache() {
         let cache_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test-data/storage.json");
@@ -537,7 +934,7 @@ mod tests {
             chain_id: 1,
         };
 
-        let backend = fork.into_backend(&env);
+        let backend = fork.into_backend(&env).unwrap();
 
         // some rng contract from etherscan
         let address: Address = "63091244180ae240c87d1f528f5f269134cb07b3".parse().unwrap();
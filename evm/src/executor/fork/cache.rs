@@ -30,14 +30,15 @@ impl BlockchainDb {
     ///   - `cache_path` is `None`
     ///   - the file the `cache_path` points to, does not exist
     ///   - the file contains malformed data, or if it couldn't be read
-    ///   - the provided `meta` differs from [BlockchainDbMeta] that's stored on disk
+    ///   - the provided `meta` refers to a different block than the [BlockchainDbMeta] that's
+    ///     stored on disk, see [BlockchainDbMeta::is_same_block()]
     pub fn new(meta: BlockchainDbMeta, cache_path: Option<PathBuf>) -> Self {
         // read cache and check if metadata matches
         let cache = cache_path
             .as_ref()
             .and_then(|p| {
                 JsonBlockCacheDB::load(p).ok().filter(|cache| {
-                    if meta != *cache.meta().read() {
+                    if !meta.is_same_block(&cache.meta().read()) {
                         warn!(target:"cache", "non-matching block metadata");
                         false
                     } else {
@@ -45,6 +46,11 @@ impl BlockchainDb {
                     }
                 })
             })
+            .map(|cache| {
+                // the cached state is valid for the current config as well
+                *cache.meta().write() = meta.clone();
+                cache
+            })
             .unwrap_or_else(|| JsonBlockCacheDB::new(Arc::new(RwLock::new(meta)), cache_path));
 
         Self { db: Arc::clone(cache.db()), meta: Arc::clone(cache.meta()), cache: Arc::new(cache) }
@@ -84,6 +90,17 @@ pub struct BlockchainDbMeta {
     pub host: String,
 }
 
+impl BlockchainDbMeta {
+    /// Returns `true` if both refer to the same block on the same chain.
+    ///
+    /// The state of a block does not depend on the execution config or on the endpoint it was
+    /// fetched from, so a cache can be reused as long as this holds.
+    pub fn is_same_block(&self, other: &Self) -> bool {
+        self.cfg_env.chain_id == other.cfg_env.chain_id &&
+            self.block_env.number == other.block_env.number
+    }
+}
+
 /// In Memory cache containing all fetched accounts and storage slots
 /// and their values from RPC
 #[derive(Debug, Default)]
@@ -225,3 +242,41 @@ impl<'de> Deserialize<'de> for JsonBlockCacheData {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(chain_id: u64, block: u64, host: &str) -> BlockchainDbMeta {
+        let mut meta = BlockchainDbMeta {
+            cfg_env: Default::default(),
+            block_env: Default::default(),
+            host: host.to_string(),
+        };
+        meta.cfg_env.chain_id = chain_id.into();
+        meta.block_env.number = block.into();
+        meta
+    }
+
+    #[test]
+    fn can_reuse_cache_of_same_block() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("1/14435000/storage.json");
+
+        let db = BlockchainDb::new(meta(1, 14435000, "mainnet.infura.io"), Some(path.clone()));
+        db.accounts().write().insert(Address::zero(), Default::default());
+        db.cache().flush();
+        assert!(path.exists());
+
+        // same block, but fetched from a different endpoint and with a different spec
+        let mut other = meta(1, 14435000, "eth-mainnet.alchemyapi.io");
+        other.cfg_env.spec_id = revm::SpecId::BERLIN;
+        let db = BlockchainDb::new(other.clone(), Some(path.clone()));
+        assert!(db.accounts().read().contains_key(&Address::zero()));
+        assert_eq!(*db.meta().read(), other);
+
+        // a different block must not be served from the cache
+        let db = BlockchainDb::new(meta(1, 14435001, "mainnet.infura.io"), Some(path));
+        assert!(db.accounts().read().is_empty());
+    }
+}
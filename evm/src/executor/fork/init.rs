@@ -1,5 +1,102 @@
-use ethers::{providers::Middleware, types::Address};
+use ethers::{
+    providers::{JsonRpcClient, Middleware, Provider, Ws},
+    types::Address,
+};
 use revm::{BlockEnv, CfgEnv, Env, TxEnv};
+use std::fmt::Display;
+
+/// The errors of nodes that no longer have the state of a block, e.g. non-archive nodes that only
+/// keep the state of the latest 128 blocks.
+///
+/// These are specific to missing state, e.g. not `header not found`, which nodes also return for
+/// blocks that do not exist yet.
+const PRUNED_STATE_ERRORS: &[&str] =
+    &["missing trie node", "historical state", "state not available", "state is not available"];
+
+/// Whether `err` means the node does not have the state of the requested block
+pub fn is_pruned_state_error(err: &impl Display) -> bool {
+    let err = err.to_string().to_lowercase();
+    PRUNED_STATE_ERRORS.iter().any(|pruned| err.contains(pruned))
+}
+
+/// The errors of a WebSocket provider whose connection to the node was closed
+const CONNECTION_CLOSED_ERRORS: &[&str] = &[
+    "websocket closed unexpectedly",
+    "connection closed",
+    "trying to work with closed connection",
+    "oneshot canceled",
+];
+
+/// Whether `err` means the connection to the node was closed, so no further requests succeed
+pub fn is_connection_closed_error(err: &impl Display) -> bool {
+    let err = err.to_string().to_lowercase();
+    CONNECTION_CLOSED_ERRORS.iter().any(|closed| err.contains(closed))
+}
+
+/// The error message for a node that does not have the state of `block`
+pub fn pruned_state_message(block: u64) -> String {
+    format!(
+        "the fork endpoint does not have the state of block {}, it is likely not an archive \
+         node. Use an archive node, or fork a recent block (e.g. without a fork block number)",
+        block
+    )
+}
+
+/// Checks that the node has the state of `block`, so forking it does not fail later on when the
+/// state of an account is fetched
+pub async fn check_state_available<M: Middleware>(provider: &M, block: u64) -> eyre::Result<()> {
+    let latest = provider.get_block_number().await.map_err(|err| eyre::eyre!(err))?.as_u64();
+    if block > latest {
+        eyre::bail!("block {} is ahead of the latest block {} of the fork endpoint", block, latest)
+    }
+    match provider.get_balance(Address::zero(), Some(block.into())).await {
+        Err(err) if is_pruned_state_error(&err) => {
+            eyre::bail!("{}: {}", pruned_state_message(block), err)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Whether the fork endpoint at `url` is a WebSocket endpoint
+pub fn is_ws_url(url: &str) -> bool {
+    url.starts_with("ws://") || url.starts_with("wss://")
+}
+
+/// Initializes the environment of a fork of the chain at `url`, over http or a WebSocket
+/// connection for `ws://` and `wss://` urls.
+///
+/// Fails if the node does not have the state of the pinned block, or of the latest block if none
+/// is pinned.
+pub async fn fork_environment(
+    url: &str,
+    override_chain_id: Option<u64>,
+    pin_block: Option<u64>,
+    origin: Address,
+) -> eyre::Result<Env> {
+    async fn checked_environment<P: JsonRpcClient>(
+        provider: &Provider<P>,
+        override_chain_id: Option<u64>,
+        pin_block: Option<u64>,
+        origin: Address,
+    ) -> eyre::Result<Env> {
+        // Old blocks are only available on archive nodes, and some nodes only serve the state of
+        // the latest block once it is synced
+        let block = match pin_block {
+            Some(block) => block,
+            None => provider.get_block_number().await?.as_u64(),
+        };
+        check_state_available(provider, block).await?;
+        environment(provider, override_chain_id, pin_block, origin).await
+    }
+
+    if is_ws_url(url) {
+        let provider = Provider::<Ws>::connect(url).await?;
+        checked_environment(&provider, override_chain_id, pin_block, origin).await
+    } else {
+        let provider = Provider::try_from(url)?;
+        checked_environment(&provider, override_chain_id, pin_block, origin).await
+    }
+}
 
 /// Initializes a REVM block environment based on a forked
 /// ethereum provider.
@@ -43,3 +140,30 @@ pub async fn environment<M: Middleware>(
         },
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_detect_pruned_state_errors() {
+        assert!(is_pruned_state_error(&"(code: -32000, message: missing trie node 1a2b (path ))"));
+        assert!(is_pruned_state_error(&"Historical state not available"));
+        assert!(!is_pruned_state_error(&"header not found"));
+        assert!(!is_pruned_state_error(&"execution reverted"));
+    }
+
+    #[test]
+    fn can_detect_connection_closed_errors() {
+        assert!(is_connection_closed_error(&"Websocket closed unexpectedly"));
+        assert!(is_connection_closed_error(&"Trying to work with closed connection"));
+        assert!(!is_connection_closed_error(&"execution reverted"));
+    }
+
+    #[test]
+    fn can_detect_ws_urls() {
+        assert!(is_ws_url("ws://localhost:8546"));
+        assert!(is_ws_url("wss://mainnet.infura.io/ws/v3/key"));
+        assert!(!is_ws_url("https://mainnet.infura.io/v3/key"));
+    }
+}
@@ -2,7 +2,7 @@ mod backend;
 pub use backend::SharedBackend;
 
 mod init;
-pub use init::environment;
+pub use init::{environment, fork_environment, is_ws_url};
 
 mod cache;
 pub use cache::{BlockchainDb, BlockchainDbMeta, JsonBlockCacheDB};
@@ -8,7 +8,7 @@ use ethers::{
     types::{Address, H256, U256},
     utils::keccak256,
 };
-use revm::{Database, EVMData};
+use revm::{BlockEnv, Database, EVMData, SubRoutine};
 
 #[derive(Clone, Debug, Default)]
 pub struct Prank {
@@ -24,6 +24,9 @@ pub struct Prank {
     pub depth: u64,
     /// Whether or not the prank stops by itself after the next call
     pub single_call: bool,
+    /// Whether `tx.origin` is set for the whole call tree of the pranked calls, or only for the
+    /// pranked calls themselves
+    pub subtree: bool,
 }
 
 fn prank(
@@ -34,8 +37,10 @@ fn prank(
     new_origin: Option<Address>,
     depth: u64,
     single_call: bool,
+    subtree: bool,
 ) -> Result<Bytes, Bytes> {
-    let prank = Prank { prank_caller, prank_origin, new_caller, new_origin, depth, single_call };
+    let prank =
+        Prank { prank_caller, prank_origin, new_caller, new_origin, depth, single_call, subtree };
 
     if state.prank.is_some() {
         return Err("You have an active prank already.".to_string().encode().into())
@@ -45,6 +50,33 @@ fn prank(
     Ok(Bytes::new())
 }
 
+#[derive(Clone, Debug, Default)]
+pub struct Broadcast {
+    /// Address of the contract that started the broadcast
+    pub broadcaster: Address,
+    /// Address of `tx.origin` when the broadcast was started, which sends the transactions
+    pub origin: Address,
+    /// The depth at which the broadcast was started
+    pub depth: u64,
+    /// Whether or not the broadcast stops by itself after the next call
+    pub single_call: bool,
+}
+
+fn broadcast(
+    state: &mut Cheatcodes,
+    broadcaster: Address,
+    origin: Address,
+    depth: u64,
+    single_call: bool,
+) -> Result<Bytes, Bytes> {
+    if state.broadcast.is_some() {
+        return Err("You have an active broadcast already.".to_string().encode().into())
+    }
+
+    state.broadcast = Some(Broadcast { broadcaster, origin, depth, single_call });
+    Ok(Bytes::new())
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct RecordAccess {
     pub reads: BTreeMap<Address, Vec<U256>>,
@@ -67,6 +99,50 @@ fn accesses(state: &mut Cheatcodes, address: Address) -> Bytes {
     }
 }
 
+/// The state of the EVM when `snapshot` was called
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+    /// The depth at which the snapshot was taken
+    pub depth: u64,
+    /// The accounts, storage and logs of the call
+    pub subroutine: SubRoutine,
+    /// The block environment
+    pub block: BlockEnv,
+}
+
+fn snapshot<DB: Database>(state: &mut Cheatcodes, data: &EVMData<'_, DB>) -> Bytes {
+    state.snapshots.push(Snapshot {
+        depth: data.subroutine.depth(),
+        subroutine: data.subroutine.clone(),
+        block: data.env.block.clone(),
+    });
+    U256::from(state.snapshots.len() - 1).encode().into()
+}
+
+fn revert_to<DB: Database>(
+    state: &Cheatcodes,
+    data: &mut EVMData<'_, DB>,
+    id: U256,
+) -> Result<Bytes, Bytes> {
+    if id >= state.snapshots.len().into() {
+        return Ok(false.encode().into())
+    }
+
+    // The snapshot holds the changes of the calls that were running when it was taken, so it can
+    // only be restored in the same call
+    let snapshot = &state.snapshots[id.as_usize()];
+    if snapshot.depth != data.subroutine.depth() {
+        return Err("A snapshot can only be reverted to in the call that took it."
+            .to_string()
+            .encode()
+            .into())
+    }
+
+    data.subroutine.clone_from(&snapshot.subroutine);
+    data.env.block = snapshot.block.clone();
+    Ok(true.encode().into())
+}
+
 pub fn apply<DB: Database>(
     state: &mut Cheatcodes,
     data: &mut EVMData<'_, DB>,
@@ -86,6 +162,23 @@ pub fn apply<DB: Database>(
             data.env.block.basefee = inner.0;
             Ok(Bytes::new())
         }
+        HEVMCalls::ChainId(inner) => {
+            // Chain ids are 64 bits wide, e.g. in the signatures of `sign`
+            if inner.0 > U256::from(u64::MAX) {
+                Err("Chain ID must be at most 2^64 - 1.".to_string().encode().into())
+            } else {
+                data.env.cfg.chain_id = inner.0;
+                Ok(Bytes::new())
+            }
+        }
+        HEVMCalls::Coinbase(inner) => {
+            data.env.block.coinbase = inner.0;
+            Ok(Bytes::new())
+        }
+        HEVMCalls::Difficulty(inner) => {
+            data.env.block.difficulty = inner.0;
+            Ok(Bytes::new())
+        }
         HEVMCalls::Store(inner) => {
             // TODO: Does this increase gas usage?
             data.subroutine.load_account(inner.0, data.db);
@@ -123,9 +216,28 @@ pub fn apply<DB: Database>(
             }
             Ok(Bytes::new())
         }
-        HEVMCalls::Prank0(inner) => {
-            prank(state, caller, data.env.tx.caller, inner.0, None, data.subroutine.depth(), true)
+        HEVMCalls::SetNonce(inner) => {
+            // TODO: Does this increase gas usage?
+            data.subroutine.load_account(inner.0, data.db);
+            // `load_account` inserted the account into the state
+            data.subroutine.state().get_mut(&inner.0).unwrap().info.nonce = inner.1;
+            Ok(Bytes::new())
+        }
+        HEVMCalls::GetNonce(inner) => {
+            // TODO: Does this increase gas usage?
+            data.subroutine.load_account(inner.0, data.db);
+            Ok(data.subroutine.account(inner.0).info.nonce.encode().into())
         }
+        HEVMCalls::Prank0(inner) => prank(
+            state,
+            caller,
+            data.env.tx.caller,
+            inner.0,
+            None,
+            data.subroutine.depth(),
+            true,
+            true,
+        ),
         HEVMCalls::Prank1(inner) => prank(
             state,
             caller,
@@ -134,10 +246,28 @@ pub fn apply<DB: Database>(
             Some(inner.1),
             data.subroutine.depth(),
             true,
+            true,
+        ),
+        HEVMCalls::Prank2(inner) => prank(
+            state,
+            caller,
+            data.env.tx.caller,
+            inner.0,
+            Some(inner.1),
+            data.subroutine.depth(),
+            true,
+            inner.2,
+        ),
+        HEVMCalls::StartPrank0(inner) => prank(
+            state,
+            caller,
+            data.env.tx.caller,
+            inner.0,
+            None,
+            data.subroutine.depth(),
+            false,
+            true,
         ),
-        HEVMCalls::StartPrank0(inner) => {
-            prank(state, caller, data.env.tx.caller, inner.0, None, data.subroutine.depth(), false)
-        }
         HEVMCalls::StartPrank1(inner) => prank(
             state,
             caller,
@@ -146,16 +276,39 @@ pub fn apply<DB: Database>(
             Some(inner.1),
             data.subroutine.depth(),
             false,
+            true,
+        ),
+        HEVMCalls::StartPrank2(inner) => prank(
+            state,
+            caller,
+            data.env.tx.caller,
+            inner.0,
+            Some(inner.1),
+            data.subroutine.depth(),
+            false,
+            inner.2,
         ),
         HEVMCalls::StopPrank(_) => {
             state.prank = None;
             Ok(Bytes::new())
         }
+        HEVMCalls::Broadcast(_) => {
+            broadcast(state, caller, data.env.tx.caller, data.subroutine.depth(), true)
+        }
+        HEVMCalls::StartBroadcast(_) => {
+            broadcast(state, caller, data.env.tx.caller, data.subroutine.depth(), false)
+        }
+        HEVMCalls::StopBroadcast(_) => {
+            state.broadcast = None;
+            Ok(Bytes::new())
+        }
         HEVMCalls::Record(_) => {
             start_record(state);
             Ok(Bytes::new())
         }
         HEVMCalls::Accesses(inner) => Ok(accesses(state, inner.0)),
+        HEVMCalls::Snapshot(_) => Ok(snapshot(state, data)),
+        HEVMCalls::RevertTo(inner) => revert_to(state, data, inner.0),
         _ => return None,
     })
 }
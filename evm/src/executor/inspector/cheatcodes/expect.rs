@@ -3,9 +3,10 @@ use crate::abi::HEVMCalls;
 use bytes::Bytes;
 use ethers::{
     abi::{AbiEncode, RawLog},
-    types::{Address, H160},
+    types::{Address, H160, U256},
 };
 use revm::{return_ok, Database, EVMData, Return};
+use std::fmt;
 
 /// For some cheatcodes we may internally change the status of the call, i.e. in `expectRevert`.
 /// Solidity will see a successful call and attempt to decode the return data. Therefore, we need
@@ -21,27 +22,35 @@ static DUMMY_CREATE_ADDRESS: Address =
 
 #[derive(Clone, Debug, Default)]
 pub struct ExpectedRevert {
-    /// The expected data returned by the revert
-    pub reason: Bytes,
+    /// The expected data returned by the revert, any revert matches if none
+    pub reason: Option<Bytes>,
+    /// Whether the data returned by the revert only has to start with the reason, as for the
+    /// selector of a custom error with arguments
+    pub partial: bool,
     /// The depth at which the revert is expected
     pub depth: u64,
 }
 
-fn expect_revert(state: &mut Cheatcodes, reason: Bytes, depth: u64) -> Result<Bytes, Bytes> {
+fn expect_revert(
+    state: &mut Cheatcodes,
+    reason: Option<Bytes>,
+    partial: bool,
+    depth: u64,
+) -> Result<Bytes, Bytes> {
     if state.expected_revert.is_some() {
         Err("You must call another function prior to expecting a second revert."
             .to_string()
             .encode()
             .into())
     } else {
-        state.expected_revert = Some(ExpectedRevert { reason, depth });
+        state.expected_revert = Some(ExpectedRevert { reason, partial, depth });
         Ok(Bytes::new())
     }
 }
 
 pub fn handle_expect_revert(
     is_create: bool,
-    expected_revert: &Bytes,
+    expected: &ExpectedRevert,
     status: Return,
     retdata: Bytes,
 ) -> Result<(Option<Address>, Bytes), Bytes> {
@@ -49,10 +58,35 @@ pub fn handle_expect_revert(
         return Err("Call did not revert as expected".to_string().encode().into())
     }
 
+    let success = if is_create {
+        (Some(DUMMY_CREATE_ADDRESS), Bytes::new())
+    } else {
+        (None, DUMMY_CALL_OUTPUT.to_vec().into())
+    };
+
+    let expected_revert = match &expected.reason {
+        Some(reason) => reason,
+        None => return Ok(success),
+    };
+
     if !expected_revert.is_empty() && retdata.is_empty() {
         return Err("Call reverted as expected, but without data".to_string().encode().into())
     }
 
+    if expected.partial {
+        return if retdata.starts_with(expected_revert) {
+            Ok(success)
+        } else {
+            Err(format!(
+                "Error != expected error: 0x{} does not start with 0x{}",
+                hex::encode(&retdata),
+                hex::encode(&expected_revert)
+            )
+            .encode()
+            .into())
+        }
+    }
+
     let (err, actual_revert): (_, Bytes) = match retdata {
         _ if retdata.len() >= 4 && retdata[0..4] == [8, 195, 121, 160] => {
             // It's a revert string, so we do some conversion to perform the check
@@ -88,11 +122,7 @@ pub fn handle_expect_revert(
     };
 
     if actual_revert == expected_revert {
-        Ok(if is_create {
-            (Some(DUMMY_CREATE_ADDRESS), Bytes::new())
-        } else {
-            (None, DUMMY_CALL_OUTPUT.to_vec().into())
-        })
+        Ok(success)
     } else {
         Err(err)
     }
@@ -114,6 +144,31 @@ pub struct ExpectedEmit {
     pub found: bool,
 }
 
+#[derive(Clone, Debug, Default)]
+pub struct ExpectedCall {
+    /// The calldata the call is expected to start with
+    pub calldata: Bytes,
+    /// The value the call is expected to transfer, any value matches if none
+    pub value: Option<U256>,
+}
+
+impl ExpectedCall {
+    /// Whether a call with `input` transferring `value` fulfills the expectation
+    pub fn matches(&self, input: &[u8], value: U256) -> bool {
+        input.starts_with(&self.calldata) && self.value.map_or(true, |expected| expected == value)
+    }
+}
+
+impl fmt::Display for ExpectedCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "data {}", ethers::types::Bytes::from(self.calldata.clone()))?;
+        if let Some(value) = self.value {
+            write!(f, " and value {}", value)?;
+        }
+        Ok(())
+    }
+}
+
 pub fn handle_expect_emit(state: &mut Cheatcodes, log: RawLog) {
     // Fill or check the expected emits
     if let Some(next_expect_to_fill) =
@@ -153,12 +208,12 @@ pub fn apply<DB: Database>(
     call: &HEVMCalls,
 ) -> Option<Result<Bytes, Bytes>> {
     Some(match call {
-        HEVMCalls::ExpectRevert0(_) => expect_revert(state, Bytes::new(), data.subroutine.depth()),
+        HEVMCalls::ExpectRevert0(_) => expect_revert(state, None, false, data.subroutine.depth()),
         HEVMCalls::ExpectRevert1(inner) => {
-            expect_revert(state, inner.0.to_vec().into(), data.subroutine.depth())
+            expect_revert(state, Some(inner.0.to_vec().into()), false, data.subroutine.depth())
         }
         HEVMCalls::ExpectRevert2(inner) => {
-            expect_revert(state, inner.0.to_vec().into(), data.subroutine.depth())
+            expect_revert(state, Some(inner.0.to_vec().into()), true, data.subroutine.depth())
         }
         HEVMCalls::ExpectEmit(inner) => {
             state.expected_emits.push(ExpectedEmit {
@@ -168,8 +223,20 @@ pub fn apply<DB: Database>(
             });
             Ok(Bytes::new())
         }
-        HEVMCalls::ExpectCall(inner) => {
-            state.expected_calls.entry(inner.0).or_default().push(inner.1.to_vec().into());
+        HEVMCalls::ExpectCall0(inner) => {
+            state
+                .expected_calls
+                .entry(inner.0)
+                .or_default()
+                .push(ExpectedCall { calldata: inner.1.to_vec().into(), value: None });
+            Ok(Bytes::new())
+        }
+        HEVMCalls::ExpectCall1(inner) => {
+            state
+                .expected_calls
+                .entry(inner.0)
+                .or_default()
+                .push(ExpectedCall { calldata: inner.2.to_vec().into(), value: Some(inner.1) });
             Ok(Bytes::new())
         }
         HEVMCalls::MockCall(inner) => {
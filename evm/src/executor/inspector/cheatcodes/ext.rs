@@ -1,23 +1,36 @@
+use super::Cheatcodes;
 use crate::abi::HEVMCalls;
 use bytes::Bytes;
 use ethers::{
-    abi::{self, AbiEncode, Token},
+    abi::{self, AbiEncode, ParamType, Token},
     prelude::{artifacts::CompactContractBytecode, ProjectPathsConfig},
 };
+use foundry_config::{FsAccessKind, FsPermissions};
 use serde::Deserialize;
-use std::{fs::File, io::Read, path::Path, process::Command};
+use std::{
+    collections::btree_map::Entry,
+    fs::File,
+    io::{BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+    process::Command,
+};
 
+/// Runs the command and returns its stdout, hex encoded output is decoded and any other output is
+/// returned as is
 fn ffi(args: &[String]) -> Result<Bytes, Bytes> {
-    let output = Command::new(&args[0])
-        .args(&args[1..])
-        .output()
-        .map_err(|err| err.to_string().encode())?
-        .stdout;
-    let output = unsafe { std::str::from_utf8_unchecked(&output) };
-    let decoded = hex::decode(&output.trim().strip_prefix("0x").unwrap_or(output))
-        .map_err(|err| err.to_string().encode())?;
+    let (program, args) =
+        args.split_first().ok_or_else(|| "FFI needs a command to run.".to_string().encode())?;
+    let output =
+        Command::new(program).args(args).output().map_err(|err| err.to_string().encode())?.stdout;
+    let decoded = std::str::from_utf8(&output)
+        .ok()
+        .and_then(|output| {
+            let output = output.trim();
+            hex::decode(output.strip_prefix("0x").unwrap_or(output)).ok()
+        })
+        .unwrap_or(output);
 
-    Ok(abi::encode(&[Token::Bytes(decoded.to_vec())]).into())
+    Ok(abi::encode(&[Token::Bytes(decoded)]).into())
 }
 
 /// An enum which unifies the deserialization of Hardhat-style artifacts with Forge-style artifacts
@@ -54,8 +67,13 @@ fn get_code(path: &str) -> Result<Bytes, Bytes> {
     } else {
         let parts: Vec<&str> = path.split(':').collect();
         let file = parts[0];
-        let contract_name =
-            if parts.len() == 1 { parts[0].replace(".sol", "") } else { parts[1].to_string() };
+        // the contract of a vyper file is named after the file, like a solidity file's main
+        // contract
+        let contract_name = if parts.len() == 1 {
+            file.strip_suffix(".vy").map(str::to_string).unwrap_or_else(|| file.replace(".sol", ""))
+        } else {
+            parts[1].to_string()
+        };
         let out_dir = ProjectPathsConfig::find_artifacts_dir(Path::new("./"));
         out_dir.join(format!("{}/{}.json", file, contract_name))
     };
@@ -76,16 +94,94 @@ fn get_code(path: &str) -> Result<Bytes, Bytes> {
     }
 }
 
-pub fn apply(ffi_enabled: bool, call: &HEVMCalls) -> Option<Result<Bytes, Bytes>> {
+/// Resolves `path` relative to the project root and ensures the [FsPermissions] allow the given
+/// kind of access to it
+fn ensure_path_allowed(
+    fs_permissions: &FsPermissions,
+    path: &str,
+    kind: FsAccessKind,
+) -> Result<PathBuf, Bytes> {
+    let path = fs_permissions.resolve(path).map_err(|err| err.to_string().encode())?;
+    if !fs_permissions.is_path_allowed(&path, kind) {
+        return Err(format!(
+            "The path {:?} is not allowed to be accessed for {} operations: grant access to it with `fs_permissions` in the config.",
+            path, kind
+        )
+        .encode()
+        .into())
+    }
+    Ok(path)
+}
+
+fn read_file(fs_permissions: &FsPermissions, path: &str) -> Result<Bytes, Bytes> {
+    let path = ensure_path_allowed(fs_permissions, path, FsAccessKind::Read)?;
+    let data = std::fs::read_to_string(path).map_err(|err| err.to_string().encode())?;
+    Ok(abi::encode(&[Token::String(data)]).into())
+}
+
+/// Reads the next line of a file, without the line break. Returns an empty string once the whole
+/// file was read.
+fn read_line(state: &mut Cheatcodes, path: &str) -> Result<Bytes, Bytes> {
+    let path = ensure_path_allowed(&state.fs_permissions, path, FsAccessKind::Read)?;
+    let reader = match state.line_readers.entry(path) {
+        Entry::Occupied(entry) => entry.into_mut(),
+        Entry::Vacant(entry) => {
+            let file = File::open(entry.key()).map_err(|err| err.to_string().encode())?;
+            entry.insert(BufReader::new(file))
+        }
+    };
+
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(|err| err.to_string().encode())?;
+    let trimmed = line.strip_suffix('\n').map(|line| line.strip_suffix('\r').unwrap_or(line));
+    Ok(abi::encode(&[Token::String(trimmed.unwrap_or(&line).to_string())]).into())
+}
+
+fn write_file(state: &mut Cheatcodes, path: &str, data: &str) -> Result<Bytes, Bytes> {
+    let path = ensure_path_allowed(&state.fs_permissions, path, FsAccessKind::Write)?;
+    std::fs::write(&path, data).map_err(|err| err.to_string().encode())?;
+    // the file is read from the start again by `readLine`
+    state.line_readers.remove(&path);
+    Ok(Bytes::new())
+}
+
+fn remove_file(state: &mut Cheatcodes, path: &str) -> Result<Bytes, Bytes> {
+    let path = ensure_path_allowed(&state.fs_permissions, path, FsAccessKind::Write)?;
+    std::fs::remove_file(&path).map_err(|err| err.to_string().encode())?;
+    state.line_readers.remove(&path);
+    Ok(Bytes::new())
+}
+
+/// Reads the environment variable `key` and parses it as a value of type `kind`
+fn env(key: &str, kind: ParamType) -> Result<Bytes, Bytes> {
+    let value = std::env::var(key)
+        .map_err(|_| format!("Failed to get environment variable `{}`", key).encode())?;
+    let tokens = foundry_utils::parse_tokens([(&kind, value.as_str())], true).map_err(|err| {
+        format!("Failed to parse environment variable `{}` as {}: {:#}", key, kind, err).encode()
+    })?;
+    Ok(abi::encode(&tokens).into())
+}
+
+pub fn apply(state: &mut Cheatcodes, call: &HEVMCalls) -> Option<Result<Bytes, Bytes>> {
     Some(match call {
         HEVMCalls::Ffi(inner) => {
-            if !ffi_enabled {
+            if !state.ffi {
                 Err("FFI disabled: run again with `--ffi` if you want to allow tests to call external scripts.".to_string().encode().into())
             } else {
                 ffi(&inner.0)
             }
         }
         HEVMCalls::GetCode(inner) => get_code(&inner.0),
+        HEVMCalls::ReadFile(inner) => read_file(&state.fs_permissions, &inner.0),
+        HEVMCalls::ReadLine(inner) => read_line(state, &inner.0),
+        HEVMCalls::WriteFile(inner) => write_file(state, &inner.0, &inner.1),
+        HEVMCalls::RemoveFile(inner) => remove_file(state, &inner.0),
+        HEVMCalls::EnvBool(inner) => env(&inner.0, ParamType::Bool),
+        HEVMCalls::EnvUint(inner) => env(&inner.0, ParamType::Uint(256)),
+        HEVMCalls::EnvInt(inner) => env(&inner.0, ParamType::Int(256)),
+        HEVMCalls::EnvAddress(inner) => env(&inner.0, ParamType::Address),
+        HEVMCalls::EnvBytes32(inner) => env(&inner.0, ParamType::FixedBytes(32)),
+        HEVMCalls::EnvString(inner) => env(&inner.0, ParamType::String),
         _ => return None,
     })
 }
@@ -0,0 +1,461 @@
+use super::Cheatcodes;
+use crate::{
+    abi::HEVMCalls,
+    executor::{
+        fork::{fork_environment, SharedBackend},
+        Fork, CHEATCODE_ADDRESS,
+    },
+};
+use bytes::Bytes;
+use ethers::{
+    abi::{self, AbiEncode, Token},
+    types::{Address, H160, H256, U256},
+};
+use foundry_utils::RuntimeOrHandle;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use revm::{
+    Account, AccountInfo, BlockEnv, CreateScheme, Database, DatabaseRef, EVMData, TransactTo, EVM,
+};
+use std::collections::{hash_map::Entry, BTreeMap, BTreeSet, HashMap};
+
+/// The backends of the forks created by `createFork`, keyed by URL and block.
+///
+/// All tests that fork the same URL at the same block share one backend, so the remote state is
+/// only fetched once for the whole suite.
+static CREATED_BACKENDS: Lazy<Mutex<HashMap<(String, u64), SharedBackend>>> =
+    Lazy::new(Default::default);
+
+/// The forks created by `createFork` and the fork that is selected or rolled
+#[derive(Clone, Debug, Default)]
+pub struct Forks {
+    /// The forks created by `createFork`, the id of a fork is its index
+    pub created: Vec<CreatedFork>,
+    /// The fork selected by `selectFork` or moved to another block by `rollFork`, if any
+    pub active: Option<ActiveFork>,
+}
+
+impl Forks {
+    /// Whether no fork was created, selected or rolled
+    pub fn is_empty(&self) -> bool {
+        self.created.is_empty() && self.active.is_none()
+    }
+}
+
+/// A fork created by `createFork`
+#[derive(Clone, Debug)]
+pub struct CreatedFork {
+    /// The backend of the fork
+    pub backend: SharedBackend,
+    /// The block environment of the fork
+    pub block: BlockEnv,
+    /// The chain id of the fork
+    pub chain_id: U256,
+    /// The state of the accounts of the fork when another fork was selected
+    pub saved: BTreeMap<Address, Account>,
+}
+
+/// The fork whose state is used instead of the state of the executor.
+///
+/// The state of an account is fetched from the fork the first time the account is used after the
+/// fork was selected or rolled. The persistent accounts keep their state.
+#[derive(Clone, Debug)]
+pub struct ActiveFork {
+    /// The id of the fork if it was created by `createFork`, `None` for the fork of the executor
+    pub id: Option<usize>,
+    /// The backend of the fork
+    pub backend: SharedBackend,
+    /// The state of the accounts of the fork when it was last selected, restored the first time
+    /// the accounts are used
+    pub saved: BTreeMap<Address, Account>,
+    /// The accounts whose state is up to date with the fork
+    pub accounts: BTreeSet<Address>,
+    /// The storage slots whose value is up to date with the fork
+    pub slots: BTreeSet<(Address, U256)>,
+    /// The accounts whose state is the same on all forks, like the test contract
+    pub persistent_accounts: BTreeSet<Address>,
+    /// Whether the fork was moved to another block by `rollFork`, in which case the contracts
+    /// that only exist locally keep their state
+    pub rolled: bool,
+}
+
+impl ActiveFork {
+    /// Whether the state of `address` is up to date with the fork
+    fn is_up_to_date(&self, address: &Address) -> bool {
+        self.persistent_accounts.contains(address) || self.accounts.contains(address)
+    }
+
+    /// Fetches the state of `address` from the fork, unless it is up to date already
+    pub fn refresh_account<DB: Database>(&mut self, data: &mut EVMData<'_, DB>, address: Address) {
+        if self.is_up_to_date(&address) {
+            return
+        }
+        self.accounts.insert(address);
+        data.subroutine.load_account(address, data.db);
+
+        if let Some(saved) = self.saved.remove(&address) {
+            self.slots.extend(saved.storage.keys().map(|slot| (address, *slot)));
+            let account = data.subroutine.state().get_mut(&address).unwrap();
+            account.info = saved.info;
+            account.storage.extend(saved.storage);
+            return
+        }
+
+        let remote = DatabaseRef::basic(&self.backend, address);
+        let local = &mut data.subroutine.state().get_mut(&address).unwrap().info;
+
+        // Contracts that do not exist on chain were deployed locally before the roll, so they
+        // keep their state
+        if self.rolled && is_empty_code(&remote) && !is_empty_code(local) {
+            self.persistent_accounts.insert(address);
+            return
+        }
+        *local = remote;
+    }
+
+    /// Fetches the value of `slot` of `address` from the fork, unless it is up to date already
+    pub fn refresh_slot<DB: Database>(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        address: Address,
+        slot: U256,
+    ) {
+        self.refresh_account(data, address);
+        if self.persistent_accounts.contains(&address) || !self.slots.insert((address, slot)) {
+            return
+        }
+
+        let value = DatabaseRef::storage(&self.backend, address, slot);
+        data.subroutine.state().get_mut(&address).unwrap().storage.insert(slot, value);
+    }
+
+    /// Marks `address` as created on the fork, so its state is up to date
+    pub fn created_account(&mut self, address: Address) {
+        self.accounts.insert(address);
+    }
+
+    /// Returns the state of the accounts that is up to date with the fork, to restore it when the
+    /// fork is selected again
+    fn save<DB: Database>(mut self, data: &mut EVMData<'_, DB>) -> BTreeMap<Address, Account> {
+        for (address, account) in data.subroutine.state().iter() {
+            if self.persistent_accounts.contains(address) || !self.accounts.contains(address) {
+                continue
+            }
+            let mut account = account.clone();
+            account.storage.retain(|slot, _| self.slots.contains(&(*address, *slot)));
+            self.saved.insert(*address, account);
+        }
+        self.saved
+    }
+}
+
+fn is_empty_code(info: &AccountInfo) -> bool {
+    info.code.as_ref().map_or(true, |code| code.is_empty())
+}
+
+/// The state of the running transaction on top of its database, so another transaction can be
+/// executed on it
+struct SubroutineDb<'a, DB> {
+    state: BTreeMap<Address, Account>,
+    db: &'a mut DB,
+    active_fork: Option<&'a ActiveFork>,
+}
+
+impl<'a, DB: Database> Database for SubroutineDb<'a, DB> {
+    fn basic(&mut self, address: H160) -> AccountInfo {
+        // The state of the accounts that are not up to date belongs to another fork or block
+        match self.active_fork {
+            Some(fork) if !fork.is_up_to_date(&address) => match fork.saved.get(&address) {
+                Some(saved) => saved.info.clone(),
+                None => DatabaseRef::basic(&fork.backend, address),
+            },
+            _ => match self.state.get(&address) {
+                Some(account) => account.info.clone(),
+                None => self.db.basic(address),
+            },
+        }
+    }
+
+    fn code_by_hash(&mut self, code_hash: H256) -> bytes::Bytes {
+        self.db.code_by_hash(code_hash)
+    }
+
+    fn storage(&mut self, address: H160, index: U256) -> U256 {
+        match self.active_fork {
+            Some(fork)
+                if !fork.persistent_accounts.contains(&address) &&
+                    !fork.slots.contains(&(address, index)) =>
+            {
+                match fork.saved.get(&address).and_then(|saved| saved.storage.get(&index)) {
+                    Some(value) => *value,
+                    None => DatabaseRef::storage(&fork.backend, address, index),
+                }
+            }
+            _ => match self.state.get(&address).and_then(|account| account.storage.get(&index)) {
+                Some(value) => *value,
+                None => self.db.storage(address, index),
+            },
+        }
+    }
+
+    fn block_hash(&mut self, number: U256) -> H256 {
+        self.db.block_hash(number)
+    }
+}
+
+/// The backend of the active fork, or of the fork of the executor
+fn fork_backend(state: &Cheatcodes) -> Result<&SharedBackend, Bytes> {
+    state
+        .forks
+        .active
+        .as_ref()
+        .map(|fork| &fork.backend)
+        .or(state.fork.as_ref())
+        .ok_or_else(|| "Reading the fork state needs a fork.".to_string().encode().into())
+}
+
+/// The accounts that keep their state when a fork is selected or rolled
+fn persistent_accounts<DB: Database>(
+    state: &Cheatcodes,
+    data: &EVMData<'_, DB>,
+    caller: Address,
+) -> BTreeSet<Address> {
+    let mut accounts = state
+        .forks
+        .active
+        .as_ref()
+        .map(|fork| fork.persistent_accounts.clone())
+        .unwrap_or_default();
+    accounts.extend([caller, data.env.tx.caller, CHEATCODE_ADDRESS]);
+    accounts
+}
+
+/// Returns the code of `who` on the forked chain, ignoring local changes
+fn fork_code(state: &Cheatcodes, who: Address) -> Result<Bytes, Bytes> {
+    let code = DatabaseRef::basic(fork_backend(state)?, who).code.unwrap_or_default();
+    Ok(abi::encode(&[Token::Bytes(code.to_vec())]).into())
+}
+
+/// Returns the value of `slot` of `who` on the forked chain, ignoring local changes
+fn fork_storage_at(state: &Cheatcodes, who: Address, slot: [u8; 32]) -> Result<Bytes, Bytes> {
+    let value = DatabaseRef::storage(fork_backend(state)?, who, U256::from(slot));
+    Ok(value.encode().into())
+}
+
+/// Creates a fork of the chain at `url_or_alias`, the url or the alias of one of the
+/// `rpc_endpoints`, at `block` or the latest block, and returns its id
+fn create_fork<DB: Database>(
+    state: &mut Cheatcodes,
+    data: &EVMData<'_, DB>,
+    url_or_alias: &str,
+    block: Option<U256>,
+) -> Result<Bytes, Bytes> {
+    let block = match block {
+        Some(block) if block > U256::from(u64::MAX) => {
+            return Err(format!("Block number {} is too large.", block).encode().into())
+        }
+        block => block.map(|block| block.as_u64()),
+    };
+    let url = state.rpc_endpoints.resolve(url_or_alias).map_err(|err| err.to_string().encode())?;
+    let env = RuntimeOrHandle::new()
+        .block_on(fork_environment(&url, None, block, data.env.tx.caller))
+        .map_err(|err| err.to_string().encode())?;
+
+    let block = env.block.number.as_u64();
+    let backend = match CREATED_BACKENDS.lock().entry((url.to_string(), block)) {
+        Entry::Occupied(entry) => entry.get().clone(),
+        Entry::Vacant(entry) => entry
+            .insert(
+                Fork {
+                    cache_path: None,
+                    url: url.to_string(),
+                    pin_block: Some(block),
+                    chain_id: env.cfg.chain_id.as_u64(),
+                }
+                .into_backend(&env)
+                .map_err(|err| err.to_string().encode())?,
+            )
+            .clone(),
+    };
+    state.forks.created.push(CreatedFork {
+        backend,
+        block: env.block,
+        chain_id: env.cfg.chain_id,
+        saved: Default::default(),
+    });
+    Ok(U256::from(state.forks.created.len() - 1).encode().into())
+}
+
+/// Makes the fork `id` the active fork, saving the state of the previously active fork
+fn select_fork<DB: Database>(
+    state: &mut Cheatcodes,
+    data: &mut EVMData<'_, DB>,
+    caller: Address,
+    id: U256,
+) -> Result<Bytes, Bytes> {
+    if id >= U256::from(state.forks.created.len()) {
+        return Err(format!("No fork with id {}.", id).encode().into())
+    }
+    let id = id.as_usize();
+    let persistent_accounts = persistent_accounts(state, data, caller);
+
+    if let Some(active) = state.forks.active.take() {
+        if let Some(active_id) = active.id {
+            let block = data.env.block.clone();
+            let chain_id = data.env.cfg.chain_id;
+            let saved = active.save(data);
+            let previous = &mut state.forks.created[active_id];
+            previous.saved = saved;
+            previous.block = block;
+            previous.chain_id = chain_id;
+        }
+    }
+
+    let fork = &mut state.forks.created[id];
+    data.env.block = fork.block.clone();
+    data.env.cfg.chain_id = fork.chain_id;
+    state.forks.active = Some(ActiveFork {
+        id: Some(id),
+        backend: fork.backend.clone(),
+        saved: std::mem::take(&mut fork.saved),
+        accounts: Default::default(),
+        slots: Default::default(),
+        persistent_accounts,
+        rolled: false,
+    });
+    Ok(Bytes::new())
+}
+
+/// Returns the id of the active fork
+fn active_fork(state: &Cheatcodes) -> Result<Bytes, Bytes> {
+    state
+        .forks
+        .active
+        .as_ref()
+        .and_then(|fork| fork.id)
+        .map(|id| U256::from(id).encode().into())
+        .ok_or_else(|| "No fork was selected.".to_string().encode().into())
+}
+
+/// Moves the active fork, or the fork of the executor, to block `number`. The state of the
+/// accounts is fetched from that block the next time they are used.
+fn roll_fork<DB: Database>(
+    state: &mut Cheatcodes,
+    data: &mut EVMData<'_, DB>,
+    caller: Address,
+    number: U256,
+) -> Result<Bytes, Bytes> {
+    if number > U256::from(u64::MAX) {
+        return Err(format!("Block number {} is too large.", number).encode().into())
+    }
+    let backend =
+        fork_backend(state)?.roll(number.as_u64()).map_err(|err| err.to_string().encode())?;
+    let persistent_accounts = persistent_accounts(state, data, caller);
+
+    let id = state.forks.active.as_ref().and_then(|fork| fork.id);
+    if let Some(id) = id {
+        state.forks.created[id].backend = backend.clone();
+    }
+    state.forks.active = Some(ActiveFork {
+        id,
+        backend,
+        saved: Default::default(),
+        accounts: Default::default(),
+        slots: Default::default(),
+        persistent_accounts,
+        rolled: true,
+    });
+    data.env.block.number = number;
+    Ok(Bytes::new())
+}
+
+/// Makes the state of `who` the same on all forks
+fn make_persistent(state: &mut Cheatcodes, who: Address) -> Result<Bytes, Bytes> {
+    match &mut state.forks.active {
+        Some(fork) => {
+            fork.persistent_accounts.insert(who);
+            Ok(Bytes::new())
+        }
+        None => Err("Accounts can only be made persistent once a fork is selected."
+            .to_string()
+            .encode()
+            .into()),
+    }
+}
+
+/// Executes the transaction with the hash `tx_hash`, fetched from the fork, on the current state
+fn transact<DB: Database>(
+    state: &mut Cheatcodes,
+    data: &mut EVMData<'_, DB>,
+    tx_hash: H256,
+) -> Result<Bytes, Bytes> {
+    let tx =
+        fork_backend(state)?.get_transaction(tx_hash).map_err(|err| err.to_string().encode())?;
+    if tx.gas > U256::from(u64::MAX) {
+        return Err(format!("The gas limit {} of the transaction is too large.", tx.gas)
+            .encode()
+            .into())
+    }
+
+    let changes = {
+        let mut evm = EVM::new();
+        evm.env = data.env.clone();
+        // As in the executor, the gas price is 0 so the transaction runs regardless of the balance
+        // of its sender
+        evm.env.block.basefee = 0.into();
+        evm.env.tx.caller = tx.from;
+        evm.env.tx.transact_to =
+            tx.to.map(TransactTo::Call).unwrap_or(TransactTo::Create(CreateScheme::Create));
+        evm.env.tx.data = tx.input.0.clone();
+        evm.env.tx.value = tx.value;
+        evm.env.tx.gas_limit = tx.gas.as_u64();
+        evm.env.tx.gas_price = 0.into();
+        evm.env.tx.gas_priority_fee = None;
+        evm.database(SubroutineDb {
+            state: data
+                .subroutine
+                .state()
+                .iter()
+                .map(|(address, account)| (*address, account.clone()))
+                .collect(),
+            db: &mut *data.db,
+            active_fork: state.forks.active.as_ref(),
+        });
+        let (_, _, _, changes, _) = evm.transact();
+        changes
+    };
+
+    // Apply the changes of the transaction to the running transaction
+    for (address, account) in changes {
+        if let Some(fork) = &mut state.forks.active {
+            fork.refresh_account(data, address);
+            fork.slots.extend(account.storage.keys().map(|slot| (address, *slot)));
+        }
+        data.subroutine.load_account(address, data.db);
+        for (slot, value) in account.storage {
+            data.subroutine.sstore(address, slot, value, data.db);
+        }
+        data.subroutine.state().get_mut(&address).unwrap().info = account.info;
+    }
+    Ok(Bytes::new())
+}
+
+pub fn apply<DB: Database>(
+    state: &mut Cheatcodes,
+    data: &mut EVMData<'_, DB>,
+    caller: Address,
+    call: &HEVMCalls,
+) -> Option<Result<Bytes, Bytes>> {
+    Some(match call {
+        HEVMCalls::GetForkCode(inner) => fork_code(state, inner.0),
+        HEVMCalls::GetForkStorageAt(inner) => fork_storage_at(state, inner.0, inner.1),
+        HEVMCalls::CreateFork0(inner) => create_fork(state, data, &inner.0, Some(inner.1)),
+        HEVMCalls::CreateFork1(inner) => create_fork(state, data, &inner.0, None),
+        HEVMCalls::SelectFork(inner) => select_fork(state, data, caller, inner.0),
+        HEVMCalls::ActiveFork(_) => active_fork(state),
+        HEVMCalls::RollFork(inner) => roll_fork(state, data, caller, inner.0),
+        HEVMCalls::MakePersistent(inner) => make_persistent(state, inner.0),
+        HEVMCalls::Transact(inner) => transact(state, data, inner.0.into()),
+        _ => return None,
+    })
+}
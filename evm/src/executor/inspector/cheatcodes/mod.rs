@@ -1,28 +1,35 @@
 /// Cheatcodes related to the execution environment.
 mod env;
-pub use env::{Prank, RecordAccess};
+pub use env::{Broadcast, Prank, RecordAccess, Snapshot};
 /// Assertion helpers (such as `expectEmit`)
 mod expect;
-pub use expect::{ExpectedEmit, ExpectedRevert};
-/// Cheatcodes that interact with the external environment (FFI etc.)
+pub use expect::{ExpectedCall, ExpectedEmit, ExpectedRevert};
+/// Cheatcodes that interact with the external environment (FFI, files etc.)
 mod ext;
+/// Cheatcodes that read or control the forks (`createFork`, `selectFork` etc.)
+mod fork;
+pub use fork::{ActiveFork, CreatedFork, Forks};
 /// Cheatcodes that configure the fuzzer
 mod fuzz;
 /// Utility cheatcodes (`sign` etc.)
 mod util;
 
 use self::expect::{handle_expect_emit, handle_expect_revert};
-use crate::{abi::HEVMCalls, executor::CHEATCODE_ADDRESS};
+use crate::{
+    abi::HEVMCalls,
+    executor::{fork::SharedBackend, CHEATCODE_ADDRESS, HARDHAT_CONSOLE_ADDRESS},
+};
 use bytes::Bytes;
 use ethers::{
     abi::{AbiDecode, AbiEncode, RawLog},
-    types::{Address, H256},
+    types::{Address, TransactionRequest, H256},
 };
+use foundry_config::{FsPermissions, RpcEndpoints};
 use revm::{
     opcode, BlockEnv, CallInputs, CreateInputs, Database, EVMData, Gas, Inspector, Interpreter,
     Return,
 };
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, fs::File, io::BufReader, path::PathBuf};
 
 /// An inspector that handles calls to various cheatcodes, each with their own behavior.
 ///
@@ -33,6 +40,21 @@ pub struct Cheatcodes {
     /// Whether FFI is enabled or not
     ffi: bool,
 
+    /// The paths the file cheatcodes are allowed to access
+    fs_permissions: FsPermissions,
+
+    /// The RPC endpoints by alias, `createFork` accepts their aliases in place of urls
+    rpc_endpoints: RpcEndpoints,
+
+    /// The readers of the files read line by line with `readLine`
+    line_readers: BTreeMap<PathBuf, BufReader<File>>,
+
+    /// The backend of the fork, read by `getForkCode` and `getForkStorageAt`
+    fork: Option<SharedBackend>,
+
+    /// The forks created by `createFork` and the fork that is selected or rolled
+    pub forks: Forks,
+
     /// The block environment
     ///
     /// Used in the cheatcode handler to overwrite the block environment separately from the
@@ -45,6 +67,13 @@ pub struct Cheatcodes {
     /// Prank information
     pub prank: Option<Prank>,
 
+    /// Broadcast information
+    pub broadcast: Option<Broadcast>,
+
+    /// The calls and creates made while broadcasting, as transactions to be sent by a script
+    /// runner
+    pub broadcastable_transactions: Vec<TransactionRequest>,
+
     /// Expected revert information
     pub expected_revert: Option<ExpectedRevert>,
 
@@ -55,15 +84,33 @@ pub struct Cheatcodes {
     pub mocked_calls: BTreeMap<Address, BTreeMap<Bytes, Bytes>>,
 
     /// Expected calls
-    pub expected_calls: BTreeMap<Address, Vec<Bytes>>,
+    pub expected_calls: BTreeMap<Address, Vec<ExpectedCall>>,
 
     /// Expected emits
     pub expected_emits: Vec<ExpectedEmit>,
+
+    /// The snapshots taken by `snapshot`, the id of a snapshot is its index
+    pub snapshots: Vec<Snapshot>,
 }
 
 impl Cheatcodes {
-    pub fn new(ffi: bool, block: BlockEnv) -> Self {
-        Self { ffi, block: Some(block), ..Default::default() }
+    pub fn new(
+        ffi: bool,
+        block: BlockEnv,
+        fs_permissions: FsPermissions,
+        rpc_endpoints: RpcEndpoints,
+        fork: Option<SharedBackend>,
+        forks: Forks,
+    ) -> Self {
+        Self {
+            ffi,
+            fs_permissions,
+            rpc_endpoints,
+            block: Some(block),
+            fork,
+            forks,
+            ..Default::default()
+        }
     }
 
     fn apply_cheatcode<DB: Database>(
@@ -80,7 +127,8 @@ impl Cheatcodes {
             .or_else(|| util::apply(self, data, &decoded))
             .or_else(|| expect::apply(self, data, &decoded))
             .or_else(|| fuzz::apply(data, &decoded))
-            .or_else(|| ext::apply(self.ffi, &decoded))
+            .or_else(|| ext::apply(self, &decoded))
+            .or_else(|| fork::apply(self, data, caller, &decoded))
             .ok_or_else(|| "Cheatcode was unhandled. This is a bug.".to_string().encode())?
     }
 }
@@ -93,7 +141,7 @@ where
         &mut self,
         data: &mut EVMData<'_, DB>,
         call: &mut CallInputs,
-        _: bool,
+        is_static: bool,
     ) -> (Return, Gas, Bytes) {
         if call.contract == CHEATCODE_ADDRESS {
             match self.apply_cheatcode(data, call.context.caller, call) {
@@ -101,21 +149,27 @@ where
                 Err(err) => (Return::Revert, Gas::new(call.gas_limit), err),
             }
         } else {
+            // Fetch the state of the called account from the active fork, if it is not up to date
+            if let Some(fork) = &mut self.forks.active {
+                fork.refresh_account(data, call.contract);
+            }
+
             // Handle expected calls
             if let Some(expecteds) = self.expected_calls.get_mut(&call.contract) {
-                if let Some(found_match) = expecteds.iter().position(|expected| {
-                    expected.len() <= call.input.len() && expected == &call.input[..expected.len()]
-                }) {
+                if let Some(found_match) = expecteds
+                    .iter()
+                    .position(|expected| expected.matches(&call.input, call.transfer.value))
+                {
                     expecteds.remove(found_match);
                 }
             }
 
-            // Handle mocked calls
+            // Handle mocked calls, the longest mocked calldata that prefixes the input matches
             if let Some(mocks) = self.mocked_calls.get(&call.contract) {
-                if let Some(mock_retdata) = mocks.get(&call.input) {
-                    return (Return::Return, Gas::new(call.gas_limit), mock_retdata.clone())
-                } else if let Some((_, mock_retdata)) =
-                    mocks.iter().find(|(mock, _)| *mock == &call.input[..mock.len()])
+                if let Some((_, mock_retdata)) = mocks
+                    .iter()
+                    .filter(|(mock, _)| call.input.starts_with(mock))
+                    .max_by_key(|(mock, _)| mock.len())
                 {
                     return (Return::Return, Gas::new(call.gas_limit), mock_retdata.clone())
                 }
@@ -137,6 +191,43 @@ where
                         data.env.tx.caller = new_origin;
                     }
                 }
+
+                // The calls nested in a pranked call see the original `tx.origin`, unless the
+                // prank applies to the whole call tree
+                if !prank.subtree && data.subroutine.depth() == prank.depth + 1 {
+                    data.env.tx.caller = prank.prank_origin;
+                }
+            }
+
+            // Record the calls of the broadcaster as transactions sent by the origin. Static calls
+            // and console logs are not transactions, they only read the state.
+            if let Some(broadcast) = &self.broadcast {
+                if data.subroutine.depth() == broadcast.depth &&
+                    call.context.caller == broadcast.broadcaster &&
+                    call.contract != HARDHAT_CONSOLE_ADDRESS
+                {
+                    call.context.caller = broadcast.origin;
+                    call.transfer.source = broadcast.origin;
+                    if is_static {
+                        return (Return::Continue, Gas::new(call.gas_limit), Bytes::new())
+                    }
+                    self.broadcastable_transactions.push(TransactionRequest {
+                        from: Some(broadcast.origin),
+                        to: Some(call.contract.into()),
+                        value: Some(call.transfer.value),
+                        data: Some(ethers::types::Bytes(call.input.clone())),
+                        ..Default::default()
+                    });
+
+                    // Sending a transaction increases the nonce of the origin, so contracts it
+                    // creates later get the addresses they get on chain
+                    data.subroutine.load_account(broadcast.origin, data.db);
+                    data.subroutine.state().get_mut(&broadcast.origin).unwrap().info.nonce += 1;
+
+                    if broadcast.single_call {
+                        self.broadcast = None;
+                    }
+                }
             }
 
             (Return::Continue, Gas::new(call.gas_limit), Bytes::new())
@@ -158,7 +249,33 @@ where
         Return::Continue
     }
 
-    fn step(&mut self, interpreter: &mut Interpreter, _: &mut EVMData<'_, DB>, _: bool) -> Return {
+    fn step(
+        &mut self,
+        interpreter: &mut Interpreter,
+        data: &mut EVMData<'_, DB>,
+        _: bool,
+    ) -> Return {
+        // Fetch the state the opcode reads from the active fork, if it is not up to date
+        if let Some(fork) = &mut self.forks.active {
+            match interpreter.contract.code[interpreter.program_counter()] {
+                opcode::SLOAD | opcode::SSTORE => {
+                    let slot = try_or_continue!(interpreter.stack().peek(0));
+                    fork.refresh_slot(data, interpreter.contract().address, slot);
+                }
+                opcode::BALANCE |
+                opcode::EXTCODESIZE |
+                opcode::EXTCODECOPY |
+                opcode::EXTCODEHASH |
+                opcode::SELFDESTRUCT => {
+                    let who = try_or_continue!(interpreter.stack().peek(0));
+                    let mut word = [0u8; 32];
+                    who.to_big_endian(&mut word);
+                    fork.refresh_account(data, Address::from_slice(&word[12..]));
+                }
+                _ => (),
+            }
+        }
+
         // Record writes and reads if `record` has been called
         if let Some(storage_accesses) = &mut self.accesses {
             match interpreter.contract.code[interpreter.program_counter()] {
@@ -212,11 +329,19 @@ where
             return (status, remaining_gas, retdata)
         }
 
-        // Clean up pranks
+        // Clean up pranks once the pranked call returns, `tx.origin` stays set for its whole
+        // subtree unless the prank only applies to the pranked call, which gets it back once a
+        // nested call returns
         if let Some(prank) = &self.prank {
-            data.env.tx.caller = prank.prank_origin;
-            if prank.single_call {
-                std::mem::take(&mut self.prank);
+            if data.subroutine.depth() == prank.depth {
+                data.env.tx.caller = prank.prank_origin;
+                if prank.single_call {
+                    std::mem::take(&mut self.prank);
+                }
+            } else if !prank.subtree && data.subroutine.depth() == prank.depth + 1 {
+                if let Some(new_origin) = prank.new_origin {
+                    data.env.tx.caller = new_origin;
+                }
             }
         }
 
@@ -224,7 +349,7 @@ where
         if let Some(expected_revert) = &self.expected_revert {
             if data.subroutine.depth() <= expected_revert.depth {
                 let expected_revert = std::mem::take(&mut self.expected_revert).unwrap();
-                return match handle_expect_revert(false, &expected_revert.reason, status, retdata) {
+                return match handle_expect_revert(false, &expected_revert, status, retdata) {
                     Err(retdata) => (Return::Revert, remaining_gas, retdata),
                     Ok((_, retdata)) => (Return::Return, remaining_gas, retdata),
                 }
@@ -257,13 +382,9 @@ where
                 return (
                     Return::Revert,
                     remaining_gas,
-                    format!(
-                        "Expected a call to 0x{} with data {}, but got none",
-                        address,
-                        ethers::types::Bytes::from(expecteds[0].clone())
-                    )
-                    .encode()
-                    .into(),
+                    format!("Expected a call to 0x{} with {}, but got none", address, expecteds[0])
+                        .encode()
+                        .into(),
                 )
             }
 
@@ -301,6 +422,31 @@ where
                     data.env.tx.caller = new_origin;
                 }
             }
+
+            // The calls nested in a pranked create see the original `tx.origin`, unless the
+            // prank applies to the whole call tree
+            if !prank.subtree && data.subroutine.depth() == prank.depth + 1 {
+                data.env.tx.caller = prank.prank_origin;
+            }
+        }
+
+        // Record the creates of the broadcaster as transactions sent by the origin, the create
+        // increases the nonce of the origin
+        if let Some(broadcast) = &self.broadcast {
+            if data.subroutine.depth() == broadcast.depth && call.caller == broadcast.broadcaster {
+                call.caller = broadcast.origin;
+                self.broadcastable_transactions.push(TransactionRequest {
+                    from: Some(broadcast.origin),
+                    to: None,
+                    value: Some(call.value),
+                    data: Some(ethers::types::Bytes(call.init_code.clone())),
+                    ..Default::default()
+                });
+
+                if broadcast.single_call {
+                    self.broadcast = None;
+                }
+            }
         }
 
         (Return::Continue, None, Gas::new(call.gas_limit), Bytes::new())
@@ -315,11 +461,24 @@ where
         remaining_gas: Gas,
         retdata: Bytes,
     ) -> (Return, Option<Address>, Gas, Bytes) {
-        // Clean up pranks
+        // Contracts deployed on the active fork are up to date with it
+        if let (Some(fork), Some(address)) = (&mut self.forks.active, address) {
+            fork.created_account(address);
+        }
+
+        // Clean up pranks once the pranked call returns, `tx.origin` stays set for its whole
+        // subtree unless the prank only applies to the pranked call, which gets it back once a
+        // nested call returns
         if let Some(prank) = &self.prank {
-            data.env.tx.caller = prank.prank_origin;
-            if prank.single_call {
-                std::mem::take(&mut self.prank);
+            if data.subroutine.depth() == prank.depth {
+                data.env.tx.caller = prank.prank_origin;
+                if prank.single_call {
+                    std::mem::take(&mut self.prank);
+                }
+            } else if !prank.subtree && data.subroutine.depth() == prank.depth + 1 {
+                if let Some(new_origin) = prank.new_origin {
+                    data.env.tx.caller = new_origin;
+                }
             }
         }
 
@@ -327,7 +486,7 @@ where
         if let Some(expected_revert) = &self.expected_revert {
             if data.subroutine.depth() <= expected_revert.depth {
                 let expected_revert = std::mem::take(&mut self.expected_revert).unwrap();
-                return match handle_expect_revert(true, &expected_revert.reason, status, retdata) {
+                return match handle_expect_revert(true, &expected_revert, status, retdata) {
                     Err(retdata) => (Return::Revert, None, remaining_gas, retdata),
                     Ok((address, retdata)) => (Return::Return, address, remaining_gas, retdata),
                 }
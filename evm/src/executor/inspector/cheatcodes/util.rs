@@ -2,7 +2,9 @@ use crate::abi::HEVMCalls;
 use bytes::Bytes;
 use ethers::{
     abi::AbiEncode,
-    prelude::{k256::ecdsa::SigningKey, LocalWallet, Signer},
+    prelude::{
+        coins_bip39::English, k256::ecdsa::SigningKey, LocalWallet, MnemonicBuilder, Signer,
+    },
     types::{H256, U256},
     utils,
 };
@@ -10,6 +12,9 @@ use revm::{Database, EVMData};
 
 use super::Cheatcodes;
 
+/// The derivation path of the keys of ethereum accounts, without the index of the account
+const DEFAULT_DERIVATION_PATH_PREFIX: &str = "m/44'/60'/0'/0/";
+
 fn addr(private_key: U256) -> Result<Bytes, Bytes> {
     if private_key.is_zero() {
         return Err("Private key cannot be 0.".to_string().encode().into())
@@ -48,6 +53,16 @@ fn sign(private_key: U256, digest: H256, chain_id: U256) -> Result<Bytes, Bytes>
     Ok((sig.v, r_bytes, s_bytes).encode().into())
 }
 
+fn derive_key(mnemonic: &str, path: &str, index: u32) -> Result<Bytes, Bytes> {
+    let wallet = MnemonicBuilder::<English>::default()
+        .phrase(mnemonic)
+        .derivation_path(&format!("{}{}", path, index))
+        .and_then(|builder| builder.build())
+        .map_err(|err| err.to_string().encode())?;
+    let private_key = U256::from_big_endian(wallet.signer().to_bytes().as_slice());
+    Ok(private_key.encode().into())
+}
+
 pub fn apply<DB: Database>(
     state: &mut Cheatcodes,
     data: &mut EVMData<'_, DB>,
@@ -56,10 +71,20 @@ pub fn apply<DB: Database>(
     Some(match call {
         HEVMCalls::Addr(inner) => addr(inner.0),
         HEVMCalls::Sign(inner) => sign(inner.0, inner.1.into(), data.env.cfg.chain_id),
+        HEVMCalls::DeriveKey0(inner) => {
+            derive_key(&inner.0, DEFAULT_DERIVATION_PATH_PREFIX, inner.1)
+        }
+        HEVMCalls::DeriveKey1(inner) => derive_key(&inner.0, &inner.1, inner.2),
         HEVMCalls::Label(inner) => {
             state.labels.insert(inner.0, inner.1.clone());
             Ok(Bytes::new())
         }
+        HEVMCalls::ToString0(inner) => Ok(utils::to_checksum(&inner.0, None).encode().into()),
+        HEVMCalls::ToString1(inner) => Ok(format!("0x{}", hex::encode(&inner.0)).encode().into()),
+        HEVMCalls::ToString2(inner) => Ok(format!("0x{}", hex::encode(inner.0)).encode().into()),
+        HEVMCalls::ToString3(inner) => Ok(inner.0.to_string().encode().into()),
+        HEVMCalls::ToString4(inner) => Ok(inner.0.to_string().encode().into()),
+        HEVMCalls::ToString5(inner) => Ok(inner.0.to_string().encode().into()),
         _ => return None,
     })
 }
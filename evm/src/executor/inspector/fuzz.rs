@@ -0,0 +1,81 @@
+use bytes::Bytes;
+use ethers::types::{Address, U256};
+use revm::{db::Database, opcode, CallInputs, EVMData, Gas, Inspector, Interpreter, Return};
+use std::collections::BTreeSet;
+
+/// A branch of a `JUMPI`: the address of the executing contract, the program counter of the
+/// `JUMPI` and whether the jump was taken
+pub type Branch = (Address, usize, bool);
+
+/// An inspector that collects the values a call produces, to be used as fuzz inputs.
+///
+/// The inspector collects the slots and values of storage writes, including the ones that are
+/// overwritten later in the call, and the words of the return data of all calls. In coverage guided
+/// mode it also records the branches the call took, which guide the fuzzer.
+#[derive(Default, Debug)]
+pub struct FuzzStateCollector {
+    /// Whether the branches are recorded
+    pub coverage_guided: bool,
+    /// The collected values as big endian words
+    pub values: Vec<[u8; 32]>,
+    /// The distinct branches taken during the call
+    pub branches: BTreeSet<Branch>,
+}
+
+impl FuzzStateCollector {
+    pub fn new(coverage_guided: bool) -> Self {
+        Self { coverage_guided, ..Default::default() }
+    }
+
+    fn insert_word(&mut self, word: U256) {
+        let mut buffer = [0u8; 32];
+        word.to_big_endian(&mut buffer);
+        self.values.push(buffer);
+    }
+}
+
+impl<DB> Inspector<DB> for FuzzStateCollector
+where
+    DB: Database,
+{
+    fn step(&mut self, interpreter: &mut Interpreter, _: &mut EVMData<'_, DB>, _: bool) -> Return {
+        match interpreter.contract.code[interpreter.program_counter()] {
+            opcode::SSTORE => {
+                let slot = try_or_continue!(interpreter.stack().peek(0));
+                let value = try_or_continue!(interpreter.stack().peek(1));
+                self.insert_word(slot);
+                self.insert_word(value);
+            }
+            opcode::JUMPI if self.coverage_guided => {
+                let condition = try_or_continue!(interpreter.stack().peek(1));
+                self.branches.insert((
+                    interpreter.contract.address,
+                    interpreter.program_counter(),
+                    !condition.is_zero(),
+                ));
+            }
+            _ => {}
+        }
+
+        Return::Continue
+    }
+
+    fn call_end(
+        &mut self,
+        _: &mut EVMData<'_, DB>,
+        _: &CallInputs,
+        remaining_gas: Gas,
+        status: Return,
+        retdata: Bytes,
+        _: bool,
+    ) -> (Return, Gas, Bytes) {
+        // ABI encoded return data is a sequence of words, a shorter last word is right padded
+        for chunk in retdata.chunks(32) {
+            let mut buffer = [0u8; 32];
+            buffer[..chunk.len()].copy_from_slice(chunk);
+            self.values.push(buffer);
+        }
+
+        (status, remaining_gas, retdata)
+    }
+}
@@ -0,0 +1,81 @@
+use crate::executor::{CHEATCODE_ADDRESS, HARDHAT_CONSOLE_ADDRESS};
+use ethers::types::{Address, U256};
+use revm::{opcode, spec_opcode_gas, Database, EVMData, Inspector, Interpreter, Return};
+
+/// An inspector that measures the gas spent on calls to the test harness, i.e. cheatcodes and
+/// Hardhat-style console logs.
+///
+/// This includes the cost of the `CALL` itself, e.g. memory expansion and account access costs,
+/// as well as the code size check of the callee, so it can be deducted from the gas reported for a
+/// test.
+#[derive(Default, Debug)]
+pub struct HarnessGas {
+    /// The total amount of gas spent on calls to the harness
+    pub gas: u64,
+    /// The depth, program counter and amount of gas spent before the pending call to the harness
+    pending: Option<(u64, usize, u64)>,
+}
+
+impl<DB> Inspector<DB> for HarnessGas
+where
+    DB: Database,
+{
+    fn step(
+        &mut self,
+        interpreter: &mut Interpreter,
+        data: &mut EVMData<'_, DB>,
+        _: bool,
+    ) -> Return {
+        // The position of the address on the stack. Solidity checks the code size of the callee
+        // before most calls, so this is part of the harness overhead as well
+        let address_index = match interpreter.contract.code[interpreter.program_counter()] {
+            opcode::EXTCODESIZE => 0,
+            opcode::CALL | opcode::CALLCODE | opcode::DELEGATECALL | opcode::STATICCALL => 1,
+            _ => return Return::Continue,
+        };
+        let to = try_or_continue!(interpreter.stack().peek(address_index));
+        if is_harness_address(to) {
+            self.pending = Some((
+                data.subroutine.depth(),
+                interpreter.program_counter(),
+                interpreter.gas().spend(),
+            ));
+        }
+
+        Return::Continue
+    }
+
+    fn step_end(
+        &mut self,
+        interpreter: &mut Interpreter,
+        data: &mut EVMData<'_, DB>,
+        _: bool,
+        _: Return,
+    ) -> Return {
+        if let Some((depth, pc, spent_before)) = self.pending {
+            if depth == data.subroutine.depth() {
+                self.pending = None;
+
+                // If the opcode ends a gas block the gas of the next block was already charged
+                let op = interpreter.contract.code[pc];
+                let next_gas_block =
+                    if spec_opcode_gas(data.env.cfg.spec_id)[op as usize].gas_block_end {
+                        interpreter.contract.gas_block(pc)
+                    } else {
+                        0
+                    };
+                let spent = interpreter.gas().spend().saturating_sub(spent_before);
+                self.gas += spent.saturating_sub(next_gas_block);
+            }
+        }
+
+        Return::Continue
+    }
+}
+
+fn is_harness_address(to: U256) -> bool {
+    let mut bytes = [0u8; 32];
+    to.to_big_endian(&mut bytes);
+    let to = Address::from_slice(&bytes[12..]);
+    to == CHEATCODE_ADDRESS || to == HARDHAT_CONSOLE_ADDRESS
+}
@@ -0,0 +1,92 @@
+use ethers::types::U256;
+use revm::{opcode, Database, EVMData, Inspector, Interpreter, Return};
+
+/// An inspector that halts execution with [Return::OutOfGas] before an opcode expands the memory
+/// beyond the limit.
+///
+/// The gas limits used in tests are usually high enough to allocate large amounts of memory,
+/// which would otherwise exhaust the memory of the host.
+#[derive(Debug, Clone, Copy)]
+pub struct MemoryLimit {
+    /// The maximum size of the memory of a call frame in bytes
+    pub limit: u64,
+}
+
+impl MemoryLimit {
+    pub fn new(limit: u64) -> Self {
+        Self { limit }
+    }
+
+    /// Checks whether accessing `size` bytes at `offset` stays within the limit
+    fn check(&self, offset: U256, size: U256) -> Return {
+        if size.is_zero() {
+            return Return::Continue
+        }
+        match offset.checked_add(size) {
+            Some(end) if end <= U256::from(self.limit) => Return::Continue,
+            _ => {
+                tracing::debug!(%offset, %size, limit = self.limit, "memory limit exceeded");
+                Return::OutOfGas
+            }
+        }
+    }
+}
+
+impl<DB> Inspector<DB> for MemoryLimit
+where
+    DB: Database,
+{
+    fn step(&mut self, interpreter: &mut Interpreter, _: &mut EVMData<'_, DB>, _: bool) -> Return {
+        let op = interpreter.contract.code[interpreter.program_counter()];
+        // The stack positions of the offsets and sizes of the memory ranges the opcode accesses
+        let ranges: &[(usize, usize)] = match op {
+            opcode::MLOAD | opcode::MSTORE => {
+                let offset = try_or_continue!(interpreter.stack().peek(0));
+                return self.check(offset, 32.into())
+            }
+            opcode::MSTORE8 => {
+                let offset = try_or_continue!(interpreter.stack().peek(0));
+                return self.check(offset, 1.into())
+            }
+            opcode::SHA3 |
+            opcode::RETURN |
+            opcode::REVERT |
+            opcode::LOG0 |
+            opcode::LOG1 |
+            opcode::LOG2 |
+            opcode::LOG3 |
+            opcode::LOG4 => &[(0, 1)],
+            opcode::CALLDATACOPY | opcode::CODECOPY | opcode::RETURNDATACOPY => &[(0, 2)],
+            opcode::EXTCODECOPY => &[(1, 3)],
+            opcode::CREATE | opcode::CREATE2 => &[(1, 2)],
+            opcode::CALL | opcode::CALLCODE => &[(3, 4), (5, 6)],
+            opcode::DELEGATECALL | opcode::STATICCALL => &[(2, 3), (4, 5)],
+            _ => return Return::Continue,
+        };
+
+        for (offset, size) in ranges {
+            let offset = try_or_continue!(interpreter.stack().peek(*offset));
+            let size = try_or_continue!(interpreter.stack().peek(*size));
+            let status = self.check(offset, size);
+            if status != Return::Continue {
+                return status
+            }
+        }
+
+        Return::Continue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_check_memory_limit() {
+        let limit = MemoryLimit::new(1024);
+        assert_eq!(limit.check(0.into(), 1024.into()), Return::Continue);
+        assert_eq!(limit.check(U256::MAX, 0.into()), Return::Continue);
+        assert_eq!(limit.check(1.into(), 1024.into()), Return::OutOfGas);
+        assert_eq!(limit.check(U256::MAX, 1.into()), Return::OutOfGas);
+    }
+}
@@ -14,8 +14,22 @@ mod stack;
 pub use stack::{InspectorData, InspectorStack};
 
 mod cheatcodes;
-pub use cheatcodes::Cheatcodes;
+pub use cheatcodes::{Cheatcodes, Forks};
 
+mod harness;
+pub use harness::HarnessGas;
+
+mod memory;
+pub use memory::MemoryLimit;
+
+mod fuzz;
+pub use fuzz::{Branch, FuzzStateCollector};
+
+mod precompiles;
+pub use precompiles::{CommandPrecompile, Precompile, PrecompileResult, Precompiles};
+
+use crate::executor::fork::SharedBackend;
+use foundry_config::{FsPermissions, RpcEndpoints};
 use revm::BlockEnv;
 
 #[derive(Default, Clone, Debug)]
@@ -24,6 +38,10 @@ pub struct InspectorStackConfig {
     pub cheatcodes: bool,
     /// Whether or not the FFI cheatcode is enabled
     pub ffi: bool,
+    /// The paths the file cheatcodes are allowed to access
+    pub fs_permissions: FsPermissions,
+    /// The RPC endpoints whose aliases can be passed to `createFork`
+    pub rpc_endpoints: RpcEndpoints,
     /// The block environment
     ///
     /// Used in the cheatcode handler to overwrite the block environment separately from the
@@ -31,8 +49,20 @@ pub struct InspectorStackConfig {
     pub block: BlockEnv,
     /// Whether or not tracing is enabled
     pub tracing: bool,
+    /// Whether or not storage reads and writes are recorded in the traces
+    pub storage_tracing: bool,
     /// Whether or not the debugger is enabled
     pub debugger: bool,
+    /// The custom precompiles
+    pub precompiles: Precompiles,
+    /// The maximum size of the memory of a call frame in bytes, if any
+    pub memory_limit: Option<u64>,
+    /// The backend of the fork, if any
+    ///
+    /// Used by the cheatcodes that read the state of the forked chain, without the local changes.
+    pub fork: Option<SharedBackend>,
+    /// The forks created, selected or rolled in committed calls
+    pub forks: Forks,
 }
 
 impl InspectorStackConfig {
@@ -41,14 +71,27 @@ impl InspectorStackConfig {
             InspectorStack { logs: Some(LogCollector::default()), ..Default::default() };
 
         if self.cheatcodes {
-            stack.cheatcodes = Some(Cheatcodes::new(self.ffi, self.block.clone()));
+            stack.cheatcodes = Some(Cheatcodes::new(
+                self.ffi,
+                self.block.clone(),
+                self.fs_permissions.clone(),
+                self.rpc_endpoints.clone(),
+                self.fork.clone(),
+                self.forks.clone(),
+            ));
+            stack.harness = Some(HarnessGas::default());
         }
         if self.tracing {
-            stack.tracer = Some(Tracer::default());
+            stack.tracer =
+                Some(Tracer { record_storage: self.storage_tracing, ..Default::default() });
         }
         if self.debugger {
             stack.debugger = Some(Debugger::default());
         }
+        if !self.precompiles.is_empty() {
+            stack.precompiles = Some(self.precompiles.clone());
+        }
+        stack.memory_limit = self.memory_limit.map(MemoryLimit::new);
         stack
     }
 }
@@ -0,0 +1,179 @@
+use bytes::Bytes;
+use ethers::{abi::AbiEncode, types::Address};
+use foundry_config::PrecompileCommand;
+use revm::{db::Database, CallInputs, EVMData, Gas, Inspector, Return};
+use std::{collections::BTreeMap, fmt, process::Command, sync::Arc};
+
+/// The base gas of a call to a [CommandPrecompile] without configured gas, the gas of the
+/// `ecrecover` precompile
+const COMMAND_BASE_GAS: u64 = 3_000;
+
+/// The gas per word of the calldata and output of a call to a [CommandPrecompile] without
+/// configured gas, the gas of the `identity` precompile
+const COMMAND_WORD_GAS: u64 = 3;
+
+/// The result of a custom precompile: the gas it used and its output on success, the revert data
+/// otherwise
+pub type PrecompileResult = Result<(u64, Bytes), Bytes>;
+
+/// A custom precompiled contract
+pub trait Precompile: Send + Sync {
+    /// Executes the precompile with the calldata `input`
+    fn call(&self, input: &[u8], gas_limit: u64) -> PrecompileResult;
+}
+
+impl<F> Precompile for F
+where
+    F: Fn(&[u8], u64) -> PrecompileResult + Send + Sync,
+{
+    fn call(&self, input: &[u8], gas_limit: u64) -> PrecompileResult {
+        self(input, gas_limit)
+    }
+}
+
+/// A precompile that is implemented by an external command.
+///
+/// The command is called with the hex encoded calldata as its last argument and has to print the
+/// hex encoded output to stdout. It is considered reverted if it exits with a non-zero status.
+///
+/// Like the `ffi` cheatcode, the command is only run if `ffi` is set, calls revert otherwise.
+#[derive(Debug, Clone)]
+pub struct CommandPrecompile {
+    pub args: Vec<String>,
+    /// The gas of a call, estimated from the size of the calldata and output if not set
+    pub gas: Option<u64>,
+    pub ffi: bool,
+}
+
+impl CommandPrecompile {
+    /// Returns the estimated gas of a call with `input` that returned `output`
+    fn estimate_gas(input: &[u8], output: &[u8]) -> u64 {
+        let words = (input.len() as u64 + 31) / 32 + (output.len() as u64 + 31) / 32;
+        COMMAND_BASE_GAS + COMMAND_WORD_GAS * words
+    }
+}
+
+impl Precompile for CommandPrecompile {
+    fn call(&self, input: &[u8], _: u64) -> PrecompileResult {
+        if !self.ffi {
+            return Err("FFI disabled: run again with `--ffi` if you want to allow custom precompiles to call external commands.".to_string().encode().into())
+        }
+        let (program, args) = self
+            .args
+            .split_first()
+            .ok_or_else(|| "empty precompile command".to_string().encode())?;
+        let output = Command::new(program)
+            .args(args)
+            .arg(format!("0x{}", hex::encode(input)))
+            .output()
+            .map_err(|err| err.to_string().encode())?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string().encode().into())
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stdout = stdout.trim();
+        let decoded = hex::decode(stdout.strip_prefix("0x").unwrap_or(stdout))
+            .map_err(|err| err.to_string().encode())?;
+        let gas = self.gas.unwrap_or_else(|| Self::estimate_gas(input, &decoded));
+        Ok((gas, decoded.into()))
+    }
+}
+
+/// An inspector that executes custom precompiles for calls to their addresses.
+#[derive(Clone, Default)]
+pub struct Precompiles {
+    precompiles: BTreeMap<Address, Arc<dyn Precompile>>,
+}
+
+impl Precompiles {
+    /// Registers the `precompile` at `address`, replacing any precompile that was registered at
+    /// that address before
+    pub fn insert(&mut self, address: Address, precompile: impl Precompile + 'static) {
+        self.precompiles.insert(address, Arc::new(precompile));
+    }
+
+    /// Registers a [CommandPrecompile] for each `address -> command` entry, which only run their
+    /// command if `ffi` is set
+    pub fn from_commands(commands: &BTreeMap<Address, PrecompileCommand>, ffi: bool) -> Self {
+        let mut precompiles = Self::default();
+        for (address, command) in commands {
+            precompiles.insert(
+                *address,
+                CommandPrecompile { args: command.command().to_vec(), gas: command.gas(), ffi },
+            );
+        }
+        precompiles
+    }
+
+    /// Returns the addresses of all registered precompiles
+    pub fn addresses(&self) -> impl Iterator<Item = &Address> + '_ {
+        self.precompiles.keys()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.precompiles.is_empty()
+    }
+}
+
+impl fmt::Debug for Precompiles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Precompiles").field("addresses", &self.precompiles.keys()).finish()
+    }
+}
+
+impl<DB> Inspector<DB> for Precompiles
+where
+    DB: Database,
+{
+    fn call(
+        &mut self,
+        _: &mut EVMData<'_, DB>,
+        call: &mut CallInputs,
+        _: bool,
+    ) -> (Return, Gas, Bytes) {
+        let precompile = match self.precompiles.get(&call.contract) {
+            Some(precompile) => precompile,
+            None => return (Return::Continue, Gas::new(call.gas_limit), Bytes::new()),
+        };
+
+        let mut gas = Gas::new(call.gas_limit);
+        match precompile.call(&call.input, call.gas_limit) {
+            Ok((used, output)) => {
+                if !gas.record_cost(used) {
+                    return (Return::OutOfGas, gas, Bytes::new())
+                }
+                (Return::Return, gas, output)
+            }
+            Err(revert) => (Return::Revert, gas, revert),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_call_command_precompile() {
+        // `echo` returns the hex encoded input as is
+        let precompile = CommandPrecompile { args: vec!["echo".to_string()], gas: None, ffi: true };
+        let (gas, output) = precompile.call(&[0xde, 0xad, 0xbe, 0xef], 100_000).unwrap();
+        assert_eq!(gas, COMMAND_BASE_GAS + 2 * COMMAND_WORD_GAS);
+        assert_eq!(output.as_ref(), &[0xde, 0xad, 0xbe, 0xef]);
+
+        let precompile =
+            CommandPrecompile { args: vec!["echo".to_string()], gas: Some(5000), ffi: true };
+        assert_eq!(precompile.call(&[0xde, 0xad, 0xbe, 0xef], 100_000).unwrap().0, 5000);
+
+        let precompile =
+            CommandPrecompile { args: vec!["false".to_string()], gas: None, ffi: true };
+        assert!(precompile.call(&[], 100_000).is_err());
+    }
+
+    #[test]
+    fn command_precompile_requires_ffi() {
+        let precompile =
+            CommandPrecompile { args: vec!["echo".to_string()], gas: None, ffi: false };
+        assert!(precompile.call(&[0xde, 0xad, 0xbe, 0xef], 100_000).is_err());
+    }
+}
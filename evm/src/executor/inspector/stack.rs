@@ -1,12 +1,15 @@
-use super::{Cheatcodes, Debugger, LogCollector, Tracer};
+use super::{
+    Branch, Cheatcodes, Debugger, Forks, FuzzStateCollector, HarnessGas, LogCollector, MemoryLimit,
+    Precompiles, Tracer,
+};
 use crate::{debug::DebugArena, trace::CallTraceArena};
 use bytes::Bytes;
 use ethers::{
     abi::RawLog,
-    types::{Address, H256},
+    types::{Address, TransactionRequest, H256},
 };
 use revm::{db::Database, CallInputs, CreateInputs, EVMData, Gas, Inspector, Interpreter, Return};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 /// Helper macro to call the same method on multiple inspectors without resorting to dynamic
 /// dispatch
@@ -25,6 +28,11 @@ pub struct InspectorData {
     pub labels: BTreeMap<Address, String>,
     pub traces: Option<CallTraceArena>,
     pub debug: Option<DebugArena>,
+    pub harness_gas: u64,
+    pub fuzz_values: Vec<[u8; 32]>,
+    pub branches: BTreeSet<Branch>,
+    pub broadcastable_transactions: Vec<TransactionRequest>,
+    pub forks: Forks,
 }
 
 /// An inspector that calls multiple inspectors in sequence.
@@ -37,15 +45,31 @@ pub struct InspectorStack {
     pub logs: Option<LogCollector>,
     pub cheatcodes: Option<Cheatcodes>,
     pub debugger: Option<Debugger>,
+    pub precompiles: Option<Precompiles>,
+    pub harness: Option<HarnessGas>,
+    pub memory_limit: Option<MemoryLimit>,
+    pub fuzz_state: Option<FuzzStateCollector>,
 }
 
 impl InspectorStack {
     pub fn collect_inspector_states(self) -> InspectorData {
+        let fuzz_state = self.fuzz_state.unwrap_or_default();
+        let (labels, broadcastable_transactions, forks) = self
+            .cheatcodes
+            .map(|cheatcodes| {
+                (cheatcodes.labels, cheatcodes.broadcastable_transactions, cheatcodes.forks)
+            })
+            .unwrap_or_default();
         InspectorData {
             logs: self.logs.map(|logs| logs.logs).unwrap_or_default(),
-            labels: self.cheatcodes.map(|cheatcodes| cheatcodes.labels).unwrap_or_default(),
+            labels,
             traces: self.tracer.map(|tracer| tracer.traces),
             debug: self.debugger.map(|debugger| debugger.arena),
+            harness_gas: self.harness.map(|harness| harness.gas).unwrap_or_default(),
+            fuzz_values: fuzz_state.values,
+            branches: fuzz_state.branches,
+            broadcastable_transactions,
+            forks,
         }
     }
 }
@@ -84,7 +108,15 @@ where
     ) -> Return {
         call_inspectors!(
             inspector,
-            [&mut self.debugger, &mut self.tracer, &mut self.logs, &mut self.cheatcodes],
+            [
+                &mut self.debugger,
+                &mut self.tracer,
+                &mut self.logs,
+                &mut self.memory_limit,
+                &mut self.cheatcodes,
+                &mut self.harness,
+                &mut self.fuzz_state
+            ],
             {
                 let status = inspector.step(interpreter, data, is_static);
 
@@ -119,7 +151,13 @@ where
     ) -> Return {
         call_inspectors!(
             inspector,
-            [&mut self.debugger, &mut self.tracer, &mut self.logs, &mut self.cheatcodes],
+            [
+                &mut self.debugger,
+                &mut self.tracer,
+                &mut self.logs,
+                &mut self.cheatcodes,
+                &mut self.harness
+            ],
             {
                 let status = inspector.step_end(interpreter, data, is_static, status);
 
@@ -141,7 +179,13 @@ where
     ) -> (Return, Gas, Bytes) {
         call_inspectors!(
             inspector,
-            [&mut self.debugger, &mut self.tracer, &mut self.logs, &mut self.cheatcodes],
+            [
+                &mut self.debugger,
+                &mut self.tracer,
+                &mut self.logs,
+                &mut self.cheatcodes,
+                &mut self.precompiles
+            ],
             {
                 let (status, gas, retdata) = inspector.call(data, call, is_static);
 
@@ -166,7 +210,13 @@ where
     ) -> (Return, Gas, Bytes) {
         call_inspectors!(
             inspector,
-            [&mut self.debugger, &mut self.tracer, &mut self.logs, &mut self.cheatcodes],
+            [
+                &mut self.debugger,
+                &mut self.tracer,
+                &mut self.logs,
+                &mut self.cheatcodes,
+                &mut self.fuzz_state
+            ],
             {
                 let (new_status, new_gas, new_retdata) = inspector.call_end(
                     data,
@@ -4,8 +4,8 @@ use crate::{
         HARDHAT_CONSOLE_ADDRESS,
     },
     trace::{
-        CallTrace, CallTraceArena, LogCallOrder, RawOrDecodedCall, RawOrDecodedLog,
-        RawOrDecodedReturnData,
+        CallKind, CallTrace, CallTraceArena, LogCallOrder, RawOrDecodedCall, RawOrDecodedLog,
+        RawOrDecodedReturnData, StorageAccess,
     },
 };
 use bytes::Bytes;
@@ -13,30 +13,41 @@ use ethers::{
     abi::RawLog,
     types::{Address, H256, U256},
 };
-use revm::{return_ok, CallInputs, CreateInputs, Database, EVMData, Gas, Inspector, Return};
+use revm::{
+    opcode, return_ok, CallInputs, CreateInputs, Database, EVMData, Gas, Inspector, Interpreter,
+    Return,
+};
 
 /// An inspector that collects call traces.
 #[derive(Default, Debug)]
 pub struct Tracer {
     pub trace_stack: Vec<usize>,
     pub traces: CallTraceArena,
+    /// Whether to record the storage reads and writes of each call
+    pub record_storage: bool,
 }
 
 impl Tracer {
+    #[allow(clippy::too_many_arguments)]
     pub fn start_trace(
         &mut self,
         depth: usize,
         address: Address,
         data: Vec<u8>,
         value: U256,
-        created: bool,
+        kind: CallKind,
+        caller: Address,
+        gas_limit: u64,
     ) {
         self.trace_stack.push(self.traces.push_trace(
             0,
             CallTrace {
                 depth,
                 address,
-                created,
+                created: kind == CallKind::Create,
+                kind,
+                caller,
+                gas_limit,
                 data: RawOrDecodedCall::Raw(data),
                 value,
                 ..Default::default()
@@ -58,6 +69,40 @@ impl<DB> Inspector<DB> for Tracer
 where
     DB: Database,
 {
+    fn step(
+        &mut self,
+        interpreter: &mut Interpreter,
+        data: &mut EVMData<'_, DB>,
+        _: bool,
+    ) -> Return {
+        if !self.record_storage {
+            return Return::Continue
+        }
+
+        let new_value = match interpreter.contract.code[interpreter.program_counter()] {
+            opcode::SLOAD => None,
+            opcode::SSTORE => Some(try_or_continue!(interpreter.stack().peek(1))),
+            _ => return Return::Continue,
+        };
+        let slot = try_or_continue!(interpreter.stack().peek(0));
+        let address = interpreter.contract().address;
+
+        // Read the current value without loading the slot into the subroutine, which would make
+        // the access warm and change its gas cost
+        let value = match data.subroutine.account(address).storage.get(&slot) {
+            Some(value) => *value,
+            None => data.db.storage(address, slot),
+        };
+
+        if let Some(idx) = self.trace_stack.last() {
+            let node = &mut self.traces.arena[*idx];
+            node.ordering.push(LogCallOrder::Storage(node.storage.len()));
+            node.storage.push(StorageAccess { address, slot, value, new_value, names: vec![] });
+        }
+
+        Return::Continue
+    }
+
     fn call(
         &mut self,
         data: &mut EVMData<'_, DB>,
@@ -70,7 +115,9 @@ where
                 call.contract,
                 call.input.to_vec(),
                 call.transfer.value,
-                false,
+                call.context.scheme.into(),
+                call.context.caller,
+                call.gas_limit,
             );
         }
 
@@ -117,7 +164,9 @@ where
             get_create_address(call, nonce),
             call.init_code.to_vec(),
             call.value,
-            true,
+            CallKind::Create,
+            call.caller,
+            call.gas_limit,
         );
 
         (Return::Continue, None, Gas::new(call.gas_limit), Bytes::new())
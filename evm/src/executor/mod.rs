@@ -18,27 +18,41 @@ pub mod fork;
 pub mod builder;
 pub use builder::{ExecutorBuilder, Fork};
 
+/// Net state changes of executions
+pub mod state_diff;
+pub use state_diff::StateDiff;
+
+/// Persisting the state of executors
+pub mod state_dump;
+pub use state_dump::{AccountState, StateDump};
+
 /// Executor EVM spec identifiers
 pub use revm::SpecId;
 
 /// Executor database trait
 pub use revm::db::DatabaseRef;
 
-use self::inspector::{InspectorData, InspectorStackConfig};
+/// Executor transaction types
+pub use revm::{CreateScheme, Log, Return, TransactOut, TransactTo, TxEnv};
+
+use self::inspector::{
+    Branch, FuzzStateCollector, InspectorData, InspectorStack, InspectorStackConfig,
+};
 use crate::{debug::DebugArena, trace::CallTraceArena, CALLER};
 use bytes::Bytes;
 use ethers::{
     abi::{Abi, Detokenize, RawLog, Tokenize},
-    prelude::{decode_function_data, encode_function_data, Address, U256},
+    prelude::{decode_function_data, encode_function_data, Address, TransactionRequest, U256},
+    utils::keccak256,
 };
 use eyre::Result;
 use foundry_utils::IntoFunction;
 use hashbrown::HashMap;
 use revm::{
     db::{CacheDB, DatabaseCommit, EmptyDB},
-    return_ok, Account, BlockEnv, CreateScheme, Env, Return, TransactOut, TransactTo, TxEnv, EVM,
+    return_ok, Account, BlockEnv, Env, EVM, KECCAK_EMPTY,
 };
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 /// A mapping of addresses to their changed state.
 pub type StateChangeset = HashMap<Address, Account>;
@@ -52,6 +66,7 @@ pub enum EvmError {
         reason: String,
         gas: u64,
         stipend: u64,
+        harness_gas: u64,
         logs: Vec<RawLog>,
         traces: Option<CallTraceArena>,
         debug: Option<DebugArena>,
@@ -92,6 +107,8 @@ pub struct CallResult<D: Detokenize> {
     pub gas: u64,
     /// The initial gas stipend for the transaction
     pub stipend: u64,
+    /// The gas spent on calls to the test harness, i.e. cheatcodes and console logs
+    pub harness_gas: u64,
     /// The logs emitted during the call
     pub logs: Vec<RawLog>,
     /// The labels assigned to addresses during the call
@@ -107,6 +124,31 @@ pub struct CallResult<D: Detokenize> {
     pub state_changeset: Option<StateChangeset>,
 }
 
+/// The result of a transaction executed with [Executor::transact] or
+/// [Executor::transact_committing]
+#[derive(Debug)]
+pub struct TransactResult {
+    /// The status of the transaction
+    pub status: Return,
+    /// The return data of a call or the address and runtime code of a created contract
+    pub out: TransactOut,
+    /// The gas used by the transaction, including the initial gas stipend
+    pub gas: u64,
+    /// The logs emitted during the transaction
+    pub logs: Vec<Log>,
+    /// The traces of the transaction
+    pub traces: Option<CallTraceArena>,
+    /// The changes the transaction made to the state
+    pub state_changeset: StateChangeset,
+}
+
+impl TransactResult {
+    /// Whether the transaction was successful
+    pub fn is_success(&self) -> bool {
+        matches!(self.status, return_ok!())
+    }
+}
+
 /// The result of a raw call.
 #[derive(Debug)]
 pub struct RawCallResult {
@@ -120,6 +162,8 @@ pub struct RawCallResult {
     pub gas: u64,
     /// The initial gas stipend for the transaction
     pub stipend: u64,
+    /// The gas spent on calls to the test harness, i.e. cheatcodes and console logs
+    pub harness_gas: u64,
     /// The logs emitted during the call
     pub logs: Vec<RawLog>,
     /// The labels assigned to addresses during the call
@@ -133,6 +177,14 @@ pub struct RawCallResult {
     /// This is only present if the changed state was not committed to the database (i.e. if you
     /// used `call` and `call_raw` not `call_committing` or `call_raw_committing`).
     pub state_changeset: Option<StateChangeset>,
+    /// The storage slots and values written and the return data of all calls as big endian
+    /// words, only collected by [`Executor::call_raw_collecting`]
+    pub fuzz_values: Vec<[u8; 32]>,
+    /// The distinct branches taken during the call, only collected by
+    /// [`Executor::call_raw_collecting`] in coverage guided mode
+    pub branches: BTreeSet<Branch>,
+    /// The calls and creates made while broadcasting with the `broadcast` cheatcodes
+    pub broadcastable_transactions: Vec<TransactionRequest>,
 }
 
 impl Default for RawCallResult {
@@ -143,15 +195,20 @@ impl Default for RawCallResult {
             result: Bytes::new(),
             gas: 0,
             stipend: 0,
+            harness_gas: 0,
             logs: Vec::new(),
             labels: BTreeMap::new(),
             traces: None,
             debug: None,
             state_changeset: None,
+            fuzz_values: Vec::new(),
+            branches: BTreeSet::new(),
+            broadcastable_transactions: Vec::new(),
         }
     }
 }
 
+#[derive(Clone)]
 pub struct Executor<DB: DatabaseRef> {
     // Note: We do not store an EVM here, since we are really
     // only interested in the database. REVM's `EVM` is a thin
@@ -164,6 +221,8 @@ pub struct Executor<DB: DatabaseRef> {
     pub(crate) db: CacheDB<DB>,
     env: Env,
     inspector_config: InspectorStackConfig,
+    /// The accounts and storage slots set or committed on top of the underlying database
+    touched: BTreeMap<Address, BTreeSet<U256>>,
 }
 
 impl<DB> Executor<DB>
@@ -179,8 +238,15 @@ where
             CHEATCODE_ADDRESS,
             revm::AccountInfo { code: Some(Bytes::from_static(&[1])), ..Default::default() },
         );
+        // Same for the custom precompiles
+        for address in inspector_config.precompiles.addresses() {
+            db.insert_cache(
+                *address,
+                revm::AccountInfo { code: Some(Bytes::from_static(&[1])), ..Default::default() },
+            );
+        }
 
-        Executor { db, env, inspector_config }
+        Executor { db, env, inspector_config, touched: Default::default() }
     }
 
     /// Set the balance of an account.
@@ -189,6 +255,7 @@ where
         account.balance = amount;
 
         self.db.insert_cache(address, account);
+        self.touched.entry(address).or_default();
     }
 
     /// Gets the balance of an account
@@ -196,12 +263,107 @@ where
         self.db.basic(address).balance
     }
 
+    /// Gets the nonce of an account
+    pub fn get_nonce(&self, address: Address) -> u64 {
+        self.db.basic(address).nonce
+    }
+
+    /// Gets the runtime code of an account
+    pub fn get_code(&self, address: Address) -> Bytes {
+        let info = self.db.basic(address);
+        match info.code {
+            Some(code) => code,
+            None if info.code_hash == KECCAK_EMPTY => Bytes::new(),
+            None => self.db.code_by_hash(info.code_hash),
+        }
+    }
+
+    /// Gets the value of a storage slot of an account
+    pub fn get_storage(&self, address: Address, slot: U256) -> U256 {
+        self.db.storage(address, slot)
+    }
+
+    /// Set the runtime code of an account.
+    pub fn set_code(&mut self, address: Address, code: Bytes) {
+        let mut account = self.db.basic(address);
+        account.code_hash = if code.is_empty() { KECCAK_EMPTY } else { keccak256(&code).into() };
+        account.code = Some(code);
+
+        self.db.insert_cache(address, account);
+        self.touched.entry(address).or_default();
+    }
+
+    /// Set the value of a storage slot of an account.
+    pub fn set_storage(&mut self, address: Address, slot: U256, value: U256) {
+        // Make sure the account is cached before its storage
+        let account = self.db.basic(address);
+        self.db.insert_cache(address, account);
+        self.db.insert_cache_storage(address, slot, value);
+        self.touched.entry(address).or_default().insert(slot);
+    }
+
+    /// Returns the execution environment
+    pub fn env(&self) -> &Env {
+        &self.env
+    }
+
+    /// Returns the execution environment, e.g. to advance the block
+    pub fn env_mut(&mut self) -> &mut Env {
+        &mut self.env
+    }
+
     /// Set the nonce of an account.
     pub fn set_nonce(&mut self, address: Address, nonce: u64) {
         let mut account = self.db.basic(address);
         account.nonce = nonce;
 
         self.db.insert_cache(address, account);
+        self.touched.entry(address).or_default();
+    }
+
+    /// Returns the state of the accounts set or committed on top of the underlying database
+    pub fn dump_state(&self) -> StateDump {
+        let accounts = self
+            .touched
+            .iter()
+            .map(|(address, slots)| {
+                let account = AccountState {
+                    balance: self.get_balance(*address),
+                    nonce: self.get_nonce(*address),
+                    code: self.get_code(*address).into(),
+                    storage: slots
+                        .iter()
+                        .map(|slot| (*slot, self.get_storage(*address, *slot)))
+                        .filter(|(_, value)| !value.is_zero())
+                        .collect(),
+                };
+                (*address, account)
+            })
+            .collect();
+        StateDump { accounts }
+    }
+
+    /// Sets the state of the accounts in `state`, e.g. to restore a state written by
+    /// [`Self::dump_state`]
+    pub fn load_state(&mut self, state: &StateDump) {
+        for (address, account) in &state.accounts {
+            self.set_balance(*address, account.balance);
+            self.set_nonce(*address, account.nonce);
+            if !account.code.0.is_empty() {
+                self.set_code(*address, account.code.0.clone());
+            }
+            for (slot, value) in &account.storage {
+                self.set_storage(*address, *slot, *value);
+            }
+        }
+    }
+
+    /// Commits `state_changeset` to the database, recording the changed accounts and slots
+    fn commit(&mut self, state_changeset: StateChangeset) {
+        for (address, account) in &state_changeset {
+            self.touched.entry(*address).or_default().extend(account.storage.keys().copied());
+        }
+        self.db.commit(state_changeset);
     }
 
     /// Calls the `setUp()` function on a contract.
@@ -229,11 +391,13 @@ where
             reverted,
             gas,
             stipend,
+            harness_gas,
             logs,
             labels,
             traces,
             debug,
             state_changeset,
+            ..
         } = self.call_raw_committing(from, to, calldata, value)?;
         match status {
             return_ok!() => {
@@ -243,6 +407,7 @@ where
                     result,
                     gas,
                     stipend,
+                    harness_gas,
                     logs,
                     labels,
                     traces,
@@ -258,6 +423,7 @@ where
                     reason,
                     gas,
                     stipend,
+                    harness_gas,
                     logs,
                     traces,
                     debug,
@@ -283,31 +449,52 @@ where
         // Build VM
         let mut evm = EVM::new();
         evm.env = self.build_env(from, TransactTo::Call(to), calldata, value);
-        evm.database(&mut self.db);
+        evm.database(&self.db);
 
         // Run the call
         let mut inspector = self.inspector_config.stack();
-        let (status, out, gas, _) = evm.inspect_commit(&mut inspector);
+        let (status, out, gas, state_changeset, _) = evm.inspect_ref(&mut inspector);
         let result = match out {
             TransactOut::Call(data) => data,
             _ => Bytes::default(),
         };
 
-        // Persist the changed block environment
+        // Persist the changed block environment and chain id
         self.inspector_config.block = evm.env.block.clone();
+        self.env.cfg.chain_id = evm.env.cfg.chain_id;
+        self.commit(state_changeset);
+
+        let InspectorData {
+            logs,
+            labels,
+            traces,
+            debug,
+            harness_gas,
+            broadcastable_transactions,
+            forks,
+            ..
+        } = inspector.collect_inspector_states();
+
+        // Persist the forks, like the block environment
+        if !forks.is_empty() {
+            self.inspector_config.forks = forks;
+        }
 
-        let InspectorData { logs, labels, traces, debug } = inspector.collect_inspector_states();
         Ok(RawCallResult {
             status,
             reverted: !matches!(status, return_ok!()),
             result,
             gas,
             stipend,
+            harness_gas,
             logs: logs.to_vec(),
             labels,
             traces,
             debug,
             state_changeset: None,
+            fuzz_values: Vec::new(),
+            branches: BTreeSet::new(),
+            broadcastable_transactions,
         })
     }
 
@@ -331,11 +518,13 @@ where
             reverted,
             gas,
             stipend,
+            harness_gas,
             logs,
             labels,
             traces,
             debug,
             state_changeset,
+            ..
         } = self.call_raw(from, to, calldata, value)?;
         match status {
             return_ok!() => {
@@ -345,6 +534,7 @@ where
                     result,
                     gas,
                     stipend,
+                    harness_gas,
                     logs,
                     labels,
                     traces,
@@ -360,6 +550,7 @@ where
                     reason,
                     gas,
                     stipend,
+                    harness_gas,
                     logs,
                     traces,
                     debug,
@@ -379,6 +570,34 @@ where
         to: Address,
         calldata: Bytes,
         value: U256,
+    ) -> Result<RawCallResult> {
+        self.call_raw_with_inspector(self.inspector_config.stack(), from, to, calldata, value)
+    }
+
+    /// Performs a raw call like [`Self::call_raw`] and collects the storage slots and values the
+    /// call writes and the return data of all calls into [`RawCallResult::fuzz_values`]. If
+    /// `coverage_guided` is set, the branches the call took are collected into
+    /// [`RawCallResult::branches`].
+    pub fn call_raw_collecting(
+        &self,
+        from: Address,
+        to: Address,
+        calldata: Bytes,
+        value: U256,
+        coverage_guided: bool,
+    ) -> Result<RawCallResult> {
+        let mut inspector = self.inspector_config.stack();
+        inspector.fuzz_state = Some(FuzzStateCollector::new(coverage_guided));
+        self.call_raw_with_inspector(inspector, from, to, calldata, value)
+    }
+
+    fn call_raw_with_inspector(
+        &self,
+        mut inspector: InspectorStack,
+        from: Address,
+        to: Address,
+        calldata: Bytes,
+        value: U256,
     ) -> Result<RawCallResult> {
         let stipend = stipend(&calldata, self.env.cfg.spec_id);
 
@@ -388,25 +607,37 @@ where
         evm.database(&self.db);
 
         // Run the call
-        let mut inspector = self.inspector_config.stack();
         let (status, out, gas, state_changeset, _) = evm.inspect_ref(&mut inspector);
         let result = match out {
             TransactOut::Call(data) => data,
             _ => Bytes::default(),
         };
 
-        let InspectorData { logs, labels, traces, debug } = inspector.collect_inspector_states();
+        let InspectorData {
+            logs,
+            labels,
+            traces,
+            debug,
+            harness_gas,
+            fuzz_values,
+            branches,
+            broadcastable_transactions,
+        } = inspector.collect_inspector_states();
         Ok(RawCallResult {
             status,
             reverted: !matches!(status, return_ok!()),
             result,
             gas,
             stipend,
+            harness_gas,
             logs: logs.to_vec(),
             labels,
             traces,
             debug,
             state_changeset: Some(state_changeset),
+            fuzz_values,
+            branches,
+            broadcastable_transactions,
         })
     }
 
@@ -414,10 +645,11 @@ where
     pub fn deploy(&mut self, from: Address, code: Bytes, value: U256) -> Result<DeployResult> {
         let mut evm = EVM::new();
         evm.env = self.build_env(from, TransactTo::Create(CreateScheme::Create), code, value);
-        evm.database(&mut self.db);
+        evm.database(&self.db);
 
         let mut inspector = self.inspector_config.stack();
-        let (status, out, gas, _) = evm.inspect_commit(&mut inspector);
+        let (status, out, gas, state_changeset, _) = evm.inspect_ref(&mut inspector);
+        self.commit(state_changeset);
         let address = match out {
             TransactOut::Create(_, Some(addr)) => addr,
             // TODO: We should have better error handling logic in the test runner
@@ -429,6 +661,40 @@ where
         Ok(DeployResult { address, gas, logs, traces, debug })
     }
 
+    /// Executes a transaction as is, i.e. unlike calls it is charged with its own gas price, and
+    /// commits the new state to the underlying database.
+    ///
+    /// The nonce of the transaction is not checked.
+    pub fn transact_committing(&mut self, tx: TxEnv) -> TransactResult {
+        let env = Env { cfg: self.env.cfg.clone(), block: self.env.block.clone(), tx };
+        let result = self.transact_with_env(env);
+        self.commit(result.state_changeset.clone());
+        result
+    }
+
+    /// Executes a transaction on the current state of the VM without charging any fees, e.g. to
+    /// estimate its gas.
+    ///
+    /// The state after the transaction is not persisted.
+    pub fn transact(&self, tx: TxEnv) -> TransactResult {
+        self.transact_with_env(Env {
+            cfg: self.env.cfg.clone(),
+            block: BlockEnv { basefee: 0.into(), ..self.env.block.clone() },
+            tx: TxEnv { gas_price: 0.into(), gas_priority_fee: None, ..tx },
+        })
+    }
+
+    fn transact_with_env(&self, env: Env) -> TransactResult {
+        let mut evm = EVM::new();
+        evm.env = env;
+        evm.database(&self.db);
+
+        let mut inspector = self.inspector_config.stack();
+        let (status, out, gas, state_changeset, logs) = evm.inspect_ref(&mut inspector);
+        let InspectorData { traces, .. } = inspector.collect_inspector_states();
+        TransactResult { status, out, gas, logs, traces, state_changeset }
+    }
+
     /// Check if a call to a test contract was successful.
     ///
     /// This function checks both the VM status of the call and DSTest's `failed`.
@@ -464,6 +730,11 @@ where
         should_fail ^ success
     }
 
+    /// Computes the net state changes of a changeset against the current state of the VM
+    pub fn state_diff(&self, state_changeset: &StateChangeset) -> StateDiff {
+        StateDiff::new(&self.db, state_changeset)
+    }
+
     fn build_env(&self, caller: Address, transact_to: TransactTo, data: Bytes, value: U256) -> Env {
         Env {
             cfg: self.env.cfg.clone(),
@@ -1,12 +1,12 @@
 use ethers::{
-    providers::{Middleware, Provider},
+    providers::{Middleware, Provider, Ws},
     types::{Address, U256},
 };
 use foundry_utils::RuntimeOrHandle;
 use revm::{BlockEnv, CfgEnv, SpecId, TxEnv};
 use serde::{Deserialize, Serialize};
 
-use super::fork::environment;
+use super::fork::{fork_environment, is_ws_url};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct EvmOpts {
@@ -32,6 +32,10 @@ pub struct EvmOpts {
     /// enables the FFI cheatcode
     pub ffi: bool,
 
+    /// the maximum size of the memory of a call frame in bytes, `0` disables the limit
+    #[serde(default)]
+    pub memory_limit: u64,
+
     /// Verbosity mode of EVM output as number of occurences
     pub verbosity: u8,
 }
@@ -39,16 +43,11 @@ pub struct EvmOpts {
 impl EvmOpts {
     pub fn evm_env(&self) -> revm::Env {
         if let Some(ref fork_url) = self.fork_url {
-            let rt = RuntimeOrHandle::new();
-            let provider =
-                Provider::try_from(fork_url.as_str()).expect("could not instantiated provider");
             let fut =
-                environment(&provider, self.env.chain_id, self.fork_block_number, self.sender);
-            match rt {
-                RuntimeOrHandle::Runtime(runtime) => runtime.block_on(fut),
-                RuntimeOrHandle::Handle(handle) => handle.block_on(fut),
-            }
-            .expect("could not instantiate forked environment")
+                fork_environment(fork_url, self.env.chain_id, self.fork_block_number, self.sender);
+            RuntimeOrHandle::new()
+                .block_on(fut)
+                .unwrap_or_else(|err| panic!("could not instantiate forked environment: {}", err))
         } else {
             revm::Env {
                 block: BlockEnv {
@@ -89,10 +88,18 @@ impl EvmOpts {
                 tracing::trace!("auto detected mainnet chain from url {}", url);
                 return Chain::Mainnet as u64
             }
-            let provider = Provider::try_from(url.as_str())
-                .unwrap_or_else(|_| panic!("Failed to establish provider to {}", url));
-
-            if let Ok(id) = foundry_utils::RuntimeOrHandle::new().block_on(provider.get_chainid()) {
+            let chain_id = foundry_utils::RuntimeOrHandle::new().block_on(async {
+                if is_ws_url(url) {
+                    Provider::<Ws>::connect(url.as_str()).await.ok()?.get_chainid().await.ok()
+                } else {
+                    Provider::try_from(url.as_str())
+                        .unwrap_or_else(|_| panic!("Failed to establish provider to {}", url))
+                        .get_chainid()
+                        .await
+                        .ok()
+                }
+            });
+            if let Some(id) = chain_id {
                 return id.as_u64()
             }
         }
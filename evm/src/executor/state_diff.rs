@@ -0,0 +1,138 @@
+use super::StateChangeset;
+use ethers::types::{Address, U256};
+use revm::{db::DatabaseRef, AccountInfo};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A value before and after an execution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Change<T> {
+    pub before: T,
+    pub after: T,
+}
+
+impl<T: PartialEq> Change<T> {
+    /// Returns the change if the value changed
+    fn new(before: T, after: T) -> Option<Self> {
+        (before != after).then(|| Self { before, after })
+    }
+}
+
+/// The net changes of a single account
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountDiff {
+    /// Whether the account was empty before the execution
+    pub created: bool,
+    pub balance: Option<Change<U256>>,
+    pub nonce: Option<Change<u64>>,
+    /// All storage slots whose value changed
+    pub storage: BTreeMap<U256, Change<U256>>,
+    /// The runtime code of the account after the execution, used to identify the contract
+    #[serde(skip)]
+    pub code: Option<Vec<u8>>,
+}
+
+impl AccountDiff {
+    /// Returns `true` if nothing changed
+    pub fn is_empty(&self) -> bool {
+        !self.created && self.balance.is_none() && self.nonce.is_none() && self.storage.is_empty()
+    }
+}
+
+/// The net state changes of an execution
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDiff {
+    pub accounts: BTreeMap<Address, AccountDiff>,
+}
+
+impl StateDiff {
+    /// Computes the diff of the `state_changeset` against the state in `db`.
+    ///
+    /// Accounts and storage slots that were only loaded during the execution are not included.
+    pub fn new<DB: DatabaseRef>(db: &DB, state_changeset: &StateChangeset) -> Self {
+        let mut accounts = BTreeMap::new();
+        for (address, account) in state_changeset {
+            let before = db.basic(*address);
+            let after = &account.info;
+
+            let mut diff = AccountDiff {
+                created: is_empty_account(&before) && !is_empty_account(after),
+                balance: Change::new(before.balance, after.balance),
+                nonce: Change::new(before.nonce, after.nonce),
+                storage: account
+                    .storage
+                    .iter()
+                    .filter_map(|(slot, value)| {
+                        Change::new(db.storage(*address, *slot), *value).map(|c| (*slot, c))
+                    })
+                    .collect(),
+                code: None,
+            };
+            if !diff.is_empty() {
+                diff.code = after.code.as_ref().map(|code| code.to_vec());
+                accounts.insert(*address, diff);
+            }
+        }
+        Self { accounts }
+    }
+
+    /// Drops the nonce change of the account, e.g. the nonce increase of the caller of a
+    /// transaction
+    pub fn ignore_nonce(&mut self, address: Address) {
+        if let Some(account) = self.accounts.get_mut(&address) {
+            account.nonce = None;
+            if account.is_empty() {
+                self.accounts.remove(&address);
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+}
+
+fn is_empty_account(info: &AccountInfo) -> bool {
+    info.balance.is_zero() &&
+        info.nonce == 0 &&
+        info.code.as_ref().map(|code| code.is_empty()).unwrap_or(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::{db::EmptyDB, Account};
+
+    #[test]
+    fn can_diff_state_changeset() {
+        let untouched = Address::random();
+        let created = Address::random();
+        let mut changeset = StateChangeset::new();
+        changeset.insert(untouched, Account::from(AccountInfo::default()));
+
+        let mut account = Account::from(AccountInfo {
+            balance: 100u64.into(),
+            nonce: 1,
+            code: Some(vec![0x60, 0x00].into()),
+            ..Default::default()
+        });
+        account.storage.insert(1u64.into(), 42u64.into());
+        account.storage.insert(2u64.into(), U256::zero());
+        changeset.insert(created, account);
+
+        let diff = StateDiff::new(&EmptyDB(), &changeset);
+        assert_eq!(diff.accounts.len(), 1);
+        let account = &diff.accounts[&created];
+        assert!(account.created);
+        assert_eq!(account.balance, Some(Change { before: U256::zero(), after: 100u64.into() }));
+        assert_eq!(account.nonce, Some(Change { before: 0, after: 1 }));
+        assert_eq!(
+            account.storage,
+            BTreeMap::from([(1u64.into(), Change { before: U256::zero(), after: 42u64.into() })])
+        );
+
+        let mut diff = diff;
+        diff.ignore_nonce(created);
+        assert!(diff.accounts[&created].nonce.is_none());
+    }
+}
@@ -0,0 +1,70 @@
+use ethers::types::{Address, Bytes, U256};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path};
+
+/// The state of an account
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountState {
+    pub balance: U256,
+    pub nonce: u64,
+    pub code: Bytes,
+    /// The non-zero storage slots of the account
+    #[serde(default)]
+    pub storage: BTreeMap<U256, U256>,
+}
+
+/// The state of the accounts of an executor, written by `--dump-state` and read by
+/// `--load-state`.
+///
+/// Only the accounts changed on top of the underlying database are kept, e.g. the contracts
+/// deployed by a script but not the untouched state of a fork.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDump {
+    pub accounts: BTreeMap<Address, AccountState>,
+}
+
+impl StateDump {
+    pub fn read(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|err| eyre::eyre!("failed to read state from {}: {}", path.display(), err))?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>) -> eyre::Result<()> {
+        Ok(std::fs::write(path, serde_json::to_string_pretty(self)?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor::{inspector::InspectorStackConfig, Executor};
+    use revm::{db::EmptyDB, Env};
+
+    #[test]
+    fn can_dump_and_load_executor_state() {
+        let executor = || Executor::new(EmptyDB(), Env::default(), InspectorStackConfig::default());
+        let (mut executor, mut loaded) = (executor(), executor());
+        let address = Address::repeat_byte(1);
+        executor.set_balance(address, 1000u64.into());
+        executor.set_nonce(address, 2);
+        executor.set_code(address, vec![0x60, 0x00].into());
+        executor.set_storage(address, 1u64.into(), 42u64.into());
+        executor.set_storage(address, 2u64.into(), U256::zero());
+
+        let state = executor.dump_state();
+        assert_eq!(
+            state.accounts[&address],
+            AccountState {
+                balance: 1000u64.into(),
+                nonce: 2,
+                code: vec![0x60, 0x00].into(),
+                storage: BTreeMap::from([(1u64.into(), 42u64.into())]),
+            }
+        );
+
+        loaded.load_state(&state);
+        assert_eq!(loaded.dump_state(), state);
+    }
+}
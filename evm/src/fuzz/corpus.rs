@@ -0,0 +1,65 @@
+//! Support for the counterexamples of fuzz tests that are replayed on later runs
+
+use ethers::types::Bytes;
+use std::path::{Path, PathBuf};
+
+/// The calldata of the past counterexamples of a fuzz test, stored as a JSON array of hex strings
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FuzzCorpus {
+    path: PathBuf,
+    calldata: Vec<Bytes>,
+}
+
+impl FuzzCorpus {
+    /// Loads the corpus stored at `path`, a missing or unreadable file is an empty corpus
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        let calldata = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self { path: path.to_path_buf(), calldata }
+    }
+
+    /// The stored counterexamples, oldest first
+    pub fn calldata(&self) -> &[Bytes] {
+        &self.calldata
+    }
+
+    /// Adds a counterexample, returns `false` if it is already stored
+    pub fn insert(&mut self, calldata: Bytes) -> bool {
+        if self.calldata.contains(&calldata) {
+            return false
+        }
+        self.calldata.push(calldata);
+        true
+    }
+
+    /// Writes the corpus to its file, creating the parent directories if necessary
+    pub fn save(&self) -> eyre::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, serde_json::to_string_pretty(&self.calldata)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_store_counterexamples() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("FuzzTest").join("testFuzz(uint256).json");
+
+        let mut corpus = FuzzCorpus::load(&path);
+        assert!(corpus.calldata().is_empty());
+        assert!(corpus.insert(Bytes::from(vec![1, 2, 3])));
+        assert!(!corpus.insert(Bytes::from(vec![1, 2, 3])));
+        corpus.save().unwrap();
+
+        assert_eq!(FuzzCorpus::load(&path), corpus);
+    }
+}
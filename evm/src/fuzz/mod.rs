@@ -1,27 +1,50 @@
-mod strategies;
+/// Strategies to generate fuzz inputs, e.g. for custom fuzz campaigns
+pub mod strategies;
+pub use strategies::{
+    actor_addresses, check_function, fuzz_calldata, fuzz_param, fuzz_param_from_state,
+    EvmFuzzState, FuzzParamOpts, FuzzRange, UnsupportedParam,
+};
+
+mod corpus;
+pub use corpus::FuzzCorpus;
 
-pub use proptest::test_runner::{Config as FuzzConfig, Reason};
+pub use proptest::test_runner::{Config as FuzzConfig, Reason, TestRunner};
 
 use crate::{
-    executor::{Executor, RawCallResult},
+    executor::{inspector::Branch, Executor, RawCallResult},
     trace::CallTraceArena,
 };
 use ethers::{
     abi::{Abi, Function, RawLog, Token},
     types::{Address, Bytes},
 };
-use proptest::test_runner::{TestCaseError, TestError, TestRunner};
+use proptest::test_runner::{TestCaseError, TestError};
+use rayon::prelude::*;
 use revm::db::DatabaseRef;
 use serde::{Deserialize, Serialize};
-use std::{cell::RefCell, collections::BTreeMap, fmt};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 use strategies::{
-    build_initial_state, collect_state_from_call, fuzz_calldata, fuzz_calldata_from_state,
-    EvmFuzzState,
+    build_initial_state, build_known_addresses, collect_addresses, collect_state_from_call,
+    deployed_functions, fuzz_calldata_with_state, fuzz_sender, CoverageCorpus, KnownValues,
 };
 
 /// Magic return code for the `assume` cheatcode
 pub const ASSUME_MAGIC_RETURN_CODE: &[u8] = b"FOUNDRY::ASSUME";
 
+/// The number of runs between checks of the time budget of a fuzz test
+const TIME_BUDGET_BATCH: u32 = 32;
+
+/// The maximum number of fuzz cases a worker keeps, e.g. of a fuzz test with a time budget. Once
+/// reached, every other case is dropped and only every other later case is kept, so the kept cases
+/// stay spread over all runs.
+const MAX_KEPT_CASES: usize = 1 << 16;
+
 /// Wrapper around an [`Executor`] which provides fuzzing support using [`proptest`](https://docs.rs/proptest/1.0.0/proptest/).
 ///
 /// After instantiation, calling `fuzz` will proceed to hammer the deployed smart contract with
@@ -34,6 +57,21 @@ pub struct FuzzedExecutor<'a, DB: DatabaseRef> {
     runner: TestRunner,
     /// The account that calls tests
     sender: Address,
+    /// The settings of the strategies of the fuzzed params
+    param_opts: FuzzParamOpts,
+    /// The file of the counterexamples of past runs of the fuzzed test
+    corpus: Option<PathBuf>,
+    /// The ranges of the values of params, keyed by param name
+    param_ranges: BTreeMap<String, FuzzRange>,
+    /// The ABIs and runtime code of the known contracts, the functions of the deployed ones are
+    /// used to generate calldata for `bytes` params
+    known_contracts: Option<&'a BTreeMap<String, (Abi, Vec<u8>)>>,
+    /// The wall clock time a test is fuzzed for, instead of the number of runs of the fuzzer
+    max_time: Option<Duration>,
+    /// The number of threads the runs of a test are split across
+    workers: usize,
+    /// The calldata of a counterexample that is replayed instead of fuzzing the test, if any
+    replay: Option<Bytes>,
 }
 
 impl<'a, DB> FuzzedExecutor<'a, DB>
@@ -42,7 +80,75 @@ where
 {
     /// Instantiates a fuzzed executor given a testrunner
     pub fn new(executor: &'a Executor<DB>, runner: TestRunner, sender: Address) -> Self {
-        Self { executor, runner, sender }
+        Self {
+            executor,
+            runner,
+            sender,
+            param_opts: Default::default(),
+            corpus: None,
+            param_ranges: Default::default(),
+            known_contracts: None,
+            max_time: None,
+            workers: 1,
+            replay: None,
+        }
+    }
+
+    /// Sets how the fuzzed params are generated, like the share of edge cases of ints
+    #[must_use]
+    pub fn with_param_opts(mut self, param_opts: FuzzParamOpts) -> Self {
+        self.param_opts = param_opts;
+        self
+    }
+
+    /// Fuzzes each test until `max_time` passed or it failed, instead of for the number of runs of
+    /// the fuzzer. The budget is checked every few runs, so it can be exceeded by a few runs.
+    #[must_use]
+    pub fn with_max_time(mut self, max_time: Option<Duration>) -> Self {
+        self.max_time = max_time;
+        self
+    }
+
+    /// Splits the runs of each test across `workers` threads, each with its own fuzzer and fuzz
+    /// state. The calls of the test do not commit to the database, so the threads share it.
+    #[must_use]
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    /// Replays the counterexamples stored in the file before generating new inputs and stores new
+    /// counterexamples in it
+    #[must_use]
+    pub fn with_corpus(mut self, corpus: Option<PathBuf>) -> Self {
+        self.corpus = corpus;
+        self
+    }
+
+    /// Only calls the test with the `calldata` of a counterexample from the sender, instead of
+    /// fuzzing it
+    #[must_use]
+    pub fn with_replay(mut self, calldata: Option<Bytes>) -> Self {
+        self.replay = calldata;
+        self
+    }
+
+    /// Draws the values of the params with a range, keyed by param name, from their range
+    #[must_use]
+    pub fn with_param_ranges(mut self, param_ranges: BTreeMap<String, FuzzRange>) -> Self {
+        self.param_ranges = param_ranges;
+        self
+    }
+
+    /// Generates `bytes` params that are calldata of the functions of the known contracts that
+    /// are deployed, for contracts that forward `bytes` to a call
+    #[must_use]
+    pub fn with_known_contracts(
+        mut self,
+        known_contracts: &'a BTreeMap<String, (Abi, Vec<u8>)>,
+    ) -> Self {
+        self.known_contracts = Some(known_contracts);
+        self
     }
 
     /// Fuzzes the provided function, assuming it is available at the contract at `address`
@@ -56,39 +162,184 @@ where
         address: Address,
         should_fail: bool,
         errors: Option<&Abi>,
+    ) -> FuzzTestResult
+    where
+        DB: Sync,
+    {
+        let runners = self.worker_runners();
+        if runners.len() <= 1 || self.replay.is_some() {
+            return self.fuzz_worker(
+                func,
+                address,
+                should_fail,
+                errors,
+                self.runner.clone(),
+                self.corpus.as_ref(),
+            )
+        }
+
+        // Only the first worker replays and stores the counterexamples of past runs, so the
+        // workers do not race on the file
+        let results = runners
+            .into_par_iter()
+            .enumerate()
+            .map(|(idx, runner)| {
+                let corpus = if idx == 0 { self.corpus.as_ref() } else { None };
+                self.fuzz_worker(func, address, should_fail, errors, runner, corpus)
+            })
+            .collect::<Vec<_>>();
+
+        // The outcome of the first worker that failed, or of the first worker if none failed, with
+        // the cases and rejects of all workers
+        let failed = results.iter().position(|result| !result.success);
+        let mut cases = Vec::new();
+        let mut runs = 0;
+        let mut rejects = 0;
+        let mut outcome = None;
+        for (idx, mut result) in results.into_iter().enumerate() {
+            runs += result.cases.runs();
+            cases.extend(
+                std::mem::replace(&mut result.cases, FuzzedCases::new(vec![])).into_cases(),
+            );
+            rejects += result.rejects;
+            if idx == failed.unwrap_or_default() {
+                outcome = Some(result);
+            }
+        }
+        let outcome = outcome.expect("there is more than one worker");
+
+        if let (Some(path), Some(counterexample)) = (&self.corpus, &outcome.counterexample) {
+            if failed != Some(0) {
+                store_counterexample(&mut FuzzCorpus::load(path), &counterexample.calldata);
+            }
+        }
+
+        FuzzTestResult { cases: FuzzedCases::with_runs(cases, runs), rejects, ..outcome }
+    }
+
+    /// Returns the fuzzers of the workers, which split the runs of the fuzzer and draw their
+    /// rngs from it so runs stay reproducible with the same seed
+    fn worker_runners(&self) -> Vec<TestRunner> {
+        let mut runner = self.runner.clone();
+        let cases = runner.config().cases;
+        // With a time budget every worker fuzzes until the time is up
+        let workers = match self.max_time {
+            Some(_) => self.workers as u32,
+            None => (self.workers as u32).min(cases).max(1),
+        };
+        (0..workers)
+            .map(|idx| {
+                let cases = match self.max_time {
+                    Some(_) => cases,
+                    None => cases / workers + u32::from(idx < cases % workers),
+                };
+                let config = FuzzConfig { cases, ..runner.config().clone() };
+                TestRunner::new_with_rng(config, runner.new_rng())
+            })
+            .collect()
+    }
+
+    /// Fuzzes the provided function with `runner` on the current thread, see [Self::fuzz]
+    fn fuzz_worker(
+        &self,
+        func: &Function,
+        address: Address,
+        should_fail: bool,
+        errors: Option<&Abi>,
+        mut runner: TestRunner,
+        corpus: Option<&PathBuf>,
     ) -> FuzzTestResult {
-        // Stores the consumed gas and calldata of every successful fuzz call
+        // Stores the consumed gas and calldata of the successful fuzz calls, up to
+        // `MAX_KEPT_CASES` of them
         let cases: RefCell<Vec<FuzzCase>> = RefCell::new(Default::default());
 
-        // Stores the result and calldata of the last failed call, if any.
-        let counterexample: RefCell<(Bytes, RawCallResult)> = RefCell::new(Default::default());
+        // The number of successful fuzz calls, and the number of calls per kept case
+        let runs = Cell::new(0usize);
+        let stride = Cell::new(1usize);
 
-        // Stores fuzz state for use with [fuzz_calldata_from_state]
+        // Whether a call failed, the following calls shrink the failing input
+        let failed = Cell::new(false);
+
+        // The number of inputs rejected by `vm.assume` before the first failure
+        let rejects = Cell::new(0u32);
+
+        // Stores the sender, calldata and result of the last failed call, if any.
+        let counterexample: RefCell<(Address, Bytes, RawCallResult)> =
+            RefCell::new(Default::default());
+
+        // The accounts that call the test, if any, otherwise the sender calls it
+        let actors = actor_addresses(self.param_opts.actors);
+
+        // Stores fuzz state for use with [fuzz_calldata_with_state]
         let state: EvmFuzzState = build_initial_state(&self.executor.db);
 
-        // TODO: We should have a `FuzzerOpts` struct where we can configure the fuzzer. When we
-        // have that, we should add a way to configure strategy weights
-        let strat = proptest::strategy::Union::new_weighted(vec![
-            (60, fuzz_calldata(func.clone())),
-            (40, fuzz_calldata_from_state(func.clone(), state.clone())),
-        ]);
+        // Stores the addresses and functions of the test environment, random values are biased
+        // towards them
+        let known = KnownValues {
+            addresses: build_known_addresses(
+                &self.executor.db,
+                [address, self.sender].into_iter().chain(actors.iter().copied()),
+            ),
+            functions: self
+                .known_contracts
+                .map(|known_contracts| deployed_functions(&self.executor.db, known_contracts))
+                .unwrap_or_default(),
+        };
+
+        // In coverage guided mode, stores the branches taken by all runs and the inputs that took
+        // new ones, which are mutated to generate new inputs
+        let covered: RefCell<BTreeSet<Branch>> = RefCell::new(Default::default());
+        let coverage_corpus = self.param_opts.coverage_guided.then(CoverageCorpus::default);
+
+        let strat = (
+            fuzz_sender(actors.clone(), self.sender),
+            fuzz_calldata_with_state(
+                func.clone(),
+                state.clone(),
+                known.clone(),
+                self.param_opts,
+                &self.param_ranges,
+                coverage_corpus.clone(),
+            ),
+        );
         tracing::debug!(func = ?func.name, should_fail, "fuzzing");
-        let run_result = self.runner.clone().run(&strat, |calldata| {
+        let run_case = |(sender, calldata): (Address, Bytes)| {
             let call = self
                 .executor
-                .call_raw(self.sender, address, calldata.0.clone(), 0.into())
+                .call_raw_collecting(
+                    sender,
+                    address,
+                    calldata.0.clone(),
+                    0.into(),
+                    self.param_opts.coverage_guided,
+                )
                 .expect("could not make raw evm call");
             let state_changeset =
                 call.state_changeset.as_ref().expect("we should have a state changeset");
 
             // Build fuzzer state
-            collect_state_from_call(&call.logs, state_changeset, state.clone());
+            collect_state_from_call(&call.logs, state_changeset, &call.fuzz_values, state.clone());
+            collect_addresses(state_changeset.keys().copied(), &known.addresses);
 
             // When assume cheat code is triggered return a special string "FOUNDRY::ASSUME"
             if call.result.as_ref() == ASSUME_MAGIC_RETURN_CODE {
+                if !failed.get() {
+                    rejects.set(rejects.get() + 1);
+                }
                 return Err(TestCaseError::reject("ASSUME: Too many rejects"))
             }
 
+            if let Some(coverage_corpus) = &coverage_corpus {
+                let mut covered = covered.borrow_mut();
+                let before = covered.len();
+                covered.extend(call.branches.iter().copied());
+                if covered.len() > before {
+                    if let Ok(tokens) = func.decode_input(&calldata.as_ref()[4..]) {
+                        coverage_corpus.borrow_mut().push(tokens);
+                    }
+                }
+            }
+
             let success = self.executor.is_success(
                 address,
                 call.reverted,
@@ -97,21 +348,41 @@ where
             );
 
             if success {
-                cases.borrow_mut().push(FuzzCase {
-                    calldata,
-                    gas: call.gas,
-                    stipend: call.stipend,
-                });
+                // After the first failure the runner only shrinks the failing input, the passing
+                // shrunk inputs are not fuzz cases
+                if failed.get() {
+                    return Ok(())
+                }
+                runs.set(runs.get() + 1);
+                if runs.get() % stride.get() == 0 {
+                    let mut cases = cases.borrow_mut();
+                    cases.push(FuzzCase {
+                        calldata,
+                        gas: call.gas,
+                        stipend: call.stipend,
+                        harness_gas: call.harness_gas,
+                    });
+                    if cases.len() >= MAX_KEPT_CASES {
+                        let mut idx = 0;
+                        cases.retain(|_| {
+                            idx += 1;
+                            idx % 2 == 0
+                        });
+                        stride.set(stride.get() * 2);
+                    }
+                }
                 Ok(())
             } else {
                 // We cannot use the calldata returned by the test runner in `TestError::Fail`,
                 // since that input represents the last run case, which may not correspond with our
-                // failure - when a fuzz case fails, proptest will try to run at least one more
-                // case to find a minimal failure case.
-                *counterexample.borrow_mut() = (calldata, call);
+                // failure - when a fuzz case fails, proptest shrinks it by running simpler inputs
+                // until none of them fails anymore, the last failing one is the minimal failure
+                // case.
+                failed.set(true);
+                *counterexample.borrow_mut() = (sender, calldata, call);
                 Err(TestCaseError::fail(
                     match foundry_utils::decode_revert(
-                        counterexample.borrow().1.result.as_ref(),
+                        counterexample.borrow().2.result.as_ref(),
                         errors,
                     ) {
                         Ok(e) => e,
@@ -119,11 +390,48 @@ where
                     },
                 ))
             }
-        });
+        };
+
+        let replay = |input: (Address, Bytes)| match run_case(input.clone()) {
+            Err(TestCaseError::Fail(reason)) => Some(TestError::Fail(reason, input)),
+            _ => None,
+        };
 
-        let (calldata, call) = counterexample.into_inner();
+        // Replay the counterexamples of past runs before generating new inputs, the first actor
+        // replays them if there are actors
+        let mut corpus = corpus.map(FuzzCorpus::load);
+        let replay_sender = actors.first().copied().unwrap_or(self.sender);
+        let replayed_failure = match &self.replay {
+            Some(calldata) => replay((self.sender, calldata.clone())),
+            None => corpus
+                .iter()
+                .flat_map(|corpus| corpus.calldata())
+                .find_map(|calldata| replay((replay_sender, calldata.clone()))),
+        };
+        let run_result = match (replayed_failure, self.max_time) {
+            // Only the counterexample is run when it is replayed
+            (None, _) if self.replay.is_some() => Ok(()),
+            (Some(err), _) => Err(err),
+            (None, None) => runner.run(&strat, &run_case),
+            (None, Some(max_time)) => {
+                // Run batches of runs until the time is up, each with a new rng derived from the
+                // one of the fuzzer so runs stay reproducible with the same seed
+                let deadline = Instant::now() + max_time;
+                let config = FuzzConfig { cases: TIME_BUDGET_BATCH, ..runner.config().clone() };
+                loop {
+                    let result = TestRunner::new_with_rng(config.clone(), runner.new_rng())
+                        .run(&strat, &run_case);
+                    if result.is_err() || Instant::now() >= deadline {
+                        break result
+                    }
+                }
+            }
+        };
+
+        let (sender, calldata, call) = counterexample.into_inner();
         let mut result = FuzzTestResult {
-            cases: FuzzedCases::new(cases.into_inner()),
+            cases: FuzzedCases::with_runs(cases.into_inner(), runs.get()),
+            rejects: rejects.get(),
             success: run_result.is_ok(),
             reason: None,
             counterexample: None,
@@ -134,7 +442,7 @@ where
 
         match run_result {
             Err(TestError::Abort(reason)) => {
-                result.reason = Some(reason.to_string());
+                result.reason = Some(abort_reason(reason.message(), rejects.get()));
             }
             Err(TestError::Fail(reason, _)) => {
                 let reason = reason.to_string();
@@ -143,7 +451,11 @@ where
                 let args = func
                     .decode_input(&calldata.as_ref()[4..])
                     .expect("could not decode fuzzer inputs");
-                result.counterexample = Some(CounterExample { calldata, args });
+                if let Some(corpus) = corpus.as_mut() {
+                    store_counterexample(corpus, &calldata);
+                }
+                let sender = (!actors.is_empty()).then(|| sender);
+                result.counterexample = Some(CounterExample { sender, calldata, args });
             }
             _ => (),
         }
@@ -152,16 +464,55 @@ where
     }
 }
 
+/// Explains why the runner aborted. Only `vm.assume` rejects inputs globally, the strategies reject
+/// the values they generate locally.
+fn abort_reason(reason: &str, rejects: u32) -> String {
+    if reason.contains("global rejects") && rejects > 0 {
+        format!(
+            "{}: `vm.assume` rejected too many inputs ({}), loosen the assumption or raise `fuzz_max_global_rejects`",
+            reason, rejects
+        )
+    } else if reason.contains("local rejects") {
+        format!(
+            "{}: the fuzzer rejected too many generated inputs, raise `fuzz_max_local_rejects`",
+            reason
+        )
+    } else {
+        format!("fuzzing aborted: {}", reason)
+    }
+}
+
+/// Stores the calldata of a counterexample in the corpus, if it is not stored yet
+fn store_counterexample(corpus: &mut FuzzCorpus, calldata: &Bytes) {
+    if corpus.insert(calldata.clone()) {
+        if let Err(err) = corpus.save() {
+            tracing::warn!(?err, "could not store the counterexample");
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CounterExample {
+    /// The actor that called the test, if the test was called by actors
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender: Option<Address>,
+
     pub calldata: Bytes,
 
-    #[serde(skip)]
+    /// The decoded args, serialized in the same format they are printed in
+    #[serde(skip_deserializing, serialize_with = "serialize_args")]
     pub args: Vec<Token>,
 }
 
+fn serialize_args<S: serde::Serializer>(args: &[Token], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(foundry_utils::format_tokens(args))
+}
+
 impl fmt::Display for CounterExample {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(sender) = self.sender {
+            write!(f, "sender={:?}, ", sender)?;
+        }
         let args = foundry_utils::format_tokens(&self.args).collect::<Vec<_>>().join(", ");
         write!(f, "calldata=0x{}, args=[{}]", hex::encode(&self.calldata), args)
     }
@@ -173,6 +524,9 @@ pub struct FuzzTestResult {
     /// Every successful fuzz test case
     pub cases: FuzzedCases,
 
+    /// The number of inputs rejected by `vm.assume`, rejected inputs are not fuzz cases
+    pub rejects: u32,
+
     /// Whether the test case was successful. This means that the transaction executed
     /// properly, or that there was a revert and that the test was expected to fail
     /// (prefixed with `testFail`)
@@ -196,17 +550,32 @@ pub struct FuzzTestResult {
     pub labeled_addresses: BTreeMap<Address, String>,
 }
 
-/// Container type for all successful test cases
+/// Container type for the successful test cases, a sample of them if there were too many to keep
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct FuzzedCases {
     cases: Vec<FuzzCase>,
+    /// The number of successful runs, which is larger than the number of cases if they were
+    /// sampled
+    #[serde(skip)]
+    runs: usize,
 }
 
 impl FuzzedCases {
-    pub fn new(mut cases: Vec<FuzzCase>) -> Self {
-        cases.sort_by_key(|c| c.gas);
-        Self { cases }
+    pub fn new(cases: Vec<FuzzCase>) -> Self {
+        let runs = cases.len();
+        Self::with_runs(cases, runs)
+    }
+
+    /// The cases kept of `runs` successful runs
+    pub fn with_runs(mut cases: Vec<FuzzCase>, runs: usize) -> Self {
+        cases.sort_by_key(|c| c.gas(false));
+        Self { cases, runs }
+    }
+
+    /// Returns the number of successful runs
+    pub fn runs(&self) -> usize {
+        self.runs
     }
 
     pub fn cases(&self) -> &[FuzzCase] {
@@ -220,10 +589,7 @@ impl FuzzedCases {
     /// Returns the median gas of all test cases
     pub fn median_gas(&self, with_stipend: bool) -> u64 {
         let mid = self.cases.len() / 2;
-        self.cases
-            .get(mid)
-            .map(|c| if with_stipend { c.gas } else { c.gas - c.stipend })
-            .unwrap_or_default()
+        self.cases.get(mid).map(|c| c.gas(with_stipend)).unwrap_or_default()
     }
 
     /// Returns the average gas use of all test cases
@@ -232,11 +598,7 @@ impl FuzzedCases {
             return 0
         }
 
-        (self
-            .cases
-            .iter()
-            .map(|c| if with_stipend { c.gas as u128 } else { (c.gas - c.stipend) as u128 })
-            .sum::<u128>() /
+        (self.cases.iter().map(|c| c.gas(with_stipend) as u128).sum::<u128>() /
             self.cases.len() as u128) as u64
     }
 
@@ -252,9 +614,7 @@ impl FuzzedCases {
 
     /// Returns the highest amount of gas spent on a fuzz case
     pub fn highest_gas(&self, with_stipend: bool) -> u64 {
-        self.highest()
-            .map(|c| if with_stipend { c.gas } else { c.gas - c.stipend })
-            .unwrap_or_default()
+        self.highest().map(|c| c.gas(with_stipend)).unwrap_or_default()
     }
 
     /// Returns the lowest amount of gas spent on a fuzz case
@@ -272,4 +632,34 @@ pub struct FuzzCase {
     pub gas: u64,
     /// The initial gas stipend for the transaction
     pub stipend: u64,
+    /// The gas spent on calls to the test harness, i.e. cheatcodes and console logs
+    #[serde(default)]
+    pub harness_gas: u64,
+}
+
+impl FuzzCase {
+    /// Returns the consumed gas, optionally without the stipend and the harness overhead
+    fn gas(&self, with_stipend: bool) -> u64 {
+        if with_stipend {
+            self.gas
+        } else {
+            self.gas.saturating_sub(self.stipend).saturating_sub(self.harness_gas)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_explain_aborts() {
+        assert!(abort_reason("Too many global rejects", 3).contains("`vm.assume` rejected"));
+        assert!(!abort_reason("Too many global rejects", 0).contains("vm.assume"));
+        assert!(abort_reason("Too many local rejects", 3).contains("fuzz_max_local_rejects"));
+        assert_eq!(
+            abort_reason("Too many flat map regens", 0),
+            "fuzzing aborted: Too many flat map regens"
+        );
+    }
 }
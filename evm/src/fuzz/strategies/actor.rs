@@ -0,0 +1,47 @@
+use ethers::{types::Address, utils::keccak256};
+use proptest::prelude::*;
+
+/// Returns the addresses of `count` actors, the same ones for every test.
+///
+/// The address of actor `i` is the last 20 bytes of `keccak256("foundry.actor.<i>")`, so tests can
+/// compute it too.
+pub fn actor_addresses(count: usize) -> Vec<Address> {
+    (0..count)
+        .map(|i| Address::from_slice(&keccak256(format!("foundry.actor.{}", i))[12..]))
+        .collect()
+}
+
+/// Returns a strategy for the sender of a fuzzed call, one of the `actors` simplified towards the
+/// first one, or `sender` if there are no actors
+pub fn fuzz_sender(actors: Vec<Address>, sender: Address) -> BoxedStrategy<Address> {
+    if actors.is_empty() {
+        return Just(sender).boxed()
+    }
+    (0..actors.len()).prop_map(move |idx| actors[idx]).boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::{strategy::ValueTree, test_runner::TestRunner};
+
+    #[test]
+    fn senders_are_actors() {
+        let actors = actor_addresses(3);
+        assert_eq!(actors[1], Address::from_slice(&keccak256(b"foundry.actor.1")[12..]));
+        assert_eq!(actor_addresses(2), actors[..2]);
+
+        let strategy = fuzz_sender(actors.clone(), Address::zero());
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..100 {
+            let mut tree = strategy.new_tree(&mut runner).unwrap();
+            assert!(actors.contains(&tree.current()));
+            while tree.simplify() {}
+            assert_eq!(tree.current(), actors[0]);
+        }
+
+        let sender = Address::from_low_u64_be(1);
+        let strategy = fuzz_sender(vec![], sender);
+        assert_eq!(strategy.new_tree(&mut runner).unwrap().current(), sender);
+    }
+}
@@ -1,13 +1,80 @@
-use super::fuzz_param;
-use ethers::{abi::Function, types::Bytes};
-use proptest::prelude::{BoxedStrategy, Strategy};
+use super::{
+    check_param, fuzz_function_pointer, fuzz_param, fuzz_param_from_state, fuzz_param_with_known,
+    CoverageCorpus, CoverageGuidedStrategy, EvmFuzzState, FuzzParamOpts, FuzzRange, KnownValues,
+    UnsupportedParam,
+};
+use ethers::{
+    abi::{Function, Param, ParamType},
+    types::Bytes,
+};
+use proptest::{
+    prelude::{BoxedStrategy, Strategy},
+    strategy::Union,
+};
+use std::collections::BTreeMap;
+
+/// Checks that all params of the function can be fuzzed, see [`check_param`]
+pub fn check_function(func: &Function) -> Result<(), UnsupportedParam> {
+    func.inputs.iter().try_for_each(|input| check_param(&input.kind))
+}
 
 /// Given a function, it returns a strategy which generates valid calldata
 /// for that function's input types.
-pub fn fuzz_calldata(func: Function) -> BoxedStrategy<Bytes> {
+pub fn fuzz_calldata(func: Function, opts: FuzzParamOpts) -> BoxedStrategy<Bytes> {
     // We need to compose all the strategies generated for each parameter in all
     // possible combinations
-    let strats = func.inputs.iter().map(|input| fuzz_param(&input.kind)).collect::<Vec<_>>();
+    let strats = func.inputs.iter().map(|input| fuzz_param(&input.kind, opts)).collect::<Vec<_>>();
+
+    strats
+        .prop_map(move |tokens| {
+            tracing::trace!(input = ?tokens);
+            func.encode_input(&tokens).unwrap().into()
+        })
+        .boxed()
+}
+
+/// Given a function and some state, it returns a strategy which generates valid calldata for that
+/// function's input types, where each parameter is drawn from the state with a probability of
+/// `opts.dictionary_weight` percent and generated randomly otherwise.
+///
+/// Random values are biased towards the `known` values of the test environment, see
+/// [`fuzz_param_with_known`]. Parameters with an entry in `ranges` are always drawn from their
+/// range, external function pointers are made of a known address and a known selector.
+///
+/// If a `corpus` is given, most inputs are mutations of its inputs, see
+/// [`CoverageGuidedStrategy`].
+pub fn fuzz_calldata_with_state(
+    func: Function,
+    state: EvmFuzzState,
+    known: KnownValues,
+    opts: FuzzParamOpts,
+    ranges: &BTreeMap<String, FuzzRange>,
+    corpus: Option<CoverageCorpus>,
+) -> BoxedStrategy<Bytes> {
+    let dictionary_weight = opts.dictionary_weight.min(100);
+    let strats = func
+        .inputs
+        .iter()
+        .map(|input| {
+            if let Some(range) = ranges.get(&input.name) {
+                return range.strategy()
+            }
+            if is_function_pointer(input) {
+                return fuzz_function_pointer(&known)
+            }
+            let options = [
+                (100 - dictionary_weight, fuzz_param_with_known(&input.kind, opts, &known)),
+                (dictionary_weight, fuzz_param_from_state(&input.kind, state.clone(), opts)),
+            ];
+            // options without weight would never be picked
+            Union::new_weighted(options.into_iter().filter(|(weight, _)| *weight > 0).collect())
+                .boxed()
+        })
+        .collect::<Vec<_>>();
+    let strats = match corpus {
+        Some(corpus) => CoverageGuidedStrategy::new(corpus, strats).boxed(),
+        None => strats.boxed(),
+    };
 
     strats
         .prop_map(move |tokens| {
@@ -16,3 +83,10 @@ pub fn fuzz_calldata(func: Function) -> BoxedStrategy<Bytes> {
         })
         .boxed()
 }
+
+/// Returns `true` if the param is an external function pointer, which the ABI encodes like
+/// `bytes24` and only tells apart by its internal type
+fn is_function_pointer(param: &Param) -> bool {
+    param.kind == ParamType::FixedBytes(24) &&
+        param.internal_type.as_deref().map_or(false, |ty| ty.starts_with("function "))
+}
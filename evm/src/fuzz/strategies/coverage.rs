@@ -0,0 +1,97 @@
+use ethers::{abi::Token, core::rand::Rng};
+use proptest::{
+    prelude::*,
+    strategy::{NewTree, ValueTree},
+    test_runner::TestRunner,
+};
+use std::{cell::RefCell, rc::Rc};
+
+/// The percentage of inputs that are mutations of the corpus once it is not empty
+pub const MUTATION_PERCENT: u32 = 70;
+
+/// The inputs of the runs that took branches no previous run took, as the tokens of the params.
+///
+/// Wrapped in a shareable container.
+pub type CoverageCorpus = Rc<RefCell<Vec<Vec<Token>>>>;
+
+/// Strategy for the params of a function that mutates the inputs of a [CoverageCorpus].
+///
+/// A mutation keeps all params of an input of the corpus but one, which is generated anew, so
+/// inputs that passed the outer conditions of a contract are used to explore the inner ones. Only
+/// the mutated param is simplified when shrinking.
+#[derive(Debug)]
+pub struct CoverageGuidedStrategy {
+    corpus: CoverageCorpus,
+    params: Vec<BoxedStrategy<Token>>,
+}
+
+impl CoverageGuidedStrategy {
+    /// Creates a strategy that mutates the inputs of `corpus` with the strategies of the params
+    pub fn new(corpus: CoverageCorpus, params: Vec<BoxedStrategy<Token>>) -> Self {
+        Self { corpus, params }
+    }
+}
+
+impl Strategy for CoverageGuidedStrategy {
+    type Tree = Box<dyn ValueTree<Value = Vec<Token>>>;
+    type Value = Vec<Token>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let corpus = self.corpus.borrow();
+        if corpus.is_empty() ||
+            self.params.is_empty() ||
+            runner.rng().gen_range(0..100) >= MUTATION_PERCENT
+        {
+            return Ok(Box::new(self.params.new_tree(runner)?))
+        }
+
+        let input = &corpus[runner.rng().gen_range(0..corpus.len())];
+        let mutated = runner.rng().gen_range(0..self.params.len());
+        let params = input
+            .iter()
+            .zip(&self.params)
+            .enumerate()
+            .map(
+                |(idx, (token, param))| {
+                    if idx == mutated {
+                        param.clone()
+                    } else {
+                        Just(token.clone()).boxed()
+                    }
+                },
+            )
+            .collect::<Vec<_>>();
+        Ok(Box::new(params.new_tree(runner)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::U256;
+
+    #[test]
+    fn mutates_one_param_of_the_corpus() {
+        let corpus = CoverageCorpus::default();
+        let input = vec![Token::Uint(1.into()), Token::Uint(2.into()), Token::Uint(3.into())];
+        corpus.borrow_mut().push(input.clone());
+        let strategy = CoverageGuidedStrategy::new(
+            corpus,
+            (0..3).map(|_| (10u64..100).prop_map(|x| Token::Uint(x.into())).boxed()).collect(),
+        );
+        let mut runner = TestRunner::deterministic();
+
+        let mut mutations = 0;
+        for _ in 0..1000 {
+            let tokens = strategy.new_tree(&mut runner).unwrap().current();
+            let kept = tokens.iter().zip(&input).filter(|(token, input)| token == input).count();
+            match kept {
+                0 => {}
+                2 => mutations += 1,
+                _ => panic!("unexpected input {:?}", tokens),
+            }
+            assert!(tokens.iter().all(|token| token.clone().into_uint() < Some(U256::from(100))));
+        }
+        assert!((600..800).contains(&mutations), "{} mutations", mutations);
+    }
+}
@@ -0,0 +1,157 @@
+use ethers::core::rand::Rng;
+use proptest::{
+    strategy::{NewTree, Strategy, ValueTree},
+    test_runner::TestRunner,
+};
+
+use ethers::types::{I256, U256};
+
+use super::uint::UintValueTree;
+
+/// Value tree for signed ints (up to int256).
+/// The magnitude of the value is simplified towards 0 like a [UintValueTree], the sign is kept.
+pub struct IntValueTree {
+    /// Whether the value is negative
+    negative: bool,
+    /// The absolute value
+    magnitude: UintValueTree,
+}
+
+impl IntValueTree {
+    /// Create a new tree starting at the value with the given sign and absolute value
+    fn new(negative: bool, magnitude: U256) -> Self {
+        Self { negative, magnitude: UintValueTree::new(magnitude, false) }
+    }
+
+    /// Create a new tree starting at `value`
+    pub fn from_int(value: I256) -> Self {
+        let magnitude = if value.is_negative() {
+            U256::zero().overflowing_sub(value.into_raw()).0
+        } else {
+            value.into_raw()
+        };
+        Self::new(value.is_negative(), magnitude)
+    }
+}
+
+impl ValueTree for IntValueTree {
+    type Value = I256;
+
+    fn current(&self) -> Self::Value {
+        let magnitude = self.magnitude.current();
+        if self.negative {
+            I256::from_raw(U256::zero().overflowing_sub(magnitude).0)
+        } else {
+            I256::from_raw(magnitude)
+        }
+    }
+
+    fn simplify(&mut self) -> bool {
+        self.magnitude.simplify()
+    }
+
+    fn complicate(&mut self) -> bool {
+        self.magnitude.complicate()
+    }
+}
+
+/// Strategy for signed ints (up to int256).
+/// The strategy combines 2 different strategies:
+/// 1. Generate a value around the edges (+/- 3 around 0, min and max possible value, +/- 1 around
+/// a positive or negative power of two), for `edge_percent` percent of the values
+/// 2. Generate a value uniformly from the range of the type
+///
+/// By default there are no edge cases, so the values are uniformly distributed.
+#[derive(Debug)]
+pub struct IntStrategy {
+    /// Bit size of int (e.g. 256)
+    bits: usize,
+    /// The percentage of edge cases
+    edge_percent: u32,
+}
+
+impl IntStrategy {
+    /// Create a new strategy.
+    /// #Arguments
+    /// * `bits` - Size of int in bits
+    pub fn new(bits: usize) -> Self {
+        Self { bits, edge_percent: 0 }
+    }
+
+    /// Sets the percentage of values that are edge cases, capped at 100
+    #[must_use]
+    pub fn with_edge_percent(mut self, edge_percent: u32) -> Self {
+        self.edge_percent = edge_percent.min(100);
+        self
+    }
+
+    fn generate_edge_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let rng = runner.rng();
+
+        // the absolute value of min, max is one less
+        let min_magnitude = U256::one() << (self.bits - 1);
+        let offset = U256::from(rng.gen_range(0..4u64));
+        let tree = match rng.gen_range(0..7) {
+            0 | 1 => IntValueTree::new(rng.gen_bool(0.5), offset),
+            2 | 3 => IntValueTree::new(true, min_magnitude - offset),
+            4 | 5 => IntValueTree::new(false, min_magnitude - 1 - offset),
+            _ => {
+                let power = U256::one() << rng.gen_range(1..self.bits - 1);
+                let magnitude = match rng.gen_range(0..3) {
+                    0 => power - 1,
+                    1 => power,
+                    _ => power + 1,
+                };
+                IntValueTree::new(rng.gen_bool(0.5), magnitude)
+            }
+        };
+
+        Ok(tree)
+    }
+
+    fn generate_random_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let raw = U256::from(&runner.rng().gen::<[u8; 32]>());
+        let value = if self.bits < 256 {
+            // Generate a uintN in the correct range, then shift it to the range of intN by
+            // subtracting 2^(N-1)
+            let uint = raw % (U256::one() << self.bits);
+            I256::from_raw(uint.overflowing_sub(U256::one() << (self.bits - 1)).0)
+        } else {
+            I256::from_raw(raw)
+        };
+
+        Ok(IntValueTree::from_int(value))
+    }
+}
+
+impl Strategy for IntStrategy {
+    type Tree = IntValueTree;
+    type Value = I256;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        if runner.rng().gen_range(0..100) < self.edge_percent {
+            self.generate_edge_tree(runner)
+        } else {
+            self.generate_random_tree(runner)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edge_cases_stay_in_range() {
+        let strategy = IntStrategy::new(8).with_edge_percent(100);
+        let mut runner = TestRunner::deterministic();
+        let (min, max) = (I256::from_raw(U256::MAX - 127), I256::from_raw(U256::from(127)));
+        let mut seen_min = false;
+        for _ in 0..1000 {
+            let value = strategy.new_tree(&mut runner).unwrap().current();
+            assert!(value >= min && value <= max, "{} is not an int8", value);
+            seen_min |= value == min;
+        }
+        assert!(seen_min);
+    }
+}
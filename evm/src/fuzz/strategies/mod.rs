@@ -1,13 +1,32 @@
 mod uint;
 pub use uint::UintStrategy;
 
+mod int;
+pub use int::IntStrategy;
+
+mod string;
+pub use string::{fuzz_string, DEFAULT_MAX_STRING_LEN};
+
 mod param;
-pub use param::{fuzz_param, fuzz_param_from_state};
+pub use param::{
+    check_param, fuzz_function_pointer, fuzz_param, fuzz_param_from_state, fuzz_param_with_known,
+    FuzzParamOpts, UnsupportedParam, MAX_PARAM_DEPTH,
+};
+
+mod range;
+pub use range::FuzzRange;
+
+mod coverage;
+pub use coverage::{CoverageCorpus, CoverageGuidedStrategy, MUTATION_PERCENT};
+
+mod actor;
+pub use actor::{actor_addresses, fuzz_sender};
 
 mod calldata;
-pub use calldata::fuzz_calldata;
+pub use calldata::{check_function, fuzz_calldata, fuzz_calldata_with_state};
 
 mod state;
 pub use state::{
-    build_initial_state, collect_state_from_call, fuzz_calldata_from_state, EvmFuzzState,
+    build_initial_state, build_known_addresses, collect_addresses, collect_state_from_call,
+    deployed_functions, fuzz_calldata_from_state, EvmFuzzState, KnownAddresses, KnownValues,
 };
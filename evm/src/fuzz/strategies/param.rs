@@ -2,68 +2,235 @@ use ethers::{
     abi::{ParamType, Token, Tokenizable},
     types::{Address, Bytes, I256, U256},
 };
-use proptest::prelude::*;
+use proptest::{
+    prelude::*,
+    strategy::{NewTree, Union},
+    test_runner::TestRunner,
+};
 
-use super::state::EvmFuzzState;
+use super::{
+    fuzz_calldata, fuzz_string,
+    int::IntValueTree,
+    state::{EvmFuzzState, KnownAddresses, KnownValues},
+    string::DEFAULT_MAX_STRING_LEN,
+    uint::UintValueTree,
+    IntStrategy, UintStrategy,
+};
 
-/// The max length of arrays we fuzz for is 256.
+/// The default max length of the arrays we fuzz for is 256.
 pub const MAX_ARRAY_LEN: usize = 256;
 
+/// The default max length of fuzzed `bytes`
+pub const MAX_BYTES_LEN: usize = 100;
+
+/// The default percentage of params that are drawn from the fuzz state
+pub const DEFAULT_DICTIONARY_WEIGHT: u32 = 40;
+
+/// The percentage of addresses from [`fuzz_param_with_known`] that are known addresses
+pub const KNOWN_ADDRESS_PERCENT: u32 = 60;
+
+/// The percentage of `bytes` from [`fuzz_param_with_known`] that are calldata of known functions
+pub const KNOWN_CALLDATA_PERCENT: u32 = 50;
+
+/// The max depth of nested arrays and tuples that are fuzzed
+pub const MAX_PARAM_DEPTH: usize = 8;
+
+/// The size in bytes of an external function pointer, an address followed by a selector
+const FUNCTION_POINTER_LEN: usize = 24;
+
+/// A param type that can not be fuzzed
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum UnsupportedParam {
+    #[error("unsupported type int{0}, the width of ints has to be a multiple of 8 up to 256")]
+    Int(usize),
+    #[error("unsupported type uint{0}, the width of uints has to be a multiple of 8 up to 256")]
+    Uint(usize),
+    #[error("unsupported type bytes{0}, the size of fixed bytes has to be between 1 and 32")]
+    FixedBytes(usize),
+    #[error("`{0}` is nested deeper than {} arrays and tuples", MAX_PARAM_DEPTH)]
+    TooDeep(ParamType),
+}
+
+/// Checks that values of the param type can be fuzzed, the strategies of this module expect
+/// checked types
+pub fn check_param(param: &ParamType) -> Result<(), UnsupportedParam> {
+    fn check(param: &ParamType, root: &ParamType, depth: usize) -> Result<(), UnsupportedParam> {
+        if depth > MAX_PARAM_DEPTH {
+            return Err(UnsupportedParam::TooDeep(root.clone()))
+        }
+        match param {
+            ParamType::Int(n) if *n == 0 || *n > 256 || n % 8 != 0 => {
+                Err(UnsupportedParam::Int(*n))
+            }
+            ParamType::Uint(n) if *n == 0 || *n > 256 || n % 8 != 0 => {
+                Err(UnsupportedParam::Uint(*n))
+            }
+            ParamType::FixedBytes(size) if *size == 0 || *size > 32 => {
+                Err(UnsupportedParam::FixedBytes(*size))
+            }
+            ParamType::Array(param) | ParamType::FixedArray(param, _) => {
+                check(param, root, depth + 1)
+            }
+            ParamType::Tuple(params) => {
+                params.iter().try_for_each(|param| check(param, root, depth + 1))
+            }
+            _ => Ok(()),
+        }
+    }
+    check(param, param, 0)
+}
+
+/// Settings of the strategies of [`fuzz_param`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuzzParamOpts {
+    /// Whether random uints are sampled uniformly from the range of their type, by default
+    /// [`UintStrategy`] favours small values. Ints are always sampled uniformly.
+    pub uniform_ints: bool,
+    /// If set, the percentage of ints and uints that are drawn from the edge cases of their type,
+    /// like 0, max, min and powers of two. By default [`UintStrategy`] draws half of the uints
+    /// from the edge cases and ints are sampled uniformly.
+    pub edge_percent: Option<u32>,
+    /// The max length of fuzzed strings in chars
+    pub max_string_len: usize,
+    /// The max length of fuzzed dynamic arrays
+    pub max_array_len: usize,
+    /// The max length of fuzzed `bytes`
+    pub max_bytes_len: usize,
+    /// The percentage of params that are drawn from the fuzz state instead of being generated
+    /// randomly, see [`fuzz_calldata_with_state`](super::fuzz_calldata_with_state)
+    pub dictionary_weight: u32,
+    /// Whether inputs that take new branches are kept and mutated preferentially, see
+    /// [`CoverageGuidedStrategy`](super::CoverageGuidedStrategy)
+    pub coverage_guided: bool,
+    /// The number of actors that call fuzz tests, see
+    /// [`actor_addresses`](super::actor_addresses). Tests are called by the default sender if
+    /// there are none.
+    pub actors: usize,
+}
+
+impl Default for FuzzParamOpts {
+    fn default() -> Self {
+        Self {
+            uniform_ints: false,
+            edge_percent: None,
+            max_string_len: DEFAULT_MAX_STRING_LEN,
+            max_array_len: MAX_ARRAY_LEN,
+            max_bytes_len: MAX_BYTES_LEN,
+            dictionary_weight: DEFAULT_DICTIONARY_WEIGHT,
+            coverage_guided: false,
+            actors: 0,
+        }
+    }
+}
+
 /// Given a parameter type, returns a strategy for generating values for that type.
 ///
 /// Works with ABI Encoder v2 tuples.
-pub fn fuzz_param(param: &ParamType) -> impl Strategy<Value = Token> {
+pub fn fuzz_param(param: &ParamType, opts: FuzzParamOpts) -> impl Strategy<Value = Token> {
     match param {
         ParamType::Address => {
             // The key to making this work is the `boxed()` call which type erases everything
             // https://altsysrq.github.io/proptest-book/proptest/tutorial/transforming-strategies.html
             any::<[u8; 20]>().prop_map(|x| Address::from_slice(&x).into_token()).boxed()
         }
-        ParamType::Bytes => any::<Vec<u8>>().prop_map(|x| Bytes::from(x).into_token()).boxed(),
-        // For ints and uints we sample from a U256, then wrap it to the correct size with a
-        // modulo operation. Note that this introduces modulo bias, but it can be removed with
-        // rejection sampling if it's determined the bias is too severe. Rejection sampling may
-        // slow down tests as it resamples bad values, so may want to benchmark the performance
-        // hit and weigh that against the current bias before implementing
-        ParamType::Int(n) => match n / 8 {
-            32 => any::<[u8; 32]>()
-                .prop_map(move |x| I256::from_raw(U256::from(&x)).into_token())
-                .boxed(),
-            y @ 1..=31 => any::<[u8; 32]>()
-                .prop_map(move |x| {
-                    // Generate a uintN in the correct range, then shift it to the range of intN
-                    // by subtracting 2^(N-1)
-                    let uint = U256::from(&x) % U256::from(2).pow(U256::from(y * 8));
-                    let max_int_plus1 = U256::from(2).pow(U256::from(y * 8 - 1));
-                    let num = I256::from_raw(uint.overflowing_sub(max_int_plus1).0);
-                    num.into_token()
-                })
-                .boxed(),
-            _ => panic!("unsupported solidity type int{}", n),
-        },
+        ParamType::Bytes => proptest::collection::vec(any::<u8>(), 0..=opts.max_bytes_len)
+            .prop_map(|x| Bytes::from(x).into_token())
+            .boxed(),
+        ParamType::Int(n) => {
+            let strategy = IntStrategy::new(*n);
+            match opts.edge_percent {
+                Some(edge_percent) => strategy.with_edge_percent(edge_percent),
+                None => strategy,
+            }
+            .prop_map(|x| x.into_token())
+            .boxed()
+        }
         ParamType::Uint(n) => {
-            super::UintStrategy::new(*n, vec![]).prop_map(|x| x.into_token()).boxed()
+            let strategy = UintStrategy::new(*n, vec![]).with_uniform(opts.uniform_ints);
+            match opts.edge_percent {
+                Some(edge_percent) => strategy.with_edge_percent(edge_percent),
+                None => strategy,
+            }
+            .prop_map(|x| x.into_token())
+            .boxed()
         }
         ParamType::Bool => any::<bool>().prop_map(|x| x.into_token()).boxed(),
-        ParamType::String => any::<Vec<u8>>()
-            .prop_map(|x| Token::String(unsafe { std::str::from_utf8_unchecked(&x).to_string() }))
-            .boxed(),
-        ParamType::Array(param) => proptest::collection::vec(fuzz_param(param), 0..MAX_ARRAY_LEN)
-            .prop_map(Token::Array)
-            .boxed(),
+        ParamType::String => fuzz_string(opts.max_string_len).prop_map(Token::String).boxed(),
+        ParamType::Array(param) => {
+            proptest::collection::vec(fuzz_param(param, opts), 0..=opts.max_array_len)
+                .prop_map(Token::Array)
+                .boxed()
+        }
         ParamType::FixedBytes(size) => (0..*size as u64)
             .map(|_| any::<u8>())
             .collect::<Vec<_>>()
             .prop_map(Token::FixedBytes)
             .boxed(),
         ParamType::FixedArray(param, size) => (0..*size as u64)
-            .map(|_| fuzz_param(param).prop_map(|param| param.into_token()))
+            .map(|_| fuzz_param(param, opts).prop_map(|param| param.into_token()))
             .collect::<Vec<_>>()
             .prop_map(Token::FixedArray)
             .boxed(),
-        ParamType::Tuple(params) => {
-            params.iter().map(fuzz_param).collect::<Vec<_>>().prop_map(Token::Tuple).boxed()
+        ParamType::Tuple(params) => params
+            .iter()
+            .map(|param| fuzz_param(param, opts))
+            .collect::<Vec<_>>()
+            .prop_map(Token::Tuple)
+            .boxed(),
+    }
+}
+
+/// Like [`fuzz_param`], but biased towards the `known` values of the test environment, including
+/// in arrays and tuples:
+/// - addresses are known addresses [`KNOWN_ADDRESS_PERCENT`] percent of the time, since random
+///   addresses almost never belong to an account
+/// - `bytes` are the calldata of a known function [`KNOWN_CALLDATA_PERCENT`] percent of the time,
+///   so contracts that forward `bytes` to a call are fuzzed, random bytes almost never start with a
+///   selector
+pub fn fuzz_param_with_known(
+    param: &ParamType,
+    opts: FuzzParamOpts,
+    known: &KnownValues,
+) -> BoxedStrategy<Token> {
+    match param {
+        ParamType::Address => {
+            let addresses = known.addresses.clone();
+            let known = KnownAddressStrategy { addresses: known.addresses.clone() }
+                .prop_map(move |index| addresses.borrow()[index.as_usize()].into_token());
+            prop_oneof![
+                KNOWN_ADDRESS_PERCENT => known,
+                100 - KNOWN_ADDRESS_PERCENT => fuzz_param(param, opts),
+            ]
+            .boxed()
         }
+        ParamType::Bytes if !known.functions.is_empty() => {
+            let calldata =
+                Union::new(known.functions.iter().map(|func| fuzz_calldata(func.clone(), opts)))
+                    .prop_map(|calldata| calldata.into_token());
+            prop_oneof![
+                KNOWN_CALLDATA_PERCENT => calldata,
+                100 - KNOWN_CALLDATA_PERCENT => fuzz_param(param, opts),
+            ]
+            .boxed()
+        }
+        ParamType::Array(param) => proptest::collection::vec(
+            fuzz_param_with_known(param, opts, known),
+            0..=opts.max_array_len,
+        )
+        .prop_map(Token::Array)
+        .boxed(),
+        ParamType::FixedArray(param, size) => {
+            proptest::collection::vec(fuzz_param_with_known(param, opts, known), *size)
+                .prop_map(Token::FixedArray)
+                .boxed()
+        }
+        ParamType::Tuple(params) => params
+            .iter()
+            .map(|param| fuzz_param_with_known(param, opts, known))
+            .collect::<Vec<_>>()
+            .prop_map(Token::Tuple)
+            .boxed(),
+        _ => fuzz_param(param, opts).boxed(),
     }
 }
 
@@ -71,72 +238,285 @@ pub fn fuzz_param(param: &ParamType) -> impl Strategy<Value = Token> {
 /// fuzz state.
 ///
 /// Works with ABI Encoder v2 tuples.
-pub fn fuzz_param_from_state(param: &ParamType, state: EvmFuzzState) -> BoxedStrategy<Token> {
-    // These are to comply with lifetime requirements
-    let state_len = state.borrow().len();
-    let s = state.clone();
-
-    // Select a value from the state
-    let value = any::<prop::sample::Index>()
-        .prop_map(move |index| index.index(state_len))
-        .prop_map(move |index| *s.borrow().iter().nth(index).unwrap());
+pub fn fuzz_param_from_state(
+    param: &ParamType,
+    state: EvmFuzzState,
+    opts: FuzzParamOpts,
+) -> BoxedStrategy<Token> {
+    // Select a value from the state, which grows with the values observed during the runs. The
+    // value shrinks towards zero, so failing inputs are minimized like random ones.
+    let value = StateWordStrategy { state: state.clone() }.prop_map(|word| {
+        let mut buffer = [0u8; 32];
+        word.to_big_endian(&mut buffer);
+        buffer
+    });
 
     // Convert the value based on the parameter type
     match param {
         ParamType::Address => {
             value.prop_map(move |value| Address::from_slice(&value[12..]).into_token()).boxed()
         }
-        ParamType::Bytes => value.prop_map(move |value| Bytes::from(value).into_token()).boxed(),
-        ParamType::Int(n) => match n / 8 {
-            32 => {
-                value.prop_map(move |value| I256::from_raw(U256::from(value)).into_token()).boxed()
-            }
-            y @ 1..=31 => value
-                .prop_map(move |value| {
-                    // Generate a uintN in the correct range, then shift it to the range of intN
-                    // by subtracting 2^(N-1)
-                    let uint = U256::from(value) % U256::from(2usize).pow(U256::from(y * 8));
-                    let max_int_plus1 = U256::from(2usize).pow(U256::from(y * 8 - 1));
-                    let num = I256::from_raw(uint.overflowing_sub(max_int_plus1).0);
-                    num.into_token()
-                })
-                .boxed(),
-            _ => panic!("unsupported solidity type int{}", n),
-        },
+        ParamType::Bytes => {
+            let len = opts.max_bytes_len.min(32);
+            value.prop_map(move |value| Bytes::from(value[..len].to_vec()).into_token()).boxed()
+        }
+        ParamType::Int(n) => {
+            StateIntStrategy { state, bits: *n }.prop_map(|x| x.into_token()).boxed()
+        }
         ParamType::Uint(n) => match n / 8 {
             32 => value.prop_map(move |value| U256::from(value).into_token()).boxed(),
-            y @ 1..=31 => value
+            y => value
                 .prop_map(move |value| {
                     (U256::from(value) % (U256::from(2usize).pow(U256::from(y * 8)))).into_token()
                 })
                 .boxed(),
-            _ => panic!("unsupported solidity type uint{}", n),
         },
         ParamType::Bool => value.prop_map(move |value| Token::Bool(value[31] == 1)).boxed(),
         ParamType::String => value
-            .prop_map(move |value| {
-                Token::String(unsafe { std::str::from_utf8_unchecked(&value[..]).to_string() })
-            })
+            .prop_map(move |value| Token::String(String::from_utf8_lossy(&value).into_owned()))
             .boxed(),
-        ParamType::Array(param) => {
-            proptest::collection::vec(fuzz_param_from_state(param, state), 0..MAX_ARRAY_LEN)
-                .prop_map(Token::Array)
-                .boxed()
-        }
+        ParamType::Array(param) => proptest::collection::vec(
+            fuzz_param_from_state(param, state, opts),
+            0..=opts.max_array_len,
+        )
+        .prop_map(Token::Array)
+        .boxed(),
         ParamType::FixedBytes(size) => {
             let size = *size;
             value.prop_map(move |value| Token::FixedBytes(value[32 - size..].to_vec())).boxed()
         }
         ParamType::FixedArray(param, size) => {
-            proptest::collection::vec(fuzz_param_from_state(param, state), 0..*size)
+            proptest::collection::vec(fuzz_param_from_state(param, state, opts), *size)
                 .prop_map(Token::FixedArray)
                 .boxed()
         }
         ParamType::Tuple(params) => params
             .iter()
-            .map(|p| fuzz_param_from_state(p, state.clone()))
+            .map(|p| fuzz_param_from_state(p, state.clone(), opts))
             .collect::<Vec<_>>()
             .prop_map(Token::Tuple)
             .boxed(),
     }
 }
+
+/// Returns a strategy for external function pointers, which are encoded like `bytes24`: the
+/// address of a known account followed by the selector of a known function, or a random selector
+/// if no functions are known
+pub fn fuzz_function_pointer(known: &KnownValues) -> BoxedStrategy<Token> {
+    let addresses = known.addresses.clone();
+    let address = KnownAddressStrategy { addresses: known.addresses.clone() }
+        .prop_map(move |index| addresses.borrow()[index.as_usize()]);
+    let selector = if known.functions.is_empty() {
+        any::<[u8; 4]>().boxed()
+    } else {
+        let selectors =
+            known.functions.iter().map(|func| func.short_signature()).collect::<Vec<_>>();
+        proptest::sample::select(selectors).boxed()
+    };
+    (address, selector)
+        .prop_map(|(address, selector)| {
+            let mut pointer = Vec::with_capacity(FUNCTION_POINTER_LEN);
+            pointer.extend_from_slice(address.as_bytes());
+            pointer.extend_from_slice(&selector);
+            Token::FixedBytes(pointer)
+        })
+        .boxed()
+}
+
+/// Strategy for the indices of [KnownAddresses], which are simplified towards the first known
+/// address, the zero address
+#[derive(Debug)]
+struct KnownAddressStrategy {
+    addresses: KnownAddresses,
+}
+
+impl Strategy for KnownAddressStrategy {
+    type Tree = UintValueTree;
+    type Value = U256;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        // known addresses are only ever appended, so the index stays valid
+        let index = runner.rng().gen_range(0..self.addresses.borrow().len());
+        Ok(UintValueTree::new(index.into(), false))
+    }
+}
+
+/// Strategy for the words of an [EvmFuzzState] as uints, which are simplified towards zero
+#[derive(Debug)]
+struct StateWordStrategy {
+    state: EvmFuzzState,
+}
+
+impl Strategy for StateWordStrategy {
+    type Tree = UintValueTree;
+    type Value = U256;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let word = select_word(&self.state, runner);
+        Ok(UintValueTree::new(U256::from(word), false))
+    }
+}
+
+/// Strategy for the words of an [EvmFuzzState] as intN, which are simplified towards zero and
+/// keep their sign
+#[derive(Debug)]
+struct StateIntStrategy {
+    state: EvmFuzzState,
+    bits: usize,
+}
+
+impl Strategy for StateIntStrategy {
+    type Tree = IntValueTree;
+    type Value = I256;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let word = U256::from(select_word(&self.state, runner));
+        let value = if self.bits < 256 {
+            // Generate a uintN in the correct range, then shift it to the range of intN by
+            // subtracting 2^(N-1)
+            let uint = word % (U256::one() << self.bits);
+            I256::from_raw(uint.overflowing_sub(U256::one() << (self.bits - 1)).0)
+        } else {
+            I256::from_raw(word)
+        };
+        Ok(IntValueTree::from_int(value))
+    }
+}
+
+/// Selects a random word of the state, the state is sampled at generation time since it grows
+/// during the runs
+fn select_word(state: &EvmFuzzState, runner: &mut TestRunner) -> [u8; 32] {
+    let state = state.borrow();
+    let index = runner.rng().gen_range(0..state.len());
+    *state.iter().nth(index).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::{strategy::ValueTree, test_runner::TestRunner};
+
+    #[test]
+    fn uniform_uints_cover_their_range() {
+        let opts =
+            FuzzParamOpts { uniform_ints: true, edge_percent: Some(0), ..Default::default() };
+        let strategy = fuzz_param(&ParamType::Uint(8), opts);
+        let mut runner = TestRunner::deterministic();
+        let mut counts = [0usize; 4];
+        for _ in 0..4000 {
+            let value = strategy.new_tree(&mut runner).unwrap().current().into_uint().unwrap();
+            assert!(value <= U256::from(u8::MAX));
+            counts[value.as_usize() / 64] += 1;
+        }
+        // every quarter of the range is hit about equally often
+        assert!(counts.iter().all(|count| (800..1200).contains(count)), "{:?}", counts);
+    }
+
+    #[test]
+    fn can_inject_edge_cases() {
+        let opts =
+            FuzzParamOpts { uniform_ints: true, edge_percent: Some(100), ..Default::default() };
+        let strategy = fuzz_param(&ParamType::Uint(256), opts);
+        let mut runner = TestRunner::deterministic();
+        let edges = [U256::zero(), U256::one(), U256::MAX, U256::MAX - 1];
+        let mut seen = [false; 4];
+        for _ in 0..1000 {
+            let value = strategy.new_tree(&mut runner).unwrap().current().into_uint().unwrap();
+            for (edge, seen) in edges.iter().zip(seen.iter_mut()) {
+                *seen |= value == *edge;
+            }
+        }
+        assert_eq!(seen, [true; 4]);
+    }
+
+    #[test]
+    fn addresses_are_biased_towards_known_addresses() {
+        let known = KnownValues::default();
+        known.addresses.borrow_mut().extend([Address::zero(), Address::from_low_u64_be(0xbeef)]);
+        let strategy = fuzz_param_with_known(&ParamType::Address, Default::default(), &known);
+        let mut runner = TestRunner::deterministic();
+
+        let mut hits = 0;
+        for _ in 0..1000 {
+            let mut tree = strategy.new_tree(&mut runner).unwrap();
+            let address = tree.current().into_address().unwrap();
+            if known.addresses.borrow().contains(&address) {
+                hits += 1;
+                // known addresses are simplified towards the zero address
+                while tree.simplify() {}
+                assert_eq!(tree.current(), Address::zero().into_token());
+            }
+        }
+        assert!((500..700).contains(&hits), "{} known addresses", hits);
+    }
+
+    #[test]
+    fn state_values_shrink_towards_zero() {
+        let state = EvmFuzzState::default();
+        let mut word = [0u8; 32];
+        word[31] = 100;
+        state.borrow_mut().insert(word);
+        let mut runner = TestRunner::deterministic();
+
+        let mut tree =
+            fuzz_param_from_state(&ParamType::Uint(256), state.clone(), Default::default())
+                .new_tree(&mut runner)
+                .unwrap();
+        assert_eq!(tree.current(), U256::from(100).into_token());
+        while tree.simplify() {}
+        assert_eq!(tree.current(), U256::zero().into_token());
+
+        // negative ints keep their sign while their magnitude shrinks
+        let mut tree = fuzz_param_from_state(&ParamType::Int(8), state, Default::default())
+            .new_tree(&mut runner)
+            .unwrap();
+        assert_eq!(tree.current(), I256::from(-28).into_token());
+        assert!(tree.simplify());
+        assert_eq!(tree.current(), I256::from(-14).into_token());
+    }
+
+    #[test]
+    fn state_words_are_selected_reproducibly() {
+        let words: Vec<[u8; 32]> = (0u8..16).map(|byte| [byte; 32]).collect();
+        let (state, reversed) = (EvmFuzzState::default(), EvmFuzzState::default());
+        state.borrow_mut().extend(words.iter().copied());
+        reversed.borrow_mut().extend(words.iter().rev().copied());
+
+        let (mut runner, mut other) = (TestRunner::deterministic(), TestRunner::deterministic());
+        for _ in 0..100 {
+            assert_eq!(select_word(&state, &mut runner), select_word(&reversed, &mut other));
+        }
+    }
+
+    #[test]
+    fn can_check_params() {
+        assert_eq!(check_param(&ParamType::Uint(256)), Ok(()));
+        assert_eq!(check_param(&ParamType::Int(12)), Err(UnsupportedParam::Int(12)));
+        assert_eq!(check_param(&ParamType::Uint(264)), Err(UnsupportedParam::Uint(264)));
+        assert_eq!(
+            check_param(&ParamType::Tuple(vec![ParamType::Bool, ParamType::FixedBytes(33)])),
+            Err(UnsupportedParam::FixedBytes(33))
+        );
+
+        let nested =
+            |depth| (0..depth).fold(ParamType::Bool, |param, _| ParamType::Array(Box::new(param)));
+        assert_eq!(check_param(&nested(MAX_PARAM_DEPTH)), Ok(()));
+        let too_deep = nested(MAX_PARAM_DEPTH + 1);
+        assert_eq!(check_param(&too_deep), Err(UnsupportedParam::TooDeep(too_deep.clone())));
+    }
+
+    #[test]
+    fn function_pointers_are_known_address_and_selector() {
+        let known = KnownValues {
+            functions: vec![foundry_utils::IntoFunction::into("function set(uint256)")],
+            ..Default::default()
+        };
+        known.addresses.borrow_mut().push(Address::from_low_u64_be(0xbeef));
+        let mut runner = TestRunner::deterministic();
+
+        let pointer = fuzz_function_pointer(&known).new_tree(&mut runner).unwrap().current();
+        let pointer = pointer.into_fixed_bytes().unwrap();
+        assert_eq!(&pointer[..20], Address::from_low_u64_be(0xbeef).as_bytes());
+        assert_eq!(pointer[20..], [0x60, 0xfe, 0x47, 0xb1]);
+    }
+}
@@ -0,0 +1,197 @@
+use super::uint::UintValueTree;
+use ethers::{
+    abi::{ParamType, Token, Tokenizable},
+    core::rand::Rng,
+    types::{I256, U256},
+};
+use eyre::{bail, eyre};
+use proptest::{prelude::*, strategy::NewTree, test_runner::TestRunner};
+use std::str::FromStr;
+
+/// The percentage of values of a range that are its bounds or next to them
+const RANGE_EDGE_PERCENT: u32 = 25;
+
+/// An inclusive range of values of an int or uint param, like `[1, 1e18]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FuzzRange {
+    Uint(U256, U256),
+    Int(I256, I256),
+}
+
+impl FuzzRange {
+    /// Parses the bounds of a range of values of type `kind`.
+    ///
+    /// Bounds are decimal, hex with a `0x` prefix or in scientific notation like `1e18` or
+    /// `2.5e6`, the bounds of ints may be negative.
+    pub fn parse(kind: &ParamType, min: &str, max: &str) -> eyre::Result<Self> {
+        let range = match kind {
+            ParamType::Uint(bits) => {
+                FuzzRange::Uint(parse_uint(min, *bits)?, parse_uint(max, *bits)?)
+            }
+            ParamType::Int(bits) => FuzzRange::Int(parse_int(min, *bits)?, parse_int(max, *bits)?),
+            _ => bail!("ranges are only supported for ints and uints, not {}", kind),
+        };
+        let ordered = match range {
+            FuzzRange::Uint(min, max) => min <= max,
+            FuzzRange::Int(min, max) => min <= max,
+        };
+        if !ordered {
+            bail!("the min {} of the range is larger than its max {}", min.trim(), max.trim())
+        }
+        Ok(range)
+    }
+
+    /// Returns a strategy for the values of the range, which are simplified towards the min
+    pub fn strategy(self) -> BoxedStrategy<Token> {
+        RangeStrategy { span: self.span() }.prop_map(move |offset| self.value_at(offset)).boxed()
+    }
+
+    /// The distance between the min and the max
+    fn span(&self) -> U256 {
+        match self {
+            FuzzRange::Uint(min, max) => *max - *min,
+            FuzzRange::Int(min, max) => max.into_raw().overflowing_sub(min.into_raw()).0,
+        }
+    }
+
+    /// The value at `offset` from the min
+    fn value_at(&self, offset: U256) -> Token {
+        match self {
+            FuzzRange::Uint(min, _) => (*min + offset).into_token(),
+            FuzzRange::Int(min, _) => {
+                I256::from_raw(min.into_raw().overflowing_add(offset).0).into_token()
+            }
+        }
+    }
+}
+
+/// Strategy for the offsets from the min of a range
+#[derive(Debug)]
+struct RangeStrategy {
+    span: U256,
+}
+
+impl Strategy for RangeStrategy {
+    type Tree = UintValueTree;
+    type Value = U256;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let rng = runner.rng();
+        let offset = if rng.gen_range(0..100) < RANGE_EDGE_PERCENT {
+            match rng.gen_range(0..4) {
+                0 => U256::zero(),
+                1 => U256::one().min(self.span),
+                2 => self.span.saturating_sub(U256::one()),
+                _ => self.span,
+            }
+        } else {
+            let raw = U256::from(&rng.gen::<[u8; 32]>());
+            if self.span == U256::MAX {
+                raw
+            } else {
+                raw % (self.span + 1)
+            }
+        };
+        Ok(UintValueTree::new(offset, false))
+    }
+}
+
+/// Parses a bound of a range of uintN
+fn parse_uint(s: &str, bits: usize) -> eyre::Result<U256> {
+    let value = parse_magnitude(s)?;
+    if bits < 256 && value >> bits != U256::zero() {
+        bail!("{} is out of range for uint{}", s.trim(), bits)
+    }
+    Ok(value)
+}
+
+/// Parses a bound of a range of intN
+fn parse_int(s: &str, bits: usize) -> eyre::Result<I256> {
+    let (negative, magnitude) = match s.trim().strip_prefix('-') {
+        Some(magnitude) => (true, parse_magnitude(magnitude)?),
+        None => (false, parse_magnitude(s)?),
+    };
+    // the magnitude of min is one larger than the one of max
+    let limit = U256::one() << (bits - 1);
+    if magnitude > limit || (!negative && magnitude == limit) {
+        bail!("{} is out of range for int{}", s.trim(), bits)
+    }
+    Ok(if negative {
+        I256::from_raw(U256::zero().overflowing_sub(magnitude).0)
+    } else {
+        I256::from_raw(magnitude)
+    })
+}
+
+/// Parses a non-negative number in decimal, hex or scientific notation
+fn parse_magnitude(s: &str) -> eyre::Result<U256> {
+    let s = s.trim();
+    let invalid = || eyre!("invalid number `{}`", s);
+    if let Some(hex) = s.strip_prefix("0x") {
+        return U256::from_str(hex).map_err(|_| invalid())
+    }
+
+    let (mantissa, exponent) = match s.split_once(|c: char| c == 'e' || c == 'E') {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse().map_err(|_| invalid())?),
+        None => (s, 0usize),
+    };
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    // the fraction has to be shifted into the integer part by the exponent
+    let exponent = exponent.checked_sub(fraction.len()).ok_or_else(invalid)?;
+    let digits = format!("{}{}", integer, fraction);
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid())
+    }
+
+    U256::from_dec_str(&digits)
+        .ok()
+        .and_then(|value| value.checked_mul(U256::from(10).checked_pow(U256::from(exponent))?))
+        .ok_or_else(|| eyre!("`{}` is larger than 2^256 - 1", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::strategy::ValueTree;
+
+    #[test]
+    fn can_parse_ranges() {
+        assert_eq!(
+            FuzzRange::parse(&ParamType::Uint(256), "1", "1e18").unwrap(),
+            FuzzRange::Uint(U256::one(), U256::exp10(18))
+        );
+        assert_eq!(
+            FuzzRange::parse(&ParamType::Uint(64), "0x10", "2.5e3").unwrap(),
+            FuzzRange::Uint(U256::from(16), U256::from(2500))
+        );
+        assert_eq!(
+            FuzzRange::parse(&ParamType::Int(8), "-128", "127").unwrap(),
+            FuzzRange::Int(I256::from(-128), I256::from(127))
+        );
+
+        assert!(FuzzRange::parse(&ParamType::Uint(8), "0", "256").is_err());
+        assert!(FuzzRange::parse(&ParamType::Uint(8), "-1", "1").is_err());
+        assert!(FuzzRange::parse(&ParamType::Int(8), "0", "128").is_err());
+        assert!(FuzzRange::parse(&ParamType::Uint(256), "2", "1").is_err());
+        assert!(FuzzRange::parse(&ParamType::Uint(256), "1.55e1", "20").is_err());
+        assert!(FuzzRange::parse(&ParamType::Bool, "0", "1").is_err());
+    }
+
+    #[test]
+    fn values_stay_in_range() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = FuzzRange::Int(I256::from(-3), I256::from(5)).strategy();
+        let mut seen = [false; 9];
+        for _ in 0..1000 {
+            let mut tree = strategy.new_tree(&mut runner).unwrap();
+            let value = tree.current().into_int().map(I256::from_raw).unwrap();
+            assert!(value >= I256::from(-3) && value <= I256::from(5), "{}", value);
+            seen[(value.as_i32() + 3) as usize] = true;
+
+            // values are simplified towards the min
+            while tree.simplify() {}
+            assert_eq!(tree.current(), I256::from(-3).into_token());
+        }
+        assert_eq!(seen, [true; 9]);
+    }
+}
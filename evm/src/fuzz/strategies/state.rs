@@ -1,32 +1,63 @@
-use super::fuzz_param_from_state;
-use crate::executor::StateChangeset;
+use super::{fuzz_param_from_state, FuzzParamOpts};
+use crate::{
+    executor::StateChangeset,
+    trace::{identifier::LocalTraceIdentifier, TraceIdentifier},
+};
 use bytes::Bytes;
 use ethers::{
-    abi::{Function, RawLog},
-    types::{H256, U256},
+    abi::{Abi, Function, RawLog},
+    types::{Address, H256, U256},
 };
 use proptest::prelude::{BoxedStrategy, Strategy};
 use revm::{
     db::{CacheDB, DatabaseRef},
-    opcode, spec_opcode_gas, SpecId,
+    opcode, spec_opcode_gas, SpecId, KECCAK_EMPTY,
+};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    io::Write,
+    rc::Rc,
 };
-use std::{cell::RefCell, collections::HashSet, io::Write, rc::Rc};
 
 /// A set of arbitrary 32 byte data from the VM used to generate values for the strategy.
 ///
+/// The words are ordered, so the words drawn from the state only depend on the seed of the runner
+/// and not on the order they were collected in.
+///
 /// Wrapped in a shareable container.
-pub type EvmFuzzState = Rc<RefCell<HashSet<[u8; 32]>>>;
+pub type EvmFuzzState = Rc<RefCell<BTreeSet<[u8; 32]>>>;
+
+/// The addresses of the accounts that are known in the test environment, like deployed contracts
+/// and senders, in the order they were found.
+///
+/// Wrapped in a shareable container.
+pub type KnownAddresses = Rc<RefCell<Vec<Address>>>;
+
+/// The values known in the test environment, random params are biased towards them since random
+/// values almost never match them
+#[derive(Debug, Clone, Default)]
+pub struct KnownValues {
+    /// The addresses of the accounts
+    pub addresses: KnownAddresses,
+    /// The functions of the contracts, used to generate calldata for `bytes` params
+    pub functions: Vec<Function>,
+}
+
+/// The number of precompiles, their addresses are `0x1` to `0x9`
+const PRECOMPILE_COUNT: u64 = 9;
 
 /// Given a function and some state, it returns a strategy which generated valid calldata for the
 /// given function's input types, based on state taken from the EVM.
 pub fn fuzz_calldata_from_state(
     func: Function,
     state: EvmFuzzState,
+    opts: FuzzParamOpts,
 ) -> BoxedStrategy<ethers::types::Bytes> {
     let strats = func
         .inputs
         .iter()
-        .map(|input| fuzz_param_from_state(&input.kind, state.clone()))
+        .map(|input| fuzz_param_from_state(&input.kind, state.clone(), opts))
         .collect::<Vec<_>>();
 
     strats
@@ -34,13 +65,15 @@ pub fn fuzz_calldata_from_state(
             tracing::trace!(input = ?tokens);
             func.encode_input(&tokens).unwrap().into()
         })
-        .no_shrink()
         .boxed()
 }
 
 /// Builds the initial [EvmFuzzState] from a database.
+///
+/// Besides the accounts and their storage, the state includes the push bytes of the code of all
+/// accounts, so constants like thresholds, addresses and selectors are fuzzed from the start.
 pub fn build_initial_state<DB: DatabaseRef>(db: &CacheDB<DB>) -> EvmFuzzState {
-    let mut state: HashSet<[u8; 32]> = HashSet::new();
+    let mut state: BTreeSet<[u8; 32]> = BTreeSet::new();
     for (address, storage) in db.storage() {
         let info = db.basic(*address);
 
@@ -54,18 +87,87 @@ pub fn build_initial_state<DB: DatabaseRef>(db: &CacheDB<DB>) -> EvmFuzzState {
             state.insert(u256_to_h256(*slot).into());
             state.insert(u256_to_h256(*value).into());
         }
+
+        // Insert push bytes
+        let code = match info.code {
+            Some(code) => code,
+            None if info.code_hash == KECCAK_EMPTY => continue,
+            None => db.code_by_hash(info.code_hash),
+        };
+        state.extend(collect_push_bytes(code));
     }
 
     Rc::new(RefCell::new(state))
 }
 
-/// Collects state changes from a [StateChangeset] and logs into an [EvmFuzzState].
+/// Builds the initial [KnownAddresses] from a database and the addresses of `actors`, like the test
+/// contract and the sender.
+///
+/// The addresses include the zero address, which is the first one, and the precompiles.
+pub fn build_known_addresses<DB: DatabaseRef>(
+    db: &CacheDB<DB>,
+    actors: impl IntoIterator<Item = Address>,
+) -> KnownAddresses {
+    let addresses = Rc::new(RefCell::new(Vec::new()));
+    collect_addresses(
+        (0..=PRECOMPILE_COUNT)
+            .map(Address::from_low_u64_be)
+            .chain(actors)
+            .chain(db.storage().keys().copied()),
+        &addresses,
+    );
+    addresses
+}
+
+/// Returns the functions of the `known_contracts` that are deployed in the database, deduplicated
+/// by selector. Tests and `setUp` are left out.
+pub fn deployed_functions<DB: DatabaseRef>(
+    db: &CacheDB<DB>,
+    known_contracts: &BTreeMap<String, (Abi, Vec<u8>)>,
+) -> Vec<Function> {
+    let identifier = LocalTraceIdentifier::new(known_contracts);
+    let mut functions = BTreeMap::new();
+    for address in db.storage().keys() {
+        let info = db.basic(*address);
+        let code = match info.code {
+            Some(code) => code,
+            None if info.code_hash == KECCAK_EMPTY => continue,
+            None => db.code_by_hash(info.code_hash),
+        };
+        if let (_, _, Some(abi)) = identifier.identify_address(address, Some(&code.to_vec())) {
+            functions.extend(
+                abi.functions()
+                    .filter(|func| !func.name.starts_with("test") && func.name != "setUp")
+                    .map(|func| (func.short_signature(), func.clone())),
+            );
+        }
+    }
+    functions.into_values().collect()
+}
+
+/// Appends the addresses that are not known yet to the [KnownAddresses], like the accounts touched
+/// by a call.
+///
+/// The new addresses are sorted, so their order doesn't depend on the order of a map.
+pub fn collect_addresses(addresses: impl IntoIterator<Item = Address>, known: &KnownAddresses) {
+    let known = &mut *known.borrow_mut();
+    let mut new =
+        addresses.into_iter().filter(|address| !known.contains(address)).collect::<Vec<_>>();
+    new.sort_unstable();
+    new.dedup();
+    known.extend(new);
+}
+
+/// Collects state changes from a [StateChangeset], logs and the values observed during the call,
+/// like intermediate storage writes and return data, into an [EvmFuzzState].
 pub fn collect_state_from_call(
     logs: &[RawLog],
     state_changeset: &StateChangeset,
+    values: &[[u8; 32]],
     state: EvmFuzzState,
 ) {
     let state = &mut *state.borrow_mut();
+    state.extend(values.iter().copied());
 
     for (address, account) in state_changeset {
         // Insert basic account information
@@ -85,20 +187,19 @@ pub fn collect_state_from_call(
                 state.insert(push_byte);
             }
         }
+    }
 
-        // Insert log topics and data
-        for log in logs {
-            log.topics.iter().for_each(|topic| {
-                state.insert(topic.0);
-            });
-            log.data.chunks(32).for_each(|chunk| {
-                let mut buffer: [u8; 32] = [0; 32];
-                let _ = (&mut buffer[..])
-                    .write(chunk)
-                    .expect("log data chunk was larger than 32 bytes");
-                state.insert(buffer);
-            });
-        }
+    // Insert log topics and data
+    for log in logs {
+        log.topics.iter().for_each(|topic| {
+            state.insert(topic.0);
+        });
+        log.data.chunks(32).for_each(|chunk| {
+            let mut buffer: [u8; 32] = [0; 32];
+            let _ =
+                (&mut buffer[..]).write(chunk).expect("log data chunk was larger than 32 bytes");
+            state.insert(buffer);
+        });
     }
 }
 
@@ -130,10 +231,9 @@ fn collect_push_bytes(code: Bytes) -> Vec<[u8; 32]> {
                 return bytes
             }
 
+            // push bytes are big endian, so they are right aligned in the word
             let mut buffer: [u8; 32] = [0; 32];
-            let _ = (&mut buffer[..])
-                .write(&code[push_start..push_end])
-                .expect("push was larger than 32 bytes");
+            buffer[32 - push_size..].copy_from_slice(&code[push_start..push_end]);
             bytes.push(buffer);
             i += push_size;
         }
@@ -149,3 +249,30 @@ fn u256_to_h256(u: U256) -> H256 {
     u.to_little_endian(h.as_mut());
     h
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_collect_push_bytes() {
+        // PUSH1 0x2a PUSH2 0x0100 ADD PUSH20 <address>
+        let mut code = vec![0x60, 0x2a, 0x61, 0x01, 0x00, 0x01, 0x73];
+        code.extend([0x11; 20]);
+
+        let bytes = collect_push_bytes(Bytes::from(code));
+        assert_eq!(bytes.len(), 3);
+        assert_eq!(U256::from(bytes[0]), U256::from(42));
+        assert_eq!(U256::from(bytes[1]), U256::from(256));
+        assert_eq!(&bytes[2][12..], &[0x11; 20]);
+    }
+
+    #[test]
+    fn can_collect_addresses() {
+        let known = KnownAddresses::default();
+        let address = Address::from_low_u64_be;
+        collect_addresses([address(3), address(1)], &known);
+        collect_addresses([address(2), address(1), address(2)], &known);
+        assert_eq!(*known.borrow(), vec![address(1), address(3), address(2)]);
+    }
+}
@@ -0,0 +1,48 @@
+use proptest::{collection::vec, prelude::*};
+
+/// The default max length of fuzzed strings in chars
+pub const DEFAULT_MAX_STRING_LEN: usize = 256;
+
+/// Returns a strategy for valid UTF-8 strings of at most `max_len` chars.
+///
+/// The strategy mixes:
+/// 1. Empty strings
+/// 2. Printable ASCII strings
+/// 3. Strings of arbitrary unicode chars, which are up to 4 bytes long each
+/// 4. ASCII strings with null bytes
+/// 5. Strings of at least half the max length
+pub fn fuzz_string(max_len: usize) -> BoxedStrategy<String> {
+    let ascii = || proptest::char::range(' ', '~');
+    let with_nulls = prop_oneof![3 => ascii(), 1 => Just('\0')];
+    let long_len = max_len / 2..=max_len;
+
+    prop_oneof![
+        5 => Just(String::new()),
+        40 => vec(ascii(), 0..=max_len).prop_map(String::from_iter),
+        30 => vec(any::<char>(), 0..=max_len).prop_map(String::from_iter),
+        10 => vec(with_nulls, 0..=max_len).prop_map(String::from_iter),
+        15 => vec(ascii(), long_len).prop_map(String::from_iter),
+    ]
+    .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::{strategy::ValueTree, test_runner::TestRunner};
+
+    #[test]
+    fn strings_are_bounded() {
+        let strategy = fuzz_string(8);
+        let mut runner = TestRunner::deterministic();
+        let (mut empty, mut non_ascii, mut nulls) = (false, false, false);
+        for _ in 0..1000 {
+            let s = strategy.new_tree(&mut runner).unwrap().current();
+            assert!(s.chars().count() <= 8, "{:?} is too long", s);
+            empty |= s.is_empty();
+            non_ascii |= !s.is_ascii();
+            nulls |= s.contains('\0');
+        }
+        assert!(empty && non_ascii && nulls);
+    }
+}
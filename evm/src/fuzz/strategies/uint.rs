@@ -24,7 +24,7 @@ impl UintValueTree {
     /// # Arguments
     /// * `start` - Starting value for the tree
     /// * `fixed` - If `true` the tree would only contain one element and won't be simplified.
-    fn new(start: U256, fixed: bool) -> Self {
+    pub fn new(start: U256, fixed: bool) -> Self {
         Self { lo: 0.into(), curr: start, hi: start, fixed }
     }
 
@@ -69,22 +69,30 @@ impl ValueTree for UintValueTree {
 
 /// Value tree for unsigned ints (up to uint256).
 /// The strategy combines 3 different strategies, each assigned a specific weight:
-/// 1. Generate purely random value in a range. This will first choose bit size uniformly (up `bits`
-/// param). Then generate a value for this bit size.
-/// 2. Generate a random value around the edges (+/- 3 around 0 and max possible value)
-/// 3. Generate a value from a predefined fixtures set
+/// 1. Generate a value around the edges (+/- 3 around 0 and max possible value, +/- 1 around a
+/// power of two)
+/// 2. Generate a value from a predefined fixtures set, or around the edges if there are none
+/// 3. Generate purely random value in a range. This will first choose bit size uniformly (up `bits`
+/// param), unless the values are sampled uniformly. Then generate a value for this bit size.
+///
+/// If `edge_percent` is set, that percentage of the values are edge cases instead and the rest
+/// are split between the fixtures and the random values according to their weights.
 #[derive(Debug)]
 pub struct UintStrategy {
     /// Bit sise of uint (e.g. 256)
     bits: usize,
     /// A set of fixtures to be generated
     fixtures: Vec<U256>,
-    /// The weight for edge cases (+/- 3 around 0 and max possible value)
+    /// The weight for edge cases
     edge_weight: usize,
+    /// The percentage of edge cases, overrides the weights if set
+    edge_percent: Option<u32>,
     /// The weight for fixtures
     fixtures_weight: usize,
     /// The weight for purely random values
     random_weight: usize,
+    /// Whether random values are sampled uniformly from the range of the type
+    uniform: bool,
 }
 
 impl UintStrategy {
@@ -97,23 +105,50 @@ impl UintStrategy {
             bits,
             fixtures,
             edge_weight: 10usize,
+            edge_percent: None,
             fixtures_weight: 40usize,
             random_weight: 50usize,
+            uniform: false,
         }
     }
 
+    /// Sets the percentage of values that are edge cases, capped at 100
+    #[must_use]
+    pub fn with_edge_percent(mut self, edge_percent: u32) -> Self {
+        self.edge_percent = Some(edge_percent.min(100));
+        self
+    }
+
+    /// Samples random values uniformly from the range of the type instead of choosing the bit
+    /// size first, which favours small values
+    #[must_use]
+    pub fn with_uniform(mut self, uniform: bool) -> Self {
+        self.uniform = uniform;
+        self
+    }
+
     fn generate_edge_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
         let rng = runner.rng();
 
-        // Choose if we want values around 0 or max
-        let is_min = rng.gen_bool(0.5);
-        let offset = U256::from(rng.gen_range(0..4));
+        // Choose if we want values around 0, max or a power of two, overflows are most likely
+        // around max so it's chosen as often as 0
         let max = if self.bits < 256 {
             (U256::from(1u8) << U256::from(self.bits)) - 1
         } else {
             U256::MAX
         };
-        let start = if is_min { offset } else { max - offset };
+        let start = match rng.gen_range(0..5) {
+            0 | 1 => U256::from(rng.gen_range(0..4u64)),
+            2 | 3 => max - rng.gen_range(0..4u64),
+            _ => {
+                let power = U256::one() << rng.gen_range(1..self.bits);
+                match rng.gen_range(0..3) {
+                    0 => power - 1,
+                    1 => power,
+                    _ => power + 1,
+                }
+            }
+        };
 
         Ok(UintValueTree::new(start, false))
     }
@@ -130,8 +165,8 @@ impl UintStrategy {
 
     fn generate_random_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
         let rng = runner.rng();
-        // generate random number of bits uniformly
-        let bits = rng.gen_range(0..=self.bits);
+        // generate random number of bits uniformly, unless the value is sampled uniformly
+        let bits = if self.uniform { self.bits } else { rng.gen_range(0..=self.bits) };
 
         // init 2 128-bit randoms
         let mut higher: u128 = rng.gen_range(0..=u128::MAX);
@@ -165,13 +200,54 @@ impl Strategy for UintStrategy {
     type Value = U256;
 
     fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
-        let total_weight = self.random_weight + self.fixtures_weight + self.edge_weight;
-        let bias = runner.rng().gen_range(0..total_weight);
-        // randomly selecty one of 3 strategies
-        match bias {
-            x if x < self.edge_weight => self.generate_edge_tree(runner),
-            x if x < self.edge_weight + self.fixtures_weight => self.generate_fixtures_tree(runner),
+        let edge_percent = match self.edge_percent {
+            Some(edge_percent) => edge_percent,
+            None => {
+                let total_weight = self.random_weight + self.fixtures_weight + self.edge_weight;
+                let bias = runner.rng().gen_range(0..total_weight);
+                // randomly selecty one of 3 strategies
+                return match bias {
+                    x if x < self.edge_weight => self.generate_edge_tree(runner),
+                    x if x < self.edge_weight + self.fixtures_weight => {
+                        self.generate_fixtures_tree(runner)
+                    }
+                    _ => self.generate_random_tree(runner),
+                }
+            }
+        };
+
+        if runner.rng().gen_range(0..100) < edge_percent {
+            return self.generate_edge_tree(runner)
+        }
+        if self.fixtures.is_empty() {
+            return self.generate_random_tree(runner)
+        }
+        let total_weight = self.random_weight + self.fixtures_weight;
+        // randomly select one of the remaining strategies
+        match runner.rng().gen_range(0..total_weight) {
+            x if x < self.fixtures_weight => self.generate_fixtures_tree(runner),
             _ => self.generate_random_tree(runner),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn half_of_the_default_values_are_edge_cases() {
+        let strategy = UintStrategy::new(256, vec![]);
+        let mut runner = TestRunner::deterministic();
+        let edges = (0..4u64).flat_map(|offset| [U256::from(offset), U256::MAX - offset]);
+        let edges = edges.collect::<Vec<_>>();
+        let mut hits = 0;
+        for _ in 0..4000 {
+            let value = strategy.new_tree(&mut runner).unwrap().current();
+            // values around a power of two can't be told apart from random ones
+            hits += edges.contains(&value) as usize;
+        }
+        // 4 in 5 edge cases are around 0 or max
+        assert!((1400..1800).contains(&hits), "{}", hits);
+    }
+}
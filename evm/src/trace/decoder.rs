@@ -1,4 +1,5 @@
 use super::{
+    precompiles::{decode_precompile, PRECOMPILES_LABEL},
     CallTraceArena, RawOrDecodedCall, RawOrDecodedLog, RawOrDecodedReturnData, TraceIdentifier,
 };
 use crate::abi::{CHEATCODE_ADDRESS, CONSOLE_ABI, HEVM_ABI};
@@ -108,17 +109,39 @@ impl CallTraceDecoder {
     }
 
     pub fn decode(&self, traces: &mut CallTraceArena) {
-        for node in traces.arena.iter_mut() {
+        let implementations = traces.implementations();
+        for (idx, node) in traces.arena.iter_mut().enumerate() {
             // Set contract name
             if let Some(contract) = self.contracts.get(&node.trace.address) {
                 node.trace.contract = Some(contract.clone());
             }
 
-            // Set label
-            if let Some(label) = self.labels.get(&node.trace.address) {
+            // Set label, calls to proxies are labeled as `Proxy -> Implementation`
+            if let Some(implementation) = implementations.get(&idx) {
+                node.trace.label = Some(format!(
+                    "{} -> {}",
+                    self.label_or_address(&node.trace.address),
+                    self.label_or_address(implementation)
+                ));
+            } else if let Some(label) = self.labels.get(&node.trace.address) {
                 node.trace.label = Some(label.clone());
             }
 
+            // Decode calls to precompiles
+            if let (RawOrDecodedCall::Raw(input), RawOrDecodedReturnData::Raw(output)) =
+                (&node.trace.data, &node.trace.output)
+            {
+                if let Some((name, inputs, output)) =
+                    decode_precompile(&node.trace.address, input, output, node.trace.success)
+                {
+                    node.trace.label.get_or_insert_with(|| PRECOMPILES_LABEL.to_string());
+                    node.trace.data = RawOrDecodedCall::Decoded(name, inputs);
+                    if let Some(output) = output {
+                        node.trace.output = RawOrDecodedReturnData::Decoded(output);
+                    }
+                }
+            }
+
             // Decode call
             if let RawOrDecodedCall::Raw(bytes) = &node.trace.data {
                 if bytes.len() >= 4 {
@@ -215,6 +238,10 @@ impl CallTraceDecoder {
         }
     }
 
+    fn label_or_address(&self, address: &Address) -> String {
+        self.labels.get(address).cloned().unwrap_or_else(|| format!("{:?}", address))
+    }
+
     fn apply_label(&self, token: &Token) -> String {
         match token {
             Token::Address(addr) => {
@@ -0,0 +1,312 @@
+use super::{CallKind, CallTraceArena, RawOrDecodedCall, RawOrDecodedReturnData};
+use serde_json::{json, Value};
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+/// The formats call traces can be printed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceFormat {
+    /// The human readable call tree
+    Text,
+    /// The serialized [CallTraceArena]
+    Json,
+    /// The format of geth's `callTracer`
+    Geth,
+    /// The format of parity's/openethereum's `trace_transaction`
+    Parity,
+}
+
+impl Default for TraceFormat {
+    fn default() -> Self {
+        TraceFormat::Text
+    }
+}
+
+impl FromStr for TraceFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(TraceFormat::Text),
+            "json" => Ok(TraceFormat::Json),
+            "geth" => Ok(TraceFormat::Geth),
+            "parity" | "openethereum" => Ok(TraceFormat::Parity),
+            s => Err(format!("Unknown trace format `{}`, expected text, json, geth or parity", s)),
+        }
+    }
+}
+
+impl fmt::Display for TraceFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceFormat::Text => f.write_str("text"),
+            TraceFormat::Json => f.write_str("json"),
+            TraceFormat::Geth => f.write_str("geth"),
+            TraceFormat::Parity => f.write_str("parity"),
+        }
+    }
+}
+
+impl TraceFormat {
+    /// Whether the format contains the raw calldata and return data, i.e. whether the traces have
+    /// to be formatted before they are decoded
+    pub fn is_raw(&self) -> bool {
+        matches!(self, TraceFormat::Geth | TraceFormat::Parity)
+    }
+
+    /// Formats the traces
+    pub fn format(&self, traces: &CallTraceArena) -> String {
+        match self {
+            TraceFormat::Text => traces.to_string(),
+            TraceFormat::Json => serde_json::to_string(traces).unwrap_or_default(),
+            TraceFormat::Geth => traces.to_geth_trace().to_string(),
+            TraceFormat::Parity => Value::Array(traces.to_parity_traces()).to_string(),
+        }
+    }
+}
+
+impl CallTraceArena {
+    /// Converts the traces into the nested call frames of geth's `callTracer`
+    pub fn to_geth_trace(&self) -> Value {
+        self.geth_frame(0)
+    }
+
+    fn geth_frame(&self, idx: usize) -> Value {
+        let node = &self.arena[idx];
+        let trace = &node.trace;
+        let kind = match trace.kind {
+            CallKind::Call => "CALL",
+            CallKind::StaticCall => "STATICCALL",
+            CallKind::CallCode => "CALLCODE",
+            CallKind::DelegateCall => "DELEGATECALL",
+            CallKind::Create => "CREATE",
+        };
+        let (input, output) = raw_data(&trace.data, &trace.output);
+
+        let mut frame = json!({
+            "type": kind,
+            "from": trace.caller,
+            "to": trace.address,
+            "value": trace.value,
+            "gas": format!("{:#x}", trace.gas_limit),
+            "gasUsed": format!("{:#x}", trace.gas_cost),
+            "input": input,
+        });
+        if trace.success {
+            frame["output"] = output.into();
+        } else {
+            frame["error"] = "execution reverted".into();
+        }
+        if !node.children.is_empty() {
+            frame["calls"] = node
+                .children
+                .iter()
+                .map(|child| self.geth_frame(*child))
+                .collect::<Vec<_>>()
+                .into();
+        }
+        frame
+    }
+
+    /// Converts the traces into the flat list of parity's `trace_transaction`
+    pub fn to_parity_traces(&self) -> Vec<Value> {
+        let mut traces = Vec::with_capacity(self.arena.len());
+        self.parity_traces(0, Vec::new(), &mut traces);
+        traces
+    }
+
+    fn parity_traces(&self, idx: usize, trace_address: Vec<usize>, traces: &mut Vec<Value>) {
+        let node = &self.arena[idx];
+        let trace = &node.trace;
+        let (input, output) = raw_data(&trace.data, &trace.output);
+
+        let (kind, action, result) = if trace.kind == CallKind::Create {
+            (
+                "create",
+                json!({
+                    "from": trace.caller,
+                    "gas": format!("{:#x}", trace.gas_limit),
+                    "init": input,
+                    "value": trace.value,
+                }),
+                json!({
+                    "address": trace.address,
+                    "code": output,
+                    "gasUsed": format!("{:#x}", trace.gas_cost),
+                }),
+            )
+        } else {
+            let call_type = match trace.kind {
+                CallKind::StaticCall => "staticcall",
+                CallKind::CallCode => "callcode",
+                CallKind::DelegateCall => "delegatecall",
+                _ => "call",
+            };
+            (
+                "call",
+                json!({
+                    "callType": call_type,
+                    "from": trace.caller,
+                    "to": trace.address,
+                    "gas": format!("{:#x}", trace.gas_limit),
+                    "input": input,
+                    "value": trace.value,
+                }),
+                json!({
+                    "gasUsed": format!("{:#x}", trace.gas_cost),
+                    "output": output,
+                }),
+            )
+        };
+
+        let mut value = json!({
+            "type": kind,
+            "action": action,
+            "subtraces": node.children.len(),
+            "traceAddress": trace_address,
+        });
+        if trace.success {
+            value["result"] = result;
+        } else {
+            value["error"] = "Reverted".into();
+        }
+        traces.push(value);
+
+        for (i, child) in node.children.iter().enumerate() {
+            let mut child_address = trace_address.clone();
+            child_address.push(i);
+            self.parity_traces(*child, child_address, traces);
+        }
+    }
+
+    /// Converts the traces into the folded stacks format of flamegraph tools like `inferno` or
+    /// speedscope.
+    ///
+    /// Every line is a call path, e.g. `Test::testDeposit;Vault::deposit;Token::transferFrom`,
+    /// followed by the gas spent in the last frame of the path, excluding the gas of its
+    /// subcalls. The traces should be decoded first to get readable frame names.
+    pub fn to_folded_stacks(&self) -> Vec<String> {
+        let mut stacks = BTreeMap::new();
+        self.fold(0, "", &mut stacks);
+        stacks
+            .into_iter()
+            .filter(|(_, gas)| *gas > 0)
+            .map(|(stack, gas)| format!("{} {}", stack, gas))
+            .collect()
+    }
+
+    fn fold(&self, idx: usize, parent: &str, stacks: &mut BTreeMap<String, u64>) {
+        let node = &self.arena[idx];
+        let trace = &node.trace;
+        let contract = trace.label.clone().unwrap_or_else(|| format!("{:?}", trace.address));
+        let function = match &trace.data {
+            _ if trace.created => "new".to_string(),
+            RawOrDecodedCall::Decoded(name, _) => name.clone(),
+            RawOrDecodedCall::Raw(bytes) => hex::encode(&bytes[..bytes.len().min(4)]),
+        };
+        let frame = format!("{}::{}", contract, function).replace(';', ":");
+        let stack = if parent.is_empty() { frame } else { format!("{};{}", parent, frame) };
+
+        let children_gas: u64 =
+            node.children.iter().map(|child| self.arena[*child].trace.gas_cost).sum();
+        *stacks.entry(stack.clone()).or_default() += trace.gas_cost.saturating_sub(children_gas);
+
+        for child in &node.children {
+            self.fold(*child, &stack, stacks);
+        }
+    }
+}
+
+/// Returns the hex encoded calldata and return data, decoded data is left empty
+fn raw_data(data: &RawOrDecodedCall, output: &RawOrDecodedReturnData) -> (String, String) {
+    let data = match data {
+        RawOrDecodedCall::Raw(bytes) => bytes.as_slice(),
+        RawOrDecodedCall::Decoded(..) => &[],
+    };
+    let output = match output {
+        RawOrDecodedReturnData::Raw(bytes) => bytes.as_slice(),
+        RawOrDecodedReturnData::Decoded(_) => &[],
+    };
+    (format!("0x{}", hex::encode(data)), format!("0x{}", hex::encode(output)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::CallTrace;
+    use ethers::types::Address;
+
+    fn traces() -> CallTraceArena {
+        let mut arena = CallTraceArena::default();
+        arena.push_trace(
+            0,
+            CallTrace {
+                depth: 0,
+                success: true,
+                address: Address::repeat_byte(1),
+                data: RawOrDecodedCall::Raw(vec![0xde, 0xad, 0xbe, 0xef]),
+                output: RawOrDecodedReturnData::Raw(vec![1]),
+                gas_cost: 100,
+                ..Default::default()
+            },
+        );
+        arena.push_trace(
+            0,
+            CallTrace {
+                depth: 1,
+                kind: CallKind::StaticCall,
+                caller: Address::repeat_byte(1),
+                address: Address::repeat_byte(2),
+                data: RawOrDecodedCall::Raw(vec![0x12, 0x34, 0x56, 0x78]),
+                ..Default::default()
+            },
+        );
+        arena
+    }
+
+    #[test]
+    fn can_export_geth_trace() {
+        let trace = traces().to_geth_trace();
+        assert_eq!(trace["type"], "CALL");
+        assert_eq!(trace["input"], "0xdeadbeef");
+        assert_eq!(trace["output"], "0x01");
+        assert_eq!(trace["gasUsed"], "0x64");
+        assert_eq!(trace["calls"][0]["type"], "STATICCALL");
+        assert_eq!(trace["calls"][0]["from"], "0x0101010101010101010101010101010101010101");
+        assert_eq!(trace["calls"][0]["error"], "execution reverted");
+    }
+
+    #[test]
+    fn can_export_parity_traces() {
+        let traces = traces().to_parity_traces();
+        assert_eq!(traces.len(), 2);
+        assert_eq!(traces[0]["subtraces"], 1);
+        assert_eq!(traces[0]["action"]["callType"], "call");
+        assert_eq!(traces[0]["result"]["output"], "0x01");
+        assert_eq!(traces[1]["traceAddress"], json!([0]));
+        assert_eq!(traces[1]["action"]["callType"], "staticcall");
+        assert_eq!(traces[1]["error"], "Reverted");
+    }
+
+    #[test]
+    fn can_fold_stacks() {
+        let mut traces = traces();
+        traces.arena[0].trace.label = Some("Test".to_string());
+        traces.arena[0].trace.data = RawOrDecodedCall::Decoded("testExample".to_string(), vec![]);
+        traces.arena[1].trace.label = Some("Token".to_string());
+        traces.arena[1].trace.gas_cost = 30;
+        assert_eq!(
+            traces.to_folded_stacks(),
+            vec![
+                "Test::testExample 70".to_string(),
+                "Test::testExample;Token::12345678 30".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn can_parse_trace_format() {
+        assert_eq!("geth".parse::<TraceFormat>().unwrap(), TraceFormat::Geth);
+        assert_eq!("Parity".parse::<TraceFormat>().unwrap(), TraceFormat::Parity);
+        assert!("tenderly".parse::<TraceFormat>().is_err());
+    }
+}
@@ -7,14 +7,24 @@ pub use identifier::TraceIdentifier;
 mod decoder;
 pub use decoder::CallTraceDecoder;
 
+mod export;
+pub use export::TraceFormat;
+
+mod precompiles;
+pub use precompiles::{decode_precompile, precompile_name};
+
 use crate::abi::CHEATCODE_ADDRESS;
 use ansi_term::Colour;
 use ethers::{
     abi::{Address, RawLog},
     types::U256,
 };
+use revm::CallScheme;
 use serde::{Deserialize, Serialize};
-use std::fmt::{self, Write};
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write},
+};
 
 /// An arena of [CallTraceNode]s
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,6 +70,33 @@ impl CallTraceArena {
         }
     }
 
+    /// Returns the implementations of all calls to proxies, by the index of the call's node.
+    ///
+    /// A call is considered to be a call to a proxy if the callee forwards the calldata as is to
+    /// another contract via `DELEGATECALL`, which covers EIP-1967 and beacon proxies as well as
+    /// minimal proxies (EIP-1167).
+    pub fn implementations(&self) -> BTreeMap<usize, Address> {
+        self.arena
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| !node.trace.created)
+            .filter_map(|(idx, node)| {
+                node.children
+                    .iter()
+                    .map(|child| &self.arena[*child].trace)
+                    .find(|child| {
+                        child.kind == CallKind::DelegateCall &&
+                            child.address != node.trace.address &&
+                            matches!(
+                                (&child.data, &node.trace.data),
+                                (RawOrDecodedCall::Raw(a), RawOrDecodedCall::Raw(b)) if a == b
+                            )
+                    })
+                    .map(|child| (idx, child.address))
+            })
+            .collect()
+    }
+
     pub fn addresses_iter(&self) -> impl Iterator<Item = (&Address, Option<&Vec<u8>>)> {
         self.arena.iter().map(|node| {
             let code = if node.trace.created {
@@ -119,6 +156,9 @@ impl fmt::Display for CallTraceArena {
                     LogCallOrder::Call(index) => {
                         inner(arena, writer, node.children[*index], &left_prefix, &right_prefix)?;
                     }
+                    LogCallOrder::Storage(index) => {
+                        writeln!(writer, "{}{}", left_prefix, node.storage[*index])?;
+                    }
                 }
             }
 
@@ -195,6 +235,38 @@ impl fmt::Display for RawOrDecodedLog {
 pub enum LogCallOrder {
     Log(usize),
     Call(usize),
+    Storage(usize),
+}
+
+/// A storage read (`SLOAD`) or write (`SSTORE`) of a call
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageAccess {
+    /// The address whose storage was accessed
+    pub address: Address,
+    /// The storage slot
+    pub slot: U256,
+    /// The value of the slot before the access
+    pub value: U256,
+    /// The value written to the slot, if this was a write
+    pub new_value: Option<U256>,
+    /// The names of the storage variables at the slot, if known
+    #[serde(default)]
+    pub names: Vec<String>,
+}
+
+impl fmt::Display for StorageAccess {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let op = if self.new_value.is_some() { "sstore" } else { "sload" };
+        write!(f, "{} slot {}", Colour::Purple.paint(op), self.slot)?;
+        if !self.names.is_empty() {
+            write!(f, " ({})", self.names.join(", "))?;
+        }
+        write!(f, ": {:#x}", self.value)?;
+        if let Some(new_value) = self.new_value {
+            write!(f, " -> {:#x}", new_value)?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
@@ -211,7 +283,10 @@ pub struct CallTraceNode {
     /// Logs
     #[serde(skip)]
     pub logs: Vec<RawOrDecodedLog>,
-    /// Ordering of child calls and logs
+    /// Storage reads and writes, only recorded if storage tracing is enabled
+    #[serde(default)]
+    pub storage: Vec<StorageAccess>,
+    /// Ordering of child calls, logs and storage accesses
     pub ordering: Vec<LogCallOrder>,
 }
 
@@ -264,6 +339,34 @@ impl fmt::Display for RawOrDecodedReturnData {
     }
 }
 
+/// The kind of a call
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum CallKind {
+    Call,
+    StaticCall,
+    CallCode,
+    /// The code of the callee is executed in the context of the caller
+    DelegateCall,
+    Create,
+}
+
+impl Default for CallKind {
+    fn default() -> Self {
+        CallKind::Call
+    }
+}
+
+impl From<CallScheme> for CallKind {
+    fn from(scheme: CallScheme) -> Self {
+        match scheme {
+            CallScheme::Call => CallKind::Call,
+            CallScheme::StaticCall => CallKind::StaticCall,
+            CallScheme::CallCode => CallKind::CallCode,
+            CallScheme::DelegateCall => CallKind::DelegateCall,
+        }
+    }
+}
+
 /// A trace of a call.
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct CallTrace {
@@ -285,6 +388,15 @@ pub struct CallTrace {
     pub address: Address,
     /// Whether the call was a contract creation or not
     pub created: bool,
+    /// The kind of the call
+    #[serde(default)]
+    pub kind: CallKind,
+    /// The address of the caller
+    #[serde(default)]
+    pub caller: Address,
+    /// The gas limit of the call
+    #[serde(default)]
+    pub gas_limit: u64,
     /// The value tranferred in the call
     pub value: U256,
     /// The calldata for the call, or the init code for contract creations
@@ -302,6 +414,9 @@ impl CallTrace {
         self.success = new_trace.success;
         self.address = new_trace.address;
         self.created = new_trace.created;
+        self.kind = new_trace.kind;
+        self.caller = new_trace.caller;
+        self.gas_limit = new_trace.gas_limit;
         self.value = new_trace.value;
         self.data = new_trace.data;
         self.output = new_trace.output;
@@ -371,3 +486,45 @@ fn trace_color(trace: &CallTrace) -> Colour {
         Colour::Red
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_label_calls_to_proxies() {
+        let proxy = Address::random();
+        let implementation = Address::random();
+        let calldata = vec![0xde, 0xad, 0xbe, 0xef];
+
+        let mut arena = CallTraceArena::default();
+        arena.push_trace(
+            0,
+            CallTrace {
+                depth: 0,
+                address: proxy,
+                data: RawOrDecodedCall::Raw(calldata.clone()),
+                ..Default::default()
+            },
+        );
+        arena.push_trace(
+            0,
+            CallTrace {
+                depth: 1,
+                address: implementation,
+                kind: CallKind::DelegateCall,
+                data: RawOrDecodedCall::Raw(calldata),
+                ..Default::default()
+            },
+        );
+        assert_eq!(arena.implementations(), BTreeMap::from([(0, implementation)]));
+
+        let decoder = CallTraceDecoder::new_with_labels(BTreeMap::from([
+            (proxy, "Proxy".to_string()),
+            (implementation, "Counter".to_string()),
+        ]));
+        decoder.decode(&mut arena);
+        assert_eq!(arena.arena[0].trace.label.as_deref(), Some("Proxy -> Counter"));
+        assert_eq!(arena.arena[1].trace.label.as_deref(), Some("Counter"));
+    }
+}
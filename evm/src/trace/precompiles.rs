@@ -0,0 +1,186 @@
+use ethers::types::{Address, U256};
+
+/// The name of the label of calls to precompiles
+pub const PRECOMPILES_LABEL: &str = "PRECOMPILES";
+
+/// Returns the name of the precompile at `address`, if any
+pub fn precompile_name(address: &Address) -> Option<&'static str> {
+    let bytes = address.as_bytes();
+    if bytes[..19].iter().any(|byte| *byte != 0) {
+        return None
+    }
+    let name = match bytes[19] {
+        0x01 => "ecrecover",
+        0x02 => "sha256",
+        0x03 => "ripemd160",
+        0x04 => "identity",
+        0x05 => "modexp",
+        0x06 => "ecadd",
+        0x07 => "ecmul",
+        0x08 => "ecpairing",
+        0x09 => "blake2f",
+        0x0a => "pointEvaluation",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Decodes a call to a precompile into the name of the precompile, its decoded inputs and, if the
+/// call was successful, its decoded output.
+///
+/// Returns `None` if there is no precompile at `address`.
+pub fn decode_precompile(
+    address: &Address,
+    input: &[u8],
+    output: &[u8],
+    success: bool,
+) -> Option<(String, Vec<String>, Option<String>)> {
+    let name = precompile_name(address)?;
+    let (inputs, decoded_output) = match name {
+        "ecrecover" => (
+            vec![
+                format!("hash: {}", hex_word(input, 0)),
+                format!("v: {}", word(input, 0x20)),
+                format!("r: {}", hex_word(input, 0x40)),
+                format!("s: {}", hex_word(input, 0x60)),
+            ],
+            // The output is empty if the signature is invalid
+            if output.is_empty() {
+                "()".to_string()
+            } else {
+                format!("{:?}", Address::from_slice(&padded(output, 12, 20)))
+            },
+        ),
+        "ripemd160" => (vec![hex(input)], format!("0x{}", hex::encode(padded(output, 12, 20)))),
+        "modexp" => {
+            let base_len = word(input, 0);
+            let exp_len = word(input, 0x20);
+            let mod_len = word(input, 0x40);
+            let mut inputs = vec![
+                format!("base_len: {}", base_len),
+                format!("exp_len: {}", exp_len),
+                format!("mod_len: {}", mod_len),
+            ];
+            // The lengths are arbitrary, so the values are only shown if they fit the input
+            let data = &input[input.len().min(0x60)..];
+            let total = base_len.saturating_add(exp_len).saturating_add(mod_len);
+            if total <= U256::from(data.len()) {
+                let (base, rest) = data.split_at(base_len.as_usize());
+                let (exp, rest) = rest.split_at(exp_len.as_usize());
+                let modulus = &rest[..mod_len.as_usize()];
+                inputs.push(format!("base: {}", hex(base)));
+                inputs.push(format!("exp: {}", hex(exp)));
+                inputs.push(format!("mod: {}", hex(modulus)));
+            }
+            (inputs, hex(output))
+        }
+        "ecadd" => (vec![point(input, 0), point(input, 0x40)], point(output, 0)),
+        "ecmul" => (vec![point(input, 0), format!("{}", word(input, 0x40))], point(output, 0)),
+        "ecpairing" => {
+            (vec![format!("{} pairs", input.len() / 192)], (!word(output, 0).is_zero()).to_string())
+        }
+        "blake2f" if input.len() == 213 => (
+            vec![
+                format!("rounds: {}", u32::from_be_bytes([input[0], input[1], input[2], input[3]])),
+                format!("h: {}", hex(&input[4..68])),
+                format!("m: {}", hex(&input[68..196])),
+                format!("t: {}", hex(&input[196..212])),
+                format!("f: {}", input[212] != 0),
+            ],
+            hex(output),
+        ),
+        "pointEvaluation" => (
+            vec![
+                format!("versioned_hash: {}", hex(&padded(input, 0, 32))),
+                format!("z: {}", hex(&padded(input, 0x20, 32))),
+                format!("y: {}", hex(&padded(input, 0x40, 32))),
+                format!("commitment: {}", hex(&padded(input, 0x60, 48))),
+                format!("proof: {}", hex(&padded(input, 0x90, 48))),
+            ],
+            format!("field_elements: {}, modulus: {}", word(output, 0), hex_word(output, 0x20)),
+        ),
+        // sha256, identity and malformed blake2f calls
+        _ => (vec![hex(input)], hex(output)),
+    };
+
+    Some((name.to_string(), inputs, success.then(|| decoded_output)))
+}
+
+/// Returns `len` bytes of `data` at `offset`, right padded with zeros like calldata
+fn padded(data: &[u8], offset: usize, len: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; len];
+    if offset < data.len() {
+        let available = &data[offset..data.len().min(offset + len)];
+        bytes[..available.len()].copy_from_slice(available);
+    }
+    bytes
+}
+
+/// Returns the 32 byte word at `offset`
+fn word(data: &[u8], offset: usize) -> U256 {
+    U256::from_big_endian(&padded(data, offset, 32))
+}
+
+fn hex_word(data: &[u8], offset: usize) -> String {
+    hex(&padded(data, offset, 32))
+}
+
+/// Formats the elliptic curve point at `offset`
+fn point(data: &[u8], offset: usize) -> String {
+    format!("({}, {})", hex_word(data, offset), hex_word(data, offset + 0x20))
+}
+
+fn hex(data: &[u8]) -> String {
+    format!("0x{}", hex::encode(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_name_precompiles() {
+        assert_eq!(precompile_name(&Address::from_low_u64_be(1)), Some("ecrecover"));
+        assert_eq!(precompile_name(&Address::from_low_u64_be(0x0a)), Some("pointEvaluation"));
+        assert_eq!(precompile_name(&Address::from_low_u64_be(0x0b)), None);
+        assert_eq!(precompile_name(&Address::repeat_byte(1)), None);
+    }
+
+    #[test]
+    fn can_decode_ecrecover() {
+        let mut input = vec![0u8; 128];
+        input[63] = 27;
+        let mut output = vec![0u8; 32];
+        output[12..].copy_from_slice(Address::repeat_byte(0x11).as_bytes());
+
+        let (name, inputs, output) =
+            decode_precompile(&Address::from_low_u64_be(1), &input, &output, true).unwrap();
+        assert_eq!(name, "ecrecover");
+        assert_eq!(inputs[1], "v: 27");
+        assert_eq!(output.unwrap(), format!("{:?}", Address::repeat_byte(0x11)));
+    }
+
+    #[test]
+    fn can_decode_ecpairing() {
+        let mut output = vec![0u8; 32];
+        output[31] = 1;
+        let (name, inputs, output) =
+            decode_precompile(&Address::from_low_u64_be(8), &[0u8; 384], &output, true).unwrap();
+        assert_eq!(name, "ecpairing");
+        assert_eq!(inputs, vec!["2 pairs".to_string()]);
+        assert_eq!(output.unwrap(), "true");
+    }
+
+    #[test]
+    fn can_decode_modexp() {
+        let mut input = vec![0u8; 0x60];
+        input[31] = 1;
+        input[63] = 1;
+        input[95] = 1;
+        input.extend([2, 3, 5]);
+        let (_, inputs, output) =
+            decode_precompile(&Address::from_low_u64_be(5), &input, &[3], false).unwrap();
+        assert_eq!(inputs[3..], ["base: 0x02", "exp: 0x03", "mod: 0x05"]);
+        assert!(output.is_none());
+    }
+}
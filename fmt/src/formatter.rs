@@ -2,6 +2,7 @@
 
 use std::fmt::Write;
 
+use foundry_config::fmt::FormatterConfig;
 use indent_write::fmt::IndentWriter;
 use solang_parser::pt::{
     ContractDefinition, DocComment, EnumDefinition, Identifier, Loc, SourceUnit, SourceUnitPart,
@@ -13,23 +14,6 @@ use crate::{
     visit::{VResult, Visitable, Visitor},
 };
 
-/// Contains the config and rule set
-#[derive(Debug, Clone)]
-pub struct FormatterConfig {
-    /// Maximum line length where formatter will try to wrap the line
-    pub line_length: usize,
-    /// Number of spaces per indentation level
-    pub tab_width: usize,
-    /// Print spaces between brackets
-    pub bracket_spacing: bool,
-}
-
-impl Default for FormatterConfig {
-    fn default() -> Self {
-        FormatterConfig { line_length: 80, tab_width: 4, bracket_spacing: false }
-    }
-}
-
 // TODO: use it inside Formatter since they're sharing same fields
 #[derive(Default)]
 struct FormatBuffer {
@@ -4,5 +4,6 @@ mod formatter;
 mod loc;
 mod visit;
 
-pub use formatter::{Formatter, FormatterConfig};
+pub use formatter::Formatter;
+pub use foundry_config::fmt::FormatterConfig;
 pub use visit::Visitable;
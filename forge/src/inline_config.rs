@@ -0,0 +1,347 @@
+//! Per-test fuzz settings in natspec comments
+//!
+//! A test function can override the global fuzz settings with `forge-config` lines in the comments
+//! right above it:
+//!
+//! ```solidity
+//! /// forge-config: fuzz.runs = 5000
+//! /// forge-config: fuzz.max_array_len = 16
+//! function testSlowInvariant(uint256[] memory xs) public { ... }
+//! ```
+//!
+//! The block of the fork can be pinned per test the same way, with
+//! `forge-config: fork.block_number = <number>`.
+//!
+//! The values of single int and uint params can be restricted to an inclusive range with
+//! `forge-fuzz` lines, which is cheaper than rejecting the values outside of it with `vm.assume`:
+//!
+//! ```solidity
+//! /// forge-fuzz: amount in [1, 1e18]
+//! function testDeposit(uint256 amount) public { ... }
+//! ```
+
+use ethers::abi::Function;
+use eyre::{bail, eyre, Result, WrapErr};
+use foundry_evm::fuzz::{FuzzParamOpts, FuzzRange};
+use proptest::test_runner::TestRunner;
+use std::{collections::BTreeMap, str::FromStr};
+
+/// The prefix of inline config lines in comments
+const INLINE_CONFIG_PREFIX: &str = "forge-config:";
+
+/// The prefix of param range lines in comments
+const PARAM_RANGE_PREFIX: &str = "forge-fuzz:";
+
+/// The fuzz settings of a single test, unset settings fall back to the global ones
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InlineFuzzConfig {
+    /// The number of runs
+    pub runs: Option<u32>,
+    /// The max number of local rejects
+    pub max_local_rejects: Option<u32>,
+    /// The max number of inputs `vm.assume` may reject
+    pub max_global_rejects: Option<u32>,
+    /// The max length of fuzzed strings in chars
+    pub max_string_len: Option<usize>,
+    /// The max length of fuzzed dynamic arrays
+    pub max_array_len: Option<usize>,
+    /// The max length of fuzzed `bytes`
+    pub max_bytes_len: Option<usize>,
+    /// The percentage of ints that are edge cases
+    pub edge_percent: Option<u32>,
+    /// The percentage of params that are drawn from the fuzz state
+    pub dictionary_weight: Option<u32>,
+    /// Whether inputs that take new branches are mutated preferentially
+    pub coverage_guided: Option<bool>,
+    /// The number of actors that call the test
+    pub actors: Option<usize>,
+    /// The block the fork is rolled to before the test runs
+    pub fork_block_number: Option<u64>,
+    /// The unparsed min and max of the ranges of params, keyed by param name. The bounds are
+    /// parsed with the types of the params by [`InlineFuzzConfig::param_ranges`].
+    pub param_ranges: BTreeMap<String, (String, String)>,
+}
+
+impl InlineFuzzConfig {
+    /// Sets the setting `key`, which is named like in `foundry.toml` without the `fuzz_` prefix
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        fn parse<T: FromStr>(key: &str, value: &str) -> Result<Option<T>> {
+            value
+                .parse()
+                .map(Some)
+                .map_err(|_| eyre!("invalid value `{}` for `fuzz.{}`", value, key))
+        }
+
+        match key {
+            "runs" => self.runs = parse(key, value)?,
+            "max_local_rejects" => self.max_local_rejects = parse(key, value)?,
+            "max_global_rejects" => self.max_global_rejects = parse(key, value)?,
+            "max_string_len" => self.max_string_len = parse(key, value)?,
+            "max_array_len" => self.max_array_len = parse(key, value)?,
+            "max_bytes_len" => self.max_bytes_len = parse(key, value)?,
+            "edge_percent" => self.edge_percent = parse(key, value)?,
+            "dictionary_weight" => self.dictionary_weight = parse(key, value)?,
+            "coverage_guided" => self.coverage_guided = parse(key, value)?,
+            "actors" => self.actors = parse(key, value)?,
+            _ => bail!("unknown setting `fuzz.{}`", key),
+        }
+        Ok(())
+    }
+
+    /// Applies the settings to the global fuzzer and param settings.
+    ///
+    /// The returned fuzzer gets a new rng derived from the one of `runner`, so runs stay
+    /// reproducible with the same seed.
+    pub fn apply(
+        &self,
+        mut runner: TestRunner,
+        opts: FuzzParamOpts,
+    ) -> (TestRunner, FuzzParamOpts) {
+        let mut config = runner.config().clone();
+        config.cases = self.runs.unwrap_or(config.cases);
+        config.max_local_rejects = self.max_local_rejects.unwrap_or(config.max_local_rejects);
+        config.max_global_rejects = self.max_global_rejects.unwrap_or(config.max_global_rejects);
+        let rng = runner.new_rng();
+
+        let opts = FuzzParamOpts {
+            max_string_len: self.max_string_len.unwrap_or(opts.max_string_len),
+            max_array_len: self.max_array_len.unwrap_or(opts.max_array_len),
+            max_bytes_len: self.max_bytes_len.unwrap_or(opts.max_bytes_len),
+            edge_percent: self.edge_percent.or(opts.edge_percent),
+            dictionary_weight: self.dictionary_weight.unwrap_or(opts.dictionary_weight),
+            coverage_guided: self.coverage_guided.unwrap_or(opts.coverage_guided),
+            actors: self.actors.unwrap_or(opts.actors),
+            ..opts
+        };
+        (TestRunner::new_with_rng(config, rng), opts)
+    }
+
+    /// Returns the ranges of the params of `func`, keyed by param name
+    pub fn param_ranges(&self, func: &Function) -> Result<BTreeMap<String, FuzzRange>> {
+        self.param_ranges
+            .iter()
+            .map(|(name, (min, max))| {
+                let param = func
+                    .inputs
+                    .iter()
+                    .find(|param| &param.name == name)
+                    .ok_or_else(|| eyre!("`{}` has no param named `{}`", func.name, name))?;
+                let range = FuzzRange::parse(&param.kind, min, max)
+                    .wrap_err_with(|| format!("invalid range of `{}`", name))?;
+                Ok((name.clone(), range))
+            })
+            .collect()
+    }
+}
+
+/// Parses the inline configs of the functions in a solidity source, keyed by contract name and
+/// function name.
+///
+/// Only functions with at least one `forge-config` line have an entry.
+pub fn parse_inline_config(
+    source: &str,
+) -> Result<BTreeMap<String, BTreeMap<String, InlineFuzzConfig>>> {
+    let mut configs: BTreeMap<String, BTreeMap<String, InlineFuzzConfig>> = BTreeMap::new();
+    let mut contract = String::new();
+    let mut pending: Option<InlineFuzzConfig> = None;
+
+    for (idx, line) in source.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue
+        }
+
+        if let Some(comment) = strip_comment(line) {
+            if let Some(setting) = comment.strip_prefix(INLINE_CONFIG_PREFIX) {
+                parse_setting(setting, pending.get_or_insert_with(Default::default))
+                    .wrap_err_with(|| format!("line {}", idx + 1))?;
+            } else if let Some(range) = comment.strip_prefix(PARAM_RANGE_PREFIX) {
+                parse_range(range, pending.get_or_insert_with(Default::default))
+                    .wrap_err_with(|| format!("line {}", idx + 1))?;
+            }
+            continue
+        }
+
+        if let Some(name) = declared_name(line, "contract") {
+            contract = name.to_string();
+        } else if let Some(name) = declared_name(line, "function") {
+            if let Some(config) = pending.take() {
+                configs.entry(contract.clone()).or_default().insert(name.to_string(), config);
+            }
+        }
+        // settings only apply to the function declared right below them
+        pending = None;
+    }
+
+    Ok(configs)
+}
+
+/// Parses a `fuzz.<key> = <value>` or `fork.block_number = <value>` setting into `config`
+fn parse_setting(setting: &str, config: &mut InlineFuzzConfig) -> Result<()> {
+    let (key, value) = setting
+        .split_once('=')
+        .ok_or_else(|| eyre!("expected `<setting> = <value>`, found `{}`", setting.trim()))?;
+    let (key, value) = (key.trim().replace('-', "_"), value.trim());
+    if let Some(key) = key.strip_prefix("fuzz.") {
+        return config.set(key, value)
+    }
+    match key.as_str() {
+        "fork.block_number" => {
+            config.fork_block_number =
+                Some(value.parse().map_err(|_| eyre!("invalid value `{}` for `{}`", value, key))?);
+            Ok(())
+        }
+        _ => bail!(
+            "unknown setting `{}`, only `fuzz.*` and `fork.block_number` settings are supported",
+            key
+        ),
+    }
+}
+
+/// Parses a `<param> in [<min>, <max>]` range into `config`
+fn parse_range(range: &str, config: &mut InlineFuzzConfig) -> Result<()> {
+    let invalid = || eyre!("expected `<param> in [<min>, <max>]`, found `{}`", range.trim());
+    let (name, bounds) = range.split_once(" in ").ok_or_else(invalid)?;
+    let (min, max) = bounds
+        .trim()
+        .strip_prefix('[')
+        .and_then(|bounds| bounds.strip_suffix(']'))
+        .and_then(|bounds| bounds.split_once(','))
+        .ok_or_else(invalid)?;
+    config
+        .param_ranges
+        .insert(name.trim().to_string(), (min.trim().to_string(), max.trim().to_string()));
+    Ok(())
+}
+
+/// Returns the content of a comment line, if the line is a comment
+fn strip_comment(line: &str) -> Option<&str> {
+    ["///", "//", "/**", "/*", "*"]
+        .iter()
+        .find_map(|prefix| line.strip_prefix(prefix))
+        .map(|comment| comment.trim_end_matches("*/").trim())
+}
+
+/// Returns the name of the contract or function declared in the line, if any
+fn declared_name<'a>(line: &'a str, keyword: &str) -> Option<&'a str> {
+    let line = line.strip_prefix("abstract ").unwrap_or(line);
+    let rest = line.strip_prefix(keyword)?.strip_prefix(' ')?.trim_start();
+    let end =
+        rest.find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$')).unwrap_or(rest.len());
+    (end > 0).then(|| &rest[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_inline_config() {
+        let source = r#"
+contract FuzzTest is DSTest {
+    /// forge-config: fuzz.runs = 5000
+    /// forge-config: fuzz.max-array-len = 16
+    /// forge-fuzz: x in [1, 1e18]
+    function testSlow(uint256[] memory xs, uint256 x) public {}
+
+    function testDefault(uint256 x) public {}
+}
+
+abstract contract OtherTest {
+    /**
+     * forge-config: fuzz.max_global_rejects = 10
+     */
+    function testAssume(uint8 x) public {}
+
+    /// forge-config: fuzz.runs = 1
+    uint256 notAFunction;
+    function testUnannotated(uint8 x) public {}
+
+    /// forge-config: fork.block-number = 15000000
+    function testPinned() public {}
+}
+"#;
+        let configs = parse_inline_config(source).unwrap();
+        assert_eq!(
+            configs,
+            BTreeMap::from([
+                (
+                    "FuzzTest".to_string(),
+                    BTreeMap::from([(
+                        "testSlow".to_string(),
+                        InlineFuzzConfig {
+                            runs: Some(5000),
+                            max_array_len: Some(16),
+                            param_ranges: BTreeMap::from([(
+                                "x".to_string(),
+                                ("1".to_string(), "1e18".to_string())
+                            )]),
+                            ..Default::default()
+                        }
+                    )])
+                ),
+                (
+                    "OtherTest".to_string(),
+                    BTreeMap::from([
+                        (
+                            "testAssume".to_string(),
+                            InlineFuzzConfig { max_global_rejects: Some(10), ..Default::default() }
+                        ),
+                        (
+                            "testPinned".to_string(),
+                            InlineFuzzConfig {
+                                fork_block_number: Some(15_000_000),
+                                ..Default::default()
+                            }
+                        ),
+                    ])
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_inline_config() {
+        let err = |source: &str| format!("{:#}", parse_inline_config(source).unwrap_err());
+        assert!(
+            err("/// forge-config: fuzz.unknown = 1").contains("unknown setting `fuzz.unknown`")
+        );
+        assert!(err("/// forge-config: fuzz.runs = many").contains("invalid value `many`"));
+        assert!(err("/// forge-config: fork.block_number = latest")
+            .contains("invalid value `latest` for `fork.block_number`"));
+        assert!(err("/// forge-config: fork.url = x").contains("unknown setting `fork.url`"));
+        assert!(err("\n/// forge-config: fuzz.runs").contains("line 2"));
+        assert!(err("/// forge-fuzz: x in (1, 2)").contains("expected `<param> in [<min>, <max>]`"));
+    }
+
+    #[test]
+    fn can_resolve_param_ranges() {
+        let func = foundry_utils::IntoFunction::into("function testDeposit(uint8 amount)");
+        let range = |min: &str, max: &str| {
+            InlineFuzzConfig {
+                param_ranges: BTreeMap::from([(
+                    "amount".to_string(),
+                    (min.to_string(), max.to_string()),
+                )]),
+                ..Default::default()
+            }
+            .param_ranges(&func)
+        };
+
+        assert_eq!(
+            range("1", "0x10").unwrap(),
+            BTreeMap::from([("amount".to_string(), FuzzRange::Uint(1.into(), 16.into()))])
+        );
+        assert!(range("1", "1e3").is_err());
+        let err = InlineFuzzConfig {
+            param_ranges: BTreeMap::from([(
+                "other".to_string(),
+                ("1".to_string(), "2".to_string()),
+            )]),
+            ..Default::default()
+        }
+        .param_ranges(&func)
+        .unwrap_err();
+        assert!(err.to_string().contains("no param named `other`"));
+    }
+}
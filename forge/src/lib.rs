@@ -1,14 +1,18 @@
 /// Gas reports
 pub mod gas_report;
 
+/// Per-test fuzz settings in natspec comments
+pub mod inline_config;
+
 /// The Forge test runner
 mod runner;
-pub use runner::{ContractRunner, TestKind, TestKindGas, TestResult};
+pub use runner::{ContractRunner, TestKind, TestKindGas, TestResult, TestSetup};
 
 /// Forge test runners for multiple contracts
 mod multi_runner;
-pub use multi_runner::{MultiContractRunner, MultiContractRunnerBuilder};
+pub use multi_runner::{DeployableContracts, MultiContractRunner, MultiContractRunnerBuilder};
 
+/// Selects the tests to run by their name, the name of their contract and their source file
 pub trait TestFilter {
     fn matches_test(&self, test_name: impl AsRef<str>) -> bool;
     fn matches_contract(&self, contract_name: impl AsRef<str>) -> bool;
@@ -1,16 +1,26 @@
-use crate::{ContractRunner, TestFilter, TestResult};
+use crate::{
+    inline_config::{parse_inline_config, InlineFuzzConfig},
+    ContractRunner, TestFilter, TestResult,
+};
 use ethers::{
     abi::Abi,
     prelude::{artifacts::CompactContractBytecode, ArtifactId, ArtifactOutput},
     solc::{Artifact, ProjectCompileOutput},
     types::{Address, Bytes, U256},
 };
-use eyre::Result;
-use foundry_evm::executor::{opts::EvmOpts, DatabaseRef, Executor, ExecutorBuilder, Fork, SpecId};
+use eyre::{Result, WrapErr};
+use foundry_config::{FsPermissions, RpcEndpoints};
+use foundry_evm::{
+    executor::{
+        builder::Backend, inspector::Precompiles, opts::EvmOpts, DatabaseRef, Executor,
+        ExecutorBuilder, Fork, SpecId,
+    },
+    fuzz::FuzzParamOpts,
+};
 use foundry_utils::PostLinkInput;
 use proptest::test_runner::TestRunner;
 use rayon::prelude::*;
-use std::{collections::BTreeMap, marker::Sync, sync::mpsc::Sender};
+use std::{collections::BTreeMap, marker::Sync, path::PathBuf, sync::mpsc::Sender, time::Duration};
 
 /// Builder used for instantiating the multi-contract runner
 #[derive(Debug, Default)]
@@ -26,6 +36,24 @@ pub struct MultiContractRunnerBuilder {
     pub evm_spec: Option<SpecId>,
     /// The fork config
     pub fork: Option<Fork>,
+    /// The custom precompiles
+    pub precompiles: Precompiles,
+    /// The paths the file cheatcodes are allowed to access
+    pub fs_permissions: FsPermissions,
+    /// The RPC endpoints whose aliases can be passed to `createFork`
+    pub rpc_endpoints: RpcEndpoints,
+    /// Whether to record the net state changes of each test
+    pub state_diff: bool,
+    /// How the params of fuzz tests are generated
+    pub fuzz_param_opts: FuzzParamOpts,
+    /// The directory of the counterexamples of fuzz tests, which are replayed on later runs
+    pub fuzz_corpus: Option<PathBuf>,
+    /// The wall clock time each fuzz test is fuzzed for instead of the number of runs, if any
+    pub fuzz_max_time: Option<Duration>,
+    /// The number of threads the runs of each fuzz test are split across
+    pub fuzz_workers: usize,
+    /// The calldata of a counterexample the fuzz tests are called with instead of being fuzzed
+    pub fuzz_replay: Option<Bytes>,
 }
 
 pub type DeployableContracts = BTreeMap<String, (Abi, Bytes, Vec<Bytes>)>;
@@ -119,6 +147,15 @@ impl MultiContractRunnerBuilder {
             errors: Some(execution_info.2),
             source_paths,
             fork: self.fork,
+            precompiles: self.precompiles,
+            fs_permissions: self.fs_permissions,
+            rpc_endpoints: self.rpc_endpoints,
+            state_diff: self.state_diff,
+            fuzz_param_opts: self.fuzz_param_opts,
+            fuzz_corpus: self.fuzz_corpus,
+            fuzz_max_time: self.fuzz_max_time,
+            fuzz_workers: self.fuzz_workers,
+            fuzz_replay: self.fuzz_replay,
         })
     }
 
@@ -151,6 +188,60 @@ impl MultiContractRunnerBuilder {
         self.fork = fork;
         self
     }
+
+    #[must_use]
+    pub fn with_precompiles(mut self, precompiles: Precompiles) -> Self {
+        self.precompiles = precompiles;
+        self
+    }
+
+    #[must_use]
+    pub fn with_fs_permissions(mut self, fs_permissions: FsPermissions) -> Self {
+        self.fs_permissions = fs_permissions;
+        self
+    }
+
+    #[must_use]
+    pub fn with_rpc_endpoints(mut self, rpc_endpoints: RpcEndpoints) -> Self {
+        self.rpc_endpoints = rpc_endpoints;
+        self
+    }
+
+    #[must_use]
+    pub fn with_state_diff(mut self, state_diff: bool) -> Self {
+        self.state_diff = state_diff;
+        self
+    }
+
+    #[must_use]
+    pub fn with_fuzz_param_opts(mut self, fuzz_param_opts: FuzzParamOpts) -> Self {
+        self.fuzz_param_opts = fuzz_param_opts;
+        self
+    }
+
+    #[must_use]
+    pub fn with_fuzz_corpus(mut self, fuzz_corpus: Option<PathBuf>) -> Self {
+        self.fuzz_corpus = fuzz_corpus;
+        self
+    }
+
+    #[must_use]
+    pub fn with_fuzz_replay(mut self, fuzz_replay: Option<Bytes>) -> Self {
+        self.fuzz_replay = fuzz_replay;
+        self
+    }
+
+    #[must_use]
+    pub fn with_fuzz_max_time(mut self, fuzz_max_time: Option<Duration>) -> Self {
+        self.fuzz_max_time = fuzz_max_time;
+        self
+    }
+
+    #[must_use]
+    pub fn with_fuzz_workers(mut self, fuzz_workers: usize) -> Self {
+        self.fuzz_workers = fuzz_workers;
+        self
+    }
 }
 
 /// A multi contract runner receives a set of contracts deployed in an EVM instance and proceeds
@@ -175,6 +266,24 @@ pub struct MultiContractRunner {
     pub source_paths: BTreeMap<String, String>,
     /// The fork config
     pub fork: Option<Fork>,
+    /// The custom precompiles
+    pub precompiles: Precompiles,
+    /// The paths the file cheatcodes are allowed to access
+    pub fs_permissions: FsPermissions,
+    /// The RPC endpoints whose aliases can be passed to `createFork`
+    pub rpc_endpoints: RpcEndpoints,
+    /// Whether to record the net state changes of each test
+    pub state_diff: bool,
+    /// How the params of fuzz tests are generated
+    pub fuzz_param_opts: FuzzParamOpts,
+    /// The directory of the counterexamples of fuzz tests, which are replayed on later runs
+    pub fuzz_corpus: Option<PathBuf>,
+    /// The wall clock time each fuzz test is fuzzed for instead of the number of runs, if any
+    pub fuzz_max_time: Option<Duration>,
+    /// The number of threads the runs of each fuzz test are split across
+    pub fuzz_workers: usize,
+    /// The calldata of a counterexample the fuzz tests are called with instead of being fuzzed
+    pub fuzz_replay: Option<Bytes>,
 }
 
 impl MultiContractRunner {
@@ -191,12 +300,47 @@ impl MultiContractRunner {
             .count()
     }
 
+    /// Parses the fuzz settings in the natspec of the tests of the contracts that match the
+    /// filter, keyed by contract and function name. Invalid settings are an error, sources that
+    /// can't be read have no settings.
+    fn inline_fuzz_config(
+        &self,
+        filter: &impl TestFilter,
+    ) -> Result<BTreeMap<String, BTreeMap<String, InlineFuzzConfig>>> {
+        let mut sources: BTreeMap<&str, BTreeMap<String, BTreeMap<String, InlineFuzzConfig>>> =
+            BTreeMap::new();
+        let mut configs = BTreeMap::new();
+        for name in self.contracts.keys().filter(|name| {
+            filter.matches_path(&self.source_paths.get(*name).unwrap()) &&
+                filter.matches_contract(name)
+        }) {
+            let path = self.source_paths[name].as_str();
+            if !sources.contains_key(path) {
+                let parsed = match std::fs::read_to_string(path) {
+                    Ok(source) => parse_inline_config(&source)
+                        .wrap_err_with(|| format!("invalid inline config in {}", path))?,
+                    Err(_) => Default::default(),
+                };
+                sources.insert(path, parsed);
+            }
+            let contract = name.rsplit(':').next().unwrap_or(name);
+            if let Some(config) = sources[path].get(contract) {
+                configs.insert(name.clone(), config.clone());
+            }
+        }
+        Ok(configs)
+    }
+
     pub fn test(
         &mut self,
         filter: &(impl TestFilter + Send + Sync),
         stream_result: Option<Sender<(String, BTreeMap<String, TestResult>)>>,
     ) -> Result<BTreeMap<String, BTreeMap<String, TestResult>>> {
-        let env = self.evm_opts.evm_env();
+        let mut env = self.evm_opts.evm_env();
+        env.cfg.spec_id = self.evm_spec;
+        // all test contracts share the same backend, so forked state is only fetched once
+        let db = Backend::new(self.fork.clone(), &env);
+        let inline_fuzz_config = self.inline_fuzz_config(filter)?;
         let results = self
             .contracts
             .par_iter()
@@ -210,15 +354,27 @@ impl MultiContractRunner {
                     .with_cheatcodes(self.evm_opts.ffi)
                     .with_config(env.clone())
                     .with_spec(self.evm_spec)
-                    .with_fork(self.fork.clone());
-
-                if self.evm_opts.verbosity >= 3 {
+                    .with_memory_limit(self.evm_opts.memory_limit)
+                    .with_precompiles(self.precompiles.clone())
+                    .with_fs_permissions(self.fs_permissions.clone())
+                    .with_rpc_endpoints(self.rpc_endpoints.clone());
+
+                if self.evm_opts.verbosity >= 5 {
+                    builder = builder.with_storage_tracing();
+                } else if self.evm_opts.verbosity >= 3 {
                     builder = builder.with_tracing();
                 }
 
-                let executor = builder.build();
-                let result =
-                    self.run_tests(name, abi, executor, deploy_code.clone(), libs, filter)?;
+                let executor = builder.build_with_backend(db.clone());
+                let result = self.run_tests(
+                    name,
+                    abi,
+                    executor,
+                    deploy_code.clone(),
+                    libs,
+                    inline_fuzz_config.get(name).cloned().unwrap_or_default(),
+                    filter,
+                )?;
                 Ok((name.clone(), result))
             })
             .filter_map(Result::<_>::ok)
@@ -234,20 +390,21 @@ impl MultiContractRunner {
         Ok(results)
     }
 
-    // The _name field is unused because we only want it for tracing
+    #[allow(clippy::too_many_arguments)]
     #[tracing::instrument(
         name = "contract",
         skip_all,
         err,
-        fields(name = %_name)
+        fields(name = %name)
     )]
     fn run_tests<DB: DatabaseRef + Send + Sync>(
         &self,
-        _name: &str,
+        name: &str,
         contract: &Abi,
         executor: Executor<DB>,
         deploy_code: Bytes,
         libs: &[Bytes],
+        inline_fuzz_config: BTreeMap<String, InlineFuzzConfig>,
         filter: &impl TestFilter,
     ) -> Result<BTreeMap<String, TestResult>> {
         let mut runner = ContractRunner::new(
@@ -258,6 +415,18 @@ impl MultiContractRunner {
             self.sender,
             self.errors.as_ref(),
             libs,
+            self.state_diff,
+            self.fuzz_param_opts,
+            // the counterexamples of `src/Contract.t.sol:ContractTest` are stored in
+            // `<corpus>/src/Contract.t.sol/ContractTest/`
+            self.fuzz_corpus
+                .as_ref()
+                .map(|dir| dir.join(name.trim_start_matches('/').replace(':', "/"))),
+            self.fuzz_max_time,
+            self.fuzz_workers,
+            self.fuzz_replay.clone(),
+            inline_fuzz_config,
+            &self.known_contracts,
         );
         runner.run_tests(filter, self.fuzzer.clone())
     }
@@ -268,13 +437,27 @@ mod tests {
     use super::*;
     use crate::{
         decode::decode_console_logs,
-        test_helpers::{filter::Filter, COMPILED, EVM_OPTS},
+        test_helpers::{filter::Filter, test_executor, COMPILED, EVM_OPTS},
+        TestKindGas,
+    };
+    use ethers::abi::Token;
+    use foundry_config::PathPermission;
+    use foundry_evm::{
+        fuzz::{actor_addresses, FuzzConfig},
+        trace::TraceKind,
     };
-    use foundry_evm::trace::TraceKind;
+    use proptest::test_runner::{RngAlgorithm, TestRng};
+
+    /// The endpoint the fork tests run against, the same as in the tests of the fork backend
+    const RPC_ENDPOINT: &str = "https://mainnet.infura.io/v3/c60b0bb42f8a4c6481ecd229eddaca27";
 
     /// Builds a base runner
     fn base_runner() -> MultiContractRunnerBuilder {
-        MultiContractRunnerBuilder::default().sender(EVM_OPTS.sender)
+        MultiContractRunnerBuilder::default().sender(EVM_OPTS.sender).with_fs_permissions(
+            FsPermissions::new([PathPermission::read_write(
+                std::fs::canonicalize("../testdata/fixtures/File").unwrap(),
+            )]),
+        )
     }
 
     /// Builds a non-tracing runner
@@ -282,6 +465,15 @@ mod tests {
         base_runner().build((*COMPILED).clone(), EVM_OPTS.clone()).unwrap()
     }
 
+    /// Builds a runner with a deterministic fuzzer
+    fn fuzz_runner(config: FuzzConfig) -> MultiContractRunner {
+        let fuzzer = TestRunner::new_with_rng(
+            FuzzConfig { failure_persistence: None, ..config },
+            TestRng::deterministic_rng(RngAlgorithm::ChaCha),
+        );
+        base_runner().fuzzer(fuzzer).build((*COMPILED).clone(), EVM_OPTS.clone()).unwrap()
+    }
+
     /// Builds a tracing runner
     fn tracing_runner() -> MultiContractRunner {
         let mut opts = EVM_OPTS.clone();
@@ -452,6 +644,21 @@ mod tests {
 
     #[test]
     fn test_cheats() {
+        // read by `testdata/cheats/Env.t.sol`
+        std::env::set_var("_FOUNDRY_TEST_ENV_BOOL", "true");
+        std::env::set_var("_FOUNDRY_TEST_ENV_UINT", "1000");
+        std::env::set_var("_FOUNDRY_TEST_ENV_UINT_HEX", "0x3e8");
+        std::env::set_var("_FOUNDRY_TEST_ENV_INT", "-1000");
+        std::env::set_var(
+            "_FOUNDRY_TEST_ENV_ADDRESS",
+            "0x7109709ECfa91a80626fF3989D68f67F5b1DD12D",
+        );
+        std::env::set_var(
+            "_FOUNDRY_TEST_ENV_BYTES32",
+            "0x00000000000000000000000000000000000000000000000000000000000003e8",
+        );
+        std::env::set_var("_FOUNDRY_TEST_ENV_STRING", "gm gm");
+
         let mut runner = runner();
         let results = runner.test(&Filter::new(".*", ".*", ".*cheats"), None).unwrap();
 
@@ -466,6 +673,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_fork() {
+        let mut runner = base_runner()
+            .with_rpc_endpoints(RpcEndpoints::new([("mainnet", RPC_ENDPOINT)]))
+            .build((*COMPILED).clone(), EVM_OPTS.clone())
+            .unwrap();
+        let results = runner.test(&Filter::new(".*", ".*", ".*fork/"), None).unwrap();
+
+        for (_, tests) in results {
+            for (test_name, result) in tests {
+                assert!(
+                    result.success,
+                    "Test {} did not pass as expected.\nReason: {:?}",
+                    test_name, result.reason
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_fuzz() {
         let mut runner = runner();
@@ -489,6 +715,237 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_fuzz_shrinks_counterexamples() {
+        let mut runner = fuzz_runner(Default::default());
+        let results = runner.test(&Filter::new(".*", ".*", ".*property/FuzzShrink"), None).unwrap();
+        let tests = &results["FuzzShrinkTest.json:FuzzShrinkTest"];
+
+        let args = |test: &str| tests[test].counterexample.as_ref().unwrap().args.clone();
+        assert_eq!(args("testShrinkUint(uint256)"), vec![Token::Uint(1000.into())]);
+        assert_eq!(
+            args("testShrinkArray(uint256[])"),
+            vec![Token::Array(vec![Token::Uint(0.into()); 3])]
+        );
+    }
+
+    #[test]
+    fn test_fuzz_reject_budget() {
+        let mut runner = fuzz_runner(FuzzConfig { max_global_rejects: 1000, ..Default::default() });
+        let results = runner.test(&Filter::new(".*", ".*", ".*property/FuzzAssume"), None).unwrap();
+        let tests = &results["FuzzAssumeTest.json:FuzzAssumeTest"];
+
+        let result = &tests["testAssumeSome(uint8)"];
+        assert!(result.success);
+        assert!(result.rejects > 0);
+        let result = &tests["testAssumeNone(uint8)"];
+        assert!(!result.success);
+        assert!(result.rejects >= 1000, "{} rejects", result.rejects);
+        assert!(
+            result.reason.as_ref().unwrap().contains("`vm.assume` rejected too many inputs"),
+            "{:?}",
+            result.reason
+        );
+    }
+
+    #[test]
+    fn test_inline_fuzz_config() {
+        let mut runner = fuzz_runner(Default::default());
+        let results =
+            runner.test(&Filter::new(".*", ".*", ".*property/FuzzInlineConfig"), None).unwrap();
+        let tests = &results["FuzzInlineConfigTest.json:FuzzInlineConfigTest"];
+
+        let runs = |test: &str| match tests[test].kind.gas_used() {
+            TestKindGas::Fuzz { runs, .. } => runs,
+            _ => panic!("{} is not a fuzz test", test),
+        };
+        assert_eq!(runs("testInlineRuns(uint256)"), 10);
+        assert_eq!(runs("testDefaultRuns(uint256)"), 256);
+        assert!(tests["testRange(uint256,int8)"].success);
+    }
+
+    #[test]
+    fn test_fuzz_max_time() {
+        let fuzzer = TestRunner::new_with_rng(
+            FuzzConfig { cases: 1, failure_persistence: None, ..Default::default() },
+            TestRng::deterministic_rng(RngAlgorithm::ChaCha),
+        );
+        let mut runner = base_runner()
+            .fuzzer(fuzzer)
+            .with_fuzz_max_time(Some(Duration::from_millis(500)))
+            .build((*COMPILED).clone(), EVM_OPTS.clone())
+            .unwrap();
+
+        let start = std::time::Instant::now();
+        let results = runner
+            .test(&Filter::new("testDefaultRuns", ".*", ".*property/FuzzInlineConfig"), None)
+            .unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(500));
+
+        let result =
+            &results["FuzzInlineConfigTest.json:FuzzInlineConfigTest"]["testDefaultRuns(uint256)"];
+        assert!(result.success);
+        // the number of runs of the fuzzer is ignored
+        assert!(matches!(result.kind.gas_used(), TestKindGas::Fuzz { runs, .. } if runs > 1));
+    }
+
+    #[test]
+    fn test_fuzz_workers() {
+        let fuzzer = TestRunner::new_with_rng(
+            FuzzConfig { failure_persistence: None, ..Default::default() },
+            TestRng::deterministic_rng(RngAlgorithm::ChaCha),
+        );
+        let mut runner = base_runner()
+            .fuzzer(fuzzer)
+            .with_fuzz_workers(4)
+            .build((*COMPILED).clone(), EVM_OPTS.clone())
+            .unwrap();
+
+        // the runs of all workers are reported
+        let results =
+            runner.test(&Filter::new(".*", ".*", ".*property/FuzzInlineConfig"), None).unwrap();
+        let tests = &results["FuzzInlineConfigTest.json:FuzzInlineConfigTest"];
+        let runs = |test: &str| match tests[test].kind.gas_used() {
+            TestKindGas::Fuzz { runs, .. } => runs,
+            _ => panic!("{} is not a fuzz test", test),
+        };
+        assert_eq!(runs("testInlineRuns(uint256)"), 10);
+        assert_eq!(runs("testDefaultRuns(uint256)"), 256);
+
+        // the shrunk counterexample of a failing worker is reported
+        let results = runner.test(&Filter::new(".*", ".*", ".*property/FuzzShrink"), None).unwrap();
+        let result = &results["FuzzShrinkTest.json:FuzzShrinkTest"]["testShrinkUint(uint256)"];
+        assert!(!result.success);
+        assert_eq!(result.counterexample.as_ref().unwrap().args, vec![Token::Uint(1000.into())]);
+    }
+
+    #[test]
+    fn test_fuzz_calldata_of_deployed_contracts() {
+        let mut runner = fuzz_runner(Default::default());
+        let results =
+            runner.test(&Filter::new(".*", ".*", ".*property/FuzzCalldata"), None).unwrap();
+        let result =
+            &results["FuzzCalldataTest.json:FuzzCalldataTest"]["testForwardCalldata(bytes)"];
+
+        assert!(!result.success);
+        let args = &result.counterexample.as_ref().unwrap().args;
+        // the selector of `set(uint256)`
+        assert!(
+            matches!(&args[0], Token::Bytes(data) if data.starts_with(&[0x60, 0xfe, 0x47, 0xb1])),
+            "{:?}",
+            args
+        );
+    }
+
+    #[test]
+    fn test_coverage_guided_fuzzing() {
+        let mut runner = fuzz_runner(Default::default());
+        let results =
+            runner.test(&Filter::new(".*", ".*", ".*property/FuzzCoverage"), None).unwrap();
+        let result = &results["FuzzCoverageTest.json:FuzzCoverageTest"]
+            ["testNestedConditions(uint256,uint256,uint256)"];
+
+        assert!(!result.success);
+        let args = &result.counterexample.as_ref().unwrap().args;
+        let residues = args
+            .iter()
+            .map(|arg| arg.clone().into_uint().unwrap().low_u64() % 16)
+            .collect::<Vec<_>>();
+        assert_eq!(residues, [5, 9, 13]);
+    }
+
+    #[test]
+    fn test_fuzz_actors() {
+        let mut runner = fuzz_runner(Default::default());
+        let results = runner.test(&Filter::new(".*", ".*", ".*property/FuzzActors"), None).unwrap();
+        let tests = &results["FuzzActorsTest.json:FuzzActorsTest"];
+
+        assert!(tests["testActorsAreFunded(uint256)"].success);
+        let result = &tests["testSecondActorFails(uint256)"];
+        assert!(!result.success);
+        assert_eq!(result.counterexample.as_ref().unwrap().sender, Some(actor_addresses(3)[1]));
+    }
+
+    #[test]
+    fn test_broadcastable_transactions() {
+        let runner = runner();
+        let (abi, code, _) = &runner.contracts["BroadcastTest.json:BroadcastTest"];
+        let mut executor = test_executor();
+        let address = executor.deploy(EVM_OPTS.sender, code.0.clone(), 0.into()).unwrap().address;
+        executor.setup(address).unwrap();
+
+        let calldata =
+            abi.function("testBroadcastSkipsStaticCalls").unwrap().encode_input(&[]).unwrap();
+        let call = executor.call_raw(EVM_OPTS.sender, address, calldata.into(), 0.into()).unwrap();
+        assert!(!call.reverted);
+
+        // only `touch` is a transaction, the static call and the console log are not
+        let transactions = &call.broadcastable_transactions;
+        assert_eq!(transactions.len(), 1, "{:?}", transactions);
+        assert_eq!(transactions[0].from, Some(EVM_OPTS.sender));
+        assert_eq!(
+            transactions[0].data.as_ref().unwrap().as_ref(),
+            &ethers::utils::id("touch()")[..]
+        );
+    }
+
+    #[test]
+    fn test_excludes_harness_gas() {
+        let mut runner = runner();
+        let results = runner.test(&Filter::new(".*", ".*", ".*gas"), None).unwrap();
+        let tests = &results["HarnessGasTest.json:HarnessGasTest"];
+
+        let (abi, code, _) = &runner.contracts["HarnessGasTest.json:HarnessGasTest"];
+        let mut executor = test_executor();
+        let address = executor.deploy(EVM_OPTS.sender, code.0.clone(), 0.into()).unwrap().address;
+        executor.setup(address).unwrap();
+        let mut call = |name: &str| {
+            let calldata = abi.function(name).unwrap().encode_input(&[]).unwrap();
+            executor.call_raw(EVM_OPTS.sender, address, calldata.into(), 0.into()).unwrap()
+        };
+
+        // nothing is deducted from a test that doesn't call the harness
+        let noop_call = call("testNoop");
+        assert_eq!(noop_call.harness_gas, 0);
+        let noop = tests["testNoop()"].kind.gas_used().gas();
+        assert_eq!(noop, noop_call.gas - noop_call.stipend);
+
+        // the (one cold and three warm) calls to the cheatcode address are deducted exactly
+        let cheatcodes_call = call("testCheatcodesOnly");
+        assert!(cheatcodes_call.harness_gas >= 2600 + 3 * 100);
+        let cheatcodes = tests["testCheatcodesOnly()"].kind.gas_used().gas();
+        assert_eq!(
+            cheatcodes,
+            cheatcodes_call.gas - cheatcodes_call.stipend - cheatcodes_call.harness_gas
+        );
+        // only the encoding of the cheatcode calls is left
+        assert!(
+            cheatcodes < noop + 4 * 100,
+            "cheatcode calls were not excluded: {} vs {}",
+            cheatcodes,
+            noop
+        );
+    }
+
+    #[test]
+    fn test_memory_limit() {
+        let mut opts = EVM_OPTS.clone();
+        opts.memory_limit = 1 << 25;
+        let mut runner = base_runner().build((*COMPILED).clone(), opts).unwrap();
+        let results = runner.test(&Filter::new(".*", ".*", ".*memory"), None).unwrap();
+
+        assert_multiple(
+            &results,
+            BTreeMap::from([(
+                "MemoryLimitTest.json:MemoryLimitTest",
+                vec![
+                    ("testSmallAllocation()", true, None, None),
+                    ("testFailHugeAllocation()", true, None, None),
+                ],
+            )]),
+        );
+    }
+
     #[test]
     fn test_trace() {
         let mut runner = tracing_runner();
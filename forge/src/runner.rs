@@ -1,28 +1,44 @@
-use crate::TestFilter;
+use crate::{inline_config::InlineFuzzConfig, TestFilter};
 use ethers::{
-    abi::{Abi, Function, RawLog},
+    abi::{Abi, AbiDecode, Function, RawLog, Token},
     types::{Address, Bytes, U256},
 };
 use eyre::Result;
 use foundry_evm::{
-    executor::{CallResult, DatabaseRef, DeployResult, EvmError, Executor},
-    fuzz::{CounterExample, FuzzedCases, FuzzedExecutor},
+    executor::{
+        abi::HEVM_ABI, CallResult, DatabaseRef, DeployResult, EvmError, Executor, StateDiff,
+        CHEATCODE_ADDRESS,
+    },
+    fuzz::{
+        actor_addresses, check_function, CounterExample, FuzzParamOpts, FuzzedCases, FuzzedExecutor,
+    },
     trace::{CallTraceArena, TraceKind},
     CALLER,
 };
 use proptest::test_runner::TestRunner;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeMap, fmt, time::Instant};
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fmt,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 
 /// The result of an executed solidity test
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct TestResult {
     /// Whether the test case was successful. This means that the transaction executed
     /// properly, or that there was a revert and that the test was expected to fail
     /// (prefixed with `testFail`)
     pub success: bool,
 
+    /// Whether the test was skipped instead of run, e.g. because its params can not be fuzzed.
+    /// The reason is in `reason`, skipped tests count as successful.
+    #[serde(default)]
+    pub skipped: bool,
+
     /// If there was a revert, this field will be populated. Note that the test can
     /// still be successful (i.e self.success == true) when it's expected to fail.
     pub reason: Option<String>,
@@ -30,6 +46,10 @@ pub struct TestResult {
     /// Minimal reproduction test case for failing fuzz tests
     pub counterexample: Option<CounterExample>,
 
+    /// The number of inputs `vm.assume` rejected, for fuzz tests
+    #[serde(default)]
+    pub rejects: u32,
+
     /// Any captured & parsed as strings logs along the test's execution which should
     /// be printed to the user.
     #[serde(skip)]
@@ -43,6 +63,10 @@ pub struct TestResult {
 
     /// Labeled addresses
     pub labeled_addresses: BTreeMap<Address, String>,
+
+    /// The net state changes of the test, if requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_diff: Option<StateDiff>,
 }
 
 impl TestResult {
@@ -94,13 +118,19 @@ pub enum TestKind {
     Fuzz(FuzzedCases),
 }
 
+impl Default for TestKind {
+    fn default() -> Self {
+        TestKind::Standard(0)
+    }
+}
+
 impl TestKind {
     /// The gas consumed by this test
     pub fn gas_used(&self) -> TestKindGas {
         match self {
             TestKind::Standard(gas) => TestKindGas::Standard(*gas),
             TestKind::Fuzz(fuzzed) => TestKindGas::Fuzz {
-                runs: fuzzed.cases().len(),
+                runs: fuzzed.runs(),
                 median: fuzzed.median_gas(false),
                 mean: fuzzed.mean_gas(false),
             },
@@ -141,6 +171,23 @@ pub struct ContractRunner<'a, DB: DatabaseRef> {
     pub initial_balance: U256,
     /// The address which will be used as the `from` field in all EVM calls
     pub sender: Address,
+    /// Whether to record the net state changes of each test
+    pub state_diff: bool,
+    /// How the params of fuzz tests are generated
+    pub fuzz_param_opts: FuzzParamOpts,
+    /// The directory of the counterexamples of the fuzz tests of the contract
+    pub fuzz_corpus: Option<PathBuf>,
+    /// The wall clock time each fuzz test is fuzzed for instead of the number of runs, if any
+    pub fuzz_max_time: Option<Duration>,
+    /// The number of threads the runs of each fuzz test are split across
+    pub fuzz_workers: usize,
+    /// The calldata of a counterexample the fuzz tests are called with instead of being fuzzed
+    pub fuzz_replay: Option<Bytes>,
+    /// The fuzz settings of single tests, keyed by function name
+    pub inline_fuzz_config: BTreeMap<String, InlineFuzzConfig>,
+    /// The ABIs and runtime code of the known contracts, fuzzed `bytes` params are often calldata
+    /// for the deployed ones
+    pub known_contracts: &'a BTreeMap<String, (Abi, Vec<u8>)>,
 }
 
 impl<'a, DB: DatabaseRef> ContractRunner<'a, DB> {
@@ -153,6 +200,14 @@ impl<'a, DB: DatabaseRef> ContractRunner<'a, DB> {
         sender: Option<Address>,
         errors: Option<&'a Abi>,
         predeploy_libs: &'a [Bytes],
+        state_diff: bool,
+        fuzz_param_opts: FuzzParamOpts,
+        fuzz_corpus: Option<PathBuf>,
+        fuzz_max_time: Option<Duration>,
+        fuzz_workers: usize,
+        fuzz_replay: Option<Bytes>,
+        inline_fuzz_config: BTreeMap<String, InlineFuzzConfig>,
+        known_contracts: &'a BTreeMap<String, (Abi, Vec<u8>)>,
     ) -> Self {
         Self {
             executor,
@@ -162,11 +217,19 @@ impl<'a, DB: DatabaseRef> ContractRunner<'a, DB> {
             sender: sender.unwrap_or_default(),
             errors,
             predeploy_libs,
+            state_diff,
+            fuzz_param_opts,
+            fuzz_corpus,
+            fuzz_max_time,
+            fuzz_workers,
+            fuzz_replay,
+            inline_fuzz_config,
+            known_contracts,
         }
     }
 }
 
-impl<'a, DB: DatabaseRef + Send + Sync> ContractRunner<'a, DB> {
+impl<'a, DB: DatabaseRef + Clone + Send + Sync> ContractRunner<'a, DB> {
     /// Deploys the test contract inside the runner from the sending account, and optionally runs
     /// the `setUp` function on the test contract.
     pub fn setup(&mut self, setup: bool) -> Result<TestSetup> {
@@ -177,6 +240,17 @@ impl<'a, DB: DatabaseRef + Send + Sync> ContractRunner<'a, DB> {
         // We set the nonce of the deployer accounts to 1 to get the same addresses as DappTools
         self.executor.set_nonce(self.sender, 1);
 
+        // The actors that call fuzz tests get the same balance as the test contract, before
+        // `setUp` so it can hand them tokens or approvals
+        let actors = self
+            .inline_fuzz_config
+            .values()
+            .filter_map(|config| config.actors)
+            .fold(self.fuzz_param_opts.actors, usize::max);
+        for actor in actor_addresses(actors) {
+            self.executor.set_balance(actor, self.initial_balance);
+        }
+
         // Deploy libraries
         let mut traces: Vec<(TraceKind, CallTraceArena)> = self
             .predeploy_libs
@@ -244,13 +318,11 @@ impl<'a, DB: DatabaseRef + Send + Sync> ContractRunner<'a, DB> {
             return Ok([(
                 "setUp()".to_string(),
                 TestResult {
-                    success: false,
                     reason: setup.reason,
-                    counterexample: None,
                     logs: setup.logs,
-                    kind: TestKind::Standard(0),
                     traces: setup.traces,
                     labeled_addresses: setup.labeled_addresses,
+                    ..Default::default()
                 },
             )]
             .into())
@@ -292,6 +364,30 @@ impl<'a, DB: DatabaseRef + Send + Sync> ContractRunner<'a, DB> {
         Ok(test_results)
     }
 
+    /// Returns the executor of the test `func`, with the fork rolled to the block pinned in the
+    /// natspec of the test, if any
+    fn test_executor(&self, func: &Function, address: Address) -> Result<Cow<'_, Executor<DB>>> {
+        let block = match self
+            .inline_fuzz_config
+            .get(&func.name)
+            .and_then(|config| config.fork_block_number)
+        {
+            Some(block) => block,
+            None => return Ok(Cow::Borrowed(&self.executor)),
+        };
+
+        // The test contract rolls the fork, so it keeps its state like with `rollFork`
+        let mut executor = self.executor.clone();
+        let calldata = HEVM_ABI.function("rollFork")?.encode_input(&[Token::Uint(block.into())])?;
+        let result =
+            executor.call_raw_committing(address, CHEATCODE_ADDRESS, calldata.into(), 0.into())?;
+        if result.reverted {
+            let reason = String::decode(&result.result).unwrap_or_default();
+            eyre::bail!("could not pin the fork to block {}: {}", block, reason)
+        }
+        Ok(Cow::Owned(executor))
+    }
+
     #[tracing::instrument(name = "test", skip_all, fields(name = %func.signature(), %should_fail))]
     pub fn run_test(
         &self,
@@ -301,16 +397,29 @@ impl<'a, DB: DatabaseRef + Send + Sync> ContractRunner<'a, DB> {
     ) -> Result<TestResult> {
         let TestSetup { address, mut logs, mut traces, mut labeled_addresses, .. } = setup;
 
+        let executor = match self.test_executor(func, address) {
+            Ok(executor) => executor,
+            Err(err) => {
+                return Ok(TestResult {
+                    reason: Some(format!("{:#}", err)),
+                    logs,
+                    traces,
+                    labeled_addresses,
+                    ..Default::default()
+                })
+            }
+        };
+
         // Run unit test
         let start = Instant::now();
-        let (reverted, reason, gas, stipend, execution_traces, state_changeset) = match self
-            .executor
+        let (reverted, reason, gas, stipend, execution_traces, state_changeset) = match executor
             .call::<(), _, _>(self.sender, address, func.clone(), (), 0.into(), self.errors)
         {
             Ok(CallResult {
                 reverted,
                 gas,
                 stipend,
+                harness_gas,
                 logs: execution_logs,
                 traces: execution_trace,
                 labels: new_labels,
@@ -319,6 +428,8 @@ impl<'a, DB: DatabaseRef + Send + Sync> ContractRunner<'a, DB> {
             }) => {
                 labeled_addresses.extend(new_labels);
                 logs.extend(execution_logs);
+                // cheatcodes and console logs are not part of the code under test
+                let gas = gas.saturating_sub(harness_gas);
                 (reverted, None, gas, stipend, execution_trace, state_changeset)
             }
             Err(EvmError::Execution {
@@ -326,6 +437,7 @@ impl<'a, DB: DatabaseRef + Send + Sync> ContractRunner<'a, DB> {
                 reason,
                 gas,
                 stipend,
+                harness_gas,
                 logs: execution_logs,
                 traces: execution_trace,
                 labels: new_labels,
@@ -334,6 +446,7 @@ impl<'a, DB: DatabaseRef + Send + Sync> ContractRunner<'a, DB> {
             }) => {
                 labeled_addresses.extend(new_labels);
                 logs.extend(execution_logs);
+                let gas = gas.saturating_sub(harness_gas);
                 (reverted, Some(reason), gas, stipend, execution_trace, state_changeset)
             }
             Err(err) => {
@@ -343,12 +456,14 @@ impl<'a, DB: DatabaseRef + Send + Sync> ContractRunner<'a, DB> {
         };
         traces.extend(execution_traces.map(|traces| (TraceKind::Execution, traces)).into_iter());
 
-        let success = self.executor.is_success(
-            setup.address,
-            reverted,
-            state_changeset.expect("we should have a state changeset"),
-            should_fail,
-        );
+        let state_changeset = state_changeset.expect("we should have a state changeset");
+        let state_diff = self.state_diff.then(|| {
+            let mut diff = executor.state_diff(&state_changeset);
+            // the nonce of the sender is always increased by the call itself
+            diff.ignore_nonce(self.sender);
+            diff
+        });
+        let success = executor.is_success(setup.address, reverted, state_changeset, should_fail);
 
         // Record test execution time
         tracing::debug!(
@@ -360,11 +475,12 @@ impl<'a, DB: DatabaseRef + Send + Sync> ContractRunner<'a, DB> {
         Ok(TestResult {
             success,
             reason,
-            counterexample: None,
             logs,
             kind: TestKind::Standard(gas.overflowing_sub(stipend).0),
             traces,
             labeled_addresses,
+            state_diff,
+            ..Default::default()
         })
     }
 
@@ -378,14 +494,64 @@ impl<'a, DB: DatabaseRef + Send + Sync> ContractRunner<'a, DB> {
     ) -> Result<TestResult> {
         let TestSetup { address, mut logs, mut traces, mut labeled_addresses, .. } = setup;
 
+        // Skip tests with params that can not be fuzzed instead of failing the whole run
+        if let Err(err) = check_function(func) {
+            return Ok(TestResult {
+                success: true,
+                skipped: true,
+                reason: Some(format!("could not fuzz the params: {}", err)),
+                logs,
+                kind: TestKind::Fuzz(FuzzedCases::new(vec![])),
+                traces,
+                labeled_addresses,
+                ..Default::default()
+            })
+        }
+
+        // Apply the settings from the natspec of the test
+        let inline_config = self.inline_fuzz_config.get(&func.name);
+        let (runner, param_opts) = match inline_config {
+            Some(config) => config.apply(runner, self.fuzz_param_opts),
+            None => (runner, self.fuzz_param_opts),
+        };
+        let executor_and_ranges = self.test_executor(func, address).and_then(|executor| {
+            let param_ranges = match inline_config {
+                Some(config) => config.param_ranges(func)?,
+                None => Default::default(),
+            };
+            Ok((executor, param_ranges))
+        });
+        let (executor, param_ranges) = match executor_and_ranges {
+            Ok(executor_and_ranges) => executor_and_ranges,
+            Err(err) => {
+                return Ok(TestResult {
+                    reason: Some(format!("{:#}", err)),
+                    logs,
+                    kind: TestKind::Fuzz(FuzzedCases::new(vec![])),
+                    traces,
+                    labeled_addresses,
+                    ..Default::default()
+                })
+            }
+        };
+
         // Run fuzz test
         let start = Instant::now();
-        let mut result = FuzzedExecutor::new(&self.executor, runner, self.sender).fuzz(
-            func,
-            address,
-            should_fail,
-            self.errors,
-        );
+        let mut result = FuzzedExecutor::new(&executor, runner, self.sender)
+            .with_param_opts(param_opts)
+            .with_param_ranges(param_ranges)
+            .with_known_contracts(self.known_contracts)
+            .with_corpus(
+                self.fuzz_corpus.as_ref().map(|dir| dir.join(format!("{}.json", func.signature()))),
+            )
+            .with_max_time(self.fuzz_max_time)
+            .with_workers(self.fuzz_workers)
+            .with_replay(
+                self.fuzz_replay
+                    .clone()
+                    .filter(|calldata| calldata.as_ref().starts_with(&func.short_signature())),
+            )
+            .fuzz(func, address, should_fail, self.errors);
 
         // Record logs, labels and traces
         logs.append(&mut result.logs);
@@ -402,10 +568,12 @@ impl<'a, DB: DatabaseRef + Send + Sync> ContractRunner<'a, DB> {
             success: result.success,
             reason: result.reason,
             counterexample: result.counterexample,
+            rejects: result.rejects,
             logs,
             kind: TestKind::Fuzz(result.cases),
             traces,
             labeled_addresses,
+            ..Default::default()
         })
     }
 }
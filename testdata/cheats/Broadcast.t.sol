@@ -0,0 +1,74 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity >=0.8.0;
+
+import "ds-test/test.sol";
+import "./Cheats.sol";
+
+contract Target {
+    address public lastSender;
+
+    function touch() public {
+        lastSender = msg.sender;
+    }
+}
+
+contract Deployed {
+    address public deployer;
+
+    constructor() {
+        deployer = msg.sender;
+    }
+}
+
+contract BroadcastTest is DSTest {
+    Cheats constant cheats = Cheats(HEVM_ADDRESS);
+
+    Target target;
+
+    function setUp() public {
+        target = new Target();
+    }
+
+    function testBroadcast() public {
+        cheats.broadcast();
+        target.touch();
+        assertEq(target.lastSender(), tx.origin, "broadcast call was not sent by tx.origin");
+
+        target.touch();
+        assertEq(target.lastSender(), address(this), "broadcast did not stop after one call");
+    }
+
+    function testStartBroadcast() public {
+        cheats.startBroadcast();
+        target.touch();
+        assertEq(target.lastSender(), tx.origin, "first call was not sent by tx.origin");
+        target.touch();
+        assertEq(target.lastSender(), tx.origin, "second call was not sent by tx.origin");
+        cheats.stopBroadcast();
+
+        target.touch();
+        assertEq(target.lastSender(), address(this), "stopBroadcast did not stop the broadcast");
+    }
+
+    function testBroadcastSkipsStaticCalls() public {
+        cheats.startBroadcast();
+        target.touch();
+        assertEq(target.lastSender(), tx.origin, "static call was not sent by tx.origin");
+        (bool success,) = address(0x000000000000000000636F6e736F6c652e6c6f67).call(
+            abi.encodeWithSignature("log(string)", "broadcasting")
+        );
+        assertTrue(success);
+        cheats.stopBroadcast();
+    }
+
+    function testBroadcastCreate() public {
+        cheats.broadcast();
+        Deployed deployed = new Deployed();
+        assertEq(deployed.deployer(), tx.origin, "broadcast create was not sent by tx.origin");
+    }
+
+    function testFailStartBroadcastTwice() public {
+        cheats.startBroadcast();
+        cheats.startBroadcast();
+    }
+}
@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity >=0.8.0;
+
+import "ds-test/test.sol";
+import "./Cheats.sol";
+
+contract ChainIdTest is DSTest {
+    Cheats constant cheats = Cheats(HEVM_ADDRESS);
+
+    function testChainId() public {
+        cheats.chainId(10);
+        assertEq(block.chainid, 10, "chainId failed");
+    }
+
+    function testChainIdFuzzed(uint64 chainId) public {
+        cheats.chainId(chainId);
+        assertEq(block.chainid, chainId, "chainId failed");
+    }
+
+    function testFailChainIdTooLarge() public {
+        cheats.chainId(uint256(type(uint64).max) + 1);
+    }
+}
+
+contract ChainIdSetUpTest is DSTest {
+    Cheats constant cheats = Cheats(HEVM_ADDRESS);
+
+    function setUp() public {
+        cheats.chainId(10);
+    }
+
+    function testChainIdIsKeptFromSetUp() public {
+        assertEq(block.chainid, 10, "chainId from setUp was lost");
+    }
+}
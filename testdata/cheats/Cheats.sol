@@ -8,6 +8,12 @@ interface Cheats {
     function roll(uint256) external;
     // Set block.basefee (newBasefee)
     function fee(uint256) external;
+    // Set block.chainid (newChainId)
+    function chainId(uint256) external;
+    // Set block.coinbase (newCoinbase)
+    function coinbase(address) external;
+    // Set block.difficulty (newDifficulty)
+    function difficulty(uint256) external;
     // Loads a storage slot from an address (who, slot)
     function load(address,bytes32) external returns (bytes32);
     // Stores a value to an address' storage slot, (who, slot, value)
@@ -16,6 +22,10 @@ interface Cheats {
     function sign(uint256,bytes32) external returns (uint8,bytes32,bytes32);
     // Gets address for a given private key, (privateKey) => (address)
     function addr(uint256) external returns (address);
+    // Derives a private key from a mnemonic at the path m/44'/60'/0'/0/{index}, (mnemonic, index) => (privateKey)
+    function deriveKey(string calldata, uint32) external returns (uint256);
+    // Derives a private key from a mnemonic at the path {path}{index}, (mnemonic, path, index) => (privateKey)
+    function deriveKey(string calldata, string calldata, uint32) external returns (uint256);
     // Performs a foreign function call via terminal, (stringInputs) => (result)
     function ffi(string[] calldata) external returns (bytes memory);
     // Sets the *next* call's msg.sender to be the input address
@@ -26,13 +36,44 @@ interface Cheats {
     function prank(address,address) external;
     // Sets all subsequent calls' msg.sender to be the input address until `stopPrank` is called, and the tx.origin to be the second input
     function startPrank(address,address) external;
+    // Like `prank(address,address)`, the tx.origin is only set for the pranked call itself and not for the calls it makes if the third input is false
+    function prank(address,address,bool) external;
+    // Like `startPrank(address,address)`, the tx.origin is only set for the pranked calls themselves and not for the calls they make if the third input is false
+    function startPrank(address,address,bool) external;
     // Resets subsequent calls' msg.sender to be `address(this)`
     function stopPrank() external;
+    // Marks the next call or create as a transaction sent by tx.origin, to be broadcast by a script
+    function broadcast() external;
+    // Marks all subsequent calls and creates as transactions sent by tx.origin until `stopBroadcast` is called
+    function startBroadcast() external;
+    // Stops marking calls and creates as transactions to broadcast
+    function stopBroadcast() external;
     // Sets an address' balance, (who, newBalance)
     function deal(address, uint256) external;
+    // Sets an address' nonce, (who, newNonce)
+    function setNonce(address, uint64) external;
+    // Gets an address' nonce, (who) => (nonce)
+    function getNonce(address) external returns (uint64);
     // Sets an address' code, (who, newCode)
     function etch(address, bytes calldata) external;
-    // Expects an error on next call
+    // Gets the code of an address on the forked chain, ignoring local changes, (who) => (code)
+    function getForkCode(address) external returns (bytes memory);
+    // Gets the value of a storage slot on the forked chain, ignoring local changes, (who, slot) => (value)
+    function getForkStorageAt(address, bytes32) external returns (bytes32);
+    // Creates a fork of a chain at a block or the latest block, (url, blockNumber) => (forkId)
+    function createFork(string calldata, uint256) external returns (uint256);
+    function createFork(string calldata) external returns (uint256);
+    // Makes a fork the active fork, its state is kept when another fork is selected, (forkId)
+    function selectFork(uint256) external;
+    // Gets the id of the active fork
+    function activeFork() external returns (uint256);
+    // Moves the fork to another block, accounts are fetched from it the next time they are used, (blockNumber)
+    function rollFork(uint256) external;
+    // Keeps the state of an address the same on all forks, (who)
+    function makePersistent(address) external;
+    // Executes a transaction of the forked chain on the current state, (txHash)
+    function transact(bytes32) external;
+    // Expects an error on next call: any revert, the exact revert data, or revert data starting with the selector
     function expectRevert() external;
     function expectRevert(bytes calldata) external;
     function expectRevert(bytes4) external;
@@ -54,10 +95,39 @@ interface Cheats {
     // Expect a call to an address with the specified calldata.
     // Calldata can either be strict or a partial match
     function expectCall(address,bytes calldata) external;
+    // Expect a call to an address with the specified value and calldata.
+    // Calldata can either be strict or a partial match
+    function expectCall(address,uint256,bytes calldata) external;
     // Gets the code from an artifact file. Takes in the relative path to the json file
     function getCode(string calldata) external returns (bytes memory);
+    // Reads the entire content of a file to a string, the path has to be allowed by `fs_permissions`
+    function readFile(string calldata) external returns (string memory);
+    // Reads the next line of a file to a string, returns an empty string once the whole file was read
+    function readLine(string calldata) external returns (string memory);
+    // Writes the data to a file, creating it if it doesn't exist and replacing its content otherwise
+    function writeFile(string calldata, string calldata) external;
+    // Removes a file
+    function removeFile(string calldata) external;
     // Labels an address in call traces
     function label(address, string calldata) external;
+    // Converts a value to its string representation: checksummed for addresses, 0x-prefixed hex for bytes and bytes32
+    function toString(address) external returns (string memory);
+    function toString(bytes calldata) external returns (string memory);
+    function toString(bytes32) external returns (string memory);
+    function toString(bool) external returns (string memory);
+    function toString(uint256) external returns (string memory);
+    function toString(int256) external returns (string memory);
     // If the condition is false, discard this run's fuzz inputs and generate new ones
     function assume(bool) external;
+    // Snapshots the state of the EVM, returns the id of the snapshot
+    function snapshot() external returns (uint256);
+    // Reverts the state of the EVM to a snapshot, returns false if there is no snapshot with the id
+    function revertTo(uint256) external returns (bool);
+    // Reads an environment variable and parses it as the returned type
+    function envBool(string calldata) external returns (bool);
+    function envUint(string calldata) external returns (uint256);
+    function envInt(string calldata) external returns (int256);
+    function envAddress(string calldata) external returns (address);
+    function envBytes32(string calldata) external returns (bytes32);
+    function envString(string calldata) external returns (string memory);
 }
@@ -0,0 +1,19 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity >=0.8.0;
+
+import "ds-test/test.sol";
+import "./Cheats.sol";
+
+contract CoinbaseTest is DSTest {
+    Cheats constant cheats = Cheats(HEVM_ADDRESS);
+
+    function testCoinbase() public {
+        cheats.coinbase(address(0xbeef));
+        assertEq(block.coinbase, address(0xbeef), "coinbase failed");
+    }
+
+    function testCoinbaseFuzzed(address coinbase) public {
+        cheats.coinbase(coinbase);
+        assertEq(block.coinbase, coinbase, "coinbase failed");
+    }
+}
@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity >=0.8.0;
+
+import "ds-test/test.sol";
+import "./Cheats.sol";
+
+contract DeriveKeyTest is DSTest {
+    Cheats constant cheats = Cheats(HEVM_ADDRESS);
+
+    string constant mnemonic = "test test test test test test test test test test test junk";
+
+    function testDeriveKey() public {
+        uint256 pk = cheats.deriveKey(mnemonic, 0);
+        assertEq(pk, 0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80);
+        assertEq(cheats.addr(pk), 0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266);
+
+        pk = cheats.deriveKey(mnemonic, 1);
+        assertEq(cheats.addr(pk), 0x70997970C51812dc3A010C7d01b50e0d17dc79C8);
+    }
+
+    function testDeriveKeyWithPath() public {
+        assertEq(cheats.deriveKey(mnemonic, "m/44'/60'/0'/0/", 1), cheats.deriveKey(mnemonic, 1));
+        assertTrue(cheats.deriveKey(mnemonic, "m/44'/60'/0'/1/", 1) != cheats.deriveKey(mnemonic, 1));
+    }
+
+    function testFailDeriveKeyInvalidMnemonic() public {
+        cheats.deriveKey("not a mnemonic", 0);
+    }
+}
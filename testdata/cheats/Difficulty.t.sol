@@ -0,0 +1,19 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity >=0.8.0;
+
+import "ds-test/test.sol";
+import "./Cheats.sol";
+
+contract DifficultyTest is DSTest {
+    Cheats constant cheats = Cheats(HEVM_ADDRESS);
+
+    function testDifficulty() public {
+        cheats.difficulty(10);
+        assertEq(block.difficulty, 10, "difficulty failed");
+    }
+
+    function testDifficultyFuzzed(uint256 difficulty) public {
+        cheats.difficulty(difficulty);
+        assertEq(block.difficulty, difficulty, "difficulty failed");
+    }
+}
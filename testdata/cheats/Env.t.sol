@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity >=0.8.0;
+
+import "ds-test/test.sol";
+import "./Cheats.sol";
+
+// The variables are set by the test that runs the cheatcode tests
+contract EnvTest is DSTest {
+    Cheats constant cheats = Cheats(HEVM_ADDRESS);
+
+    function testEnvBool() public {
+        assertTrue(cheats.envBool("_FOUNDRY_TEST_ENV_BOOL"));
+    }
+
+    function testEnvUint() public {
+        assertEq(cheats.envUint("_FOUNDRY_TEST_ENV_UINT"), 1000);
+        assertEq(cheats.envUint("_FOUNDRY_TEST_ENV_UINT_HEX"), 1000);
+    }
+
+    function testEnvInt() public {
+        assertEq(cheats.envInt("_FOUNDRY_TEST_ENV_INT"), -1000);
+    }
+
+    function testEnvAddress() public {
+        assertEq(cheats.envAddress("_FOUNDRY_TEST_ENV_ADDRESS"), HEVM_ADDRESS);
+    }
+
+    function testEnvBytes32() public {
+        assertEq(cheats.envBytes32("_FOUNDRY_TEST_ENV_BYTES32"), bytes32(uint256(1000)));
+    }
+
+    function testEnvString() public {
+        assertEq(cheats.envString("_FOUNDRY_TEST_ENV_STRING"), "gm gm");
+    }
+
+    function testFailEnvUnset() public {
+        cheats.envUint("_FOUNDRY_TEST_ENV_UNSET");
+    }
+
+    function testFailEnvNotANumber() public {
+        cheats.envUint("_FOUNDRY_TEST_ENV_STRING");
+    }
+}
@@ -4,6 +4,12 @@ pragma solidity >=0.8.0;
 import "ds-test/test.sol";
 import "./Cheats.sol";
 
+contract Answer {
+    function answer() public pure returns (uint256) {
+        return 42;
+    }
+}
+
 contract EtchTest is DSTest {
     Cheats constant cheats = Cheats(HEVM_ADDRESS);
 
@@ -13,4 +19,18 @@ contract EtchTest is DSTest {
         cheats.etch(target, code);
         assertEq(string(code), string(target.code));
     }
+
+    function testEtchCodeSizeAndHash() public {
+        address target = address(11);
+        bytes memory code = hex"60016002";
+        cheats.etch(target, code);
+        assertEq(target.code.length, code.length);
+        assertEq(target.codehash, keccak256(code));
+    }
+
+    function testEtchIsCallable() public {
+        address target = 0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2;
+        cheats.etch(target, address(new Answer()).code);
+        assertEq(Answer(target).answer(), 42);
+    }
 }
@@ -16,6 +16,10 @@ contract Contract {
     function add(uint256 a, uint256 b) public pure returns (uint256) {
         return a + b;
     }
+
+    function pay(uint256 a) public payable returns (uint256) {
+        return a + msg.value;
+    }
 }
 
 contract NestedContract {
@@ -96,6 +100,36 @@ contract ExpectCallTest is DSTest {
         );
     }
 
+    function testExpectCallWithValue() public {
+        Contract target = new Contract();
+        cheats.expectCall(
+            address(target),
+            1,
+            abi.encodeWithSelector(target.pay.selector, 2)
+        );
+        target.pay{value: 1}(2);
+    }
+
+    function testExpectCallWithValueSelector() public {
+        Contract target = new Contract();
+        cheats.expectCall(
+            address(target),
+            1,
+            abi.encodeWithSelector(target.pay.selector)
+        );
+        target.pay{value: 1}(2);
+    }
+
+    function testFailExpectCallWithValue() public {
+        Contract target = new Contract();
+        cheats.expectCall(
+            address(target),
+            1,
+            abi.encodeWithSelector(target.pay.selector, 2)
+        );
+        target.pay{value: 2}(2);
+    }
+
     function testFailExpectCallWithMoreParameters() public {
         Contract target = new Contract();
         cheats.expectCall(
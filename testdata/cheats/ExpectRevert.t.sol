@@ -6,6 +6,7 @@ import "./Cheats.sol";
 
 contract Reverter {
     error CustomError();
+    error CustomErrorWithArgs(uint256 amount, address who);
 
     function revertWithMessage(string memory message) public pure {
         require(false, message);
@@ -21,6 +22,10 @@ contract Reverter {
         revert CustomError();
     }
 
+    function revertWithCustomErrorWithArgs(uint256 amount, address who) public pure {
+        revert CustomErrorWithArgs(amount, who);
+    }
+
     function nestedRevert(Reverter inner, string memory message) public pure {
         inner.revertWithMessage(message);
     }
@@ -73,6 +78,52 @@ contract ExpectRevertTest is DSTest {
         reverter.revertWithCustomError();
     }
 
+    function testExpectRevertCustomErrorWithArgs() public {
+        Reverter reverter = new Reverter();
+        cheats.expectRevert(
+            abi.encodeWithSelector(Reverter.CustomErrorWithArgs.selector, 1, address(this))
+        );
+        reverter.revertWithCustomErrorWithArgs(1, address(this));
+    }
+
+    function testExpectRevertCustomErrorSelector() public {
+        Reverter reverter = new Reverter();
+        cheats.expectRevert(Reverter.CustomErrorWithArgs.selector);
+        reverter.revertWithCustomErrorWithArgs(1, address(this));
+        cheats.expectRevert(Reverter.CustomError.selector);
+        reverter.revertWithCustomError();
+    }
+
+    function testFailExpectRevertCustomErrorArgsDoNotMatch() public {
+        Reverter reverter = new Reverter();
+        cheats.expectRevert(
+            abi.encodeWithSelector(Reverter.CustomErrorWithArgs.selector, 2, address(this))
+        );
+        reverter.revertWithCustomErrorWithArgs(1, address(this));
+    }
+
+    function testFailExpectRevertCustomErrorSelectorDoesNotMatch() public {
+        Reverter reverter = new Reverter();
+        cheats.expectRevert(Reverter.CustomError.selector);
+        reverter.revertWithCustomErrorWithArgs(1, address(this));
+    }
+
+    function testExpectRevertAny() public {
+        Reverter reverter = new Reverter();
+        cheats.expectRevert();
+        reverter.revertWithMessage("any message");
+        cheats.expectRevert();
+        reverter.revertWithCustomErrorWithArgs(1, address(this));
+        cheats.expectRevert();
+        reverter.panic();
+    }
+
+    function testFailExpectRevertAnyDidNotRevert() public {
+        Reverter reverter = new Reverter();
+        cheats.expectRevert();
+        reverter.doNotRevert();
+    }
+
     function testExpectRevertNested() public {
         Reverter reverter = new Reverter();
         Reverter inner = new Reverter();
@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity >=0.8.0;
+
+import "ds-test/test.sol";
+import "./Cheats.sol";
+
+contract ForkStateTest is DSTest {
+    Cheats constant cheats = Cheats(HEVM_ADDRESS);
+
+    function testFailGetForkCodeWithoutFork() public {
+        cheats.getForkCode(address(this));
+    }
+
+    function testFailGetForkStorageAtWithoutFork() public {
+        cheats.getForkStorageAt(address(this), bytes32(0));
+    }
+
+    function testFailSelectUnknownFork() public {
+        cheats.selectFork(0);
+    }
+
+    function testFailActiveForkWithoutFork() public {
+        cheats.activeFork();
+    }
+
+    function testFailMakePersistentWithoutFork() public {
+        cheats.makePersistent(address(this));
+    }
+
+    function testFailRollForkWithoutFork() public {
+        cheats.rollFork(1);
+    }
+
+    function testFailTransactWithoutFork() public {
+        cheats.transact(bytes32(0));
+    }
+}
@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity >=0.8.0;
+
+import "ds-test/test.sol";
+import "./Cheats.sol";
+
+contract FsTest is DSTest {
+    Cheats constant cheats = Cheats(HEVM_ADDRESS);
+
+    function testReadFile() public {
+        string memory path = "../testdata/fixtures/File/read.txt";
+
+        assertEq(cheats.readFile(path), "hello readable world\nthis is the second line");
+    }
+
+    function testReadLine() public {
+        string memory path = "../testdata/fixtures/File/read.txt";
+
+        assertEq(cheats.readLine(path), "hello readable world");
+        assertEq(cheats.readLine(path), "this is the second line");
+        assertEq(cheats.readLine(path), "");
+    }
+
+    function testReadLineAfterWrite() public {
+        string memory path = "../testdata/fixtures/File/read_line.txt";
+
+        cheats.writeFile(path, "first\nsecond");
+        assertEq(cheats.readLine(path), "first");
+
+        // writing the file starts reading it from the start again
+        cheats.writeFile(path, "third\n");
+        assertEq(cheats.readLine(path), "third");
+        assertEq(cheats.readLine(path), "");
+
+        cheats.removeFile(path);
+    }
+
+    function testWriteFile() public {
+        string memory path = "../testdata/fixtures/File/write_file.txt";
+        string memory data = "hello writable world";
+
+        cheats.writeFile(path, data);
+        assertEq(cheats.readFile(path), data);
+
+        cheats.removeFile(path);
+    }
+
+    function testCannotAccessOutsideOfPermissions() public {
+        try cheats.readFile("../testdata/cheats/Cheats.sol") returns (string memory) {
+            fail();
+        } catch {}
+
+        try cheats.writeFile("../testdata/fixtures/File/../../cheats/escape.txt", "escaped") {
+            fail();
+        } catch {}
+    }
+}
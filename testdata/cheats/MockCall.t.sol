@@ -96,6 +96,43 @@ contract MockCallTest is DSTest {
         assertEq(target.add(6, 4), 10);
     }
 
+    function testMockCalldataLongerThanInput() public {
+        Mock target = new Mock();
+
+        cheats.mockCall(
+            address(target),
+            abi.encodeWithSelector(target.add.selector, 5, 5),
+            abi.encode(11)
+        );
+
+        assertEq(target.numberA(), 1);
+        assertEq(target.add(5, 5), 11);
+    }
+
+    function testMockMostSpecificCalldata() public {
+        Mock target = new Mock();
+
+        cheats.mockCall(
+            address(target),
+            abi.encodeWithSelector(target.add.selector, 5, 5),
+            abi.encode(11)
+        );
+        cheats.mockCall(
+            address(target),
+            abi.encodeWithSelector(target.add.selector),
+            abi.encode(12)
+        );
+        cheats.mockCall(
+            address(target),
+            abi.encodeWithSelector(target.add.selector, 5),
+            abi.encode(13)
+        );
+
+        assertEq(target.add(5, 5), 11);
+        assertEq(target.add(5, 6), 13);
+        assertEq(target.add(6, 4), 12);
+    }
+
     function testClearMockedCalls() public {
         Mock target = new Mock();
 
@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity >=0.8.0;
+
+import "ds-test/test.sol";
+import "./Cheats.sol";
+
+contract Foo {}
+
+contract NonceTest is DSTest {
+    Cheats constant cheats = Cheats(HEVM_ADDRESS);
+
+    function testSetNonce() public {
+        address target = address(1);
+        assertEq(cheats.getNonce(target), 0);
+
+        cheats.setNonce(target, 10);
+        assertEq(cheats.getNonce(target), 10);
+
+        // the nonce can be lowered
+        cheats.setNonce(target, 5);
+        assertEq(cheats.getNonce(target), 5);
+    }
+
+    function testSetNonceCreate() public {
+        cheats.setNonce(address(this), 100);
+        Foo foo = new Foo();
+
+        // the RLP encoding of (address(this), 100)
+        address expected = address(
+            uint160(uint256(keccak256(abi.encodePacked(bytes1(0xd6), bytes1(0x94), address(this), uint8(100)))))
+        );
+        assertEq(address(foo), expected);
+        assertEq(cheats.getNonce(address(this)), 101);
+    }
+}
@@ -52,6 +52,43 @@ contract NestedVictim {
     }
 }
 
+contract NestedVictimCheckingAfter {
+    Victim innerVictim;
+
+    constructor(Victim victim) {
+        innerVictim = victim;
+    }
+
+    function assertOriginAfterNestedCall(address expectedOrigin) public view {
+        innerVictim.assertCallerAndOrigin(
+            address(this),
+            "msg.sender was incorrectly set for nested victim",
+            expectedOrigin,
+            "tx.origin was incorrectly set for nested victim"
+        );
+        require(tx.origin == expectedOrigin, "tx.origin was reset by a nested call");
+    }
+}
+
+contract NestedVictimCheckingOrigins {
+    Victim innerVictim;
+
+    constructor(Victim victim) {
+        innerVictim = victim;
+    }
+
+    function assertOrigins(address outerOrigin, address innerOrigin) public view {
+        require(tx.origin == outerOrigin, "tx.origin was incorrectly set for the victim");
+        innerVictim.assertCallerAndOrigin(
+            address(this),
+            "msg.sender was incorrectly set for nested victim",
+            innerOrigin,
+            "tx.origin was incorrectly set for nested victim"
+        );
+        require(tx.origin == outerOrigin, "tx.origin was not restored after the nested call");
+    }
+}
+
 contract NestedPranker {
     Cheats constant cheats = Cheats(
         address(bytes20(uint160(uint256(keccak256('hevm cheat code')))))
@@ -230,6 +267,41 @@ contract PrankTest is DSTest {
         );
     }
 
+    function testPrankOriginOnlyForPrankedCalls(address sender, address origin) public {
+        address oldOrigin = tx.origin;
+        NestedVictimCheckingOrigins victim = new NestedVictimCheckingOrigins(new Victim());
+
+        cheats.prank(sender, origin, false);
+        victim.assertOrigins(origin, oldOrigin);
+        victim.assertOrigins(oldOrigin, oldOrigin);
+
+        cheats.startPrank(sender, origin, false);
+        victim.assertOrigins(origin, oldOrigin);
+        victim.assertOrigins(origin, oldOrigin);
+        cheats.stopPrank();
+
+        cheats.startPrank(sender, origin, true);
+        victim.assertOrigins(origin, origin);
+        cheats.stopPrank();
+
+        victim.assertOrigins(oldOrigin, oldOrigin);
+    }
+
+    function testPrankOriginSurvivesNestedCalls(address sender, address origin) public {
+        address oldOrigin = tx.origin;
+        NestedVictimCheckingAfter victim = new NestedVictimCheckingAfter(new Victim());
+
+        cheats.prank(sender, origin);
+        victim.assertOriginAfterNestedCall(origin);
+
+        cheats.startPrank(sender, origin);
+        victim.assertOriginAfterNestedCall(origin);
+        victim.assertOriginAfterNestedCall(origin);
+        cheats.stopPrank();
+
+        victim.assertOriginAfterNestedCall(oldOrigin);
+    }
+
     /// This test checks that depth is working correctly with respect
     /// to the `startPrank` and `stopPrank` cheatcodes.
     ///
@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity >=0.8.0;
+
+import "ds-test/test.sol";
+import "./Cheats.sol";
+
+contract Counter {
+    uint256 public count;
+
+    function increment() public {
+        count++;
+    }
+}
+
+contract SnapshotTest is DSTest {
+    Cheats constant cheats = Cheats(HEVM_ADDRESS);
+    Counter counter;
+
+    function setUp() public {
+        counter = new Counter();
+    }
+
+    function testSnapshot() public {
+        counter.increment();
+        uint256 snapshot = cheats.snapshot();
+
+        counter.increment();
+        counter.increment();
+        assertEq(counter.count(), 3);
+
+        assertTrue(cheats.revertTo(snapshot));
+        assertEq(counter.count(), 1);
+
+        // a snapshot can be reverted to more than once
+        counter.increment();
+        assertTrue(cheats.revertTo(snapshot));
+        assertEq(counter.count(), 1);
+    }
+
+    function testSnapshotBlock() public {
+        cheats.warp(100);
+        cheats.roll(10);
+        uint256 snapshot = cheats.snapshot();
+
+        cheats.warp(200);
+        cheats.roll(20);
+
+        assertTrue(cheats.revertTo(snapshot));
+        assertEq(block.timestamp, 100);
+        assertEq(block.number, 10);
+    }
+
+    function testSnapshotBalance() public {
+        address alice = address(0xa11ce);
+        cheats.deal(alice, 1 ether);
+        uint256 snapshot = cheats.snapshot();
+
+        cheats.deal(alice, 2 ether);
+        assertTrue(cheats.revertTo(snapshot));
+        assertEq(alice.balance, 1 ether);
+    }
+
+    function testRevertToUnknownSnapshot() public {
+        assertTrue(!cheats.revertTo(1));
+        uint256 snapshot = cheats.snapshot();
+        assertTrue(!cheats.revertTo(snapshot + 1));
+    }
+}
@@ -9,6 +9,16 @@ contract Storage {
     uint public slot1 = 20;
 }
 
+contract PackedStorage {
+    uint128 public low = 1;
+    uint128 public high = 2;
+    address private owner = address(3);
+
+    function isOwner(address who) public view returns (bool) {
+        return who == owner;
+    }
+}
+
 contract StoreTest is DSTest {
     Cheats constant cheats = Cheats(HEVM_ADDRESS);
     Storage store;
@@ -35,4 +45,23 @@ contract StoreTest is DSTest {
         assertEq(store.slot0(), slot0, "store failed");
         assertEq(store.slot1(), slot1, "store failed"); 
     }
+
+    function testStorePackedSlot() public {
+        PackedStorage packed = new PackedStorage();
+        bytes32 slot = cheats.load(address(packed), bytes32(0));
+        assertEq(uint256(slot), (uint256(2) << 128) | 1, "packed slot is incorrect");
+
+        // Overwrite `high` and keep `low`
+        cheats.store(address(packed), bytes32(0), bytes32((uint256(5) << 128) | uint256(uint128(uint256(slot)))));
+        assertEq(packed.low(), 1, "store changed the other value of the packed slot");
+        assertEq(packed.high(), 5, "store failed on packed slot");
+    }
+
+    function testStorePrivateVariable() public {
+        PackedStorage packed = new PackedStorage();
+        assertEq(address(uint160(uint256(cheats.load(address(packed), bytes32(uint256(1)))))), address(3));
+
+        cheats.store(address(packed), bytes32(uint256(1)), bytes32(uint256(uint160(address(this)))));
+        assertTrue(packed.isOwner(address(this)), "store failed on private variable");
+    }
 }
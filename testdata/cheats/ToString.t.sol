@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity >=0.8.0;
+
+import "ds-test/test.sol";
+import "./Cheats.sol";
+
+contract ToStringTest is DSTest {
+    Cheats constant cheats = Cheats(HEVM_ADDRESS);
+
+    function testAddressToString() public {
+        assertEq(
+            cheats.toString(0x7109709ECfa91a80626fF3989D68f67F5b1DD12D),
+            "0x7109709ECfa91a80626fF3989D68f67F5b1DD12D"
+        );
+    }
+
+    function testBytesToString() public {
+        // a hex literal converts to both `bytes` and `bytes32`, so it is typed first
+        bytes memory data = hex"0102abcd";
+        assertEq(cheats.toString(data), "0x0102abcd");
+        assertEq(cheats.toString(bytes("")), "0x");
+    }
+
+    function testBytes32LiteralToString() public {
+        bytes32 data = hex"0102abcd";
+        assertEq(
+            cheats.toString(data),
+            "0x0102abcd00000000000000000000000000000000000000000000000000000000"
+        );
+    }
+
+    function testBytes32ToString() public {
+        assertEq(
+            cheats.toString(bytes32(uint256(1))),
+            "0x0000000000000000000000000000000000000000000000000000000000000001"
+        );
+    }
+
+    function testBoolToString() public {
+        assertEq(cheats.toString(true), "true");
+        assertEq(cheats.toString(false), "false");
+    }
+
+    function testUintToString() public {
+        assertEq(cheats.toString(uint256(0)), "0");
+        assertEq(cheats.toString(uint256(1337)), "1337");
+        assertEq(
+            cheats.toString(type(uint256).max),
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+        );
+    }
+
+    function testIntToString() public {
+        assertEq(cheats.toString(int256(-1337)), "-1337");
+        assertEq(cheats.toString(int256(42)), "42");
+    }
+}
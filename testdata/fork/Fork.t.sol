@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity >=0.8.0;
+
+import "ds-test/test.sol";
+import "../cheats/Cheats.sol";
+
+// Runs against the `mainnet` alias of the RPC endpoints of the runner
+contract ForkTest is DSTest {
+    Cheats constant cheats = Cheats(HEVM_ADDRESS);
+
+    address constant WETH = 0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2;
+
+    uint256 mainnet;
+
+    function setUp() public {
+        mainnet = cheats.createFork("mainnet", 15_000_000);
+    }
+
+    function testCanSelectForkByAlias() public {
+        cheats.selectFork(mainnet);
+        assertEq(cheats.activeFork(), mainnet);
+        assertEq(block.number, 15_000_000);
+        assertEq(block.chainid, 1);
+        assertTrue(WETH.code.length > 0, "WETH was not fetched from the fork");
+    }
+
+    function testCanCreateForkAtLatestBlock() public {
+        uint256 latest = cheats.createFork("mainnet");
+        cheats.selectFork(latest);
+        assertEq(cheats.activeFork(), latest);
+        assertGt(block.number, 15_000_000);
+    }
+
+    function testCanRollFork() public {
+        cheats.selectFork(mainnet);
+        cheats.rollFork(15_000_001);
+        assertEq(block.number, 15_000_001);
+        assertEq(cheats.activeFork(), mainnet);
+        assertTrue(WETH.code.length > 0, "WETH was not fetched from the rolled fork");
+    }
+
+    function testCanTransact() public {
+        // the first transaction of mainnet, which sends 31337 wei
+        uint256 fork = cheats.createFork("mainnet", 46_147);
+        cheats.selectFork(fork);
+        address recipient = address(bytes20(hex"5df9b87991262f6ba471f09758cde1c0fc1de734"));
+        uint256 balance = recipient.balance;
+        cheats.transact(0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060);
+        assertEq(recipient.balance, balance + 31337);
+    }
+
+    function testCanGetForkCode() public {
+        cheats.selectFork(mainnet);
+        cheats.etch(WETH, hex"00");
+        assertTrue(cheats.getForkCode(WETH).length > 0, "the local code was read");
+        assertEq(WETH.code.length, 1);
+    }
+
+    function testCanGetForkStorageAt() public {
+        cheats.selectFork(mainnet);
+        // the name of WETH is a short string, stored left-aligned with twice its length in the
+        // last byte
+        bytes32 name = bytes32("Wrapped Ether") | bytes32(uint256(26));
+        cheats.store(WETH, bytes32(0), bytes32(0));
+        assertEq(cheats.getForkStorageAt(WETH, bytes32(0)), name);
+        assertEq(cheats.load(WETH, bytes32(0)), bytes32(0));
+    }
+
+    function testFailRollForkToHugeBlock() public {
+        cheats.selectFork(mainnet);
+        cheats.rollFork(type(uint256).max);
+    }
+
+    function testFailSelectForkWithHugeId() public {
+        cheats.selectFork(type(uint256).max);
+    }
+
+    function testFailCreateForkAtHugeBlock() public {
+        cheats.createFork("mainnet", type(uint256).max);
+    }
+}
+
+contract ForkSelectedInSetUpTest is DSTest {
+    Cheats constant cheats = Cheats(HEVM_ADDRESS);
+
+    function setUp() public {
+        cheats.selectFork(cheats.createFork("mainnet", 15_000_000));
+    }
+
+    function testKeepsTheForkSelectedInSetUp() public {
+        assertEq(block.chainid, 1);
+        assertEq(block.number, 15_000_000);
+    }
+}
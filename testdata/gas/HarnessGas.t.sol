@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity >=0.8.0;
+
+import "ds-test/test.sol";
+import "../cheats/Cheats.sol";
+
+contract HarnessGasTest is DSTest {
+    Cheats constant cheats = Cheats(HEVM_ADDRESS);
+
+    function testNoop() public {}
+
+    function testCheatcodesOnly() public {
+        cheats.roll(1);
+        cheats.warp(2);
+        cheats.roll(3);
+        cheats.warp(4);
+    }
+}
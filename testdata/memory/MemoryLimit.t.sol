@@ -0,0 +1,19 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity >=0.8.0;
+
+import "ds-test/test.sol";
+
+contract MemoryLimitTest is DSTest {
+    function testSmallAllocation() public {
+        bytes memory data = new bytes(1024);
+        assertEq(data.length, 1024);
+    }
+
+    // 64 MiB are affordable with the test gas limit, so this only fails because it exceeds the
+    // 32 MiB memory limit of the test runner
+    function testFailHugeAllocation() public {
+        assembly {
+            mstore(0x4000000, 1)
+        }
+    }
+}
@@ -0,0 +1,17 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity >=0.8.0;
+
+import "ds-test/test.sol";
+
+contract FuzzActorsTest is DSTest {
+    /// forge-config: fuzz.actors = 3
+    function testActorsAreFunded(uint256) public {
+        assertTrue(msg.sender.balance > 0);
+    }
+
+    /// forge-config: fuzz.actors = 3
+    function testSecondActorFails(uint256) public {
+        address second = address(uint160(uint256(keccak256("foundry.actor.1"))));
+        assertTrue(msg.sender != second);
+    }
+}
@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity >=0.8.0;
+
+import "ds-test/test.sol";
+import "../cheats/Cheats.sol";
+
+contract FuzzAssumeTest is DSTest {
+    Cheats constant cheats = Cheats(HEVM_ADDRESS);
+
+    function testAssumeSome(uint8 x) public {
+        cheats.assume(x % 2 == 0);
+        assertEq(x % 2, 0);
+    }
+
+    function testAssumeNone(uint8 x) public {
+        cheats.assume(x > type(uint8).max);
+    }
+}
@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity >=0.8.0;
+
+import "ds-test/test.sol";
+
+contract CalldataTarget {
+    uint256 public x;
+
+    function set(uint256 value) external {
+        x = value;
+    }
+}
+
+contract FuzzCalldataTest is DSTest {
+    CalldataTarget target;
+
+    function setUp() public {
+        target = new CalldataTarget();
+    }
+
+    // fails once the fuzzer finds calldata for `set` with a non-zero value
+    function testForwardCalldata(bytes memory data) public {
+        (bool success, ) = address(target).call(data);
+        assertTrue(!success || target.x() == 0);
+    }
+}
@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity >=0.8.0;
+
+import "ds-test/test.sol";
+
+contract FuzzCoverageTest is DSTest {
+    /// forge-config: fuzz.runs = 2000
+    /// forge-config: fuzz.coverage_guided = true
+    function testNestedConditions(uint256 a, uint256 b, uint256 c) public {
+        if (a % 16 == 5) {
+            if (b % 16 == 9) {
+                if (c % 16 == 13) {
+                    fail();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity >=0.8.0;
+
+import "ds-test/test.sol";
+
+contract FuzzInlineConfigTest is DSTest {
+    /// forge-config: fuzz.runs = 10
+    function testInlineRuns(uint256 x) public {
+        assertTrue(x == x);
+    }
+
+    function testDefaultRuns(uint256 x) public {
+        assertTrue(x == x);
+    }
+
+    /// forge-fuzz: amount in [1, 1e18]
+    /// forge-fuzz: delta in [-5, 5]
+    function testRange(uint256 amount, int8 delta) public {
+        assertTrue(amount >= 1 && amount <= 1e18);
+        assertTrue(delta >= -5 && delta <= 5);
+    }
+}
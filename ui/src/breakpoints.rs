@@ -0,0 +1,204 @@
+//! Breakpoints and watchpoints of the debugger
+
+use ethers::{
+    solc::{artifacts::ContractBytecodeSome, sourcemap::SourceMap},
+    types::{Address, U256},
+};
+use forge::debug::{DebugStep, Instruction};
+use std::collections::BTreeMap;
+
+const SSTORE: u8 = 0x55;
+const REVERT: u8 = 0xfd;
+const INVALID: u8 = 0xfe;
+
+/// A source line the debugger stops at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Breakpoint {
+    /// The index of the source file, as used in the source maps
+    pub source: u32,
+    /// The line in the source file, starting at 1
+    pub line: usize,
+}
+
+/// A storage slot the debugger stops at when it is written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Watchpoint {
+    /// The name of the contract whose storage is watched, any contract if `None`
+    pub contract: Option<String>,
+    pub slot: U256,
+}
+
+/// The position of a step in the flattened debug arena, the index of the call and of the step
+pub type Position = (usize, usize);
+
+/// Returns the positions of all steps at which a breakpoint is hit or a watched slot is written.
+///
+/// A breakpoint is only hit by the first step of a consecutive run of steps on its line, so
+/// continuing from a breakpoint moves to the next time the line is executed.
+pub fn find_stops(
+    debug_arena: &[(Address, Vec<DebugStep>, bool)],
+    identified_contracts: &BTreeMap<Address, String>,
+    known_contracts: &BTreeMap<String, ContractBytecodeSome>,
+    source_code: &BTreeMap<u32, String>,
+    breakpoints: &[Breakpoint],
+    watchpoints: &[Watchpoint],
+) -> Vec<Position> {
+    // the offsets at which the lines of the sources with breakpoints start
+    let line_starts: BTreeMap<u32, Vec<usize>> = breakpoints
+        .iter()
+        .filter_map(|bp| {
+            let source = source_code.get(&bp.source)?;
+            let starts = std::iter::once(0)
+                .chain(source.match_indices('\n').map(|(idx, _)| idx + 1))
+                .collect();
+            Some((bp.source, starts))
+        })
+        .collect();
+
+    let mut stops = Vec::new();
+    for (call, (address, steps, creation)) in debug_arena.iter().enumerate() {
+        let contract = identified_contracts.get(address);
+        let sourcemap = if breakpoints.is_empty() {
+            None
+        } else {
+            contract
+                .and_then(|name| known_contracts.get(name))
+                .and_then(|known| source_map(known, *creation))
+        };
+
+        let mut prev_line = None;
+        for (idx, step) in steps.iter().enumerate() {
+            let line = sourcemap.as_ref().and_then(|sourcemap| sourcemap.get(step.ic)).and_then(
+                |element| {
+                    let source = element.index?;
+                    let starts = line_starts.get(&source)?;
+                    Some((source, starts.partition_point(|start| *start <= element.offset)))
+                },
+            );
+            let at_breakpoint = line.map_or(false, |(source, line)| {
+                prev_line != Some((source, line)) &&
+                    breakpoints.iter().any(|bp| bp.source == source && bp.line == line)
+            });
+            prev_line = line;
+
+            let at_watchpoint = matches!(step.instruction, Instruction::OpCode(SSTORE)) &&
+                step.stack.last().map_or(false, |slot| {
+                    watchpoints.iter().any(|wp| {
+                        wp.slot == *slot &&
+                            wp.contract.as_ref().map_or(true, |c| Some(c) == contract)
+                    })
+                });
+
+            if at_breakpoint || at_watchpoint {
+                stops.push((call, idx));
+            }
+        }
+    }
+    stops
+}
+
+/// Returns the positions of all steps that revert or hit an invalid opcode
+pub fn find_reverts(debug_arena: &[(Address, Vec<DebugStep>, bool)]) -> Vec<Position> {
+    debug_arena
+        .iter()
+        .enumerate()
+        .flat_map(|(call, (_, steps, _))| {
+            steps.iter().enumerate().filter_map(move |(idx, step)| {
+                matches!(step.instruction, Instruction::OpCode(REVERT | INVALID))
+                    .then(|| (call, idx))
+            })
+        })
+        .collect()
+}
+
+/// Returns the creation or runtime source map of the contract
+fn source_map(known: &ContractBytecodeSome, creation: bool) -> Option<SourceMap> {
+    if creation {
+        known.bytecode.source_map()?.ok()
+    } else {
+        known.deployed_bytecode.bytecode.as_ref()?.source_map()?.ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(ic: usize, op: u8, stack: Vec<U256>) -> DebugStep {
+        DebugStep { ic, instruction: Instruction::OpCode(op), stack, ..Default::default() }
+    }
+
+    fn contract(creation_map: &str, runtime_map: &str) -> ContractBytecodeSome {
+        serde_json::from_value(serde_json::json!({
+            "abi": [],
+            "bytecode": { "object": "0x00", "sourceMap": creation_map },
+            "deployedBytecode": { "object": "0x00", "sourceMap": runtime_map },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn can_find_breakpoints() {
+        let address = Address::repeat_byte(1);
+        let identified = BTreeMap::from([(address, "Counter".to_string())]);
+        // lines 1, 2, 2, 3 and 2 again
+        let known = BTreeMap::from([(
+            "Counter".to_string(),
+            contract("", "0:1:0:-:0;2:1:0:-:0;2:1:0:-:0;4:1:0:-:0;2:1:0:-:0"),
+        )]);
+        let sources = BTreeMap::from([(0, "a\nb\nc\n".to_string())]);
+        let steps = (0..5).map(|ic| step(ic, 0x01, vec![])).collect();
+        let arena = vec![(address, steps, false)];
+
+        let breakpoints = [Breakpoint { source: 0, line: 2 }];
+        let stops = find_stops(&arena, &identified, &known, &sources, &breakpoints, &[]);
+        // the second step on line 2 continues the first one
+        assert_eq!(stops, vec![(0, 1), (0, 4)]);
+
+        let breakpoints = [Breakpoint { source: 1, line: 2 }];
+        assert!(find_stops(&arena, &identified, &known, &sources, &breakpoints, &[]).is_empty());
+    }
+
+    #[test]
+    fn can_find_watchpoints() {
+        let (counter, other) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let identified = BTreeMap::from([(counter, "Counter".to_string())]);
+        // `SSTORE` takes the slot from the top of the stack
+        let steps = || {
+            vec![
+                step(0, SSTORE, vec![1u64.into(), 7u64.into()]),
+                step(1, 0x54, vec![7u64.into()]),
+                step(2, SSTORE, vec![7u64.into(), 1u64.into()]),
+            ]
+        };
+        let arena = vec![(counter, steps(), false), (other, steps(), false)];
+        let find = |watchpoints: &[Watchpoint]| {
+            find_stops(&arena, &identified, &BTreeMap::new(), &BTreeMap::new(), &[], watchpoints)
+        };
+
+        assert_eq!(find(&[Watchpoint { contract: None, slot: 7u64.into() }]), vec![(0, 0), (1, 0)]);
+        assert_eq!(
+            find(&[Watchpoint { contract: Some("Counter".to_string()), slot: 1u64.into() }]),
+            vec![(0, 2)]
+        );
+        assert!(find(&[Watchpoint { contract: None, slot: 2u64.into() }]).is_empty());
+    }
+
+    #[test]
+    fn can_find_reverts() {
+        let arena = vec![
+            (Address::zero(), vec![step(0, 0x01, vec![]), step(1, REVERT, vec![])], false),
+            (Address::zero(), vec![step(0, INVALID, vec![])], true),
+        ];
+        assert_eq!(find_reverts(&arena), vec![(0, 1), (1, 0)]);
+    }
+
+    #[test]
+    fn uses_creation_or_runtime_source_map() {
+        let known = contract("1:2:0:-:0", "3:4:1:-:0");
+        let creation = source_map(&known, true).unwrap();
+        assert_eq!((creation[0].offset, creation[0].index), (1, Some(0)));
+        let runtime = source_map(&known, false).unwrap();
+        assert_eq!((runtime[0].offset, runtime[0].index), (3, Some(1)));
+    }
+}
@@ -17,6 +17,10 @@ use std::{
     thread,
     time::{Duration, Instant},
 };
+
+mod breakpoints;
+pub use breakpoints::{Breakpoint, Watchpoint};
+
 use tui::{
     backend::{Backend, CrosstermBackend},
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -49,6 +53,8 @@ pub struct Tui {
     identified_contracts: BTreeMap<Address, String>,
     known_contracts: BTreeMap<String, ContractBytecodeSome>,
     source_code: BTreeMap<u32, String>,
+    breakpoints: Vec<Breakpoint>,
+    watchpoints: Vec<Watchpoint>,
 }
 
 impl Tui {
@@ -75,9 +81,23 @@ impl Tui {
             identified_contracts,
             known_contracts,
             source_code,
+            breakpoints: Vec::new(),
+            watchpoints: Vec::new(),
         })
     }
 
+    /// Sets the source lines to stop at
+    pub fn with_breakpoints(mut self, breakpoints: Vec<Breakpoint>) -> Self {
+        self.breakpoints = breakpoints;
+        self
+    }
+
+    /// Sets the storage slots to stop at when they are written
+    pub fn with_watchpoints(mut self, watchpoints: Vec<Watchpoint>) -> Self {
+        self.watchpoints = watchpoints;
+        self
+    }
+
     /// Grab number from buffer. Used for something like '10k' to move up 10 operations
     fn buffer_as_number(buffer: &str, default_value: usize) -> usize {
         if let Ok(num) = buffer.parse() {
@@ -168,7 +188,7 @@ impl Tui {
         let block_controls = Block::default();
 
         let text_output = Text::from(Span::styled(
-            "[q]: quit | [k/j]: prev/next op | [a/s]: prev/next jump | [c/C]: prev/next call | [g/G]: start/end",
+            "[q]: quit | [k/j]: prev/next op | [a/s]: prev/next jump | [c/C]: prev/next call | [g/G]: start/end | [N/n]: prev/next breakpoint | [r]: next revert",
             Style::default().add_modifier(Modifier::DIM)
         ));
         let paragraph = Paragraph::new(text_output)
@@ -210,8 +230,14 @@ impl Tui {
                             // This includes an offset and length. This vector is in
                             // instruction pointer order, meaning the location of
                             // the instruction - sum(push_bytes[..pc])
-                            if let Some(source_idx) = sourcemap[ic].index {
-                                if let Some(source) = source_code.get(&source_idx) {
+                            if let Some(source_idx) = sourcemap.get(ic).and_then(|el| el.index) {
+                                // sources generated by the compiler, like the yul utility
+                                // functions of the via-ir pipeline, are not part of `source_code`
+                                if let Some(source) =
+                                    source_code.get(&source_idx).filter(|source| {
+                                        sourcemap[ic].offset + sourcemap[ic].length <= source.len()
+                                    })
+                                {
                                     let offset = sourcemap[ic].offset;
                                     let len = sourcemap[ic].length;
 
@@ -718,6 +744,15 @@ impl Ui for Tui {
         let mut draw_memory: DrawMemory = DrawMemory::default();
 
         let debug_call: Vec<(Address, Vec<DebugStep>, bool)> = self.debug_arena.clone();
+        let stops = breakpoints::find_stops(
+            &debug_call,
+            &self.identified_contracts,
+            &self.known_contracts,
+            &self.source_code,
+            &self.breakpoints,
+            &self.watchpoints,
+        );
+        let reverts = breakpoints::find_reverts(&debug_call);
         let mut opcode_list: Vec<String> =
             debug_call[0].1.iter().map(|step| step.pretty_opcode()).collect();
         let mut last_index = 0;
@@ -851,6 +886,34 @@ impl Ui for Tui {
                         }
                         self.key_buffer.clear();
                     }
+                    // Continue to the next breakpoint or watchpoint
+                    KeyCode::Char('n') => {
+                        let current = (draw_memory.inner_call_index, self.current_step);
+                        if let Some((call, step)) = stops.iter().find(|stop| **stop > current) {
+                            draw_memory.inner_call_index = *call;
+                            self.current_step = *step;
+                        }
+                        self.key_buffer.clear();
+                    }
+                    // Go back to the previous breakpoint or watchpoint
+                    KeyCode::Char('N') => {
+                        let current = (draw_memory.inner_call_index, self.current_step);
+                        if let Some((call, step)) = stops.iter().rev().find(|stop| **stop < current)
+                        {
+                            draw_memory.inner_call_index = *call;
+                            self.current_step = *step;
+                        }
+                        self.key_buffer.clear();
+                    }
+                    // Go to the next revert
+                    KeyCode::Char('r') => {
+                        let current = (draw_memory.inner_call_index, self.current_step);
+                        if let Some((call, step)) = reverts.iter().find(|stop| **stop > current) {
+                            draw_memory.inner_call_index = *call;
+                            self.current_step = *step;
+                        }
+                        self.key_buffer.clear();
+                    }
                     KeyCode::Char(other) => match other {
                         '0' | '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' => {
                             self.key_buffer.push(other);